@@ -46,7 +46,15 @@
 //! will truncate the fractional part of the float rather than properly round to the nearest
 //! integer. Use the provided `cast` function or [`From`]/[`Into`] conversions, which handle the
 //! rounding properly. Note that precision loss will still occur when rounding from a float to an
-//! int, although rounding lessens the problem.
+//! int, although rounding lessens the problem. Conversely, converting to `usize` or `isize` goes
+//! through `f64`, which can't represent integers beyond 2<sup>53</sup> exactly.
+//!
+//! The same applies to values that are already floats: converting a [`Size`] or [`Position`] to
+//! `f64` keeps the fractional part, and truncating it later will be off by one pixel whenever it is
+//! at or above `.5`. When whole pixels are needed regardless of the target type, use
+//! [`Size::to_physical_rounded`] / [`Size::to_logical_rounded`] (and the [`Position`]
+//! equivalents), which round each component to the nearest integer, with halfway cases rounded away
+//! from zero.
 //!
 //! ## Cargo Features
 //!
@@ -54,6 +62,8 @@
 //!
 //! * `serde`: Enables serialization/deserialization of certain types with [Serde](https://crates.io/crates/serde).
 //! * `mint`: Enables mint (math interoperability standard types) conversions.
+//! * `half`: Implements [`Pixel`] for [`half::f16`](https://docs.rs/half/latest/half/struct.f16.html),
+//!   to store coordinates in half precision.
 //!
 //!
 //! [points]: https://en.wikipedia.org/wiki/Point_(typography)
@@ -62,13 +72,20 @@
 #![cfg_attr(docsrs, feature(doc_auto_cfg, doc_cfg_hide), doc(cfg_hide(doc, docsrs)))]
 #![forbid(unsafe_code)]
 
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, Div, Mul, Sub};
+
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 pub trait Pixel: Copy + Into<f64> {
     fn from_f64(f: f64) -> Self;
+    fn into_f64(self) -> f64 {
+        self.into()
+    }
     fn cast<P: Pixel>(self) -> P {
-        P::from_f64(self.into())
+        P::from_f64(self.into_f64())
     }
 }
 
@@ -112,6 +129,12 @@ impl Pixel for f64 {
         f
     }
 }
+#[cfg(feature = "half")]
+impl Pixel for half::f16 {
+    fn from_f64(f: f64) -> Self {
+        half::f16::from_f64(f)
+    }
+}
 
 /// Checks that the scale factor is a normal positive `f64`.
 ///
@@ -123,6 +146,30 @@ pub fn validate_scale_factor(scale_factor: f64) -> bool {
     scale_factor.is_sign_positive() && scale_factor.is_normal()
 }
 
+/// Like [`validate_scale_factor`], but returns the offending scale factor as an error.
+#[inline]
+pub fn check_scale_factor(scale_factor: f64) -> Result<(), ScaleFactorError> {
+    if validate_scale_factor(scale_factor) {
+        Ok(())
+    } else {
+        Err(ScaleFactorError(scale_factor))
+    }
+}
+
+/// The error returned by the `checked_*` conversions when given an invalid scale factor.
+///
+/// See [`validate_scale_factor`] for what is considered valid.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ScaleFactorError(pub f64);
+
+impl fmt::Display for ScaleFactorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid scale factor: {}", self.0)
+    }
+}
+
+impl std::error::Error for ScaleFactorError {}
+
 /// A logical pixel unit.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Default, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -154,7 +201,7 @@ impl<P: Pixel> LogicalUnit<P> {
     #[inline]
     pub fn to_physical<X: Pixel>(&self, scale_factor: f64) -> PhysicalUnit<X> {
         assert!(validate_scale_factor(scale_factor));
-        PhysicalUnit::new(self.0.into() * scale_factor).cast()
+        PhysicalUnit::new(self.0.into_f64() * scale_factor).cast()
     }
 
     #[inline]
@@ -205,6 +252,18 @@ impl<P: Pixel> From<LogicalUnit<P>> for i32 {
     }
 }
 
+impl<P: Pixel> From<LogicalUnit<P>> for usize {
+    fn from(v: LogicalUnit<P>) -> usize {
+        v.0.into_f64().round() as usize
+    }
+}
+
+impl<P: Pixel> From<LogicalUnit<P>> for isize {
+    fn from(v: LogicalUnit<P>) -> isize {
+        v.0.into_f64().round() as isize
+    }
+}
+
 impl<P: Pixel> From<LogicalUnit<P>> for f32 {
     fn from(v: LogicalUnit<P>) -> f32 {
         v.0.cast()
@@ -245,7 +304,21 @@ impl<P: Pixel> PhysicalUnit<P> {
     #[inline]
     pub fn to_logical<X: Pixel>(&self, scale_factor: f64) -> LogicalUnit<X> {
         assert!(validate_scale_factor(scale_factor));
-        LogicalUnit::new(self.0.into() / scale_factor).cast()
+        LogicalUnit::new(self.0.into_f64() / scale_factor).cast()
+    }
+
+    /// Like [`to_logical`](Self::to_logical), but rounds down instead of to the nearest value.
+    #[inline]
+    pub fn to_logical_floor<X: Pixel>(&self, scale_factor: f64) -> LogicalUnit<X> {
+        assert!(validate_scale_factor(scale_factor));
+        LogicalUnit::new((self.0.into_f64() / scale_factor).floor()).cast()
+    }
+
+    /// Like [`to_logical`](Self::to_logical), but rounds up instead of to the nearest value.
+    #[inline]
+    pub fn to_logical_ceil<X: Pixel>(&self, scale_factor: f64) -> LogicalUnit<X> {
+        assert!(validate_scale_factor(scale_factor));
+        LogicalUnit::new((self.0.into_f64() / scale_factor).ceil()).cast()
     }
 
     #[inline]
@@ -296,6 +369,18 @@ impl<P: Pixel> From<PhysicalUnit<P>> for i32 {
     }
 }
 
+impl<P: Pixel> From<PhysicalUnit<P>> for usize {
+    fn from(v: PhysicalUnit<P>) -> usize {
+        v.0.into_f64().round() as usize
+    }
+}
+
+impl<P: Pixel> From<PhysicalUnit<P>> for isize {
+    fn from(v: PhysicalUnit<P>) -> isize {
+        v.0.into_f64().round() as isize
+    }
+}
+
 impl<P: Pixel> From<PhysicalUnit<P>> for f32 {
     fn from(v: PhysicalUnit<P>) -> f32 {
         v.0.cast()
@@ -388,11 +473,22 @@ impl<P: Pixel> LogicalPosition<P> {
     #[inline]
     pub fn to_physical<X: Pixel>(&self, scale_factor: f64) -> PhysicalPosition<X> {
         assert!(validate_scale_factor(scale_factor));
-        let x = self.x.into() * scale_factor;
-        let y = self.y.into() * scale_factor;
+        let x = self.x.into_f64() * scale_factor;
+        let y = self.y.into_f64() * scale_factor;
         PhysicalPosition::new(x, y).cast()
     }
 
+    /// Like [`to_physical`](Self::to_physical), but returns an error instead of panicking on an
+    /// invalid scale factor.
+    #[inline]
+    pub fn checked_to_physical<X: Pixel>(
+        &self,
+        scale_factor: f64,
+    ) -> Result<PhysicalPosition<X>, ScaleFactorError> {
+        check_scale_factor(scale_factor)?;
+        Ok(self.to_physical(scale_factor))
+    }
+
     #[inline]
     pub fn cast<X: Pixel>(&self) -> LogicalPosition<X> {
         LogicalPosition { x: self.x.cast(), y: self.y.cast() }
@@ -464,8 +560,37 @@ impl<P: Pixel> PhysicalPosition<P> {
     #[inline]
     pub fn to_logical<X: Pixel>(&self, scale_factor: f64) -> LogicalPosition<X> {
         assert!(validate_scale_factor(scale_factor));
-        let x = self.x.into() / scale_factor;
-        let y = self.y.into() / scale_factor;
+        let x = self.x.into_f64() / scale_factor;
+        let y = self.y.into_f64() / scale_factor;
+        LogicalPosition::new(x, y).cast()
+    }
+
+    /// Like [`to_logical`](Self::to_logical), but returns an error instead of panicking on an
+    /// invalid scale factor.
+    #[inline]
+    pub fn checked_to_logical<X: Pixel>(
+        &self,
+        scale_factor: f64,
+    ) -> Result<LogicalPosition<X>, ScaleFactorError> {
+        check_scale_factor(scale_factor)?;
+        Ok(self.to_logical(scale_factor))
+    }
+
+    /// Like [`to_logical`](Self::to_logical), but rounds down instead of to the nearest value.
+    #[inline]
+    pub fn to_logical_floor<X: Pixel>(&self, scale_factor: f64) -> LogicalPosition<X> {
+        assert!(validate_scale_factor(scale_factor));
+        let x = (self.x.into_f64() / scale_factor).floor();
+        let y = (self.y.into_f64() / scale_factor).floor();
+        LogicalPosition::new(x, y).cast()
+    }
+
+    /// Like [`to_logical`](Self::to_logical), but rounds up instead of to the nearest value.
+    #[inline]
+    pub fn to_logical_ceil<X: Pixel>(&self, scale_factor: f64) -> LogicalPosition<X> {
+        assert!(validate_scale_factor(scale_factor));
+        let x = (self.x.into_f64() / scale_factor).ceil();
+        let y = (self.y.into_f64() / scale_factor).ceil();
         LogicalPosition::new(x, y).cast()
     }
 
@@ -540,11 +665,22 @@ impl<P: Pixel> LogicalSize<P> {
     #[inline]
     pub fn to_physical<X: Pixel>(&self, scale_factor: f64) -> PhysicalSize<X> {
         assert!(validate_scale_factor(scale_factor));
-        let width = self.width.into() * scale_factor;
-        let height = self.height.into() * scale_factor;
+        let width = self.width.into_f64() * scale_factor;
+        let height = self.height.into_f64() * scale_factor;
         PhysicalSize::new(width, height).cast()
     }
 
+    /// Like [`to_physical`](Self::to_physical), but returns an error instead of panicking on an
+    /// invalid scale factor.
+    #[inline]
+    pub fn checked_to_physical<X: Pixel>(
+        &self,
+        scale_factor: f64,
+    ) -> Result<PhysicalSize<X>, ScaleFactorError> {
+        check_scale_factor(scale_factor)?;
+        Ok(self.to_physical(scale_factor))
+    }
+
     #[inline]
     pub fn cast<X: Pixel>(&self) -> LogicalSize<X> {
         LogicalSize { width: self.width.cast(), height: self.height.cast() }
@@ -613,8 +749,40 @@ impl<P: Pixel> PhysicalSize<P> {
     #[inline]
     pub fn to_logical<X: Pixel>(&self, scale_factor: f64) -> LogicalSize<X> {
         assert!(validate_scale_factor(scale_factor));
-        let width = self.width.into() / scale_factor;
-        let height = self.height.into() / scale_factor;
+        let width = self.width.into_f64() / scale_factor;
+        let height = self.height.into_f64() / scale_factor;
+        LogicalSize::new(width, height).cast()
+    }
+
+    /// Like [`to_logical`](Self::to_logical), but returns an error instead of panicking on an
+    /// invalid scale factor.
+    #[inline]
+    pub fn checked_to_logical<X: Pixel>(
+        &self,
+        scale_factor: f64,
+    ) -> Result<LogicalSize<X>, ScaleFactorError> {
+        check_scale_factor(scale_factor)?;
+        Ok(self.to_logical(scale_factor))
+    }
+
+    /// Like [`to_logical`](Self::to_logical), but rounds down instead of to the nearest value.
+    ///
+    /// This is useful to make sure that content laid out in logical coordinates never exceeds the
+    /// physical size.
+    #[inline]
+    pub fn to_logical_floor<X: Pixel>(&self, scale_factor: f64) -> LogicalSize<X> {
+        assert!(validate_scale_factor(scale_factor));
+        let width = (self.width.into_f64() / scale_factor).floor();
+        let height = (self.height.into_f64() / scale_factor).floor();
+        LogicalSize::new(width, height).cast()
+    }
+
+    /// Like [`to_logical`](Self::to_logical), but rounds up instead of to the nearest value.
+    #[inline]
+    pub fn to_logical_ceil<X: Pixel>(&self, scale_factor: f64) -> LogicalSize<X> {
+        assert!(validate_scale_factor(scale_factor));
+        let width = (self.width.into_f64() / scale_factor).ceil();
+        let height = (self.height.into_f64() / scale_factor).ceil();
         LogicalSize::new(width, height).cast()
     }
 
@@ -689,6 +857,46 @@ impl Size {
         }
     }
 
+    /// Like [`to_logical`](Self::to_logical), but returns an error instead of panicking on an
+    /// invalid scale factor.
+    ///
+    /// The scale factor is validated even if the conversion wouldn't use it.
+    pub fn checked_to_logical<P: Pixel>(
+        &self,
+        scale_factor: f64,
+    ) -> Result<LogicalSize<P>, ScaleFactorError> {
+        check_scale_factor(scale_factor)?;
+        Ok(self.to_logical(scale_factor))
+    }
+
+    /// Like [`to_physical`](Self::to_physical), but returns an error instead of panicking on an
+    /// invalid scale factor.
+    ///
+    /// The scale factor is validated even if the conversion wouldn't use it.
+    pub fn checked_to_physical<P: Pixel>(
+        &self,
+        scale_factor: f64,
+    ) -> Result<PhysicalSize<P>, ScaleFactorError> {
+        check_scale_factor(scale_factor)?;
+        Ok(self.to_physical(scale_factor))
+    }
+
+    /// Converts to a logical size, rounding each component to the nearest integer.
+    ///
+    /// Halfway cases are rounded away from zero, even when `P` is a floating-point type.
+    pub fn to_logical_rounded<P: Pixel>(&self, scale_factor: f64) -> LogicalSize<P> {
+        let size = self.to_logical::<f64>(scale_factor);
+        LogicalSize::new(size.width.round(), size.height.round()).cast()
+    }
+
+    /// Converts to a physical size, rounding each component to the nearest integer.
+    ///
+    /// Halfway cases are rounded away from zero, even when `P` is a floating-point type.
+    pub fn to_physical_rounded<P: Pixel>(&self, scale_factor: f64) -> PhysicalSize<P> {
+        let size = self.to_physical::<f64>(scale_factor);
+        PhysicalSize::new(size.width.round(), size.height.round()).cast()
+    }
+
     pub fn clamp<S: Into<Size>>(input: S, min: S, max: S, scale_factor: f64) -> Size {
         let (input, min, max) = (
             input.into().to_physical::<f64>(scale_factor),
@@ -743,6 +951,46 @@ impl Position {
             Position::Logical(position) => position.to_physical(scale_factor),
         }
     }
+
+    /// Like [`to_logical`](Self::to_logical), but returns an error instead of panicking on an
+    /// invalid scale factor.
+    ///
+    /// The scale factor is validated even if the conversion wouldn't use it.
+    pub fn checked_to_logical<P: Pixel>(
+        &self,
+        scale_factor: f64,
+    ) -> Result<LogicalPosition<P>, ScaleFactorError> {
+        check_scale_factor(scale_factor)?;
+        Ok(self.to_logical(scale_factor))
+    }
+
+    /// Like [`to_physical`](Self::to_physical), but returns an error instead of panicking on an
+    /// invalid scale factor.
+    ///
+    /// The scale factor is validated even if the conversion wouldn't use it.
+    pub fn checked_to_physical<P: Pixel>(
+        &self,
+        scale_factor: f64,
+    ) -> Result<PhysicalPosition<P>, ScaleFactorError> {
+        check_scale_factor(scale_factor)?;
+        Ok(self.to_physical(scale_factor))
+    }
+
+    /// Converts to a logical position, rounding each component to the nearest integer.
+    ///
+    /// Halfway cases are rounded away from zero, even when `P` is a floating-point type.
+    pub fn to_logical_rounded<P: Pixel>(&self, scale_factor: f64) -> LogicalPosition<P> {
+        let position = self.to_logical::<f64>(scale_factor);
+        LogicalPosition::new(position.x.round(), position.y.round()).cast()
+    }
+
+    /// Converts to a physical position, rounding each component to the nearest integer.
+    ///
+    /// Halfway cases are rounded away from zero, even when `P` is a floating-point type.
+    pub fn to_physical_rounded<P: Pixel>(&self, scale_factor: f64) -> PhysicalPosition<P> {
+        let position = self.to_physical::<f64>(scale_factor);
+        PhysicalPosition::new(position.x.round(), position.y.round()).cast()
+    }
 }
 
 impl<P: Pixel> From<PhysicalPosition<P>> for Position {
@@ -759,6 +1007,70 @@ impl<P: Pixel> From<LogicalPosition<P>> for Position {
     }
 }
 
+macro_rules! impl_ordered {
+    ($(#[$attr:meta])* $name:ident($inner:ident { $a:ident, $b:ident })) => {
+        $(#[$attr])*
+        #[derive(Debug, Copy, Clone)]
+        pub struct $name(pub $inner);
+
+        impl $name {
+            fn key(&self) -> (bool, u64, u64) {
+                match self.0 {
+                    $inner::Physical(value) => (false, value.$a as u64, value.$b as u64),
+                    $inner::Logical(value) => (true, value.$a.to_bits(), value.$b.to_bits()),
+                }
+            }
+        }
+
+        impl PartialEq for $name {
+            fn eq(&self, other: &Self) -> bool {
+                self.key() == other.key()
+            }
+        }
+
+        impl Eq for $name {}
+
+        impl Hash for $name {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.key().hash(state);
+            }
+        }
+
+        impl From<$inner> for $name {
+            #[inline]
+            fn from(value: $inner) -> Self {
+                $name(value)
+            }
+        }
+
+        impl From<$name> for $inner {
+            #[inline]
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+    };
+}
+
+impl_ordered!(
+    /// A [`Size`] wrapper that implements [`Eq`] and [`Hash`], e.g. to use sizes as `HashMap` keys.
+    ///
+    /// Logical sizes are compared by the bit patterns of their `f64` components rather than by
+    /// value. This means that `0.0` and `-0.0` are distinct keys, and that a NaN component is equal to
+    /// itself as long as the bits match.
+    OrderedSize(Size { width, height })
+);
+
+impl_ordered!(
+    /// A [`Position`] wrapper that implements [`Eq`] and [`Hash`], e.g. to use positions as
+    /// `HashMap` keys.
+    ///
+    /// Logical positions are compared by the bit patterns of their `f64` components rather than by
+    /// value. This means that `0.0` and `-0.0` are distinct keys, and that a NaN component is equal to
+    /// itself as long as the bits match.
+    OrderedPosition(Position { x, y })
+);
+
 /// The logical distance between the edges of two rectangles.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Default, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -792,10 +1104,10 @@ impl<P: Pixel> LogicalInsets<P> {
     #[inline]
     pub fn to_physical<X: Pixel>(&self, scale_factor: f64) -> PhysicalInsets<X> {
         assert!(validate_scale_factor(scale_factor));
-        let top = self.top.into() * scale_factor;
-        let left = self.left.into() * scale_factor;
-        let bottom = self.bottom.into() * scale_factor;
-        let right = self.right.into() * scale_factor;
+        let top = self.top.into_f64() * scale_factor;
+        let left = self.left.into_f64() * scale_factor;
+        let bottom = self.bottom.into_f64() * scale_factor;
+        let right = self.right.into_f64() * scale_factor;
         PhysicalInsets::new(top, left, bottom, right).cast()
     }
 
@@ -843,10 +1155,10 @@ impl<P: Pixel> PhysicalInsets<P> {
     #[inline]
     pub fn to_logical<X: Pixel>(&self, scale_factor: f64) -> LogicalInsets<X> {
         assert!(validate_scale_factor(scale_factor));
-        let top = self.top.into() / scale_factor;
-        let left = self.left.into() / scale_factor;
-        let bottom = self.bottom.into() / scale_factor;
-        let right = self.right.into() / scale_factor;
+        let top = self.top.into_f64() / scale_factor;
+        let left = self.left.into_f64() / scale_factor;
+        let bottom = self.bottom.into_f64() / scale_factor;
+        let right = self.right.into_f64() / scale_factor;
         LogicalInsets::new(top, left, bottom, right).cast()
     }
 
@@ -903,9 +1215,100 @@ impl<P: Pixel> From<LogicalInsets<P>> for Insets {
     }
 }
 
+// Component-wise arithmetic, computed in `f64` so that integer pixel types are rounded properly.
+macro_rules! impl_arithmetic {
+    ($ty:ident { $a:ident, $b:ident }) => {
+        impl<P: Pixel> Add for $ty<P> {
+            type Output = Self;
+
+            #[inline]
+            fn add(self, other: Self) -> Self {
+                $ty::new(
+                    P::from_f64(self.$a.into_f64() + other.$a.into_f64()),
+                    P::from_f64(self.$b.into_f64() + other.$b.into_f64()),
+                )
+            }
+        }
+
+        impl<P: Pixel> Sub for $ty<P> {
+            type Output = Self;
+
+            #[inline]
+            fn sub(self, other: Self) -> Self {
+                $ty::new(
+                    P::from_f64(self.$a.into_f64() - other.$a.into_f64()),
+                    P::from_f64(self.$b.into_f64() - other.$b.into_f64()),
+                )
+            }
+        }
+
+        impl<P: Pixel> Mul<f64> for $ty<P> {
+            type Output = Self;
+
+            #[inline]
+            fn mul(self, scalar: f64) -> Self {
+                $ty::new(
+                    P::from_f64(self.$a.into_f64() * scalar),
+                    P::from_f64(self.$b.into_f64() * scalar),
+                )
+            }
+        }
+
+        impl<P: Pixel> Div<f64> for $ty<P> {
+            type Output = Self;
+
+            #[inline]
+            fn div(self, scalar: f64) -> Self {
+                $ty::new(
+                    P::from_f64(self.$a.into_f64() / scalar),
+                    P::from_f64(self.$b.into_f64() / scalar),
+                )
+            }
+        }
+    };
+}
+
+impl_arithmetic!(LogicalPosition { x, y });
+impl_arithmetic!(PhysicalPosition { x, y });
+impl_arithmetic!(LogicalSize { width, height });
+impl_arithmetic!(PhysicalSize { width, height });
+
+// Offsetting a position by a size, e.g. to get the bottom-right corner of a rectangle.
+macro_rules! impl_position_size_arithmetic {
+    ($position:ident, $size:ident) => {
+        impl<P: Pixel> Add<$size<P>> for $position<P> {
+            type Output = Self;
+
+            #[inline]
+            fn add(self, size: $size<P>) -> Self {
+                $position::new(
+                    P::from_f64(self.x.into_f64() + size.width.into_f64()),
+                    P::from_f64(self.y.into_f64() + size.height.into_f64()),
+                )
+            }
+        }
+
+        impl<P: Pixel> Sub<$size<P>> for $position<P> {
+            type Output = Self;
+
+            #[inline]
+            fn sub(self, size: $size<P>) -> Self {
+                $position::new(
+                    P::from_f64(self.x.into_f64() - size.width.into_f64()),
+                    P::from_f64(self.y.into_f64() - size.height.into_f64()),
+                )
+            }
+        }
+    };
+}
+
+impl_position_size_arithmetic!(LogicalPosition, LogicalSize);
+impl_position_size_arithmetic!(PhysicalPosition, PhysicalSize);
+
 #[cfg(test)]
 mod tests {
-    use std::collections::HashSet;
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::{HashMap, HashSet};
 
     use super::*;
 
@@ -966,6 +1369,17 @@ mod tests {
         test_pixel_int_i16 => i16
     }
 
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn test_pixel_int_precision() {
+        assert_eq!(usize::from(PhysicalUnit::new(42u32)), 42);
+        assert_eq!(usize::from(PhysicalUnit::new(41.5)), 42);
+        assert_eq!(isize::from(LogicalUnit::new(-42.4)), -42);
+        // Integers above 2^53 can't be represented exactly as `f64`.
+        let large = ((1u64 << 53) + 1) as f64;
+        assert_eq!(usize::from(PhysicalUnit::new(large)), 1 << 53);
+    }
+
     macro_rules! assert_approx_eq {
         ($a:expr, $b:expr $(,)?) => {
             assert!(($a - $b).abs() < 0.001, "{} is not approximately equal to {}", $a, $b);
@@ -1066,6 +1480,22 @@ mod tests {
         test_pixel_float_f64 => f64
     }
 
+    #[cfg(feature = "half")]
+    #[test]
+    fn test_pixel_float_f16() {
+        use half::f16;
+
+        assert_eq!(<f16 as Pixel>::from_f64(37.5), f16::from_f32(37.5));
+        assert_eq!(<f16 as Pixel>::cast::<u32>(f16::from_f32(37.5)), 38);
+
+        let position = LogicalPosition::new(f16::from_f32(10.0), f16::from_f32(20.5));
+        assert_eq!(position.to_physical::<u32>(2.0), PhysicalPosition::new(20, 41));
+        assert_eq!(
+            PhysicalSize::new(30u32, 45).to_logical::<f16>(1.5),
+            LogicalSize::new(f16::from_f32(20.0), f16::from_f32(30.0))
+        );
+    }
+
     #[test]
     fn test_validate_scale_factor() {
         assert!(validate_scale_factor(1.0));
@@ -1106,6 +1536,19 @@ mod tests {
         assert_eq!(x, 1.0);
     }
 
+    #[test]
+    fn test_physical_unit_to_logical_rounding() {
+        let unit = PhysicalUnit::new(5u32);
+        assert_eq!(unit.to_logical::<u32>(2.0), LogicalUnit::new(3));
+        assert_eq!(unit.to_logical_floor::<u32>(2.0), LogicalUnit::new(2));
+        assert_eq!(unit.to_logical_ceil::<u32>(2.0), LogicalUnit::new(3));
+
+        let unit = PhysicalUnit::new(7u32);
+        assert_eq!(unit.to_logical::<u32>(1.5), LogicalUnit::new(5));
+        assert_eq!(unit.to_logical_floor::<u32>(1.5), LogicalUnit::new(4));
+        assert_eq!(unit.to_logical_ceil::<u32>(1.5), LogicalUnit::new(5));
+    }
+
     #[test]
     fn test_logical_position() {
         let log_pos = LogicalPosition::new(1.0, 2.0);
@@ -1142,6 +1585,18 @@ mod tests {
         assert_eq!(x, [1.0, 2.0]);
     }
 
+    #[test]
+    fn test_physical_position_to_logical_rounding() {
+        let position = PhysicalPosition::new(5u32, 7);
+        assert_eq!(position.to_logical::<u32>(2.0), LogicalPosition::new(3, 4));
+        assert_eq!(position.to_logical_floor::<u32>(2.0), LogicalPosition::new(2, 3));
+        assert_eq!(position.to_logical_ceil::<u32>(2.0), LogicalPosition::new(3, 4));
+
+        assert_eq!(position.to_logical::<u32>(1.5), LogicalPosition::new(3, 5));
+        assert_eq!(position.to_logical_floor::<u32>(1.5), LogicalPosition::new(3, 4));
+        assert_eq!(position.to_logical_ceil::<u32>(1.5), LogicalPosition::new(4, 5));
+    }
+
     #[test]
     fn test_logical_size() {
         let log_size = LogicalSize::new(1.0, 2.0);
@@ -1178,6 +1633,50 @@ mod tests {
         assert_eq!(x, [1.0, 2.0]);
     }
 
+    #[test]
+    fn test_physical_size_to_logical_rounding() {
+        let size = PhysicalSize::new(1001u32, 1000);
+        assert_eq!(size.to_logical::<u32>(2.0), LogicalSize::new(501, 500));
+        assert_eq!(size.to_logical_floor::<u32>(2.0), LogicalSize::new(500, 500));
+        assert_eq!(size.to_logical_ceil::<u32>(2.0), LogicalSize::new(501, 500));
+
+        assert_eq!(size.to_logical::<u32>(1.5), LogicalSize::new(667, 667));
+        assert_eq!(size.to_logical_floor::<u32>(1.5), LogicalSize::new(667, 666));
+        assert_eq!(size.to_logical_ceil::<u32>(1.5), LogicalSize::new(668, 667));
+        assert_eq!(size.to_logical_floor::<f64>(1.5), LogicalSize::new(667.0, 666.0));
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(
+            PhysicalPosition::new(10, 20) + PhysicalPosition::new(5, 5),
+            PhysicalPosition::new(15, 25)
+        );
+        assert_eq!(
+            PhysicalPosition::new(10, 20) - PhysicalPosition::new(5, 5),
+            PhysicalPosition::new(5, 15)
+        );
+        assert_eq!(LogicalPosition::new(1.5, 2.0) * 2.0, LogicalPosition::new(3.0, 4.0));
+        assert_eq!(LogicalSize::new(3.0, 4.0) / 2.0, LogicalSize::new(1.5, 2.0));
+        assert_eq!(
+            PhysicalSize::new(100u32, 50) + PhysicalSize::new(1, 2),
+            PhysicalSize::new(101, 52)
+        );
+
+        // Integer pixels are rounded rather than truncated.
+        assert_eq!(PhysicalSize::new(5u32, 7) * 1.5, PhysicalSize::new(8, 11));
+        assert_eq!(PhysicalPosition::new(5i32, -7) / 2.0, PhysicalPosition::new(3, -4));
+
+        assert_eq!(
+            PhysicalPosition::new(10, 20) + PhysicalSize::new(30, 40),
+            PhysicalPosition::new(40, 60)
+        );
+        assert_eq!(
+            LogicalPosition::new(10.0, 20.0) - LogicalSize::new(2.5, 5.0),
+            LogicalPosition::new(7.5, 15.0)
+        );
+    }
+
     #[test]
     fn test_size() {
         assert_eq!(Size::new(PhysicalSize::new(1, 2)), Size::Physical(PhysicalSize::new(1, 2)));
@@ -1225,6 +1724,109 @@ mod tests {
         assert_eq!(Size::clamp(large, small, medium, 1.0), medium_physical);
     }
 
+    #[test]
+    fn test_checked_conversions() {
+        for scale_factor in [0.0, -1.0, f64::NAN, f64::INFINITY, f64::MIN_POSITIVE / 2.0] {
+            // Compare bits, since NaN != NaN.
+            let matches = |result: Result<(), ScaleFactorError>| {
+                result.is_err_and(|error| error.0.to_bits() == scale_factor.to_bits())
+            };
+            assert!(matches(check_scale_factor(scale_factor)));
+            let size = LogicalSize::new(1.0, 2.0);
+            assert!(matches(size.checked_to_physical::<u32>(scale_factor).map(drop)));
+            let size = PhysicalSize::new(1, 2);
+            assert!(matches(size.checked_to_logical::<f64>(scale_factor).map(drop)));
+            let size = Size::new(LogicalSize::new(1.0, 2.0));
+            assert!(matches(size.checked_to_logical::<f64>(scale_factor).map(drop)));
+            assert!(matches(size.checked_to_physical::<u32>(scale_factor).map(drop)));
+            let position = Position::new(PhysicalPosition::new(1, 2));
+            assert!(matches(position.checked_to_logical::<f64>(scale_factor).map(drop)));
+            assert!(matches(position.checked_to_physical::<i32>(scale_factor).map(drop)));
+        }
+
+        assert_eq!(
+            LogicalSize::new(1.0, 2.0).checked_to_physical::<u32>(2.0),
+            Ok(PhysicalSize::new(2, 4))
+        );
+        assert_eq!(
+            PhysicalSize::new(2, 4).checked_to_logical::<f64>(2.0),
+            Ok(LogicalSize::new(1.0, 2.0))
+        );
+        assert_eq!(
+            Size::new(PhysicalSize::new(2, 4)).checked_to_logical::<f64>(2.0),
+            Ok(LogicalSize::new(1.0, 2.0))
+        );
+        assert_eq!(
+            Position::new(LogicalPosition::new(1.0, 2.0)).checked_to_physical::<i32>(2.0),
+            Ok(PhysicalPosition::new(2, 4))
+        );
+        assert_eq!(ScaleFactorError(0.0).to_string(), "invalid scale factor: 0");
+    }
+
+    #[test]
+    fn test_ordered() {
+        fn hash(value: impl Hash) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let a = OrderedSize(Size::new(LogicalSize::new(1.5, 2.0)));
+        let b = OrderedSize::from(Size::new(LogicalSize::new(1.5, 2.0)));
+        assert_eq!(a, b);
+        assert_eq!(hash(a), hash(b));
+        assert_ne!(a, OrderedSize(Size::new(PhysicalSize::new(1, 2))));
+        assert_ne!(
+            OrderedSize(Size::new(LogicalSize::new(0.0, 0.0))),
+            OrderedSize(Size::new(LogicalSize::new(-0.0, 0.0)))
+        );
+        let nan = OrderedSize(Size::new(LogicalSize::new(f64::NAN, 0.0)));
+        assert_eq!(nan, nan);
+
+        let mut pipelines = HashMap::new();
+        pipelines.insert(a, "logical");
+        pipelines.insert(OrderedSize(Size::new(PhysicalSize::new(3, 4))), "physical");
+        assert_eq!(pipelines.get(&b), Some(&"logical"));
+        assert_eq!(pipelines.get(&Size::new(PhysicalSize::new(3, 4)).into()), Some(&"physical"));
+        assert_eq!(pipelines.get(&Size::new(PhysicalSize::new(4, 3)).into()), None);
+        assert_eq!(Size::from(b), Size::new(LogicalSize::new(1.5, 2.0)));
+
+        let mut positions = HashMap::new();
+        positions.insert(OrderedPosition(Position::new(PhysicalPosition::new(-1, 2))), 1);
+        positions.insert(OrderedPosition(Position::new(LogicalPosition::new(-1.0, 2.0))), 2);
+        assert_eq!(positions.get(&Position::new(PhysicalPosition::new(-1, 2)).into()), Some(&1));
+        assert_eq!(positions.get(&Position::new(LogicalPosition::new(-1.0, 2.0)).into()), Some(&2));
+    }
+
+    #[test]
+    fn test_size_rounded() {
+        assert_eq!(
+            Size::Logical(LogicalSize::new(100.0, 100.0)).to_physical_rounded::<u32>(1.5),
+            PhysicalSize::new(150, 150)
+        );
+
+        // Matches the rounding done by the Wayland backend: 101 * 1.5 = 151.5.
+        let wayland = |logical: u32, scale_factor: f64| (logical as f64 * scale_factor).round();
+        let size = Size::Logical(LogicalSize::new(101.0, 101.0));
+        assert_eq!(size.to_physical_rounded::<f64>(1.5), PhysicalSize::new(152.0, 152.0));
+        assert_eq!(size.to_physical_rounded::<f64>(1.5).width, wayland(101, 1.5));
+        assert_eq!(size.to_physical::<f64>(1.5), PhysicalSize::new(151.5, 151.5));
+
+        let size = Size::Physical(PhysicalSize::new(3, 5));
+        assert_eq!(size.to_logical_rounded::<f64>(2.0), LogicalSize::new(2.0, 3.0));
+        assert_eq!(size.to_logical::<f64>(2.0), LogicalSize::new(1.5, 2.5));
+    }
+
+    #[test]
+    fn test_position_rounded() {
+        let position = Position::Logical(LogicalPosition::new(-1.5, 2.5));
+        assert_eq!(position.to_physical_rounded::<f64>(1.0), PhysicalPosition::new(-2.0, 3.0));
+        assert_eq!(position.to_physical_rounded::<i32>(1.0), PhysicalPosition::new(-2, 3));
+
+        let position = Position::Physical(PhysicalPosition::new(3, -3));
+        assert_eq!(position.to_logical_rounded::<f64>(2.0), LogicalPosition::new(2.0, -2.0));
+    }
+
     #[test]
     fn test_position() {
         assert_eq!(
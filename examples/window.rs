@@ -318,13 +318,8 @@ impl Application {
 
     fn dump_monitors(&self, event_loop: &dyn ActiveEventLoop) {
         info!("Monitors information");
-        let primary_monitor = event_loop.primary_monitor();
         for monitor in event_loop.available_monitors() {
-            let intro = if primary_monitor.as_ref() == Some(&monitor) {
-                "Primary monitor"
-            } else {
-                "Monitor"
-            };
+            let intro = if monitor.is_primary() { "Primary monitor" } else { "Monitor" };
 
             if let Some(name) = monitor.name() {
                 info!("{intro}: {name}");
@@ -347,6 +342,10 @@ impl Application {
                 info!("  Position: {x},{y}");
             }
 
+            if let Some((width, height)) = monitor.physical_size_mm() {
+                info!("  Physical size: {width}x{height} mm");
+            }
+
             info!("  Scale factor: {}", monitor.scale_factor());
 
             info!("  Available modes (width x height x bit-depth):");
@@ -436,6 +435,9 @@ impl ApplicationHandler for Application {
                 info!("Theme changed to {theme:?}");
                 window.set_draw_theme(theme);
             },
+            WindowEvent::AccentColorChanged(accent_color) => {
+                info!("Accent color changed to {accent_color:?}");
+            },
             WindowEvent::RedrawRequested => {
                 if let Err(err) = window.draw() {
                     error!("Error drawing window: {err}");
@@ -444,6 +446,9 @@ impl ApplicationHandler for Application {
             WindowEvent::Occluded(occluded) => {
                 window.set_occluded(occluded);
             },
+            WindowEvent::FullscreenChanged { state } => {
+                info!("Fullscreen state of Window={window_id:?} changed to {state:?}");
+            },
             WindowEvent::CloseRequested => {
                 info!("Closing Window={window_id:?}");
                 self.windows.remove(&window_id);
@@ -1,7 +1,8 @@
 //! End user application handling.
 
 use crate::event::{DeviceEvent, DeviceId, StartCause, WindowEvent};
-use crate::event_loop::ActiveEventLoop;
+use crate::event_loop::{ActiveEventLoop, EventAction, EventFilter};
+use crate::monitor::MonitorHandle;
 #[cfg(any(docsrs, macos_platform))]
 use crate::platform::macos::ApplicationHandlerExtMacOS;
 use crate::window::WindowId;
@@ -115,6 +116,16 @@ pub trait ApplicationHandler {
     /// there may be several executions of the event loop, including multiple redraws to windows,
     /// between [`EventLoopProxy::wake_up()`] being called and the event being delivered.
     ///
+    /// Since the wake ups are merged, a thread sending faster than the application handles its
+    /// messages doesn't flood the event loop, only the channel. To apply backpressure, use a
+    /// bounded channel such as [`std::sync::mpsc::sync_channel`], whose `try_send` hands the
+    /// message back when the channel is full. If only the latest message of a kind matters, store
+    /// the messages in a map keyed by their kind instead of a channel, and drain it here.
+    ///
+    /// To target a specific window, send its [`WindowId`] along with the message, e.g. a channel of
+    /// `(WindowId, T)`, and look the window up here. Delivery is asynchronous, so the window may
+    /// have been closed in the meantime, in which case the message should be dropped.
+    ///
     /// [`EventLoopProxy::wake_up()`]: crate::event_loop::EventLoopProxy::wake_up
     ///
     /// # Example
@@ -227,6 +238,21 @@ pub trait ApplicationHandler {
         let _ = event_loop;
     }
 
+    /// Emitted periodically at the interval set with [`ActiveEventLoop::set_idle_interval()`].
+    ///
+    /// This is a good place to run periodic background work, such as autosaving, without having
+    /// to use [`ControlFlow::Poll`] or to keep track of [`ControlFlow::WaitUntil`] deadlines
+    /// alongside the other reasons to wake up the event loop.
+    ///
+    /// Ticks are emitted right before [`about_to_wait()`][Self::about_to_wait]. Ticks missed while
+    /// the application was busy are skipped rather than emitted all at once.
+    ///
+    /// [`ControlFlow::Poll`]: crate::event_loop::ControlFlow::Poll
+    /// [`ControlFlow::WaitUntil`]: crate::event_loop::ControlFlow::WaitUntil
+    fn idle_tick(&mut self, event_loop: &dyn ActiveEventLoop) {
+        let _ = event_loop;
+    }
+
     /// Emitted when the application has been suspended.
     ///
     /// See [`resumed()`][Self::resumed].
@@ -320,6 +346,9 @@ pub trait ApplicationHandler {
 
     /// Emitted when the application has received a memory warning.
     ///
+    /// The severity of the warning is not conveyed, applications should release whatever caches
+    /// they can rebuild later.
+    ///
     /// ## Platform-specific
     ///
     /// ### Android
@@ -341,11 +370,41 @@ pub trait ApplicationHandler {
     ///
     /// ### Others
     ///
-    /// - **macOS / Orbital / Wayland / Web / Windows:** Unsupported.
+    /// - **macOS / Orbital / Wayland / Web / Windows / X11:** Unsupported.
     fn memory_warning(&mut self, event_loop: &dyn ActiveEventLoop) {
         let _ = event_loop;
     }
 
+    /// Emitted when a monitor was connected.
+    ///
+    /// The monitor is also returned by [`ActiveEventLoop::available_monitors()`] from now on.
+    /// Monitors that are present when the event loop starts are not reported.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Only emitted once the detailed monitor permission was granted, see
+    ///   `ActiveEventLoopExtWeb::request_detailed_monitor_permission()`.
+    /// - **Android / iOS / Orbital / Windows:** Unsupported.
+    fn monitor_connected(&mut self, event_loop: &dyn ActiveEventLoop, monitor: MonitorHandle) {
+        let _ = (event_loop, monitor);
+    }
+
+    /// Emitted when a monitor was disconnected.
+    ///
+    /// The handle compares equal to the one previously returned by
+    /// [`ActiveEventLoop::available_monitors()`] or passed to [`monitor_connected()`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Only emitted once the detailed monitor permission was granted, see
+    ///   `ActiveEventLoopExtWeb::request_detailed_monitor_permission()`.
+    /// - **Android / iOS / Orbital / Windows:** Unsupported.
+    ///
+    /// [`monitor_connected()`]: Self::monitor_connected
+    fn monitor_disconnected(&mut self, event_loop: &dyn ActiveEventLoop, monitor: MonitorHandle) {
+        let _ = (event_loop, monitor);
+    }
+
     /// The macOS-specific handler.
     ///
     /// The return value from this should not change at runtime.
@@ -403,6 +462,11 @@ impl<A: ?Sized + ApplicationHandler> ApplicationHandler for &mut A {
         (**self).about_to_wait(event_loop);
     }
 
+    #[inline]
+    fn idle_tick(&mut self, event_loop: &dyn ActiveEventLoop) {
+        (**self).idle_tick(event_loop);
+    }
+
     #[inline]
     fn suspended(&mut self, event_loop: &dyn ActiveEventLoop) {
         (**self).suspended(event_loop);
@@ -423,6 +487,16 @@ impl<A: ?Sized + ApplicationHandler> ApplicationHandler for &mut A {
         (**self).memory_warning(event_loop);
     }
 
+    #[inline]
+    fn monitor_connected(&mut self, event_loop: &dyn ActiveEventLoop, monitor: MonitorHandle) {
+        (**self).monitor_connected(event_loop, monitor);
+    }
+
+    #[inline]
+    fn monitor_disconnected(&mut self, event_loop: &dyn ActiveEventLoop, monitor: MonitorHandle) {
+        (**self).monitor_disconnected(event_loop, monitor);
+    }
+
     #[cfg(any(docsrs, macos_platform))]
     #[inline]
     fn macos_handler(&mut self) -> Option<&mut dyn ApplicationHandlerExtMacOS> {
@@ -477,6 +551,11 @@ impl<A: ?Sized + ApplicationHandler> ApplicationHandler for Box<A> {
         (**self).about_to_wait(event_loop);
     }
 
+    #[inline]
+    fn idle_tick(&mut self, event_loop: &dyn ActiveEventLoop) {
+        (**self).idle_tick(event_loop);
+    }
+
     #[inline]
     fn suspended(&mut self, event_loop: &dyn ActiveEventLoop) {
         (**self).suspended(event_loop);
@@ -497,9 +576,127 @@ impl<A: ?Sized + ApplicationHandler> ApplicationHandler for Box<A> {
         (**self).memory_warning(event_loop);
     }
 
+    #[inline]
+    fn monitor_connected(&mut self, event_loop: &dyn ActiveEventLoop, monitor: MonitorHandle) {
+        (**self).monitor_connected(event_loop, monitor);
+    }
+
+    #[inline]
+    fn monitor_disconnected(&mut self, event_loop: &dyn ActiveEventLoop, monitor: MonitorHandle) {
+        (**self).monitor_disconnected(event_loop, monitor);
+    }
+
     #[cfg(any(docsrs, macos_platform))]
     #[inline]
     fn macos_handler(&mut self) -> Option<&mut dyn ApplicationHandlerExtMacOS> {
         (**self).macos_handler()
     }
 }
+
+/// Runs the [event filter][crate::event_loop::EventLoop::set_event_filter] before dispatching
+/// window events to the wrapped application.
+pub(crate) struct FilteredApp<A> {
+    app: A,
+    pub(crate) filter: Option<EventFilter>,
+}
+
+impl<A: ApplicationHandler> FilteredApp<A> {
+    pub(crate) fn new(app: A, filter: Option<EventFilter>) -> Self {
+        Self { app, filter }
+    }
+}
+
+#[deny(clippy::missing_trait_methods)]
+impl<A: ApplicationHandler> ApplicationHandler for FilteredApp<A> {
+    #[inline]
+    fn new_events(&mut self, event_loop: &dyn ActiveEventLoop, cause: StartCause) {
+        self.app.new_events(event_loop, cause);
+    }
+
+    #[inline]
+    fn resumed(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.resumed(event_loop);
+    }
+
+    #[inline]
+    fn can_create_surfaces(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.can_create_surfaces(event_loop);
+    }
+
+    #[inline]
+    fn proxy_wake_up(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.proxy_wake_up(event_loop);
+    }
+
+    #[inline]
+    fn window_event(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        window_id: WindowId,
+        mut event: WindowEvent,
+    ) {
+        if let Some(filter) = &mut self.filter {
+            if filter(event_loop, window_id, &mut event) == EventAction::Drop {
+                return;
+            }
+        }
+
+        self.app.window_event(event_loop, window_id, event);
+    }
+
+    #[inline]
+    fn device_event(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        device_id: Option<DeviceId>,
+        event: DeviceEvent,
+    ) {
+        self.app.device_event(event_loop, device_id, event);
+    }
+
+    #[inline]
+    fn about_to_wait(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.about_to_wait(event_loop);
+    }
+
+    #[inline]
+    fn idle_tick(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.idle_tick(event_loop);
+    }
+
+    #[inline]
+    fn suspended(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.suspended(event_loop);
+    }
+
+    #[inline]
+    fn destroy_surfaces(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.destroy_surfaces(event_loop);
+    }
+
+    #[inline]
+    fn exiting(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.exiting(event_loop);
+    }
+
+    #[inline]
+    fn memory_warning(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.app.memory_warning(event_loop);
+    }
+
+    #[inline]
+    fn monitor_connected(&mut self, event_loop: &dyn ActiveEventLoop, monitor: MonitorHandle) {
+        self.app.monitor_connected(event_loop, monitor);
+    }
+
+    #[inline]
+    fn monitor_disconnected(&mut self, event_loop: &dyn ActiveEventLoop, monitor: MonitorHandle) {
+        self.app.monitor_disconnected(event_loop, monitor);
+    }
+
+    #[cfg(any(docsrs, macos_platform))]
+    #[inline]
+    fn macos_handler(&mut self) -> Option<&mut dyn ApplicationHandlerExtMacOS> {
+        self.app.macos_handler()
+    }
+}
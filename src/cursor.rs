@@ -45,6 +45,10 @@ impl From<CustomCursor> for Cursor {
 ///
 /// **Web**: Some browsers have limits on cursor sizes usually at 128x128.
 ///
+/// Animated cursors can only be created on Web, with `CustomCursorExtWeb::from_animation`, on X11,
+/// with `CustomCursorExtX11::from_animation`, and on Windows, with
+/// `CustomCursorExtWindows::from_animation`.
+///
 /// # Example
 ///
 /// ```no_run
@@ -80,7 +84,9 @@ pub struct CustomCursor {
 impl CustomCursor {
     /// Creates a new cursor from an rgba buffer.
     ///
-    /// The alpha channel is assumed to be **not** premultiplied.
+    /// The alpha channel is assumed to be **not** premultiplied. Backends that need premultiplied
+    /// alpha convert the image themselves. Use [`CustomCursor::from_premultiplied_rgba`] if your
+    /// image is already premultiplied.
     pub fn from_rgba(
         rgba: impl Into<Vec<u8>>,
         width: u16,
@@ -99,6 +105,39 @@ impl CustomCursor {
                 height,
                 hotspot_x,
                 hotspot_y,
+                false,
+            )?,
+        })
+    }
+
+    /// Creates a new cursor from an rgba buffer whose color channels are premultiplied by alpha.
+    ///
+    /// The image is handed as is to backends that use premultiplied alpha, while the others
+    /// convert it back to straight alpha. See [`CustomCursor::from_rgba`] for the other arguments.
+    pub fn from_premultiplied_rgba(
+        rgba: impl Into<Vec<u8>>,
+        width: u16,
+        height: u16,
+        hotspot_x: u16,
+        hotspot_y: u16,
+    ) -> Result<CustomCursorSource, BadImage> {
+        let _span = tracing::debug_span!(
+            "winit::Cursor::from_premultiplied_rgba",
+            width,
+            height,
+            hotspot_x,
+            hotspot_y
+        )
+        .entered();
+
+        Ok(CustomCursorSource {
+            inner: PlatformCustomCursorSource::from_rgba(
+                rgba.into(),
+                width,
+                height,
+                hotspot_x,
+                hotspot_y,
+                true,
             )?,
         })
     }
@@ -164,6 +203,27 @@ impl fmt::Display for BadImage {
 
 impl Error for BadImage {}
 
+/// An error produced when creating an animated cursor with invalid arguments.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BadAnimation {
+    /// Produced when no cursors were supplied.
+    Empty,
+    /// Produced when a supplied cursor is an animation.
+    Animation,
+}
+
+impl fmt::Display for BadAnimation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "No cursors supplied"),
+            Self::Animation => write!(f, "A supplied cursor is an animation"),
+        }
+    }
+}
+
+impl Error for BadAnimation {}
+
 /// Platforms export this directly as `PlatformCustomCursorSource` if they need to only work with
 /// images.
 #[allow(dead_code)]
@@ -178,8 +238,9 @@ impl OnlyCursorImageSource {
         height: u16,
         hotspot_x: u16,
         hotspot_y: u16,
+        premultiplied: bool,
     ) -> Result<Self, BadImage> {
-        CursorImage::from_rgba(rgba, width, height, hotspot_x, hotspot_y).map(Self)
+        CursorImage::from_rgba(rgba, width, height, hotspot_x, hotspot_y, premultiplied).map(Self)
     }
 }
 
@@ -210,6 +271,8 @@ pub(crate) struct CursorImage {
     pub(crate) height: u16,
     pub(crate) hotspot_x: u16,
     pub(crate) hotspot_y: u16,
+    /// Whether the color channels of `rgba` are premultiplied by alpha.
+    pub(crate) premultiplied: bool,
 }
 
 impl CursorImage {
@@ -219,6 +282,7 @@ impl CursorImage {
         height: u16,
         hotspot_x: u16,
         hotspot_y: u16,
+        premultiplied: bool,
     ) -> Result<Self, BadImage> {
         if width > MAX_CURSOR_SIZE || height > MAX_CURSOR_SIZE {
             return Err(BadImage::TooLarge { width, height });
@@ -243,7 +307,7 @@ impl CursorImage {
             return Err(BadImage::HotspotOutOfBounds { width, height, hotspot_x, hotspot_y });
         }
 
-        Ok(CursorImage { rgba, width, height, hotspot_x, hotspot_y })
+        Ok(CursorImage { rgba, width, height, hotspot_x, hotspot_y, premultiplied })
     }
 }
 
@@ -259,8 +323,9 @@ impl NoCustomCursor {
         height: u16,
         hotspot_x: u16,
         hotspot_y: u16,
+        premultiplied: bool,
     ) -> Result<Self, BadImage> {
-        CursorImage::from_rgba(rgba, width, height, hotspot_x, hotspot_y)?;
+        CursorImage::from_rgba(rgba, width, height, hotspot_x, hotspot_y, premultiplied)?;
         Ok(Self)
     }
 }
@@ -49,6 +49,7 @@ use crate::dpi::{PhysicalPosition, PhysicalSize};
 use crate::error::RequestError;
 use crate::event_loop::AsyncRequestSerial;
 use crate::keyboard::{self, ModifiersKeyState, ModifiersKeys, ModifiersState};
+use crate::monitor::MonitorHandle;
 use crate::platform_impl;
 #[cfg(doc)]
 use crate::window::Window;
@@ -100,6 +101,11 @@ pub(crate) enum Event {
     /// [`ApplicationHandler::about_to_wait()`]: crate::application::ApplicationHandler::about_to_wait()
     AboutToWait,
 
+    /// See [`ApplicationHandler::idle_tick()`] for details.
+    ///
+    /// [`ApplicationHandler::idle_tick()`]: crate::application::ApplicationHandler::idle_tick()
+    IdleTick,
+
     /// See [`ApplicationHandler::exiting()`] for details.
     ///
     /// [`ApplicationHandler::exiting()`]: crate::application::ApplicationHandler::exiting()
@@ -110,6 +116,16 @@ pub(crate) enum Event {
     /// [`ApplicationHandler::memory_warning()`]: crate::application::ApplicationHandler::memory_warning()
     MemoryWarning,
 
+    /// See [`ApplicationHandler::monitor_connected()`] for details.
+    ///
+    /// [`ApplicationHandler::monitor_connected()`]: crate::application::ApplicationHandler::monitor_connected()
+    MonitorConnected(MonitorHandle),
+
+    /// See [`ApplicationHandler::monitor_disconnected()`] for details.
+    ///
+    /// [`ApplicationHandler::monitor_disconnected()`]: crate::application::ApplicationHandler::monitor_disconnected()
+    MonitorDisconnected(MonitorHandle),
+
     /// User requested a wake up.
     UserWakeUp,
 }
@@ -118,11 +134,15 @@ pub(crate) enum Event {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum StartCause {
     /// Sent if the time specified by [`ControlFlow::WaitUntil`] has been reached. Contains the
-    /// moment the timeout was requested and the requested resume time. The actual resume time is
-    /// guaranteed to be equal to or after the requested resume time.
+    /// moment the timeout was requested, the requested resume time and the actual resume time.
+    ///
+    /// The actual resume time is guaranteed to be equal to or after the requested resume time,
+    /// `actual_resume - requested_resume` is how late the event loop woke up. Some lateness is
+    /// expected, it depends on the resolution of the OS timers and on the load of the system. Web
+    /// browsers notably clamp and throttle timers, for example in background tabs.
     ///
     /// [`ControlFlow::WaitUntil`]: crate::event_loop::ControlFlow::WaitUntil
-    ResumeTimeReached { start: Instant, requested_resume: Instant },
+    ResumeTimeReached { start: Instant, requested_resume: Instant, actual_resume: Instant },
 
     /// Sent if the OS has new events to send to the window, after a wait was requested. Contains
     /// the moment the wait was requested and the resume time, if requested.
@@ -166,7 +186,9 @@ pub enum WindowEvent {
     ///
     /// ## Platform-specific
     ///
-    /// - **iOS / Android / Web / Wayland:** Unsupported.
+    /// - **iOS / Android / Web:** Unsupported.
+    /// - **Wayland:** Unsupported, the xdg-shell protocol never tells clients the position of
+    ///   their toplevel windows.
     Moved(PhysicalPosition<i32>),
 
     /// The window has been requested to close.
@@ -360,6 +382,33 @@ pub enum WindowEvent {
         /// interaction, or an unknown pointer source.
         primary: bool,
 
+        /// The number of presses of this button in quick succession, this one included.
+        ///
+        /// A release reports the count of the press it ends, so a press with a count of `2` is a
+        /// double-click.
+        ///
+        /// ## Platform-specific
+        ///
+        /// - **macOS:** Reported by the system.
+        /// - **Windows:** Computed by winit with the system double-click time and distance.
+        /// - **X11:** Computed by winit with the double-click interval from XSettings, or 500ms
+        ///   when it isn't set.
+        /// - **Wayland:** Computed by winit with the interval set through
+        #[cfg_attr(
+            any(wayland_platform, docsrs),
+            doc = "  [`EventLoopBuilderExtWayland::with_double_click_interval()`][crate::platform::wayland::EventLoopBuilderExtWayland::with_double_click_interval()],"
+        )]
+        #[cfg_attr(
+            not(any(wayland_platform, docsrs)),
+            doc = "  `EventLoopBuilderExtWayland::with_double_click_interval()`,"
+        )]
+        ///   500ms by default.
+        /// - **Web:** Reported by the browser when the event carries it, otherwise computed by
+        ///   winit with a 500ms interval.
+        /// - **Android / iOS / Orbital:** Always `1`.
+        /// - Always `1` for touch input.
+        click_count: u32,
+
         button: ButtonSource,
     },
 
@@ -478,13 +527,26 @@ pub enum WindowEvent {
     /// ## Platform-specific
     ///
     /// - **iOS / Android / X11 / Wayland / Orbital:** Unsupported.
+    /// - **Web:** Reported when the result of the `prefers-color-scheme` media query changes. The
+    ///   listener is removed together with the window, or when the event loop exits.
     ThemeChanged(Theme),
 
+    /// The system accent color has changed, see [`ActiveEventLoop::accent_color`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// Only emitted on **macOS**, **Windows** and **X11**.
+    ///
+    /// [`ActiveEventLoop::accent_color`]: crate::event_loop::ActiveEventLoop::accent_color
+    AccentColorChanged([u8; 4]),
+
     /// The window has been occluded (completely hidden from view).
     ///
     /// This is different to window visibility as it depends on whether the window is closed,
     /// minimised, set invisible, or fully occluded by another window.
     ///
+    /// Use [`Window::occlusion`] to find out how much of the window is hidden.
+    ///
     /// ## Platform-specific
     ///
     /// ### iOS
@@ -507,8 +569,26 @@ pub enum WindowEvent {
     /// [`border`]: https://developer.mozilla.org/en-US/docs/Web/CSS/border
     /// [`padding`]: https://developer.mozilla.org/en-US/docs/Web/CSS/padding
     /// [`transform`]: https://developer.mozilla.org/en-US/docs/Web/CSS/transform
+    /// [`Window::occlusion`]: crate::window::Window::occlusion
     Occluded(bool),
 
+    /// The window is transitioning into or out of fullscreen, or has completed such a transition.
+    ///
+    /// This is emitted both for changes requested with [`Window::set_fullscreen`] and for changes
+    /// made by the user or the system. While a transition is in progress, the size and position
+    /// of the window may not reflect its final state yet.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** All phases are reported for the animated borderless fullscreen transition,
+    ///   exclusive fullscreen isn't reported.
+    /// - **Wayland / X11:** Only [`FullscreenState::Entered`] and [`FullscreenState::Exited`] are
+    ///   emitted, once the compositor or window manager has applied the change.
+    /// - **iOS / Android / Web / Windows / Orbital:** Unsupported.
+    ///
+    /// [`Window::set_fullscreen`]: crate::window::Window::set_fullscreen
+    FullscreenChanged { state: FullscreenState },
+
     /// Emitted when a window should be redrawn.
     ///
     /// This gets triggered in a few scenarios:
@@ -538,6 +618,8 @@ pub enum PointerKind {
     ///
     /// **macOS:** Unsupported.
     Touch(FingerId),
+    /// See [`PointerSource::Pen`] for more details.
+    Pen,
     Unknown,
 }
 
@@ -588,6 +670,33 @@ pub enum PointerSource {
         ///   force will be 0.5 when a button is pressed or 0.0 otherwise.
         force: Option<Force>,
     },
+    /// Represents a pen, also called stylus, on a graphics tablet or a touch screen.
+    ///
+    /// The pen tip and its barrel buttons are reported as [`ButtonSource::Mouse`] buttons, with
+    /// the tip being [`MouseButton::Left`].
+    ///
+    /// Each axis is [`None`] if the pen doesn't report it.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Requires the tablet to be exposed through XInput2 valuators, like the `wacom`
+    ///   and `libinput` drivers do. `twist` is always [`None`].
+    /// - **Wayland:** Requires the compositor to support the tablet protocol. Pens aren't reported
+    ///   over client-side decorations.
+    /// - **macOS:** The axes are never [`None`], the ones the pen doesn't have are `0`.
+    /// - **Windows / iOS / Android / Orbital:** Unsupported.
+    Pen {
+        /// How hard the pen is pressed, in the range `[0, 1]`.
+        pressure: Option<f32>,
+        /// The tilt of the pen along the X and Y axes in degrees, in the range `[-90, 90]`.
+        ///
+        /// Positive values tilt the pen towards the right and towards the user respectively, and
+        /// `(0, 0)` is perpendicular to the surface.
+        tilt: Option<(f32, f32)>,
+        /// The clockwise rotation of the pen around its own axis in degrees, in the range
+        /// `[0, 360)`.
+        twist: Option<f32>,
+    },
     Unknown,
 }
 
@@ -596,6 +705,7 @@ impl From<PointerSource> for PointerKind {
         match source {
             PointerSource::Mouse => Self::Mouse,
             PointerSource::Touch { finger_id, .. } => Self::Touch(finger_id),
+            PointerSource::Pen { .. } => Self::Pen,
             PointerSource::Unknown => Self::Unknown,
         }
     }
@@ -710,13 +820,15 @@ impl FingerId {
 pub enum DeviceEvent {
     /// Change in physical position of a pointing device.
     ///
-    /// This represents raw, unfiltered physical motion. Not to be confused with
-    /// [`WindowEvent::PointerMoved`].
+    /// This represents physical motion, which may or may not have been accelerated by the OS, see
+    /// `raw`. Not to be confused with [`WindowEvent::PointerMoved`].
     ///
     /// ## Platform-specific
     ///
-    /// **Web:** Only returns raw data, not OS accelerated, if [`CursorGrabMode::Locked`] is used
-    /// and browser support is available, see
+    /// - **X11 / Wayland / Windows:** Always raw.
+    /// - **macOS / Orbital:** Never raw, the delta has the OS pointer acceleration applied.
+    /// - **Web:** Only raw if [`CursorGrabMode::Locked`] is used and browser support is available,
+    ///   see
     #[cfg_attr(
         any(web_platform, docsrs),
         doc = "[`ActiveEventLoopExtWeb::is_cursor_lock_raw()`][crate::platform::web::ActiveEventLoopExtWeb::is_cursor_lock_raw()]."
@@ -733,6 +845,11 @@ pub enum DeviceEvent {
         ///
         /// Different devices may use different units.
         delta: (f64, f64),
+        /// Whether `delta` bypassed the OS pointer acceleration.
+        ///
+        /// Applications applying their own sensitivity curve, like first-person camera controls,
+        /// should only do so for raw deltas to avoid accelerating the motion twice.
+        raw: bool,
     },
 
     /// Physical scroll event
@@ -900,6 +1017,17 @@ pub struct KeyEvent {
 }
 
 /// Describes keyboard modifiers event.
+///
+/// Besides the [`state`], the `*_state` methods tell which side of a modifier is held, e.g. to
+/// tell the right alt key apart from the left one.
+///
+/// ## Platform-specific
+///
+/// - **Windows:** The left control key that the system sends along with AltGr isn't reported.
+/// - **iOS / Android / Web / Orbital / Wayland / x11:** The side of the modifiers is always
+///   [`ModifiersKeyState::Unknown`].
+///
+/// [`state`]: Self::state
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Modifiers {
@@ -1047,6 +1175,22 @@ pub enum Ime {
     Disabled,
 }
 
+/// The phase of a fullscreen transition, see [`WindowEvent::FullscreenChanged`].
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FullscreenState {
+    /// The window started transitioning into fullscreen.
+    Entering,
+    /// The window is now fullscreen.
+    Entered,
+    /// The window started transitioning out of fullscreen.
+    Exiting,
+    /// The window is no longer fullscreen.
+    ///
+    /// This is also emitted when a transition into fullscreen failed.
+    Exited,
+}
+
 /// Describes touch-screen input state.
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -1172,6 +1316,7 @@ pub struct SurfaceSizeWriter {
 
 impl SurfaceSizeWriter {
     #[cfg(not(orbital_platform))]
+    #[cfg_attr(all(null_platform, not(any(x11_platform, wayland_platform))), allow(dead_code))]
     pub(crate) fn new(new_surface_size: Weak<Mutex<PhysicalSize<u32>>>) -> Self {
         Self { new_surface_size }
     }
@@ -1275,6 +1420,7 @@ mod tests {
                     primary: true,
                     state: event::ElementState::Pressed,
                     position: (0, 0).into(),
+                    click_count: 1,
                     button: event::MouseButton::Other(0).into(),
                 });
                 with_window_event(PointerButton {
@@ -1282,6 +1428,7 @@ mod tests {
                     primary: true,
                     state: event::ElementState::Released,
                     position: (0, 0).into(),
+                    click_count: 1,
                     button: event::ButtonSource::Touch {
                         finger_id: fid,
                         force: Some(event::Force::Normalized(0.0)),
@@ -1305,7 +1452,9 @@ mod tests {
                 });
                 with_window_event(TouchpadPressure { device_id: None, pressure: 0.0, stage: 0 });
                 with_window_event(ThemeChanged(crate::window::Theme::Light));
+                with_window_event(AccentColorChanged([0, 0, 0, 0]));
                 with_window_event(Occluded(true));
+                with_window_event(FullscreenChanged { state: event::FullscreenState::Entered });
             }
 
             #[allow(deprecated)]
@@ -1315,7 +1464,7 @@ mod tests {
                 let with_device_event =
                     |dev_ev| x(event::Event::DeviceEvent { device_id: None, event: dev_ev });
 
-                with_device_event(PointerMotion { delta: (0.0, 0.0).into() });
+                with_device_event(PointerMotion { delta: (0.0, 0.0).into(), raw: true });
                 with_device_event(MouseWheel {
                     delta: event::MouseScrollDelta::LineDelta(0.0, 0.0),
                 });
@@ -1345,6 +1494,25 @@ mod tests {
         assert_eq!(force3.normalized(), 2.0);
     }
 
+    #[test]
+    fn modifiers_sides() {
+        use crate::keyboard::{ModifiersKeyState, ModifiersKeys, ModifiersState};
+
+        let modifiers = event::Modifiers {
+            state: ModifiersState::CONTROL | ModifiersState::ALT,
+            pressed_mods: ModifiersKeys::RCONTROL | ModifiersKeys::LALT,
+        };
+        assert!(!modifiers.lcontrol_state().is_pressed());
+        assert!(modifiers.rcontrol_state().is_pressed());
+        assert!(modifiers.lalt_state().is_pressed());
+        assert!(!modifiers.ralt_state().is_pressed());
+        assert!(!modifiers.lshift_state().is_pressed());
+
+        let modifiers = event::Modifiers::from(ModifiersState::CONTROL);
+        assert_eq!(modifiers.lcontrol_state(), ModifiersKeyState::Unknown);
+        assert_eq!(modifiers.rcontrol_state(), ModifiersKeyState::Unknown);
+    }
+
     #[allow(clippy::clone_on_copy)]
     #[test]
     fn ensure_attrs_do_not_panic() {
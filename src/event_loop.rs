@@ -8,6 +8,8 @@
 //!
 //! See the root-level documentation for information on how to create and use an event loop to
 //! handle events.
+#[cfg(not(web_platform))]
+use std::cell::Cell;
 use std::fmt;
 use std::marker::PhantomData;
 #[cfg(any(x11_platform, wayland_platform))]
@@ -18,15 +20,18 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use rwh_06::{DisplayHandle, HandleError, HasDisplayHandle};
+use smol_str::SmolStr;
 #[cfg(web_platform)]
 use web_time::{Duration, Instant};
 
-use crate::application::ApplicationHandler;
-use crate::error::{EventLoopError, RequestError};
+use crate::application::{ApplicationHandler, FilteredApp};
+use crate::error::{EventLoopError, NotSupportedError, RequestError};
+use crate::event::WindowEvent;
+use crate::keyboard::{ModifiersState, PhysicalKey};
 use crate::monitor::MonitorHandle;
 use crate::platform_impl;
 use crate::utils::AsAny;
-use crate::window::{CustomCursor, CustomCursorSource, Theme, Window, WindowAttributes};
+use crate::window::{CustomCursor, CustomCursorSource, Theme, Window, WindowAttributes, WindowId};
 
 /// Provides a way to retrieve events from the system and from the windows that were registered to
 /// the events loop.
@@ -45,6 +50,7 @@ use crate::window::{CustomCursor, CustomCursorSource, Theme, Window, WindowAttri
 /// [`Window`]: crate::window::Window
 pub struct EventLoop {
     pub(crate) event_loop: platform_impl::EventLoop,
+    pub(crate) event_filter: Option<EventFilter>,
     pub(crate) _marker: PhantomData<*mut ()>, // Not Send nor Sync
 }
 
@@ -61,6 +67,44 @@ pub struct EventLoopBuilder {
 
 static EVENT_LOOP_CREATED: AtomicBool = AtomicBool::new(false);
 
+// On Web, `run_app` never returns, so the flag would never be reset.
+#[cfg(not(web_platform))]
+thread_local! {
+    static EVENT_LOOP_RUNNING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Marks the event loop as running on the current thread for as long as it is alive.
+///
+/// Nested event loops are not supported, and the platforms fail in confusing ways when asked to
+/// run one, so this turns the mistake into a clear panic instead.
+#[cfg(not(web_platform))]
+pub(crate) struct RunningGuard {
+    _private: (),
+}
+
+#[cfg(not(web_platform))]
+impl RunningGuard {
+    #[track_caller]
+    pub(crate) fn enter(entry_point: &str) -> Self {
+        if EVENT_LOOP_RUNNING.with(|running| running.replace(true)) {
+            panic!(
+                "`{entry_point}` was called while an event loop is already running on this \
+                 thread; nested event loops are not supported, return from the current \
+                 `ApplicationHandler` method instead"
+            );
+        }
+
+        Self { _private: () }
+    }
+}
+
+#[cfg(not(web_platform))]
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        EVENT_LOOP_RUNNING.with(|running| running.set(false));
+    }
+}
+
 impl EventLoopBuilder {
     /// Builds a new event loop.
     ///
@@ -71,6 +115,9 @@ impl EventLoopBuilder {
     ///
     /// ## Panics
     ///
+    /// Attempting to create the event loop from within a running event loop will panic, except on
+    /// Web.
+    ///
     /// Attempting to create the event loop off the main thread will panic. This
     /// restriction isn't strictly necessary on all platforms, but is imposed to
     /// eliminate any nasty surprises when porting to platforms that require it.
@@ -82,6 +129,8 @@ impl EventLoopBuilder {
     ///
     /// - **Wayland/X11:** to prevent running under `Wayland` or `X11` unset `WAYLAND_DISPLAY` or
     ///   `DISPLAY` respectively when building the event loop.
+    /// - **Headless:** an event loop built with `EventLoopBuilderExtNull::with_headless` can be
+    ///   created on any thread and more than once.
     /// - **Android:** must be configured with an `AndroidApp` from `android_main()` by calling
     ///   [`.with_android_app(app)`] before calling `.build()`, otherwise it'll panic.
     ///
@@ -99,7 +148,19 @@ impl EventLoopBuilder {
     pub fn build(&mut self) -> Result<EventLoop, EventLoopError> {
         let _span = tracing::debug_span!("winit::EventLoopBuilder::build").entered();
 
-        if EVENT_LOOP_CREATED.swap(true, Ordering::Relaxed) {
+        #[cfg(not(web_platform))]
+        if EVENT_LOOP_RUNNING.with(Cell::get) {
+            panic!(
+                "`EventLoop` was created while an event loop is already running on this thread; \
+                 use the `ActiveEventLoop` passed to the `ApplicationHandler` instead"
+            );
+        }
+
+        #[cfg(null_platform)]
+        let recreation_allowed = self.platform_specific.is_headless();
+        #[cfg(not(null_platform))]
+        let recreation_allowed = false;
+        if !recreation_allowed && EVENT_LOOP_CREATED.swap(true, Ordering::Relaxed) {
             return Err(EventLoopError::RecreationAttempt);
         }
 
@@ -107,10 +168,36 @@ impl EventLoopBuilder {
         #[allow(clippy::unnecessary_mut_passed)]
         Ok(EventLoop {
             event_loop: platform_impl::EventLoop::new(&mut self.platform_specific)?,
+            event_filter: None,
             _marker: PhantomData,
         })
     }
 
+    /// Sets the theme of the whole application.
+    ///
+    /// Unlike [`WindowAttributes::with_theme`], this also affects application-wide UI such as the
+    /// menu bar and system dialogs, and is the default for windows that don't specify a theme.
+    /// Setting it before any window is created avoids a flash of the system theme at startup.
+    ///
+    /// Defaults to `None`, which follows the system theme.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Sets the appearance of `NSApp`.
+    /// - **Windows:** Sets the preferred app mode, which is only supported on Windows 10 1903 and
+    ///   newer.
+    /// - **iOS / Android / Web / Wayland / X11 / Orbital:** Unsupported.
+    #[inline]
+    pub fn with_theme(&mut self, theme: Option<Theme>) -> &mut Self {
+        #[cfg(any(macos_platform, windows_platform))]
+        {
+            self.platform_specific.theme = theme;
+        }
+        #[cfg(not(any(macos_platform, windows_platform)))]
+        let _ = theme;
+        self
+    }
+
     #[cfg(web_platform)]
     pub(crate) fn allow_event_loop_recreation() {
         EVENT_LOOP_CREATED.store(false, Ordering::Relaxed);
@@ -129,6 +216,21 @@ impl fmt::Debug for EventLoop {
     }
 }
 
+/// A closure intercepting window events, see [`EventLoop::set_event_filter()`].
+pub(crate) type EventFilter =
+    Box<dyn FnMut(&dyn ActiveEventLoop, WindowId, &mut WindowEvent) -> EventAction>;
+
+/// Returned by the [event filter][EventLoop::set_event_filter()] to decide what happens to an
+/// event.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum EventAction {
+    /// Pass the event, including any changes made by the filter, on to the
+    /// [`ApplicationHandler`].
+    Dispatch,
+    /// Drop the event, the [`ApplicationHandler`] will never see it.
+    Drop,
+}
+
 /// Set through [`ActiveEventLoop::set_control_flow()`].
 ///
 /// Indicates the desired behavior of the event loop after [`about_to_wait`] is called.
@@ -222,12 +324,43 @@ impl EventLoop {
     ///
     /// [^1]: `spawn_app()` is only available on the Web platform.
     ///
+    /// ## Panics
+    ///
+    /// Panics if an event loop is already running on the current thread, e.g. when called from
+    /// within an [`ApplicationHandler`] method. This isn't checked on Web.
+    ///
     /// [`set_control_flow()`]: ActiveEventLoop::set_control_flow()
     /// [`run_app()`]: Self::run_app()
     #[inline]
     #[cfg(not(all(web_platform, target_feature = "exception-handling")))]
     pub fn run_app<A: ApplicationHandler>(self, app: A) -> Result<(), EventLoopError> {
-        self.event_loop.run_app(app)
+        #[cfg(not(web_platform))]
+        let _running = RunningGuard::enter("EventLoop::run_app");
+        self.event_loop.run_app(FilteredApp::new(app, self.event_filter))
+    }
+
+    /// Sets a filter that sees every [`WindowEvent`] before it is dispatched to
+    /// [`ApplicationHandler::window_event()`].
+    ///
+    /// The filter may modify the event in place, and returns whether it should be
+    /// [dispatched][EventAction::Dispatch] or [dropped][EventAction::Drop]. This allows
+    /// implementing middleware such as logging, input remapping or recording on top of an
+    /// application without changing its handler.
+    ///
+    /// Dropping events the application relies on, e.g. [`WindowEvent::SurfaceResized`] or
+    /// [`WindowEvent::RedrawRequested`], can leave it in an inconsistent state.
+    ///
+    /// Replaces any previously set filter.
+    pub fn set_event_filter<F>(&mut self, filter: F)
+    where
+        F: FnMut(&dyn ActiveEventLoop, WindowId, &mut WindowEvent) -> EventAction + 'static,
+    {
+        self.event_filter = Some(Box::new(filter));
+    }
+
+    /// Removes the filter set with [`EventLoop::set_event_filter()`].
+    pub fn clear_event_filter(&mut self) {
+        self.event_filter = None;
     }
 
     /// Creates an [`EventLoopProxy`] that can be used to dispatch user events
@@ -327,6 +460,24 @@ pub trait ActiveEventLoop: AsAny {
         window_attributes: WindowAttributes,
     ) -> Result<Box<dyn Window>, RequestError>;
 
+    /// Create several windows at once.
+    ///
+    /// The results are in the same order as the attributes. This can be faster than calling
+    /// [`ActiveEventLoop::create_window`] for each window, when creating a window involves waiting
+    /// for the windowing system.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Sends the requests of all the windows before waiting for the X server.
+    /// - **Wayland:** Waits for the compositor to configure all the windows at once.
+    /// - **Others:** Creates the windows one after another.
+    fn create_windows(
+        &self,
+        window_attributes: Vec<WindowAttributes>,
+    ) -> Vec<Result<Box<dyn Window>, RequestError>> {
+        window_attributes.into_iter().map(|attributes| self.create_window(attributes)).collect()
+    }
+
     /// Create custom cursor.
     ///
     /// ## Platform-specific
@@ -386,17 +537,127 @@ pub trait ActiveEventLoop: AsAny {
     /// - **iOS / Android / Wayland / x11 / Orbital:** Unsupported.
     fn system_theme(&self) -> Option<Theme>;
 
+    /// Returns the system accent color, as sRGB `[red, green, blue, alpha]`.
+    ///
+    /// Returns `None` if there's no accent color, or if it cannot be determined on the current
+    /// platform. [`WindowEvent::AccentColorChanged`] is emitted when it changes.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Uses `NSColor.controlAccentColor`, available since macOS 10.14.
+    /// - **Windows:** Uses the DWM colorization color.
+    /// - **X11:** Uses the `selected_bg_color` entry of the `Gtk/ColorScheme` XSetting.
+    /// - **Wayland:** Uses the `accent-color` setting of the XDG desktop portal, queried with
+    ///   `dbus-send` on every call.
+    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    ///
+    /// [`WindowEvent::AccentColorChanged`]: crate::event::WindowEvent::AccentColorChanged
+    fn accent_color(&self) -> Option<[u8; 4]>;
+
+    /// Returns the text that the physical key would produce with the given modifiers.
+    ///
+    /// This is useful to display key bindings in the user's keyboard layout. The lookup uses the
+    /// layout that is *currently* active, so the result may change when the user switches
+    /// layouts. Dead keys, compose sequences and the state of the lock keys are not taken into
+    /// account.
+    ///
+    /// Returns `None` if the key doesn't produce any text with these modifiers.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows / Wayland / x11:** Holding [`ModifiersState::CONTROL`] may produce a control
+    ///   character. On Windows, [`ModifiersState::CONTROL`] with [`ModifiersState::ALT`] selects
+    ///   the AltGr level.
+    /// - **macOS / iOS / Android / Web / Orbital:** Unsupported.
+    fn key_text(&self, key: PhysicalKey, modifiers: ModifiersState) -> Option<SmolStr>;
+
     /// Sets the [`ControlFlow`].
     fn set_control_flow(&self, control_flow: ControlFlow);
 
     /// Gets the current [`ControlFlow`].
     fn control_flow(&self) -> ControlFlow;
 
+    /// Sets the interval at which [`ApplicationHandler::idle_tick()`] is emitted, or [`None`] to
+    /// stop emitting it.
+    ///
+    /// The ticks are independent of the [`ControlFlow`], and the event loop wakes up for them
+    /// even when waiting with [`ControlFlow::Wait`]. The first tick is emitted one interval after
+    /// this call.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Browsers clamp and throttle timers, notably in background tabs.
+    /// - **iOS / Android / Orbital:** Always returns an [`RequestError::NotSupported`].
+    fn set_idle_interval(&self, interval: Option<Duration>) -> Result<(), RequestError> {
+        let _ = interval;
+        Err(NotSupportedError::new("set_idle_interval is not supported").into())
+    }
+
+    /// Sets whether [`WindowEvent::SurfaceResized`] events are coalesced.
+    ///
+    /// When enabled, the intermediate resizes of a window within a single event loop iteration
+    /// are dropped, and only the latest size is delivered after the other events of the
+    /// iteration. This is useful for applications that only care about the final size, e.g.
+    /// while the user is dragging the window border.
+    ///
+    /// [`WindowEvent::ScaleFactorChanged`] is never coalesced, since the application may want to
+    /// respond to it with a new surface size.
+    ///
+    /// The default is `false`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows / macOS:** The latest size is also delivered right before each
+    ///   [`WindowEvent::RedrawRequested`], since dragging the window border runs a modal loop that
+    ///   holds back the end of the iteration.
+    /// - **Wayland:** Has no effect, resizes are already coalesced within an iteration.
+    /// - **iOS / Android / Orbital:** Unsupported.
+    ///
+    /// [`WindowEvent::SurfaceResized`]: crate::event::WindowEvent::SurfaceResized
+    /// [`WindowEvent::ScaleFactorChanged`]: crate::event::WindowEvent::ScaleFactorChanged
+    /// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
+    fn set_resize_coalescing(&self, enabled: bool);
+
+    /// Converts a [`MouseScrollDelta::LineDelta`] value to logical pixels, matching how far the
+    /// system scrolls natively.
+    ///
+    /// This is useful for applications that scroll by pixels, so that wheel steps scroll as far
+    /// as they do in other applications. The value depends on user settings that can change at
+    /// runtime without any event being emitted, so it should be queried again when handling
+    /// each scroll event instead of being cached.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Uses the "lines to scroll" setting (`SPI_GETWHEELSCROLLLINES`), with lines of
+    ///   16 pixels. Scrolling by pages isn't taken into account.
+    /// - **Wayland:** Uses the distance the compositor associated with the latest wheel step.
+    /// - **Others, or until a wheel step was received on Wayland:** A line is 3 times 16 pixels,
+    ///   the default on Windows.
+    ///
+    /// [`MouseScrollDelta::LineDelta`]: crate::event::MouseScrollDelta::LineDelta
+    fn scroll_lines_to_pixels(&self, lines: f32) -> f32;
+
     /// This exits the event loop.
     ///
     /// See [`exiting`][crate::application::ApplicationHandler::exiting].
     fn exit(&self);
 
+    /// This exits the event loop with the given exit code.
+    ///
+    /// A non-zero code is returned from [`EventLoop::run_app()`] as
+    /// [`EventLoopError::ExitFailure`], and from
+    /// [`EventLoopExtPumpEvents::pump_app_events()`] as [`PumpStatus::Exit`]. Calling
+    /// [`exit()`][Self::exit] is equivalent to calling this with `0`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS:** Unsupported, the same as [`exit()`][Self::exit].
+    /// - **Web:** The exit code is ignored, as [`EventLoop::run_app()`] never returns.
+    ///
+    /// [`EventLoopExtPumpEvents::pump_app_events()`]: crate::platform::pump_events::EventLoopExtPumpEvents::pump_app_events
+    /// [`PumpStatus::Exit`]: crate::platform::pump_events::PumpStatus::Exit
+    fn exit_with_code(&self, code: i32);
+
     /// Returns if the [`EventLoop`] is about to stop.
     ///
     /// See [`exit()`][Self::exit].
@@ -466,6 +727,9 @@ impl Eq for OwnedDisplayHandle {}
 pub(crate) trait EventLoopProxyProvider: Send + Sync {
     /// See [`EventLoopProxy::wake_up`] for details.
     fn wake_up(&self);
+
+    /// See [`EventLoopProxy::is_alive`] for details.
+    fn is_alive(&self) -> bool;
 }
 
 /// Control the [`EventLoop`], possibly from a different thread, without referencing it directly.
@@ -500,6 +764,26 @@ impl EventLoopProxy {
         self.proxy.wake_up();
     }
 
+    /// Whether the [`EventLoop`] this proxy belongs to still exists.
+    ///
+    /// Once this returns `false`, calls to [`wake_up`] won't be delivered anymore, so producers
+    /// can use this to stop doing work on behalf of an event loop that is gone.
+    ///
+    /// The result is only advisory: the event loop may be dropped on another thread right after
+    /// this returned `true`.
+    ///
+    /// [`wake_up`]: Self::wake_up
+    ///
+    /// # Platform-specific
+    ///
+    /// - **iOS:** Always returns `true`, as the event loop never returns.
+    /// - **Web:** Returns `false` once the event loop was destroyed after [`exit()`].
+    ///
+    /// [`exit()`]: ActiveEventLoop::exit
+    pub fn is_alive(&self) -> bool {
+        self.proxy.is_alive()
+    }
+
     pub(crate) fn new(proxy: Arc<dyn EventLoopProxyProvider>) -> Self {
         Self { proxy }
     }
@@ -14,8 +14,45 @@ pub(crate) struct Pixel {
 
 pub(crate) const PIXEL_SIZE: usize = mem::size_of::<Pixel>();
 
+/// Multiplies the color channels of straight alpha RGBA pixels by their alpha, in place.
+///
+/// Used by backends whose image formats expect premultiplied alpha.
+#[allow(dead_code)] // Not every platform needs this.
+pub(crate) fn premultiply_rgba(rgba: &mut [u8]) {
+    for pixel in rgba.chunks_exact_mut(PIXEL_SIZE) {
+        let alpha = pixel[3] as u16;
+        for channel in &mut pixel[..3] {
+            *channel = ((*channel as u16 * alpha + 127) / 255) as u8;
+        }
+    }
+}
+
+/// Divides the color channels of premultiplied alpha RGBA pixels by their alpha, in place.
+///
+/// Used by backends whose image formats expect straight alpha.
+#[allow(dead_code)] // Not every platform needs this.
+pub(crate) fn unpremultiply_rgba(rgba: &mut [u8]) {
+    for pixel in rgba.chunks_exact_mut(PIXEL_SIZE) {
+        let alpha = pixel[3] as u16;
+        if alpha == 0 {
+            continue;
+        }
+        for channel in &mut pixel[..3] {
+            *channel = ((*channel as u16 * 255 + alpha / 2) / alpha).min(255) as u8;
+        }
+    }
+}
+
+/// Swaps the blue and red channels of BGRA pixels, in place, turning them into RGBA pixels.
+pub(crate) fn bgra_to_rgba(bgra: &mut [u8]) {
+    for pixel in bgra.chunks_exact_mut(PIXEL_SIZE) {
+        pixel.swap(0, 2);
+    }
+}
+
 #[derive(Debug)]
-/// An error produced when using [`Icon::from_rgba`] with invalid arguments.
+/// An error produced when using [`Icon::from_rgba`] and the other [`Icon`] constructors with
+/// invalid arguments.
 pub enum BadIcon {
     /// Produced when the length of the `rgba` argument isn't divisible by 4, thus `rgba` can't be
     /// safely interpreted as 32bpp RGBA pixels.
@@ -55,6 +92,8 @@ pub(crate) struct RgbaIcon {
     pub(crate) rgba: Vec<u8>,
     pub(crate) width: u32,
     pub(crate) height: u32,
+    /// Whether the color channels of `rgba` are premultiplied by alpha.
+    pub(crate) premultiplied: bool,
 }
 
 /// For platforms which don't have window icons (e.g. Web)
@@ -66,7 +105,12 @@ mod constructors {
     use super::*;
 
     impl RgbaIcon {
-        pub fn from_rgba(rgba: Vec<u8>, width: u32, height: u32) -> Result<Self, BadIcon> {
+        pub fn from_rgba(
+            rgba: Vec<u8>,
+            width: u32,
+            height: u32,
+            premultiplied: bool,
+        ) -> Result<Self, BadIcon> {
             if rgba.len() % PIXEL_SIZE != 0 {
                 return Err(BadIcon::ByteCountNotDivisibleBy4 { byte_count: rgba.len() });
             }
@@ -79,15 +123,20 @@ mod constructors {
                     pixel_count,
                 })
             } else {
-                Ok(RgbaIcon { rgba, width, height })
+                Ok(RgbaIcon { rgba, width, height, premultiplied })
             }
         }
     }
 
     impl NoIcon {
-        pub fn from_rgba(rgba: Vec<u8>, width: u32, height: u32) -> Result<Self, BadIcon> {
+        pub fn from_rgba(
+            rgba: Vec<u8>,
+            width: u32,
+            height: u32,
+            premultiplied: bool,
+        ) -> Result<Self, BadIcon> {
             // Create the rgba icon anyway to validate the input
-            let _ = RgbaIcon::from_rgba(rgba, width, height)?;
+            let _ = RgbaIcon::from_rgba(rgba, width, height, premultiplied)?;
             Ok(NoIcon)
         }
     }
@@ -110,9 +159,88 @@ impl Icon {
     ///
     /// The length of `rgba` must be divisible by 4, and `width * height` must equal
     /// `rgba.len() / 4`. Otherwise, this will return a `BadIcon` error.
+    ///
+    /// The alpha channel is assumed to be **not** premultiplied. Backends that need premultiplied
+    /// alpha convert the image themselves.
     pub fn from_rgba(rgba: Vec<u8>, width: u32, height: u32) -> Result<Self, BadIcon> {
         let _span = tracing::debug_span!("winit::Icon::from_rgba", width, height).entered();
 
-        Ok(Icon { inner: PlatformIcon::from_rgba(rgba, width, height)? })
+        Ok(Icon { inner: PlatformIcon::from_rgba(rgba, width, height, false)? })
+    }
+
+    /// Creates an icon from 32bpp RGBA data whose color channels are premultiplied by alpha.
+    ///
+    /// The image is handed as is to backends that use premultiplied alpha, while the others
+    /// convert it back to straight alpha. See [`Icon::from_rgba`] for the other arguments.
+    pub fn from_premultiplied_rgba(
+        rgba: Vec<u8>,
+        width: u32,
+        height: u32,
+    ) -> Result<Self, BadIcon> {
+        let _span =
+            tracing::debug_span!("winit::Icon::from_premultiplied_rgba", width, height).entered();
+
+        Ok(Icon { inner: PlatformIcon::from_rgba(rgba, width, height, true)? })
+    }
+
+    /// Creates an icon from 32bpp BGRA data, with the alpha channel **not** premultiplied.
+    ///
+    /// See [`Icon::from_rgba`] for the other arguments.
+    pub fn from_bgra(mut bgra: Vec<u8>, width: u32, height: u32) -> Result<Self, BadIcon> {
+        bgra_to_rgba(&mut bgra);
+        Self::from_rgba(bgra, width, height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn premultiplied_rgba_round_trip() {
+        // A 2x2 image with opaque, half transparent and fully transparent pixels.
+        let rgba = vec![
+            255, 0, 0, 255, //
+            0, 255, 0, 128, //
+            255, 255, 255, 128, //
+            0, 0, 0, 0,
+        ];
+
+        let mut premultiplied = rgba.clone();
+        premultiply_rgba(&mut premultiplied);
+        assert_eq!(premultiplied, [255, 0, 0, 255, 0, 128, 0, 128, 128, 128, 128, 128, 0, 0, 0, 0]);
+
+        unpremultiply_rgba(&mut premultiplied);
+        assert_eq!(premultiplied, rgba);
+    }
+
+    #[test]
+    fn bgra_round_trip() {
+        let rgba = vec![
+            1, 2, 3, 255, //
+            4, 5, 6, 128, //
+            7, 8, 9, 64, //
+            10, 11, 12, 0,
+        ];
+
+        let mut bgra = rgba.clone();
+        bgra_to_rgba(&mut bgra);
+        assert_eq!(bgra, [3, 2, 1, 255, 6, 5, 4, 128, 9, 8, 7, 64, 12, 11, 10, 0]);
+
+        bgra_to_rgba(&mut bgra);
+        assert_eq!(bgra, rgba);
+    }
+
+    #[test]
+    fn bgra_validates_dimensions() {
+        assert!(matches!(
+            Icon::from_bgra(vec![0; 15], 2, 2),
+            Err(BadIcon::ByteCountNotDivisibleBy4 { byte_count: 15 })
+        ));
+        assert!(matches!(
+            Icon::from_premultiplied_rgba(vec![0; 16], 2, 3),
+            Err(BadIcon::DimensionsVsPixelCount { .. })
+        ));
+        assert!(Icon::from_bgra(vec![0; 16], 2, 2).is_ok());
     }
 }
@@ -85,6 +85,12 @@ pub use smol_str::SmolStr;
 ///
 /// - Correctly match key press and release events.
 /// - On non-Web platforms, support assigning keybinds to virtually any key through a UI.
+///
+/// All variants exist on every platform, so that code matching on them and keybinds serialized
+/// on one platform remain valid on the others. Only the variant of the current platform (or
+/// [`Unidentified`]) is ever produced by Winit, the other ones can be covered by a wildcard arm.
+///
+/// [`Unidentified`]: Self::Unidentified
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum NativeKeyCode {
@@ -1746,6 +1752,13 @@ pub enum ModifiersKeyState {
     Unknown,
 }
 
+impl ModifiersKeyState {
+    /// Returns `true` if the key is known to be pressed.
+    pub fn is_pressed(&self) -> bool {
+        *self == Self::Pressed
+    }
+}
+
 // NOTE: the exact modifier key is not used to represent modifiers state in the
 // first place due to a fact that modifiers state could be changed without any
 // key being pressed and on some platforms like Wayland/X11 which key resulted
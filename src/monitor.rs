@@ -131,9 +131,38 @@ impl MonitorHandle {
         self.inner.position()
     }
 
+    /// Returns the work area of the monitor, the part that isn't covered by taskbars, docks or
+    /// panels, as its top-left corner and size.
+    ///
+    /// The position is in the same desktop coordinates as [`MonitorHandle::position()`], whose
+    /// origin is the top-left corner of the virtual desktop, and the work area always lies within
+    /// the bounds of the monitor.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Uses `_NET_WORKAREA` of the current desktop, clipped to the monitor. It is read
+    ///   when the monitor list is queried, and is the whole monitor if the window manager doesn't
+    ///   set it.
+    /// - **Wayland / iOS / Android / Orbital:** Always returns [`None`].
+    /// - **Web:** Always returns [`None`] without
+    #[cfg_attr(
+        any(web_platform, docsrs),
+        doc = "  [detailed monitor permissions][crate::platform::web::ActiveEventLoopExtWeb::request_detailed_monitor_permission]."
+    )]
+    #[cfg_attr(not(any(web_platform, docsrs)), doc = "  detailed monitor permissions.")]
+    #[inline]
+    pub fn work_area(&self) -> Option<(PhysicalPosition<i32>, PhysicalSize<u32>)> {
+        self.inner.work_area()
+    }
+
     /// Returns the scale factor of the underlying monitor. To map logical pixels to physical
     /// pixels and vice versa, use [`Window::scale_factor`].
     ///
+    /// This is available before any window exists, e.g. to pick the initial size of a window
+    /// that will be placed on this monitor. Once created, a window usually uses the scale factor
+    /// of the monitor it is on, but the two can differ: a window spanning several monitors uses
+    /// the factor of only one of them, and the system may apply its own factor to windows.
+    ///
     /// See the [`dpi`] module for more information.
     ///
     /// ## Platform-specific
@@ -155,7 +184,51 @@ impl MonitorHandle {
         self.inner.scale_factor()
     }
 
+    /// Returns the physical size of the monitor in millimeters, as `(width, height)`.
+    ///
+    /// Together with the resolution of the [current video mode], this can be used to compute
+    /// the real DPI of the monitor, for example to render content at real-world scale.
+    ///
+    /// Returns `None` if the size is unknown, which is common for projectors and virtual
+    /// displays.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / Web / Orbital:** Always returns `None`.
+    ///
+    /// [current video mode]: Self::current_video_mode
+    #[inline]
+    pub fn physical_size_mm(&self) -> Option<(f64, f64)> {
+        self.inner.physical_size_mm()
+    }
+
+    /// Returns whether this is the primary monitor of the system.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland / Orbital:** There is no primary monitor on Wayland, so this always returns
+    ///   `false`. Orbital only has a single monitor, which is always the primary one.
+    /// - **Web:** Always returns `false` without
+    #[cfg_attr(
+        any(web_platform, docsrs),
+        doc = "  [detailed monitor permissions][crate::platform::web::ActiveEventLoopExtWeb::request_detailed_monitor_permission]."
+    )]
+    #[cfg_attr(not(any(web_platform, docsrs)), doc = "  detailed monitor permissions.")]
+    #[inline]
+    pub fn is_primary(&self) -> bool {
+        self.inner.is_primary()
+    }
+
     /// Returns the currently active video mode of this monitor.
+    ///
+    /// Returns `None` if the active mode can't be determined.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Uses the mode of the RandR CRTC driving the monitor.
+    /// - **Wayland:** Uses the mode the compositor flagged as current on the `wl_output`, if any.
+    /// - **Web:** Best-effort, made up of the screen size and color depth, without a refresh rate.
+    /// - **Orbital:** Always returns `None`.
     #[inline]
     pub fn current_video_mode(&self) -> Option<VideoMode> {
         self.inner.current_video_mode()
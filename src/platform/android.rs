@@ -71,6 +71,7 @@
 //!    event loop (as shown above).
 
 use self::activity::{AndroidApp, ConfigurationRef, Rect};
+use crate::error::RequestError;
 use crate::event_loop::{ActiveEventLoop, EventLoop, EventLoopBuilder};
 use crate::window::{Window, WindowAttributes};
 
@@ -97,6 +98,17 @@ pub trait WindowExtAndroid {
     fn content_rect(&self) -> Rect;
 
     fn config(&self) -> ConfigurationRef;
+
+    /// Shows or hides the soft keyboard.
+    ///
+    /// Unlike [`Window::set_ime_allowed`], which also shows the soft keyboard when IME gets
+    /// allowed, this doesn't change whether IME is allowed. This allows bringing the keyboard back
+    /// after the user dismissed it, or hiding it while IME stays allowed.
+    ///
+    /// The request is explicit, so the keyboard stays visible until hidden again by the user or
+    /// this method. Showing the keyboard requires the window to have focus, otherwise
+    /// [`RequestError::Ignored`] is returned.
+    fn set_ime_visible(&self, visible: bool) -> Result<(), RequestError>;
 }
 
 impl WindowExtAndroid for dyn Window + '_ {
@@ -109,6 +121,11 @@ impl WindowExtAndroid for dyn Window + '_ {
         let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
         window.config()
     }
+
+    fn set_ime_visible(&self, visible: bool) -> Result<(), RequestError> {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.set_ime_visible(visible)
+    }
 }
 
 impl ActiveEventLoopExtAndroid for dyn ActiveEventLoop + '_ {
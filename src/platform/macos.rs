@@ -7,12 +7,14 @@
 //!
 //! ## Custom `NSApplicationDelegate`
 //!
-//! Winit usually handles everything related to the lifecycle events of the application. Sometimes,
-//! though, you might want to do more niche stuff, such as [handle when the user re-activates the
-//! application][reopen]. Such functionality is not exposed directly in Winit, since it would
-//! increase the API surface by quite a lot.
+//! Winit usually handles everything related to the lifecycle events of the application, and a few
+//! of the more common application delegate methods are exposed through
+//! [`ApplicationHandlerExtMacOS`], such as [`reopen`][ApplicationHandlerExtMacOS::reopen] for when
+//! the user clicks the dock icon. Sometimes, though, you might want to do more niche stuff, such as
+//! [handle when the user opens files or URLs with the application][open-urls]. Such functionality
+//! is not exposed directly in Winit, since it would increase the API surface by quite a lot.
 //!
-//! [reopen]: https://developer.apple.com/documentation/appkit/nsapplicationdelegate/1428638-applicationshouldhandlereopen?language=objc
+//! [open-urls]: https://developer.apple.com/documentation/appkit/nsapplicationdelegate/2887193-application?language=objc
 //!
 //! Instead, Winit guarantees that it will not register an application delegate, so the solution is
 //! to register your own application delegate, as outlined in the following example (see
@@ -162,6 +164,58 @@ pub trait WindowExtMacOS {
 
     /// Getter for the [`WindowExtMacOS::set_unified_titlebar`].
     fn unified_titlebar(&self) -> bool;
+
+    /// Sets the subtitle shown below the window title, for example the path of a document.
+    ///
+    /// An empty string removes the subtitle. Requires macOS 11 or later.
+    ///
+    /// <https://developer.apple.com/documentation/appkit/nswindow/3608198-subtitle>
+    fn set_subtitle(&self, subtitle: &str);
+
+    /// Getter for the [`WindowExtMacOS::set_subtitle`].
+    fn subtitle(&self) -> String;
+
+    /// Sets how the window behaves in Spaces, Mission Control and when cycling through windows.
+    ///
+    /// [`WindowExtMacOS::set_simple_fullscreen`] doesn't use native fullscreen, and is therefore
+    /// unaffected by the `FULL_SCREEN_*` flags. It neither saves nor restores the collection
+    /// behavior, so the window keeps it while in simple fullscreen. Native fullscreen through
+    /// [`Window::set_fullscreen`] is unavailable with [`CollectionBehavior::FULL_SCREEN_NONE`].
+    ///
+    /// <https://developer.apple.com/documentation/appkit/nswindow/collectionbehavior>
+    ///
+    /// [`Window::set_fullscreen`]: crate::window::Window::set_fullscreen
+    fn set_collection_behavior(&self, behavior: CollectionBehavior);
+
+    /// Getter for the [`WindowExtMacOS::set_collection_behavior`].
+    fn collection_behavior(&self) -> CollectionBehavior;
+
+    /// Removes the window from the screen without changing the key window or activating the
+    /// application, unlike [`Window::set_visible`].
+    ///
+    /// This is useful for hiding windows created with
+    /// [`WindowAttributesExtMacOS::with_panel`]. No [`WindowEvent::Occluded`] is emitted for the
+    /// window being ordered out, and [`Window::is_visible`] returns `Some(false)` afterwards.
+    ///
+    /// <https://developer.apple.com/documentation/appkit/nswindow/orderout(_:)>
+    ///
+    /// [`Window::set_visible`]: crate::window::Window::set_visible
+    /// [`Window::is_visible`]: crate::window::Window::is_visible
+    /// [`WindowEvent::Occluded`]: crate::event::WindowEvent::Occluded
+    fn order_out(&self);
+
+    /// Moves the window to the front of its level, even if the application is not active, without
+    /// making it the key window.
+    ///
+    /// This is the counterpart of [`WindowExtMacOS::order_out`]. No [`WindowEvent::Occluded`] is
+    /// emitted for the window being ordered back in, unless its occlusion differs from before it
+    /// was ordered out. [`Window::is_visible`] returns `Some(true)` afterwards.
+    ///
+    /// <https://developer.apple.com/documentation/appkit/nswindow/orderfrontregardless()>
+    ///
+    /// [`Window::is_visible`]: crate::window::Window::is_visible
+    /// [`WindowEvent::Occluded`]: crate::event::WindowEvent::Occluded
+    fn order_front_regardless(&self);
 }
 
 impl WindowExtMacOS for dyn Window + '_ {
@@ -272,6 +326,42 @@ impl WindowExtMacOS for dyn Window + '_ {
         let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
         window.maybe_wait_on_main(|w| w.unified_titlebar())
     }
+
+    #[inline]
+    fn set_subtitle(&self, subtitle: &str) {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.maybe_wait_on_main(|w| w.set_subtitle(subtitle))
+    }
+
+    #[inline]
+    fn subtitle(&self) -> String {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.maybe_wait_on_main(|w| w.subtitle())
+    }
+
+    #[inline]
+    fn set_collection_behavior(&self, behavior: CollectionBehavior) {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.maybe_wait_on_main(|w| w.set_collection_behavior(behavior))
+    }
+
+    #[inline]
+    fn collection_behavior(&self) -> CollectionBehavior {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.maybe_wait_on_main(|w| w.collection_behavior())
+    }
+
+    #[inline]
+    fn order_out(&self) {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.maybe_wait_on_main(|w| w.order_out())
+    }
+
+    #[inline]
+    fn order_front_regardless(&self) {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.maybe_wait_on_main(|w| w.order_front_regardless())
+    }
 }
 
 /// Corresponds to `NSApplicationActivationPolicy`.
@@ -289,6 +379,85 @@ pub enum ActivationPolicy {
     Prohibited,
 }
 
+bitflags::bitflags! {
+    /// Corresponds to `NSWindowCollectionBehavior`.
+    ///
+    /// See [`WindowExtMacOS::set_collection_behavior`].
+    #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub struct CollectionBehavior: u32 {
+        /// Corresponds to `NSWindowCollectionBehaviorDefault`.
+        const DEFAULT = 0;
+        /// The window appears in all Spaces.
+        const CAN_JOIN_ALL_SPACES = 1 << 0;
+        /// Making the window active moves it to the active Space.
+        const MOVE_TO_ACTIVE_SPACE = 1 << 1;
+        /// The window participates in Spaces and Exposé.
+        const MANAGED = 1 << 2;
+        /// The window floats in Spaces and is hidden by Exposé.
+        const TRANSIENT = 1 << 3;
+        /// The window is unaffected by Exposé, and stays visible and stationary.
+        const STATIONARY = 1 << 4;
+        /// The window participates in cycling through windows with Cmd+`.
+        const PARTICIPATES_IN_CYCLE = 1 << 5;
+        /// The window is skipped when cycling through windows with Cmd+`.
+        const IGNORES_CYCLE = 1 << 6;
+        /// The window can enter native fullscreen.
+        const FULL_SCREEN_PRIMARY = 1 << 7;
+        /// The window can be shown in the same Space as a fullscreen window.
+        const FULL_SCREEN_AUXILIARY = 1 << 8;
+        /// The window can't enter native fullscreen.
+        const FULL_SCREEN_NONE = 1 << 9;
+        /// The window can be tiled with another window in fullscreen.
+        const FULL_SCREEN_ALLOWS_TILING = 1 << 11;
+        /// The window can't be tiled with another window in fullscreen.
+        const FULL_SCREEN_DISALLOWS_TILING = 1 << 12;
+        /// The window is a primary window. Requires macOS 13 or later.
+        const PRIMARY = 1 << 16;
+        /// The window is an auxiliary window. Requires macOS 13 or later.
+        const AUXILIARY = 1 << 17;
+        /// The window can be shown over other applications' fullscreen windows. Requires macOS 13
+        /// or later.
+        const CAN_JOIN_ALL_APPLICATIONS = 1 << 18;
+    }
+}
+
+/// An AppKit window level, see [`WindowAttributesExtMacOS::with_native_window_level`].
+///
+/// Windows with a higher level are shown above windows with a lower level. The cross-platform
+/// [`WindowLevel`] maps to these as follows:
+///
+/// | [`WindowLevel`]                   | AppKit level                   |
+/// | --------------------------------- | ------------------------------ |
+/// | [`AlwaysOnBottom`]                | `NSNormalWindowLevel - 1`      |
+/// | [`Normal`]                        | `NSNormalWindowLevel` (0)      |
+/// | [`AlwaysOnTop`]                   | `NSFloatingWindowLevel` (3)    |
+///
+/// [`WindowLevel`]: crate::window::WindowLevel
+/// [`AlwaysOnBottom`]: crate::window::WindowLevel::AlwaysOnBottom
+/// [`Normal`]: crate::window::WindowLevel::Normal
+/// [`AlwaysOnTop`]: crate::window::WindowLevel::AlwaysOnTop
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum NativeWindowLevel {
+    /// Corresponds to `NSNormalWindowLevel` (0).
+    Normal,
+    /// Corresponds to `NSFloatingWindowLevel` (3).
+    Floating,
+    /// Corresponds to `NSModalPanelWindowLevel` (8).
+    ModalPanel,
+    /// Corresponds to `NSMainMenuWindowLevel` (24).
+    MainMenu,
+    /// Corresponds to `NSStatusWindowLevel` (25).
+    Status,
+    /// Corresponds to `NSPopUpMenuWindowLevel` (101).
+    PopUpMenu,
+    /// Corresponds to `NSScreenSaverWindowLevel` (1000).
+    ScreenSaver,
+    /// Any other level, passed to AppKit as-is.
+    Raw(i64),
+}
+
 /// Additional methods on [`WindowAttributes`] that are specific to MacOS.
 ///
 /// **Note:** Properties dealing with the titlebar will be overwritten by the
@@ -334,6 +503,16 @@ pub trait WindowAttributesExtMacOS {
     /// [`NSPanel`]: https://developer.apple.com/documentation/appkit/NSPanel?language=objc
     /// [`NonactivatingPanel`]: https://developer.apple.com/documentation/appkit/nswindow/stylemask-swift.struct/nonactivatingpanel?language=objc
     fn with_panel(self, panel: bool) -> Self;
+    /// Sets the initial [`NSWindow.level`], taking precedence over
+    /// [`WindowAttributes::with_window_level`].
+    ///
+    /// This allows levels that [`WindowLevel`] can't express, see [`NativeWindowLevel`] for how
+    /// both map to AppKit levels. Calling [`Window::set_window_level`] later replaces it.
+    ///
+    /// [`NSWindow.level`]: https://developer.apple.com/documentation/appkit/nswindow/level?language=objc
+    /// [`WindowLevel`]: crate::window::WindowLevel
+    /// [`Window::set_window_level`]: crate::window::Window::set_window_level
+    fn with_native_window_level(self, level: NativeWindowLevel) -> Self;
 }
 
 impl WindowAttributesExtMacOS for WindowAttributes {
@@ -420,6 +599,12 @@ impl WindowAttributesExtMacOS for WindowAttributes {
         self.platform_specific.panel = panel;
         self
     }
+
+    #[inline]
+    fn with_native_window_level(mut self, level: NativeWindowLevel) -> Self {
+        self.platform_specific.native_window_level = Some(level);
+        self
+    }
 }
 
 pub trait EventLoopBuilderExtMacOS {
@@ -475,6 +660,13 @@ pub trait EventLoopBuilderExtMacOS {
     ///
     /// The default behavior is to ignore other applications and activate when launched.
     fn with_activate_ignoring_other_apps(&mut self, ignore: bool) -> &mut Self;
+
+    /// Used to deliver [`ApplicationHandlerExtMacOS::reopen`].
+    ///
+    /// Winit has to replace the handler AppKit registers for the reopen Apple Event to be told
+    /// about it, and then emulates what AppKit does. This is disabled by default, so that AppKit
+    /// keeps handling the event itself unless the application asks for it.
+    fn with_reopen_events(&mut self, enable: bool) -> &mut Self;
 }
 
 impl EventLoopBuilderExtMacOS for EventLoopBuilder {
@@ -495,6 +687,12 @@ impl EventLoopBuilderExtMacOS for EventLoopBuilder {
         self.platform_specific.activate_ignoring_other_apps = ignore;
         self
     }
+
+    #[inline]
+    fn with_reopen_events(&mut self, enable: bool) -> &mut Self {
+        self.platform_specific.reopen_events = enable;
+        self
+    }
 }
 
 /// Additional methods on [`MonitorHandle`] that are specific to MacOS.
@@ -635,4 +833,35 @@ pub trait ApplicationHandlerExtMacOS: ApplicationHandler {
         let _ = window_id;
         let _ = action;
     }
+
+    /// The application was reopened, usually because the user clicked its icon in the dock while it
+    /// was already running.
+    ///
+    /// `has_visible_windows` is `false` if none of the windows of the application are visible,
+    /// for example because they have all been closed or minimized. Single-window applications
+    /// will commonly want to recreate or show their window in that case.
+    ///
+    /// This corresponds to the [`applicationShouldHandleReopen:hasVisibleWindows:`] method on
+    /// `NSApplicationDelegate`. The default AppKit behavior of deminiaturizing a window when none
+    /// are visible still applies, and an application delegate registered by the user is still
+    /// consulted about it.
+    ///
+    /// This is only delivered when enabled with [`EventLoopBuilderExtMacOS::with_reopen_events`].
+    ///
+    /// ## Ordering
+    ///
+    /// This is only delivered once the application has finished launching, i.e. after
+    /// [`ApplicationHandler::can_create_surfaces`] (macOS does not emit
+    /// [`ApplicationHandler::resumed`], this is its equivalent here). It is
+    /// dispatched like other events, in the order the system sends them, so any
+    /// [`WindowEvent`]s caused by AppKit deminiaturizing a window will arrive in
+    /// [`ApplicationHandler::window_event`] after this.
+    ///
+    /// [`applicationShouldHandleReopen:hasVisibleWindows:`]: https://developer.apple.com/documentation/appkit/nsapplicationdelegate/1428638-applicationshouldhandlereopen?language=objc
+    /// [`WindowEvent`]: crate::event::WindowEvent
+    #[doc(alias = "applicationShouldHandleReopen:hasVisibleWindows:")]
+    fn reopen(&mut self, event_loop: &dyn ActiveEventLoop, has_visible_windows: bool) {
+        let _ = event_loop;
+        let _ = has_visible_windows;
+    }
 }
@@ -8,6 +8,8 @@ pub mod android;
 pub mod ios;
 #[cfg(any(macos_platform, docsrs))]
 pub mod macos;
+#[cfg(any(null_platform, docsrs))]
+pub mod null;
 #[cfg(any(orbital_platform, docsrs))]
 pub mod orbital;
 #[cfg(any(x11_platform, wayland_platform, docsrs))]
@@ -28,6 +30,7 @@ pub mod x11;
     android_platform,
     x11_platform,
     wayland_platform,
+    null_platform,
     docsrs,
 ))]
 pub mod run_on_demand;
@@ -38,6 +41,7 @@ pub mod run_on_demand;
     android_platform,
     x11_platform,
     wayland_platform,
+    null_platform,
     docsrs,
 ))]
 pub mod pump_events;
@@ -0,0 +1,148 @@
+//! # Null
+//!
+//! A backend that doesn't connect to any display server, enabled with the `null` Cargo feature.
+//! It is useful to run applications and their tests in CI or on servers, where neither X11 nor
+//! Wayland is available. It is only used when selected with
+//! [`EventLoopBuilderExtNull::with_headless`].
+//!
+//! Windows only exist in memory: their attributes can be changed and queried, and changes
+//! requested by the application, such as [`Window::request_surface_size`], are applied
+//! immediately. Nothing is ever rendered, and [`raw-window-handle`] handles are not available.
+//!
+//! Since there is no input, the only events delivered on their own are
+//! [`WindowEvent::RedrawRequested`] and [`WindowEvent::Destroyed`]. Everything else has to be
+//! injected with an [`EventInjector`], for example to simulate the user resizing or closing a
+//! window.
+//!
+//! There is a single virtual monitor, with a size of 1920x1080 and a scale factor of `1.0` by
+//! default, see [`EventInjector::set_monitor`].
+//!
+//! [`Window::request_surface_size`]: crate::window::Window::request_surface_size
+//! [`raw-window-handle`]: https://crates.io/crates/raw-window-handle
+//! [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
+//! [`WindowEvent::Destroyed`]: crate::event::WindowEvent::Destroyed
+use std::fmt;
+use std::sync::Arc;
+
+use crate::dpi::PhysicalSize;
+use crate::event::WindowEvent;
+use crate::event_loop::{ActiveEventLoop, EventLoop, EventLoopBuilder};
+use crate::platform_impl::null::Shared;
+use crate::window::WindowId;
+
+/// Additional methods on [`ActiveEventLoop`] that are specific to the headless backend.
+pub trait ActiveEventLoopExtNull {
+    /// True if the [`ActiveEventLoop`] uses the headless backend.
+    fn is_headless(&self) -> bool;
+
+    /// Returns an injector for events of this event loop, or `None` if it doesn't use the
+    /// headless backend.
+    fn event_injector(&self) -> Option<EventInjector>;
+}
+
+impl ActiveEventLoopExtNull for dyn ActiveEventLoop + '_ {
+    #[inline]
+    fn is_headless(&self) -> bool {
+        self.as_any().downcast_ref::<crate::platform_impl::null::ActiveEventLoop>().is_some()
+    }
+
+    #[inline]
+    fn event_injector(&self) -> Option<EventInjector> {
+        let event_loop =
+            self.as_any().downcast_ref::<crate::platform_impl::null::ActiveEventLoop>()?;
+        Some(EventInjector { shared: Arc::clone(event_loop.shared()) })
+    }
+}
+
+/// Additional methods on [`EventLoop`] that are specific to the headless backend.
+pub trait EventLoopExtNull {
+    /// True if the [`EventLoop`] uses the headless backend.
+    fn is_headless(&self) -> bool;
+
+    /// Returns an injector for events of this event loop, or `None` if it doesn't use the
+    /// headless backend.
+    ///
+    /// This can be used before running the event loop, e.g. to configure the virtual monitor or
+    /// to hand the injector to another thread.
+    fn event_injector(&self) -> Option<EventInjector>;
+}
+
+impl EventLoopExtNull for EventLoop {
+    #[inline]
+    fn is_headless(&self) -> bool {
+        self.event_loop.is_null()
+    }
+
+    #[inline]
+    fn event_injector(&self) -> Option<EventInjector> {
+        match &self.event_loop {
+            crate::platform_impl::EventLoop::Null(event_loop) => {
+                Some(EventInjector { shared: Arc::clone(event_loop.shared()) })
+            },
+            #[cfg(any(x11_platform, wayland_platform))]
+            _ => None,
+        }
+    }
+}
+
+/// Additional methods on [`EventLoopBuilder`] that are specific to the headless backend.
+pub trait EventLoopBuilderExtNull {
+    /// Force using the headless backend, regardless of the available display servers.
+    ///
+    /// Unlike other backends, a headless event loop can be created on any thread, and as many
+    /// times as needed, e.g. once per test. Only one event loop can run on a thread at a time.
+    fn with_headless(&mut self) -> &mut Self;
+}
+
+impl EventLoopBuilderExtNull for EventLoopBuilder {
+    #[inline]
+    fn with_headless(&mut self) -> &mut Self {
+        self.platform_specific.forced_backend = Some(crate::platform_impl::Backend::Null);
+        self
+    }
+}
+
+/// Injects events into a headless event loop.
+///
+/// The injector can be cloned and sent to other threads. Injected events are queued, and
+/// delivered to [`ApplicationHandler::window_event`] in order on the next iteration of the event
+/// loop, which is woken up if needed.
+///
+/// Before delivering an event, the state of the window is updated to match it:
+///
+/// - [`WindowEvent::SurfaceResized`] changes [`Window::surface_size`].
+/// - [`WindowEvent::Moved`] changes [`Window::outer_position`].
+/// - [`WindowEvent::Focused`] changes [`Window::has_focus`].
+///
+/// Other events, such as [`WindowEvent::CloseRequested`], are delivered as is. Events for windows
+/// that don't exist are still delivered.
+///
+/// [`ApplicationHandler::window_event`]: crate::application::ApplicationHandler::window_event
+/// [`Window::surface_size`]: crate::window::Window::surface_size
+/// [`Window::outer_position`]: crate::window::Window::outer_position
+/// [`Window::has_focus`]: crate::window::Window::has_focus
+#[derive(Clone)]
+pub struct EventInjector {
+    shared: Arc<Shared>,
+}
+
+impl EventInjector {
+    /// Queue a window event.
+    pub fn send_window_event(&self, window_id: WindowId, event: WindowEvent) {
+        self.shared.send_event(window_id, event);
+    }
+
+    /// Change the virtual monitor.
+    ///
+    /// This affects monitor queries and the scale factor of windows created afterwards, no
+    /// events are sent.
+    pub fn set_monitor(&self, size: PhysicalSize<u32>, scale_factor: f64) {
+        self.shared.set_monitor(size, scale_factor);
+    }
+}
+
+impl fmt::Debug for EventInjector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventInjector").finish_non_exhaustive()
+    }
+}
@@ -1,7 +1,7 @@
 use std::time::Duration;
 
-use crate::application::ApplicationHandler;
-use crate::event_loop::EventLoop;
+use crate::application::{ApplicationHandler, FilteredApp};
+use crate::event_loop::{EventLoop, RunningGuard};
 
 /// Additional methods on [`EventLoop`] for pumping events within an external event loop
 pub trait EventLoopExtPumpEvents {
@@ -61,6 +61,11 @@ pub trait EventLoopExtPumpEvents {
     ///     then handled that later since there would always be a chance that
     ///     other lifecycle events occur while the event is buffered.
     ///
+    /// ## Panics
+    ///
+    /// Panics if called while an event loop is already running on the current thread, e.g. from
+    /// within an [`ApplicationHandler`] method.
+    ///
     /// ## Supported Platforms
     ///
     /// - Windows
@@ -112,7 +117,11 @@ impl EventLoopExtPumpEvents for EventLoop {
         timeout: Option<Duration>,
         app: A,
     ) -> PumpStatus {
-        self.event_loop.pump_app_events(timeout, app)
+        let _running = RunningGuard::enter("EventLoopExtPumpEvents::pump_app_events");
+        let mut app = FilteredApp::new(app, self.event_filter.take());
+        let status = self.event_loop.pump_app_events(timeout, &mut app);
+        self.event_filter = app.filter;
+        status
     }
 }
 
@@ -1,9 +1,11 @@
-use crate::application::ApplicationHandler;
+use crate::application::{ApplicationHandler, FilteredApp};
 use crate::error::EventLoopError;
-use crate::event_loop::EventLoop;
+use crate::event_loop::{EventLoop, RunningGuard};
 #[cfg(doc)]
 use crate::{
-    event_loop::ActiveEventLoop, platform::pump_events::EventLoopExtPumpEvents, window::Window,
+    event_loop::{ActiveEventLoop, ControlFlow, EventLoopProxy},
+    platform::pump_events::EventLoopExtPumpEvents,
+    window::Window,
 };
 
 /// Additional methods on [`EventLoop`] to return control flow to the caller.
@@ -19,6 +21,12 @@ pub trait EventLoopExtRunOnDemand {
     /// Winit application, but internally each instantiation may re-use some common window
     /// system resources, such as a display server connection.
     ///
+    /// Each run starts with a cleared exit request, and the control flow is reset to
+    /// [`ControlFlow::default()`] when a run exits, so a control flow set before the first run is
+    /// honored. What persists across runs is the connection to the windowing system along
+    /// with the state tracked from it, like monitors and keyboard layouts, as well as the
+    /// [`EventLoopProxy`]s created from the event loop, which can keep waking it up.
+    ///
     /// This API is not designed to run an event loop in bursts that you can exit from and return
     /// to while maintaining the full state of your application. (If you need something like this
     /// you can look at the [`EventLoopExtPumpEvents::pump_app_events()`] API)
@@ -43,6 +51,10 @@ pub trait EventLoopExtRunOnDemand {
     /// You are strongly encouraged to use [`EventLoop::run_app()`] for portability, unless you
     /// specifically need the ability to re-run a single event loop more than once
     ///
+    /// # Panics
+    ///
+    /// Panics if an event loop is already running on the current thread.
+    ///
     /// # Supported Platforms
     /// - Windows
     /// - Linux
@@ -65,7 +77,11 @@ pub trait EventLoopExtRunOnDemand {
 
 impl EventLoopExtRunOnDemand for EventLoop {
     fn run_app_on_demand<A: ApplicationHandler>(&mut self, app: A) -> Result<(), EventLoopError> {
-        self.event_loop.run_app_on_demand(app)
+        let _running = RunningGuard::enter("EventLoopExtRunOnDemand::run_app_on_demand");
+        let mut app = FilteredApp::new(app, self.event_filter.take());
+        let result = self.event_loop.run_app_on_demand(&mut app);
+        self.event_filter = app.filter;
+        result
     }
 }
 
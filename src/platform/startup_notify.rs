@@ -47,6 +47,18 @@ pub trait WindowExtStartupNotify {
     ///
     /// The token will be delivered inside
     fn request_activation_token(&self) -> Result<AsyncRequestSerial, RequestError>;
+
+    /// Focus the window with the given [`ActivationToken`].
+    ///
+    /// Unlike [`Window::focus_window`], this passes the focus stealing prevention of the
+    /// compositor or window manager, as long as the token is still valid. The token should come
+    /// from a recent user interaction, either requested with
+    /// [`WindowExtStartupNotify::request_activation_token`] on the window the user interacted
+    /// with, or handed over by another process, like the one that launched this application, see
+    /// [`EventLoopExtStartupNotify::read_token_from_env`].
+    ///
+    /// Each token can only be used once.
+    fn focus_window_with_token(&self, token: ActivationToken) -> Result<(), RequestError>;
 }
 
 pub trait WindowAttributesExtStartupNotify {
@@ -89,6 +101,23 @@ impl WindowExtStartupNotify for dyn Window + '_ {
 
         Err(NotSupportedError::new("startup notify is not supported").into())
     }
+
+    fn focus_window_with_token(&self, token: ActivationToken) -> Result<(), RequestError> {
+        #[cfg(wayland_platform)]
+        if let Some(window) = self.as_any().downcast_ref::<crate::platform_impl::wayland::Window>()
+        {
+            return window.focus_window_with_token(token);
+        }
+
+        #[cfg(x11_platform)]
+        if let Some(window) =
+            self.as_any().downcast_ref::<crate::platform_impl::x11::window::Window>()
+        {
+            return window.focus_window_with_token(token);
+        }
+
+        Err(NotSupportedError::new("startup notify is not supported").into())
+    }
 }
 
 impl WindowAttributesExtStartupNotify for WindowAttributes {
@@ -13,6 +13,14 @@
 //! * `wayland-csd-adwaita` (default).
 //! * `wayland-csd-adwaita-crossfont`.
 //! * `wayland-csd-adwaita-notitle`.
+use std::ffi::c_void;
+use std::ptr::NonNull;
+use std::time::Duration;
+
+use sctk::reexports::client::Proxy;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use crate::event_loop::{ActiveEventLoop, EventLoop, EventLoopBuilder};
 use crate::monitor::MonitorHandle;
 pub use crate::window::Theme;
@@ -22,6 +30,16 @@ use crate::window::{Window as CoreWindow, WindowAttributes};
 pub trait ActiveEventLoopExtWayland {
     /// True if the [`ActiveEventLoop`] uses Wayland.
     fn is_wayland(&self) -> bool;
+
+    /// Returns whether using [`CursorGrabMode::Locked`] returns raw, un-accelerated mouse input.
+    ///
+    /// This requires the compositor to support both the `relative-pointer` and the
+    /// `pointer-constraints` protocols. The raw deltas are delivered through
+    /// [`DeviceEvent::PointerMotion`]. Returns `false` if the event loop doesn't use Wayland.
+    ///
+    /// [`CursorGrabMode::Locked`]: crate::window::CursorGrabMode::Locked
+    /// [`DeviceEvent::PointerMotion`]: crate::event::DeviceEvent::PointerMotion
+    fn is_cursor_lock_raw(&self) -> bool;
 }
 
 impl ActiveEventLoopExtWayland for dyn ActiveEventLoop + '_ {
@@ -29,6 +47,13 @@ impl ActiveEventLoopExtWayland for dyn ActiveEventLoop + '_ {
     fn is_wayland(&self) -> bool {
         self.as_any().downcast_ref::<crate::platform_impl::wayland::ActiveEventLoop>().is_some()
     }
+
+    #[inline]
+    fn is_cursor_lock_raw(&self) -> bool {
+        self.as_any()
+            .downcast_ref::<crate::platform_impl::wayland::ActiveEventLoop>()
+            .is_some_and(|event_loop| event_loop.is_cursor_lock_raw())
+    }
 }
 
 /// Additional methods on [`EventLoop`] that are specific to Wayland.
@@ -54,6 +79,14 @@ pub trait EventLoopBuilderExtWayland {
     /// By default, the window is only allowed to be created on the main
     /// thread, to make platform compatibility easier.
     fn with_any_thread(&mut self, any_thread: bool) -> &mut Self;
+
+    /// The maximum time between two presses of the same button for them to be counted as a
+    /// multi-click in [`WindowEvent::PointerButton::click_count`].
+    ///
+    /// Wayland has no way to query the system setting, so this defaults to 500 milliseconds.
+    ///
+    /// [`WindowEvent::PointerButton::click_count`]: crate::event::WindowEvent::PointerButton
+    fn with_double_click_interval(&mut self, interval: Duration) -> &mut Self;
 }
 
 impl EventLoopBuilderExtWayland for EventLoopBuilder {
@@ -68,14 +101,125 @@ impl EventLoopBuilderExtWayland for EventLoopBuilder {
         self.platform_specific.any_thread = any_thread;
         self
     }
+
+    #[inline]
+    fn with_double_click_interval(&mut self, interval: Duration) -> &mut Self {
+        self.platform_specific.double_click_interval = Some(interval);
+        self
+    }
 }
 
 /// Additional methods on [`Window`] that are specific to Wayland.
 ///
+/// The raw handles returned here are meant for integrating compositor protocols that Winit doesn't
+/// support itself. Winit stays in charge of the surface state: changing it through these handles,
+/// e.g. by attaching buffers, committing, or requesting frame callbacks, is unsupported and may
+/// desync Winit's frame callbacks and configure handling.
+///
+/// The raw handles are `None` if the window isn't a Wayland window.
+///
 /// [`Window`]: crate::window::Window
-pub trait WindowExtWayland {}
+pub trait WindowExtWayland {
+    /// The `wl_surface` of the window, as a `wl_proxy` pointer.
+    ///
+    /// This is the same surface as in the [`raw-window-handle`] window handle.
+    ///
+    /// [`raw-window-handle`]: https://docs.rs/raw-window-handle
+    fn wl_surface(&self) -> Option<NonNull<c_void>>;
+
+    /// The `xdg_surface` of the window, as a `wl_proxy` pointer.
+    fn xdg_surface(&self) -> Option<NonNull<c_void>>;
+
+    /// The `xdg_toplevel` of the window, as a `wl_proxy` pointer.
+    fn xdg_toplevel(&self) -> Option<NonNull<c_void>>;
 
-impl WindowExtWayland for dyn CoreWindow + '_ {}
+    /// Change the application ID of the window after it has been created.
+    ///
+    /// This replaces the `general` name from [`WindowAttributesExtWayland::with_name`], and
+    /// should match the `.desktop` file the window is associated with. If the window hasn't been
+    /// configured by the compositor yet, the change is applied once it is.
+    ///
+    /// This does nothing if the window isn't a Wayland window.
+    fn set_app_id(&self, app_id: &str);
+
+    /// Re-color the client-side decorations with the given theme variant.
+    ///
+    /// By default the decorations follow the system preference, use [`Window::set_theme`] with
+    /// `None` to go back to that. The theme set here is also returned by [`Window::theme`].
+    ///
+    /// Returns `false` and does nothing if the decorations can't be themed, i.e. when the
+    /// compositor draws server-side decorations, or when Winit was built without one of the
+    /// `wayland-csd-adwaita` features. Also returns `false` if the window isn't a Wayland window.
+    ///
+    /// [`Window::set_theme`]: crate::window::Window::set_theme
+    /// [`Window::theme`]: crate::window::Window::theme
+    fn set_csd_theme(&self, theme: CsdTheme) -> bool;
+
+    /// The preferred scale last sent by the compositor through `wp_fractional_scale_v1`, e.g.
+    /// `1.25`.
+    ///
+    /// Returns `None` when the compositor doesn't support the protocol, in which case the scale
+    /// factor comes from the integer `wl_output` scale, or when it hasn't sent a scale yet. Also
+    /// returns `None` if the window isn't a Wayland window.
+    ///
+    /// When this is `Some`, it is the same value as [`Window::scale_factor`] and
+    /// [`WindowEvent::ScaleFactorChanged`], which are not rounded, so this is mostly useful to
+    /// know that the scale is exact rather than derived from the output.
+    ///
+    /// [`Window::scale_factor`]: crate::window::Window::scale_factor
+    /// [`WindowEvent::ScaleFactorChanged`]: crate::event::WindowEvent::ScaleFactorChanged
+    fn fractional_scale(&self) -> Option<f64>;
+}
+
+impl WindowExtWayland for dyn CoreWindow + '_ {
+    #[inline]
+    fn wl_surface(&self) -> Option<NonNull<c_void>> {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::wayland::Window>()?;
+        NonNull::new(window.surface().id().as_ptr().cast())
+    }
+
+    #[inline]
+    fn xdg_surface(&self) -> Option<NonNull<c_void>> {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::wayland::Window>()?;
+        NonNull::new(window.xdg_surface().id().as_ptr().cast())
+    }
+
+    #[inline]
+    fn xdg_toplevel(&self) -> Option<NonNull<c_void>> {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::wayland::Window>()?;
+        NonNull::new(window.xdg_toplevel().id().as_ptr().cast())
+    }
+
+    #[inline]
+    fn fractional_scale(&self) -> Option<f64> {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::wayland::Window>()?;
+        window.fractional_scale()
+    }
+
+    #[inline]
+    fn set_csd_theme(&self, theme: CsdTheme) -> bool {
+        let theme = if theme.light { Theme::Light } else { Theme::Dark };
+        self.as_any()
+            .downcast_ref::<crate::platform_impl::wayland::Window>()
+            .is_some_and(|window| window.set_csd_theme(theme))
+    }
+
+    #[inline]
+    fn set_app_id(&self, app_id: &str) {
+        if let Some(window) = self.as_any().downcast_ref::<crate::platform_impl::wayland::Window>()
+        {
+            window.set_app_id(app_id);
+        }
+    }
+}
+
+/// The theme variant of the client-side decorations, see [`WindowExtWayland::set_csd_theme`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CsdTheme {
+    /// Whether to use the light variant instead of the dark one.
+    pub light: bool,
+}
 
 /// Additional methods on [`WindowAttributes`] that are specific to Wayland.
 pub trait WindowAttributesExtWayland {
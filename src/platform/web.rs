@@ -12,7 +12,12 @@
 //! Winit [insert it into the DOM for you][insert], or [retrieve the canvas][get] and insert it
 //! yourself.
 //!
+//! To render from a worker, a window can additionally be given an [`OffscreenCanvas`][offscreen]
+//! with [`WindowAttributesExtWeb::with_offscreen_canvas()`], which is then used for its window
+//! handle.
+//!
 //! [canvas]: https://developer.mozilla.org/en-US/docs/Web/API/HTMLCanvasElement
+//! [offscreen]: https://developer.mozilla.org/en-US/docs/Web/API/OffscreenCanvas
 //! [with_canvas]: WindowAttributesExtWeb::with_canvas
 //! [get]: WindowExtWeb::canvas
 //! [insert]: WindowAttributesExtWeb::with_append
@@ -52,9 +57,9 @@ use std::time::Duration;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 #[cfg(web_platform)]
-use web_sys::HtmlCanvasElement;
+use web_sys::{HtmlCanvasElement, OffscreenCanvas};
 
-use crate::application::ApplicationHandler;
+use crate::application::{ApplicationHandler, FilteredApp};
 use crate::cursor::CustomCursorSource;
 use crate::error::NotSupportedError;
 use crate::event_loop::{ActiveEventLoop, EventLoop};
@@ -67,17 +72,29 @@ use crate::platform_impl::{
     MonitorPermissionFuture as PlatformMonitorPermissionFuture,
     OrientationLockFuture as PlatformOrientationLockFuture,
 };
+pub use crate::window::BadAnimation;
 use crate::window::{CustomCursor, Window, WindowAttributes};
 
 #[cfg(not(web_platform))]
 #[doc(hidden)]
 pub struct HtmlCanvasElement;
 
+#[cfg(not(web_platform))]
+#[doc(hidden)]
+pub struct OffscreenCanvas;
+
 pub trait WindowExtWeb {
     /// Only returns the canvas if called from inside the window context (the
     /// main thread).
     fn canvas(&self) -> Option<Ref<'_, HtmlCanvasElement>>;
 
+    /// Returns the [`OffscreenCanvas`] passed to
+    /// [`WindowAttributesExtWeb::with_offscreen_canvas()`], if any.
+    ///
+    /// Only returns the canvas if called from inside the window context (the main thread).
+    #[cfg_attr(not(web_platform), doc = "", doc = "[`OffscreenCanvas`]: #only-available-on-wasm")]
+    fn offscreen_canvas(&self) -> Option<Ref<'_, OffscreenCanvas>>;
+
     /// Returns [`true`] if calling `event.preventDefault()` is enabled.
     ///
     /// See [`WindowExtWeb::set_prevent_default()`] for more details.
@@ -100,6 +117,47 @@ pub trait WindowExtWeb {
     ///
     /// [`CursorGrabMode::Locked`]: crate::window::CursorGrabMode::Locked
     fn is_cursor_lock_raw(&self) -> bool;
+
+    /// Returns what caused the last [`WindowEvent::ScaleFactorChanged`] of this window.
+    ///
+    /// Browsers don't report this directly, so it is inferred when the `devicePixelRatio` changes,
+    /// see [`ScaleChangeSource`] for details. Returns [`ScaleChangeSource::Unknown`] before the
+    /// first change.
+    ///
+    /// [`WindowEvent::ScaleFactorChanged`]: crate::event::WindowEvent::ScaleFactorChanged
+    fn last_scale_change_source(&self) -> ScaleChangeSource;
+
+    /// Sets whether Winit resizes the canvas.
+    ///
+    /// When enabled, [`Window::request_surface_size()`], [`Window::set_min_surface_size()`],
+    /// [`Window::set_max_surface_size()`] and [`SurfaceSizeWriter`] set the CSS `width`,
+    /// `height`, `min-width`, `min-height`, `max-width` and `max-height` properties of the canvas.
+    ///
+    /// When disabled, Winit never sets these properties and these requests are ignored. The
+    /// canvas size is then left to the CSS of the page, and Winit only reads it to report
+    /// [`WindowEvent::SurfaceResized`]. Requests made while disabled are not applied when it is
+    /// re-enabled.
+    ///
+    /// This is independent of [`WindowExtWeb::set_prevent_default()`]. In particular, when
+    /// `preventDefault()` isn't called, Ctrl+Wheel zooms the page, which changes the size of the
+    /// canvas in physical pixels even if Winit doesn't resize it. This is still reported through
+    /// [`WindowEvent::ScaleFactorChanged`], but any size set through the [`SurfaceSizeWriter`] is
+    /// ignored.
+    ///
+    /// Enabled by default, see [`ActiveEventLoopExtWeb::set_automatic_canvas_resize()`].
+    ///
+    /// [`Window::request_surface_size()`]: crate::window::Window::request_surface_size
+    /// [`Window::set_min_surface_size()`]: crate::window::Window::set_min_surface_size
+    /// [`Window::set_max_surface_size()`]: crate::window::Window::set_max_surface_size
+    /// [`SurfaceSizeWriter`]: crate::event::SurfaceSizeWriter
+    /// [`WindowEvent::SurfaceResized`]: crate::event::WindowEvent::SurfaceResized
+    /// [`WindowEvent::ScaleFactorChanged`]: crate::event::WindowEvent::ScaleFactorChanged
+    fn set_automatic_canvas_resize(&self, automatic: bool);
+
+    /// Returns whether Winit resizes the canvas.
+    ///
+    /// See [`WindowExtWeb::set_automatic_canvas_resize()`].
+    fn automatic_canvas_resize(&self) -> bool;
 }
 
 impl WindowExtWeb for dyn Window + '_ {
@@ -111,6 +169,14 @@ impl WindowExtWeb for dyn Window + '_ {
             .canvas()
     }
 
+    #[inline]
+    fn offscreen_canvas(&self) -> Option<Ref<'_, OffscreenCanvas>> {
+        self.as_any()
+            .downcast_ref::<crate::platform_impl::Window>()
+            .expect("non Web window on Web")
+            .offscreen_canvas()
+    }
+
     fn prevent_default(&self) -> bool {
         self.as_any()
             .downcast_ref::<crate::platform_impl::Window>()
@@ -131,6 +197,27 @@ impl WindowExtWeb for dyn Window + '_ {
             .expect("non Web window on Web")
             .is_cursor_lock_raw()
     }
+
+    fn last_scale_change_source(&self) -> ScaleChangeSource {
+        self.as_any()
+            .downcast_ref::<crate::platform_impl::Window>()
+            .expect("non Web window on Web")
+            .last_scale_change_source()
+    }
+
+    fn set_automatic_canvas_resize(&self, automatic: bool) {
+        self.as_any()
+            .downcast_ref::<crate::platform_impl::Window>()
+            .expect("non Web window on Web")
+            .set_automatic_canvas_resize(automatic)
+    }
+
+    fn automatic_canvas_resize(&self) -> bool {
+        self.as_any()
+            .downcast_ref::<crate::platform_impl::Window>()
+            .expect("non Web window on Web")
+            .automatic_canvas_resize()
+    }
 }
 
 pub trait WindowAttributesExtWeb {
@@ -143,6 +230,33 @@ pub trait WindowAttributesExtWeb {
     #[cfg_attr(not(web_platform), doc = "", doc = "[`HtmlCanvasElement`]: #only-available-on-wasm")]
     fn with_canvas(self, canvas: Option<HtmlCanvasElement>) -> Self;
 
+    /// Pass an [`OffscreenCanvas`] to render to, e.g. one obtained with
+    /// [`HtmlCanvasElement::transferControlToOffscreen()`], so that rendering can be moved to a
+    /// worker. The window handle will then refer to the [`OffscreenCanvas`] instead of the
+    /// [`HtmlCanvasElement`].
+    ///
+    /// Winit never changes the `width` and `height` attributes of either canvas, only its CSS
+    /// size; keeping the [`OffscreenCanvas`] size in sync with [`WindowEvent::SurfaceResized`] is
+    /// up to the user.
+    ///
+    /// Input, [`WindowEvent::SurfaceResized`] and [`WindowEvent::Occluded`] still rely on the
+    /// [`HtmlCanvasElement`] being laid out in the DOM. If it isn't inserted into the Web page,
+    /// which is the case if none is passed with [`with_canvas()`](Self::with_canvas) and
+    /// [`with_append()`](Self::with_append) isn't used, these are unsupported.
+    ///
+    /// [`None`] by default.
+    ///
+    /// [`HtmlCanvasElement::transferControlToOffscreen()`]: https://developer.mozilla.org/en-US/docs/Web/API/HTMLCanvasElement/transferControlToOffscreen
+    /// [`WindowEvent::SurfaceResized`]: crate::event::WindowEvent::SurfaceResized
+    /// [`WindowEvent::Occluded`]: crate::event::WindowEvent::Occluded
+    #[cfg_attr(
+        not(web_platform),
+        doc = "",
+        doc = "[`HtmlCanvasElement`]: #only-available-on-wasm",
+        doc = "[`OffscreenCanvas`]: #only-available-on-wasm"
+    )]
+    fn with_offscreen_canvas(self, canvas: Option<OffscreenCanvas>) -> Self;
+
     /// Sets whether `event.preventDefault()` should be called on events on the
     /// canvas that have side effects.
     ///
@@ -161,6 +275,14 @@ pub trait WindowAttributesExtWeb {
     ///
     /// Disabled by default.
     fn with_append(self, append: bool) -> Self;
+
+    /// Sets whether Winit resizes the canvas, including when applying
+    /// [`WindowAttributes::with_surface_size()`] and the minimum and maximum surface sizes.
+    ///
+    /// See [`WindowExtWeb::set_automatic_canvas_resize()`] for details.
+    ///
+    /// Defaults to [`ActiveEventLoopExtWeb::automatic_canvas_resize()`].
+    fn with_automatic_canvas_resize(self, automatic: bool) -> Self;
 }
 
 impl WindowAttributesExtWeb for WindowAttributes {
@@ -169,6 +291,11 @@ impl WindowAttributesExtWeb for WindowAttributes {
         self
     }
 
+    fn with_offscreen_canvas(mut self, canvas: Option<OffscreenCanvas>) -> Self {
+        self.platform_specific.set_offscreen_canvas(canvas);
+        self
+    }
+
     fn with_prevent_default(mut self, prevent_default: bool) -> Self {
         self.platform_specific.prevent_default = prevent_default;
         self
@@ -183,6 +310,11 @@ impl WindowAttributesExtWeb for WindowAttributes {
         self.platform_specific.append = append;
         self
     }
+
+    fn with_automatic_canvas_resize(mut self, automatic: bool) -> Self {
+        self.platform_specific.automatic_canvas_resize = Some(automatic);
+        self
+    }
 }
 
 /// Additional methods on `EventLoop` that are specific to the Web.
@@ -213,7 +345,8 @@ pub trait EventLoopExtWeb {
 
     /// Sets the strategy for [`ControlFlow::Poll`].
     ///
-    /// See [`PollStrategy`].
+    /// See [`PollStrategy`]. Changing the strategy while polling takes effect on the next
+    /// iteration of the event loop.
     ///
     /// [`ControlFlow::Poll`]: crate::event_loop::ControlFlow::Poll
     fn set_poll_strategy(&self, strategy: PollStrategy);
@@ -264,7 +397,7 @@ pub trait EventLoopExtWeb {
 
 impl EventLoopExtWeb for EventLoop {
     fn spawn_app<A: ApplicationHandler + 'static>(self, app: A) {
-        self.event_loop.spawn_app(app);
+        self.event_loop.spawn_app(FilteredApp::new(app, self.event_filter));
     }
 
     fn set_poll_strategy(&self, strategy: PollStrategy) {
@@ -299,7 +432,8 @@ impl EventLoopExtWeb for EventLoop {
 pub trait ActiveEventLoopExtWeb {
     /// Sets the strategy for [`ControlFlow::Poll`].
     ///
-    /// See [`PollStrategy`].
+    /// See [`PollStrategy`]. Changing the strategy while polling takes effect on the next
+    /// iteration of the event loop.
     ///
     /// [`ControlFlow::Poll`]: crate::event_loop::ControlFlow::Poll
     fn set_poll_strategy(&self, strategy: PollStrategy);
@@ -325,6 +459,19 @@ pub trait ActiveEventLoopExtWeb {
     /// [`ControlFlow::WaitUntil`]: crate::event_loop::ControlFlow::WaitUntil
     fn wait_until_strategy(&self) -> WaitUntilStrategy;
 
+    /// Sets whether windows created afterwards resize their canvas, unless overridden with
+    /// [`WindowAttributesExtWeb::with_automatic_canvas_resize()`].
+    ///
+    /// See [`WindowExtWeb::set_automatic_canvas_resize()`] for details.
+    ///
+    /// Enabled by default.
+    fn set_automatic_canvas_resize(&self, automatic: bool);
+
+    /// Returns whether windows created afterwards resize their canvas by default.
+    ///
+    /// See [`ActiveEventLoopExtWeb::set_automatic_canvas_resize()`].
+    fn automatic_canvas_resize(&self) -> bool;
+
     /// Async version of [`ActiveEventLoop::create_custom_cursor()`] which waits until the
     /// cursor has completely finished loading.
     fn create_custom_cursor_async(&self, source: CustomCursorSource) -> CustomCursorFuture;
@@ -403,6 +550,24 @@ impl ActiveEventLoopExtWeb for dyn ActiveEventLoop + '_ {
         event_loop.wait_until_strategy()
     }
 
+    #[inline]
+    fn set_automatic_canvas_resize(&self, automatic: bool) {
+        let event_loop = self
+            .as_any()
+            .downcast_ref::<crate::platform_impl::ActiveEventLoop>()
+            .expect("non Web event loop on Web");
+        event_loop.set_automatic_canvas_resize(automatic);
+    }
+
+    #[inline]
+    fn automatic_canvas_resize(&self) -> bool {
+        let event_loop = self
+            .as_any()
+            .downcast_ref::<crate::platform_impl::ActiveEventLoop>()
+            .expect("non Web event loop on Web");
+        event_loop.automatic_canvas_resize()
+    }
+
     #[inline]
     fn is_cursor_lock_raw(&self) -> bool {
         let event_loop = self
@@ -440,6 +605,25 @@ impl ActiveEventLoopExtWeb for dyn ActiveEventLoop + '_ {
     }
 }
 
+/// What caused the `devicePixelRatio` to change, see
+/// [`WindowExtWeb::last_scale_change_source()`].
+///
+/// This is a best-effort guess: a browser zoom changes the ratio between the outer and inner size
+/// of the browser window, while moving it to a display with a different scale factor doesn't.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ScaleChangeSource {
+    /// The user changed the zoom level of the page.
+    BrowserZoom,
+    /// The browser window moved to a display with a different scale factor, or the scale factor of
+    /// the display changed.
+    DisplayChange,
+    /// The source couldn't be determined, e.g. because the outer size of the browser window isn't
+    /// accessible.
+    #[default]
+    Unknown,
+}
+
 /// Strategy used for [`ControlFlow::Poll`][crate::event_loop::ControlFlow::Poll].
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -465,6 +649,17 @@ pub enum PollStrategy {
     /// [`setTimeout()`]: https://developer.mozilla.org/en-US/docs/Web/API/setTimeout
     #[default]
     Scheduler,
+    /// Uses [`Window.requestAnimationFrame()`] to queue the next event loop.
+    ///
+    /// This strategy runs once per frame, aligned to the refresh rate of the display, which suits
+    /// continuously rendering applications. Browsers pause animation frames in background tabs,
+    /// so the event loop won't run at all while the page is hidden.
+    ///
+    /// [`ControlFlow::WaitUntil`][crate::event_loop::ControlFlow::WaitUntil] is unaffected and
+    /// keeps using its own [`WaitUntilStrategy`].
+    ///
+    /// [`Window.requestAnimationFrame()`]: https://developer.mozilla.org/en-US/docs/Web/API/Window/requestAnimationFrame
+    AnimationFrame,
 }
 
 /// Strategy used for [`ControlFlow::WaitUntil`][crate::event_loop::ControlFlow::WaitUntil].
@@ -537,27 +732,6 @@ impl CustomCursorExtWeb for CustomCursor {
     }
 }
 
-/// An error produced when using [`CustomCursor::from_animation`] with invalid arguments.
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub enum BadAnimation {
-    /// Produced when no cursors were supplied.
-    Empty,
-    /// Produced when a supplied cursor is an animation.
-    Animation,
-}
-
-impl fmt::Display for BadAnimation {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::Empty => write!(f, "No cursors supplied"),
-            Self::Animation => write!(f, "A supplied cursor is an animation"),
-        }
-    }
-}
-
-impl Error for BadAnimation {}
-
 #[cfg(not(web_platform))]
 struct PlatformCustomCursorFuture;
 
@@ -6,17 +6,21 @@ use std::borrow::Borrow;
 use std::ffi::c_void;
 use std::ops::Deref;
 use std::path::Path;
+use std::time::Duration;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 #[cfg(windows_platform)]
 use windows_sys::Win32::Foundation::HANDLE;
 
-use crate::dpi::PhysicalSize;
+use crate::dpi::{PhysicalSize, Position};
+use crate::error::RequestError;
 use crate::event::DeviceId;
 use crate::event_loop::EventLoopBuilder;
 use crate::monitor::MonitorHandle;
-use crate::window::{BadIcon, Icon, Window, WindowAttributes};
+use crate::window::{
+    BadAnimation, BadIcon, CustomCursor, CustomCursorSource, Icon, Window, WindowAttributes,
+};
 
 /// Window Handle type used by Win32 API
 pub type HWND = isize;
@@ -286,6 +290,15 @@ pub trait WindowExtWindows {
     /// Supported starting with Windows 11 Build 22000.
     fn set_corner_preference(&self, preference: CornerPreference);
 
+    /// Changes the position of the cursor in window coordinates, even if the window isn't focused.
+    ///
+    /// [`Window::set_cursor_position`] returns [`RequestError::Ignored`] when the window isn't
+    /// focused, since warping the cursor from the background takes it away from the application
+    /// the user is interacting with. Only use this when that is actually intended.
+    ///
+    /// [`RequestError::Ignored`]: crate::error::RequestError::Ignored
+    fn set_cursor_position_force(&self, position: Position) -> Result<(), RequestError>;
+
     /// Get the raw window handle for this [`Window`] without checking for thread affinity.
     ///
     /// Window handles in Win32 have a property called "thread affinity" that ties them to their
@@ -399,6 +412,12 @@ impl WindowExtWindows for dyn Window + '_ {
         window.set_corner_preference(preference)
     }
 
+    #[inline]
+    fn set_cursor_position_force(&self, position: Position) -> Result<(), RequestError> {
+        let window = self.as_any().downcast_ref::<crate::platform_impl::Window>().unwrap();
+        window.set_cursor_position_force(position)
+    }
+
     unsafe fn window_handle_any_thread(
         &self,
     ) -> Result<rwh_06::WindowHandle<'_>, rwh_06::HandleError> {
@@ -695,3 +714,46 @@ impl IconExtWindows for Icon {
         Ok(Icon { inner: win_icon })
     }
 }
+
+/// Additional methods on [`CustomCursor`] that are specific to Windows.
+pub trait CustomCursorExtWindows {
+    /// Returns if this cursor is an animation.
+    fn is_animation(&self) -> bool;
+
+    /// Creates a new animated cursor from multiple [`CustomCursor`]s, shown for equal parts of
+    /// `duration`. Supplied `cursors` can't be empty or other animations.
+    ///
+    /// The animation is driven by the system. If it can't be created natively, the window
+    /// advances the frames with a timer instead, which stops when the window shows another
+    /// cursor or is destroyed.
+    fn from_animation(
+        duration: Duration,
+        cursors: Vec<CustomCursor>,
+    ) -> Result<CustomCursorSource, BadAnimation>;
+}
+
+impl CustomCursorExtWindows for CustomCursor {
+    fn is_animation(&self) -> bool {
+        self.inner.is_animation()
+    }
+
+    fn from_animation(
+        duration: Duration,
+        cursors: Vec<CustomCursor>,
+    ) -> Result<CustomCursorSource, BadAnimation> {
+        if cursors.is_empty() {
+            return Err(BadAnimation::Empty);
+        }
+
+        if cursors.iter().any(CustomCursor::is_animation) {
+            return Err(BadAnimation::Animation);
+        }
+
+        Ok(CustomCursorSource {
+            inner: crate::platform_impl::PlatformCustomCursorSource::Animation {
+                duration,
+                cursors,
+            },
+        })
+    }
+}
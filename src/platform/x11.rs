@@ -1,11 +1,18 @@
 //! # X11
+use std::ffi::c_void;
+use std::ptr::NonNull;
+use std::time::Duration;
+
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::dpi::Size;
 use crate::event_loop::{ActiveEventLoop, EventLoop, EventLoopBuilder};
 use crate::monitor::MonitorHandle;
-use crate::window::{Window as CoreWindow, WindowAttributes};
+use crate::platform_impl::{PlatformCustomCursor, PlatformCustomCursorSource};
+use crate::window::{
+    BadAnimation, CustomCursor, CustomCursorSource, Icon, Window as CoreWindow, WindowAttributes,
+};
 
 /// X window type. Maps directly to
 /// [`_NET_WM_WINDOW_TYPE`](https://specifications.freedesktop.org/wm-spec/wm-spec-1.5.html).
@@ -52,6 +59,27 @@ pub enum WindowType {
     Normal,
 }
 
+/// How [`Window::request_user_attention`] is forwarded to the window manager.
+///
+/// Window managers differ in which of these they honor, so both can be enabled. The default only
+/// sets the urgency hint.
+///
+/// [`Window::request_user_attention`]: crate::window::Window::request_user_attention
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AttentionMechanism {
+    /// Set the ICCCM urgency hint in `WM_HINTS`.
+    pub urgency_hint: bool,
+    /// Add `_NET_WM_STATE_DEMANDS_ATTENTION` to the EWMH `_NET_WM_STATE` of the window.
+    pub net_wm_state: bool,
+}
+
+impl Default for AttentionMechanism {
+    fn default() -> Self {
+        Self { urgency_hint: true, net_wm_state: false }
+    }
+}
+
 /// The first argument in the provided hook will be the pointer to `XDisplay`
 /// and the second one the pointer to [`XErrorEvent`]. The returned `bool` is an
 /// indicator whether the error was handled by the callback.
@@ -87,6 +115,50 @@ pub fn register_xlib_error_hook(hook: XlibErrorHook) {
 pub trait ActiveEventLoopExtX11 {
     /// True if the [`ActiveEventLoop`] uses X11.
     fn is_x11(&self) -> bool;
+
+    /// The `xcb_connection_t` used by the event loop, or `None` if it doesn't use X11.
+    ///
+    /// This is meant for rendering stacks that need the connection directly, prefer the
+    /// [`raw-window-handle`] display handle where possible.
+    ///
+    /// The pointer is valid for as long as the event loop is alive, and is owned by Winit: it
+    /// must not be disconnected.
+    ///
+    /// [`raw-window-handle`]: https://docs.rs/raw-window-handle
+    fn xcb_connection(&self) -> Option<NonNull<c_void>>;
+
+    /// The Xlib `Display` used by the event loop, or `None` if it doesn't use X11.
+    ///
+    /// The pointer is valid for as long as the event loop is alive, and is owned by Winit: it
+    /// must not be closed. Use [`register_xlib_error_hook`] instead of `XSetErrorHandler` to
+    /// handle errors caused by requests made through it.
+    fn xlib_display(&self) -> Option<NonNull<c_void>>;
+
+    /// The current value of the [XSETTINGS] setting with the given name, such as `Xft/DPI`,
+    /// `Gtk/CursorThemeName` or `Net/ThemeName`.
+    ///
+    /// Returns `None` if the event loop doesn't use X11, if no settings manager is running or if
+    /// the setting isn't set. The settings are read from the server on every call.
+    ///
+    /// Note that `Xft/DPI` is stored in 1024ths of a dot per inch. Winit already takes it into
+    /// account for the scale factor, and emits [`WindowEvent::ScaleFactorChanged`] when it
+    /// changes.
+    ///
+    /// [XSETTINGS]: https://specifications.freedesktop.org/xsettings-spec/0.5/
+    /// [`WindowEvent::ScaleFactorChanged`]: crate::event::WindowEvent::ScaleFactorChanged
+    fn xsettings(&self, name: &str) -> Option<XSettingValue>;
+
+    /// Returns whether using [`CursorGrabMode::Locked`] returns raw, un-accelerated mouse input.
+    ///
+    /// Always returns `false`, as [`CursorGrabMode::Locked`] isn't supported on X11. Raw motion is
+    /// still available regardless of the grab mode: it's read from XInput2 raw events and
+    /// delivered through [`DeviceEvent::PointerMotion`], so [`CursorGrabMode::Confined`] combined
+    /// with a hidden cursor is the closest equivalent.
+    ///
+    /// [`CursorGrabMode::Locked`]: crate::window::CursorGrabMode::Locked
+    /// [`CursorGrabMode::Confined`]: crate::window::CursorGrabMode::Confined
+    /// [`DeviceEvent::PointerMotion`]: crate::event::DeviceEvent::PointerMotion
+    fn is_cursor_lock_raw(&self) -> bool;
 }
 
 impl ActiveEventLoopExtX11 for dyn ActiveEventLoop + '_ {
@@ -94,6 +166,68 @@ impl ActiveEventLoopExtX11 for dyn ActiveEventLoop + '_ {
     fn is_x11(&self) -> bool {
         self.as_any().downcast_ref::<crate::platform_impl::x11::ActiveEventLoop>().is_some()
     }
+
+    #[inline]
+    fn xcb_connection(&self) -> Option<NonNull<c_void>> {
+        let event_loop =
+            self.as_any().downcast_ref::<crate::platform_impl::x11::ActiveEventLoop>()?;
+        NonNull::new(event_loop.x_connection().xcb_connection().get_raw_xcb_connection())
+    }
+
+    #[inline]
+    fn xlib_display(&self) -> Option<NonNull<c_void>> {
+        let event_loop =
+            self.as_any().downcast_ref::<crate::platform_impl::x11::ActiveEventLoop>()?;
+        NonNull::new(event_loop.x_connection().display.cast())
+    }
+
+    #[inline]
+    fn xsettings(&self, name: &str) -> Option<XSettingValue> {
+        let event_loop =
+            self.as_any().downcast_ref::<crate::platform_impl::x11::ActiveEventLoop>()?;
+        event_loop.x_connection().xsettings(name)
+    }
+
+    #[inline]
+    fn is_cursor_lock_raw(&self) -> bool {
+        false
+    }
+}
+
+/// The value of an XSETTINGS setting, see [`ActiveEventLoopExtX11::xsettings`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum XSettingValue {
+    Integer(i32),
+    /// A string, with invalid UTF-8 replaced.
+    String(String),
+    /// A color as 16-bit red, green, blue and alpha channels.
+    Color([u16; 4]),
+}
+
+impl XSettingValue {
+    /// The value if the setting is an integer.
+    pub fn as_integer(&self) -> Option<i32> {
+        match self {
+            Self::Integer(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// The value if the setting is a string.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// The value if the setting is a color.
+    pub fn as_color(&self) -> Option<[u16; 4]> {
+        match self {
+            Self::Color(color) => Some(*color),
+            _ => None,
+        }
+    }
 }
 
 /// Additional methods on [`EventLoop`] that are specific to X11.
@@ -105,7 +239,7 @@ pub trait EventLoopExtX11 {
 impl EventLoopExtX11 for EventLoop {
     #[inline]
     fn is_x11(&self) -> bool {
-        !self.event_loop.is_wayland()
+        self.event_loop.is_x11()
     }
 }
 
@@ -138,9 +272,102 @@ impl EventLoopBuilderExtX11 for EventLoopBuilder {
 /// Additional methods on [`Window`] that are specific to X11.
 ///
 /// [`Window`]: crate::window::Window
-pub trait WindowExtX11 {}
+pub trait WindowExtX11 {
+    /// Change the `general` and `instance` names of the window after it has been created.
+    ///
+    /// This replaces the `WM_CLASS(STRING)` property that was set from
+    /// [`WindowAttributesExtX11::with_name`], e.g. for applications that switch between different
+    /// document types. Note that some window managers only read the property when the window is
+    /// mapped, and won't react to it changing afterwards.
+    ///
+    /// This does nothing if the window isn't an X11 window.
+    fn set_wm_class(&self, general: &str, instance: &str);
 
-impl WindowExtX11 for dyn CoreWindow {}
+    /// Choose how [`Window::request_user_attention`] is forwarded to the window manager.
+    ///
+    /// The change applies to the next call to [`Window::request_user_attention`], so set it
+    /// before requesting attention. Clearing the request only clears the mechanisms that are
+    /// enabled at that point.
+    ///
+    /// This does nothing if the window isn't an X11 window.
+    ///
+    /// [`Window::request_user_attention`]: crate::window::Window::request_user_attention
+    fn set_attention_mechanism(&self, mechanism: AttentionMechanism);
+
+    /// The X11 visual the window was created with.
+    ///
+    /// This is the visual requested with [`WindowAttributesExtX11::with_x11_visual`] if any.
+    /// Otherwise, for windows created with [`WindowAttributes::with_transparent`], winit picks
+    /// a 32-bit `TrueColor` visual with an alpha channel when the screen has one, and falls back
+    /// to the visual of the parent window like other windows do.
+    ///
+    /// Returns `None` if the window isn't an X11 window.
+    fn x11_visual_id(&self) -> Option<XVisualID>;
+
+    /// The X11 screen the window was created on, as chosen with
+    /// [`WindowAttributesExtX11::with_x11_screen`] or the default screen of the display.
+    ///
+    /// Returns `None` if the window isn't an X11 window.
+    fn x11_screen_id(&self) -> Option<i32>;
+
+    /// Set a larger icon for the taskbar and task switchers.
+    ///
+    /// X11 has a single `_NET_WM_ICON` property, which lists icons of different sizes for the
+    /// window manager and taskbar to pick from. The taskbar icon is listed after the icon set with
+    /// [`Window::set_window_icon`], which stays the small titlebar icon, so that each can pick a
+    /// crisp icon of the size it needs. The choice is up to them though. A good size here is
+    /// 256x256.
+    ///
+    /// Wayland has no equivalent, compositors only use the window icon or the desktop entry.
+    ///
+    /// This does nothing if the window isn't an X11 window.
+    ///
+    /// [`Window::set_window_icon`]: crate::window::Window::set_window_icon
+    fn set_taskbar_icon(&self, taskbar_icon: Option<Icon>);
+}
+
+impl WindowExtX11 for dyn CoreWindow {
+    #[inline]
+    fn set_wm_class(&self, general: &str, instance: &str) {
+        if let Some(window) =
+            self.as_any().downcast_ref::<crate::platform_impl::x11::window::Window>()
+        {
+            window.set_wm_class(general, instance);
+        }
+    }
+
+    #[inline]
+    fn set_attention_mechanism(&self, mechanism: AttentionMechanism) {
+        if let Some(window) =
+            self.as_any().downcast_ref::<crate::platform_impl::x11::window::Window>()
+        {
+            window.set_attention_mechanism(mechanism);
+        }
+    }
+
+    #[inline]
+    fn x11_visual_id(&self) -> Option<XVisualID> {
+        self.as_any()
+            .downcast_ref::<crate::platform_impl::x11::window::Window>()
+            .map(|window| window.visual_id())
+    }
+
+    #[inline]
+    fn x11_screen_id(&self) -> Option<i32> {
+        self.as_any()
+            .downcast_ref::<crate::platform_impl::x11::window::Window>()
+            .map(|window| window.screen_id())
+    }
+
+    #[inline]
+    fn set_taskbar_icon(&self, taskbar_icon: Option<Icon>) {
+        if let Some(window) =
+            self.as_any().downcast_ref::<crate::platform_impl::x11::window::Window>()
+        {
+            window.set_taskbar_icon(taskbar_icon.map(|icon| icon.inner));
+        }
+    }
+}
 
 /// Additional methods on [`WindowAttributes`] that are specific to X11.
 pub trait WindowAttributesExtX11 {
@@ -194,6 +421,25 @@ pub trait WindowAttributesExtX11 {
     /// # Ok(()) }
     /// ```
     fn with_embed_parent_window(self, parent_window_id: XWindow) -> Self;
+
+    /// Only switch to the scale factor of another monitor once at least `threshold` of the
+    /// window's area is on it.
+    ///
+    /// By default, the window takes the scale factor of the monitor it overlaps the most, which
+    /// can make it flip back and forth when dragged around the boundary between two monitors with
+    /// different scale factors. A threshold such as `0.6` avoids this, at the cost of keeping the
+    /// previous scale factor while the window is split between both monitors.
+    ///
+    /// The threshold is clamped to the `0.5..=1.0` range. It is checked whenever the window moves,
+    /// and the scale factor changes right away once it is crossed: there is no debounce delay,
+    /// since the window may stop moving before it elapses. This is only available on X11, since
+    /// on Windows the system picks the scale factor of a window.
+    fn with_scale_factor_hysteresis(self, threshold: f64) -> Self;
+
+    /// Build window with a larger icon for the taskbar and task switchers.
+    ///
+    /// See [`WindowExtX11::set_taskbar_icon`] for details.
+    fn with_taskbar_icon(self, taskbar_icon: Option<Icon>) -> Self;
 }
 
 impl WindowAttributesExtX11 for WindowAttributes {
@@ -239,6 +485,18 @@ impl WindowAttributesExtX11 for WindowAttributes {
         self.platform_specific.x11.embed_window = Some(parent_window_id);
         self
     }
+
+    #[inline]
+    fn with_scale_factor_hysteresis(mut self, threshold: f64) -> Self {
+        self.platform_specific.x11.scale_factor_hysteresis = Some(threshold.clamp(0.5, 1.0));
+        self
+    }
+
+    #[inline]
+    fn with_taskbar_icon(mut self, taskbar_icon: Option<Icon>) -> Self {
+        self.platform_specific.x11.taskbar_icon = taskbar_icon;
+        self
+    }
 }
 
 /// Additional methods on `MonitorHandle` that are specific to X11.
@@ -253,3 +511,49 @@ impl MonitorHandleExtX11 for MonitorHandle {
         self.inner.native_identifier()
     }
 }
+
+/// Additional methods on [`CustomCursor`] that are specific to X11.
+pub trait CustomCursorExtX11 {
+    /// Returns if this cursor is an animation.
+    fn is_animation(&self) -> bool;
+
+    /// Creates a new animated cursor from multiple [`CustomCursor`]s, shown for equal parts of
+    /// `duration`. Supplied `cursors` can't be empty or other animations.
+    ///
+    /// The animation is driven by the X server.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** When the same build runs on Wayland, only the first cursor is shown.
+    fn from_animation(
+        duration: Duration,
+        cursors: Vec<CustomCursor>,
+    ) -> Result<CustomCursorSource, BadAnimation>;
+}
+
+impl CustomCursorExtX11 for CustomCursor {
+    fn is_animation(&self) -> bool {
+        match &self.inner {
+            PlatformCustomCursor::X(cursor) => cursor.is_animation(),
+            #[cfg(wayland_platform)]
+            PlatformCustomCursor::Wayland(_) => false,
+        }
+    }
+
+    fn from_animation(
+        duration: Duration,
+        cursors: Vec<CustomCursor>,
+    ) -> Result<CustomCursorSource, BadAnimation> {
+        if cursors.is_empty() {
+            return Err(BadAnimation::Empty);
+        }
+
+        if cursors.iter().any(CustomCursor::is_animation) {
+            return Err(BadAnimation::Animation);
+        }
+
+        Ok(CustomCursorSource {
+            inner: PlatformCustomCursorSource::Animation { duration, cursors },
+        })
+    }
+}
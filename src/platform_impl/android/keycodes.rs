@@ -1,6 +1,8 @@
 use android_activity::input::{KeyAction, KeyEvent, KeyMapChar, Keycode};
 use android_activity::AndroidApp;
+use smol_str::SmolStr;
 
+use crate::event::ElementState;
 use crate::keyboard::{Key, KeyCode, KeyLocation, NamedKey, NativeKey, NativeKeyCode, PhysicalKey};
 
 pub fn to_physical_key(keycode: Keycode) -> PhysicalKey {
@@ -564,6 +566,25 @@ pub fn to_logical(key_char: Option<KeyMapChar>, keycode: Keycode) -> Key {
     }
 }
 
+/// Maps the action of a key event to its state, and whether the press is a repeat.
+///
+/// `KeyAction::Multiple` reports a run of repeated presses of the same key.
+pub fn to_state_and_repeat(action: KeyAction, repeat_count: i32) -> (ElementState, bool) {
+    match action {
+        KeyAction::Down => (ElementState::Pressed, repeat_count > 0),
+        KeyAction::Multiple => (ElementState::Pressed, true),
+        _ => (ElementState::Released, false),
+    }
+}
+
+/// The text produced by a key press, including control characters like backspace.
+pub fn to_text(state: ElementState, logical_key: &Key) -> Option<SmolStr> {
+    match state {
+        ElementState::Pressed => logical_key.to_text().map(SmolStr::new),
+        ElementState::Released => None,
+    }
+}
+
 pub fn to_location(keycode: Keycode) -> KeyLocation {
     use android_activity::input::Keycode::*;
 
@@ -606,3 +627,36 @@ pub fn to_location(keycode: Keycode) -> KeyLocation {
         _ => KeyLocation::Standard,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_text() {
+        let cases = [
+            (Keycode::A, Some(KeyMapChar::Unicode('a')), Key::Character("a".into()), Some("a")),
+            (Keycode::E, Some(KeyMapChar::Unicode('é')), Key::Character("é".into()), Some("é")),
+            (Keycode::Grave, Some(KeyMapChar::CombiningAccent('`')), Key::Dead(Some('`')), None),
+            (Keycode::Del, None, Key::Named(NamedKey::Backspace), Some("\x08")),
+            (Keycode::Enter, None, Key::Named(NamedKey::Enter), Some("\r")),
+            (Keycode::Space, None, Key::Named(NamedKey::Space), Some(" ")),
+            (Keycode::ForwardDel, None, Key::Named(NamedKey::Delete), None),
+        ];
+
+        for (keycode, key_char, logical_key, text) in cases {
+            assert_eq!(to_logical(key_char, keycode), logical_key, "{keycode:?}");
+            let pressed = to_text(ElementState::Pressed, &logical_key);
+            assert_eq!(pressed.as_deref(), text, "{keycode:?}");
+            assert_eq!(to_text(ElementState::Released, &logical_key), None, "{keycode:?}");
+        }
+    }
+
+    #[test]
+    fn key_state_and_repeat() {
+        assert_eq!(to_state_and_repeat(KeyAction::Down, 0), (ElementState::Pressed, false));
+        assert_eq!(to_state_and_repeat(KeyAction::Down, 3), (ElementState::Pressed, true));
+        assert_eq!(to_state_and_repeat(KeyAction::Multiple, 3), (ElementState::Pressed, true));
+        assert_eq!(to_state_and_repeat(KeyAction::Up, 0), (ElementState::Released, false));
+    }
+}
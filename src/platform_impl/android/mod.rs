@@ -4,10 +4,13 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
-use android_activity::input::{InputEvent, KeyAction, Keycode, MotionAction};
+use android_activity::input::{InputEvent, Keycode, MotionAction};
 use android_activity::{
     AndroidApp, AndroidAppWaker, ConfigurationRef, InputStatus, MainEvent, Rect,
 };
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use smol_str::SmolStr;
 use tracing::{debug, trace, warn};
 
 use crate::application::ApplicationHandler;
@@ -20,6 +23,7 @@ use crate::event_loop::{
     EventLoopProxy as CoreEventLoopProxy, EventLoopProxyProvider,
     OwnedDisplayHandle as CoreOwnedDisplayHandle,
 };
+use crate::keyboard::{ModifiersState, PhysicalKey};
 use crate::monitor::{MonitorHandle as RootMonitorHandle, VideoMode};
 use crate::platform::pump_events::PumpStatus;
 use crate::window::{
@@ -146,7 +150,7 @@ impl EventLoop {
             window_target: ActiveEventLoop {
                 app: android_app.clone(),
                 control_flow: Cell::new(ControlFlow::default()),
-                exit: Cell::new(false),
+                exit: Cell::new(None),
                 redraw_requester: RedrawRequester::new(&redraw_flag, android_app.create_waker()),
                 event_loop_proxy,
             },
@@ -371,6 +375,7 @@ impl EventLoop {
                                 primary,
                                 state: event::ElementState::Pressed,
                                 position,
+                                click_count: 1,
                                 button: match tool_type {
                                     android_activity::input::ToolType::Finger => {
                                         event::ButtonSource::Touch { finger_id, force }
@@ -414,6 +419,7 @@ impl EventLoop {
                                     primary,
                                     state: event::ElementState::Released,
                                     position,
+                                    click_count: 1,
                                     button: match tool_type {
                                         android_activity::input::ToolType::Finger => {
                                             event::ButtonSource::Touch { finger_id, force }
@@ -457,27 +463,25 @@ impl EventLoop {
                         input_status = InputStatus::Unhandled
                     },
                     keycode => {
-                        let state = match key.action() {
-                            KeyAction::Down => event::ElementState::Pressed,
-                            KeyAction::Up => event::ElementState::Released,
-                            _ => event::ElementState::Released,
-                        };
+                        let (state, repeat) =
+                            keycodes::to_state_and_repeat(key.action(), key.repeat_count());
 
                         let key_char = keycodes::character_map_and_combine_key(
                             android_app,
                             key,
                             &mut self.combining_accent,
                         );
+                        let logical_key = keycodes::to_logical(key_char, keycode);
 
                         let event = event::WindowEvent::KeyboardInput {
                             device_id: Some(DeviceId::from_raw(key.device_id() as i64)),
                             event: event::KeyEvent {
                                 state,
                                 physical_key: keycodes::to_physical_key(keycode),
-                                logical_key: keycodes::to_logical(key_char, keycode),
+                                text: keycodes::to_text(state, &logical_key),
+                                logical_key,
                                 location: keycodes::to_location(keycode),
-                                repeat: key.repeat_count() > 0,
-                                text: None,
+                                repeat,
                                 platform_specific: KeyEventExtra {},
                             },
                             is_synthetic: false,
@@ -504,7 +508,7 @@ impl EventLoop {
         mut app: A,
     ) -> Result<(), EventLoopError> {
         self.window_target.clear_exit();
-        loop {
+        let exit = loop {
             match self.pump_app_events(None, &mut app) {
                 PumpStatus::Exit(0) => {
                     break Ok(());
@@ -516,7 +520,11 @@ impl EventLoop {
                     continue;
                 },
             }
-        }
+        };
+
+        self.window_target.control_flow.set(ControlFlow::default());
+
+        exit
     }
 
     pub fn pump_app_events<A: ApplicationHandler>(
@@ -542,12 +550,12 @@ impl EventLoop {
         if !self.exiting() {
             self.poll_events_with_timeout(timeout, &mut app);
         }
-        if self.exiting() {
+        if let Some(code) = self.window_target.exit_code() {
             self.loop_running = false;
 
             app.exiting(&self.window_target);
 
-            PumpStatus::Exit(0)
+            PumpStatus::Exit(code)
         } else {
             PumpStatus::Continue
         }
@@ -615,10 +623,15 @@ impl EventLoop {
                 ControlFlow::Poll => StartCause::Poll,
                 ControlFlow::Wait => StartCause::WaitCancelled { start, requested_resume: None },
                 ControlFlow::WaitUntil(deadline) => {
-                    if Instant::now() < deadline {
+                    let actual_resume = Instant::now();
+                    if actual_resume < deadline {
                         StartCause::WaitCancelled { start, requested_resume: Some(deadline) }
                     } else {
-                        StartCause::ResumeTimeReached { start, requested_resume: deadline }
+                        StartCause::ResumeTimeReached {
+                            start,
+                            requested_resume: deadline,
+                            actual_resume,
+                        }
                     }
                 },
             };
@@ -636,14 +649,21 @@ impl EventLoop {
     }
 }
 
+impl Drop for EventLoop {
+    fn drop(&mut self) {
+        self.window_target.event_loop_proxy.alive.store(false, Ordering::Relaxed);
+    }
+}
+
 pub struct EventLoopProxy {
     wake_up: AtomicBool,
+    alive: AtomicBool,
     waker: AndroidAppWaker,
 }
 
 impl EventLoopProxy {
     fn new(waker: AndroidAppWaker) -> Self {
-        Self { wake_up: AtomicBool::new(false), waker }
+        Self { wake_up: AtomicBool::new(false), alive: AtomicBool::new(true), waker }
     }
 }
 
@@ -652,19 +672,27 @@ impl EventLoopProxyProvider for EventLoopProxy {
         self.wake_up.store(true, Ordering::Relaxed);
         self.waker.wake();
     }
+
+    fn is_alive(&self) -> bool {
+        self.alive.load(Ordering::Relaxed)
+    }
 }
 
 pub struct ActiveEventLoop {
     pub(crate) app: AndroidApp,
     control_flow: Cell<ControlFlow>,
-    exit: Cell<bool>,
+    exit: Cell<Option<i32>>,
     redraw_requester: RedrawRequester,
     event_loop_proxy: Arc<EventLoopProxy>,
 }
 
 impl ActiveEventLoop {
     fn clear_exit(&self) {
-        self.exit.set(false);
+        self.exit.set(None);
+    }
+
+    fn exit_code(&self) -> Option<i32> {
+        self.exit.get()
     }
 }
 
@@ -699,6 +727,14 @@ impl RootActiveEventLoop for ActiveEventLoop {
         None
     }
 
+    fn accent_color(&self) -> Option<[u8; 4]> {
+        None
+    }
+
+    fn key_text(&self, _key: PhysicalKey, _modifiers: ModifiersState) -> Option<SmolStr> {
+        None
+    }
+
     fn listen_device_events(&self, _allowed: DeviceEvents) {}
 
     fn set_control_flow(&self, control_flow: ControlFlow) {
@@ -709,12 +745,22 @@ impl RootActiveEventLoop for ActiveEventLoop {
         self.control_flow.get()
     }
 
+    fn set_resize_coalescing(&self, _enabled: bool) {}
+
+    fn scroll_lines_to_pixels(&self, lines: f32) -> f32 {
+        lines * 3.0 * 16.0
+    }
+
     fn exit(&self) {
-        self.exit.set(true)
+        self.exit_with_code(0)
+    }
+
+    fn exit_with_code(&self, code: i32) {
+        self.exit.set(Some(code))
     }
 
     fn exiting(&self) -> bool {
-        self.exit.get()
+        self.exit.get().is_some()
     }
 
     fn owned_display_handle(&self) -> CoreOwnedDisplayHandle {
@@ -744,6 +790,7 @@ impl rwh_06::HasDisplayHandle for OwnedDisplayHandle {
 }
 
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PlatformSpecificWindowAttributes;
 
 pub(crate) struct Window {
@@ -769,6 +816,20 @@ impl Window {
         self.app.content_rect()
     }
 
+    pub fn set_ime_visible(&self, visible: bool) -> Result<(), RequestError> {
+        if visible {
+            // The input method manager ignores requests from windows without focus.
+            if !HAS_FOCUS.load(Ordering::Relaxed) {
+                return Err(RequestError::Ignored);
+            }
+            self.app.show_soft_input(false);
+        } else {
+            self.app.hide_soft_input(false);
+        }
+
+        Ok(())
+    }
+
     // Allow the usage of HasRawWindowHandle inside this function
     #[allow(deprecated)]
     fn raw_window_handle_rwh_06(&self) -> Result<rwh_06::RawWindowHandle, rwh_06::HandleError> {
@@ -830,6 +891,14 @@ impl CoreWindow for Window {
         self.redraw_requester.request_redraw()
     }
 
+    fn request_redraw_with_priority(&self, _priority: window::RedrawPriority) {
+        self.request_redraw();
+    }
+
+    fn request_redraw_at(&self, _deadline: Instant) {
+        self.request_redraw();
+    }
+
     fn pre_present_notify(&self) {}
 
     fn surface_position(&self) -> PhysicalPosition<i32> {
@@ -864,6 +933,14 @@ impl CoreWindow for Window {
 
     fn set_max_surface_size(&self, _: Option<Size>) {}
 
+    fn min_surface_size(&self) -> Option<PhysicalSize<u32>> {
+        None
+    }
+
+    fn max_surface_size(&self) -> Option<PhysicalSize<u32>> {
+        None
+    }
+
     fn surface_resize_increments(&self) -> Option<PhysicalSize<u32>> {
         None
     }
@@ -872,6 +949,8 @@ impl CoreWindow for Window {
 
     fn set_title(&self, _title: &str) {}
 
+    fn set_title_with_subtitle(&self, _title: &str, _subtitle: Option<&str>) {}
+
     fn set_transparent(&self, _transparent: bool) {}
 
     fn set_blur(&self, _blur: bool) {}
@@ -882,6 +961,10 @@ impl CoreWindow for Window {
         None
     }
 
+    fn occlusion(&self) -> Option<f32> {
+        None
+    }
+
     fn set_resizable(&self, _resizeable: bool) {}
 
     fn is_resizable(&self) -> bool {
@@ -936,6 +1019,8 @@ impl CoreWindow for Window {
 
     fn set_ime_purpose(&self, _purpose: ImePurpose) {}
 
+    fn set_ime_theme(&self, _theme: Option<Theme>) {}
+
     fn focus_window(&self) {}
 
     fn request_user_attention(&self, _request_type: Option<window::UserAttentionType>) {}
@@ -950,6 +1035,8 @@ impl CoreWindow for Window {
         Err(NotSupportedError::new("set_cursor_grab is not supported").into())
     }
 
+    fn set_cursor_grab_restore_on_focus(&self, _restore: bool) {}
+
     fn set_cursor_visible(&self, _: bool) {}
 
     fn drag_window(&self) -> Result<(), RequestError> {
@@ -973,7 +1060,9 @@ impl CoreWindow for Window {
         None
     }
 
-    fn set_content_protected(&self, _protected: bool) {}
+    fn set_content_protected(&self, _protected: bool) -> bool {
+        false
+    }
 
     fn has_focus(&self) -> bool {
         HAS_FOCUS.load(Ordering::Relaxed)
@@ -1016,10 +1105,22 @@ impl MonitorHandle {
         unreachable!()
     }
 
+    pub fn work_area(&self) -> Option<(PhysicalPosition<i32>, PhysicalSize<u32>)> {
+        unreachable!()
+    }
+
     pub fn scale_factor(&self) -> f64 {
         unreachable!()
     }
 
+    pub fn physical_size_mm(&self) -> Option<(f64, f64)> {
+        unreachable!()
+    }
+
+    pub fn is_primary(&self) -> bool {
+        unreachable!()
+    }
+
     pub fn current_video_mode(&self) -> Option<VideoMode> {
         unreachable!()
     }
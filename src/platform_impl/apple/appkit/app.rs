@@ -1,10 +1,12 @@
 #![allow(clippy::unnecessary_cast)]
 
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
 
-use objc2::{define_class, msg_send, MainThreadMarker};
+use objc2::rc::Retained;
+use objc2::runtime::AnyObject;
+use objc2::{class, define_class, msg_send, sel, DefinedClass, MainThreadMarker, MainThreadOnly};
 use objc2_app_kit::{NSApplication, NSEvent, NSEventModifierFlags, NSEventType, NSResponder};
-use objc2_foundation::NSObject;
+use objc2_foundation::{NSObject, NSObjectProtocol};
 
 use super::app_state::AppState;
 use crate::event::{DeviceEvent, ElementState};
@@ -40,6 +42,82 @@ define_class!(
     }
 );
 
+/// `kCoreEventClass`.
+const CORE_EVENT_CLASS: u32 = u32::from_be_bytes(*b"aevt");
+/// `kAEReopenApplication`.
+const REOPEN_APPLICATION_EVENT: u32 = u32::from_be_bytes(*b"rapp");
+
+define_class!(
+    #[unsafe(super(NSObject))]
+    #[thread_kind = MainThreadOnly]
+    #[name = "WinitReopenHandler"]
+    #[ivars = Weak<AppState>]
+    pub(super) struct ReopenHandler;
+
+    impl ReopenHandler {
+        // This is what AppKit uses to call `applicationShouldHandleReopen:hasVisibleWindows:` on
+        // the application delegate, which we don't declare ourselves, so that the user stays in
+        // control of it.
+        #[unsafe(method(handleReopenEvent:withReplyEvent:))]
+        fn handle_reopen_event(&self, _event: &AnyObject, _reply: &AnyObject) {
+            trace_scope!("kAEReopenApplication");
+            let mtm = MainThreadMarker::from(self);
+            let app = NSApplication::sharedApplication(mtm);
+            let has_visible_windows = app.windows().iter().any(|window| window.isVisible());
+
+            if let Some(app_state) = self.ivars().upgrade() {
+                app_state.maybe_queue_with_handler(move |app, event_loop| {
+                    if let Some(handler) = app.macos_handler() {
+                        handler.reopen(event_loop, has_visible_windows);
+                    }
+                });
+            }
+
+            // Installing our handler replaced the one from AppKit, so emulate what it does: ask
+            // the delegate, and bring back a minimized window if nothing is visible.
+            let should_handle_sel = sel!(applicationShouldHandleReopen:hasVisibleWindows:);
+            let should_handle = match app.delegate() {
+                Some(delegate) if delegate.respondsToSelector(should_handle_sel) => unsafe {
+                    delegate
+                        .applicationShouldHandleReopen_hasVisibleWindows(&app, has_visible_windows)
+                },
+                _ => true,
+            };
+            if should_handle && !has_visible_windows {
+                if let Some(window) = app.windows().iter().find(|window| window.isMiniaturized()) {
+                    unsafe { window.deminiaturize(None) };
+                }
+            }
+        }
+    }
+);
+
+impl ReopenHandler {
+    /// Register the handler for the reopen Apple Event.
+    ///
+    /// This must be done after the application has finished launching, since AppKit installs its
+    /// own handler while launching.
+    pub(super) fn install(app_state: Weak<AppState>, mtm: MainThreadMarker) -> Retained<Self> {
+        let this = mtm.alloc().set_ivars(app_state);
+        let this: Retained<Self> = unsafe { msg_send![super(this), init] };
+
+        // The Apple Event manager does not retain the handler, the caller has to keep it alive.
+        unsafe {
+            let manager: Retained<AnyObject> =
+                msg_send![class!(NSAppleEventManager), sharedAppleEventManager];
+            let _: () = msg_send![
+                &manager,
+                setEventHandler: &*this,
+                andSelector: sel!(handleReopenEvent:withReplyEvent:),
+                forEventClass: CORE_EVENT_CLASS,
+                andEventID: REOPEN_APPLICATION_EVENT,
+            ];
+        }
+
+        this
+    }
+}
+
 fn maybe_dispatch_device_event(app_state: &Rc<AppState>, event: &NSEvent) {
     let event_type = unsafe { event.r#type() };
     #[allow(non_upper_case_globals)]
@@ -55,6 +133,7 @@ fn maybe_dispatch_device_event(app_state: &Rc<AppState>, event: &NSEvent) {
                 app_state.maybe_queue_with_handler(move |app, event_loop| {
                     app.device_event(event_loop, None, DeviceEvent::PointerMotion {
                         delta: (delta_x, delta_y),
+                        raw: false,
                     });
                 });
             }
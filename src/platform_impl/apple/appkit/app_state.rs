@@ -1,23 +1,36 @@
 use std::cell::{Cell, OnceCell, RefCell};
+use std::cmp::Reverse;
 use std::mem;
 use std::rc::{Rc, Weak};
 use std::sync::atomic::Ordering as AtomicOrdering;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use dispatch2::MainThreadBound;
-use objc2::MainThreadMarker;
+use objc2::rc::Retained;
+use objc2::{ClassType, MainThreadMarker};
 use objc2_app_kit::{NSApplication, NSApplicationActivationPolicy, NSRunningApplication};
-use objc2_foundation::NSNotification;
+use objc2_foundation::{NSNotification, NSObjectProtocol};
 
 use super::super::event_handler::EventHandler;
-use super::event_loop::{stop_app_immediately, ActiveEventLoop, EventLoopProxy, PanicInfo};
+use super::app::ReopenHandler;
+use super::event_loop::{
+    accent_color, stop_app_immediately, ActiveEventLoop, EventLoopProxy, PanicInfo,
+};
 use super::menu;
+use super::monitor::{self, MonitorHandle};
 use super::observer::{EventLoopWaker, RunLoop};
+use super::window::window_id;
+use super::window_delegate::WindowDelegate;
 use crate::application::ApplicationHandler;
 use crate::event::{StartCause, WindowEvent};
 use crate::event_loop::ControlFlow;
-use crate::window::WindowId;
+use crate::monitor::MonitorHandle as RootMonitorHandle;
+use crate::platform_impl::hotplug::MonitorChanges;
+use crate::platform_impl::idle::IdleTimer;
+use crate::platform_impl::redraw::RedrawSchedule;
+use crate::platform_impl::resize::ResizeCoalescer;
+use crate::window::{RedrawPriority, WindowId};
 
 #[derive(Debug)]
 pub(super) struct AppState {
@@ -25,6 +38,7 @@ pub(super) struct AppState {
     activation_policy: Option<NSApplicationActivationPolicy>,
     default_menu: bool,
     activate_ignoring_other_apps: bool,
+    reopen_events: bool,
     run_loop: RunLoop,
     event_loop_proxy: Arc<EventLoopProxy>,
     event_handler: EventHandler,
@@ -36,13 +50,22 @@ pub(super) struct AppState {
     is_launched: Cell<bool>,
     /// Whether an `EventLoop` is currently running.
     is_running: Cell<bool>,
-    /// Whether the user has requested the event loop to exit.
-    exit: Cell<bool>,
+    /// The exit code, if the user has requested the event loop to exit.
+    exit: Cell<Option<i32>>,
     control_flow: Cell<ControlFlow>,
     waker: RefCell<EventLoopWaker>,
     start_time: Cell<Option<Instant>>,
     wait_timeout: Cell<Option<Instant>>,
-    pending_redraw: RefCell<Vec<WindowId>>,
+    pending_redraw: RefCell<Vec<(WindowId, RedrawPriority)>>,
+    scheduled_redraws: RedrawSchedule,
+    idle_timer: IdleTimer,
+    resize_coalescer: ResizeCoalescer,
+    reopen_handler: OnceCell<Retained<ReopenHandler>>,
+    /// The last accent color, to only report actual changes.
+    accent_color: Cell<Option<[u8; 4]>>,
+    /// The monitors reported so far with their UUID, starting with the ones connected at startup,
+    /// to report hotplug.
+    monitors: RefCell<Vec<(MonitorHandle, Option<[u8; 16]>)>>,
     // NOTE: This is strongly referenced by our `NSWindowDelegate` and our `NSView` subclass, and
     // as such should be careful to not add fields that, in turn, strongly reference those.
 }
@@ -58,6 +81,7 @@ impl AppState {
         activation_policy: Option<NSApplicationActivationPolicy>,
         default_menu: bool,
         activate_ignoring_other_apps: bool,
+        reopen_events: bool,
     ) -> Rc<Self> {
         let this = Rc::new(AppState {
             mtm,
@@ -65,6 +89,7 @@ impl AppState {
             event_loop_proxy: Arc::new(EventLoopProxy::new()),
             default_menu,
             activate_ignoring_other_apps,
+            reopen_events,
             run_loop: RunLoop::main(mtm),
             event_handler: EventHandler::new(),
             stop_on_launch: Cell::new(false),
@@ -73,12 +98,18 @@ impl AppState {
             stop_on_redraw: Cell::new(false),
             is_launched: Cell::new(false),
             is_running: Cell::new(false),
-            exit: Cell::new(false),
+            exit: Cell::new(None),
             control_flow: Cell::new(ControlFlow::default()),
             waker: RefCell::new(EventLoopWaker::new()),
             start_time: Cell::new(None),
             wait_timeout: Cell::new(None),
             pending_redraw: RefCell::new(vec![]),
+            scheduled_redraws: RedrawSchedule::default(),
+            idle_timer: IdleTimer::default(),
+            resize_coalescer: ResizeCoalescer::default(),
+            reopen_handler: OnceCell::new(),
+            accent_color: Cell::new(accent_color()),
+            monitors: RefCell::new(monitors_with_uuid()),
         });
 
         GLOBAL.get(mtm).set(this.clone()).expect("application state can only be set once");
@@ -129,6 +160,12 @@ impl AppState {
             menu::initialize(&app);
         }
 
+        // Only replace the reopen handler of AppKit when asked to.
+        if self.reopen_events {
+            self.reopen_handler
+                .get_or_init(|| ReopenHandler::install(Rc::downgrade(self), self.mtm));
+        }
+
         self.waker.borrow_mut().start();
 
         self.set_is_running(true);
@@ -156,6 +193,52 @@ impl AppState {
         self.internal_exit();
     }
 
+    pub fn system_colors_did_change(self: &Rc<Self>, _notification: &NSNotification) {
+        trace_scope!("NSSystemColorsDidChangeNotification");
+        // The notification is posted when any system color changes.
+        let accent_color = accent_color();
+        if self.accent_color.replace(accent_color) == accent_color {
+            return;
+        }
+
+        let Some(accent_color) = accent_color else {
+            return;
+        };
+
+        let app = NSApplication::sharedApplication(self.mtm);
+        for window in app.windows().iter() {
+            let is_winit_window = window
+                .delegate()
+                .is_some_and(|delegate| delegate.isKindOfClass(WindowDelegate::class()));
+            if is_winit_window {
+                self.queue_window_event(
+                    window_id(&window),
+                    WindowEvent::AccentColorChanged(accent_color),
+                );
+            }
+        }
+    }
+
+    pub fn screen_parameters_did_change(self: &Rc<Self>, _notification: &NSNotification) {
+        trace_scope!("NSApplicationDidChangeScreenParametersNotification");
+        let monitors = monitors_with_uuid();
+        let previous = self.monitors.replace(monitors.clone());
+
+        // The display IDs change with the video mode, and disconnected displays may not have a
+        // UUID anymore, so compare the UUIDs they had while connected.
+        let changes = MonitorChanges::new(&previous, &monitors, |(_, a), (_, b)| a == b);
+        for (inner, _) in changes.disconnected {
+            self.maybe_queue_with_handler(move |app, event_loop| {
+                app.monitor_disconnected(event_loop, RootMonitorHandle { inner })
+            });
+        }
+        for (inner, _) in changes.connected {
+            self.maybe_queue_with_handler(move |app, event_loop| {
+                app.monitor_connected(event_loop, RootMonitorHandle { inner })
+            });
+        }
+    }
+
     /// Place the event handler in the application state for the duration
     /// of the given closure.
     pub fn set_event_handler<R>(
@@ -221,15 +304,19 @@ impl AppState {
         self.is_running.get()
     }
 
-    pub fn exit(&self) {
-        self.exit.set(true)
+    pub fn exit(&self, code: i32) {
+        self.exit.set(Some(code))
     }
 
     pub fn clear_exit(&self) {
-        self.exit.set(false)
+        self.exit.set(None)
     }
 
     pub fn exiting(&self) -> bool {
+        self.exit.get().is_some()
+    }
+
+    pub fn exit_code(&self) -> Option<i32> {
         self.exit.get()
     }
 
@@ -241,10 +328,37 @@ impl AppState {
         self.control_flow.get()
     }
 
+    pub fn set_idle_interval(&self, interval: Option<Duration>) {
+        // The waker is restarted for the next tick once the handler returns, in `cleared`.
+        self.idle_timer.set_interval(interval)
+    }
+
+    pub fn set_resize_coalescing(&self, enabled: bool) {
+        self.resize_coalescer.set_enabled(enabled)
+    }
+
+    pub fn queue_window_event(self: &Rc<Self>, window_id: WindowId, event: WindowEvent) {
+        if let Some(event) = self.resize_coalescer.coalesce(window_id, event) {
+            self.maybe_queue_with_handler(move |app, event_loop| {
+                app.window_event(event_loop, window_id, event);
+            });
+        }
+    }
+
+    fn dispatch_coalesced_resizes(self: &Rc<Self>) {
+        for (window_id, size) in self.resize_coalescer.take() {
+            self.with_handler(|app, event_loop| {
+                app.window_event(event_loop, window_id, WindowEvent::SurfaceResized(size));
+            });
+        }
+    }
+
     pub fn handle_redraw(self: &Rc<Self>, window_id: WindowId) {
         // Redraw request might come out of order from the OS.
         // -> Don't go back into the event handler when our callstack originates from there
         if !self.event_handler.in_use() {
+            // Redraw with the latest size, notably during a live resize.
+            self.dispatch_coalesced_resizes();
             self.with_handler(|app, event_loop| {
                 app.window_event(event_loop, window_id, WindowEvent::RedrawRequested);
             });
@@ -259,14 +373,22 @@ impl AppState {
         }
     }
 
-    pub fn queue_redraw(&self, window_id: WindowId) {
+    pub fn queue_redraw(&self, window_id: WindowId, priority: RedrawPriority) {
         let mut pending_redraw = self.pending_redraw.borrow_mut();
-        if !pending_redraw.contains(&window_id) {
-            pending_redraw.push(window_id);
+        match pending_redraw.iter_mut().find(|(id, _)| *id == window_id) {
+            Some((_, pending)) => *pending = priority.max(*pending),
+            None => pending_redraw.push((window_id, priority)),
         }
         self.run_loop.wakeup();
     }
 
+    pub fn queue_redraw_at(&self, window_id: WindowId, deadline: Instant) {
+        // Wake up the run loop so that the waker is started for the new deadline.
+        if self.scheduled_redraws.schedule(window_id, deadline) {
+            self.run_loop.wakeup();
+        }
+    }
+
     #[track_caller]
     pub fn maybe_queue_with_handler(
         self: &Rc<Self>,
@@ -327,8 +449,9 @@ impl AppState {
             ControlFlow::Poll => StartCause::Poll,
             ControlFlow::Wait => StartCause::WaitCancelled { start, requested_resume: None },
             ControlFlow::WaitUntil(requested_resume) => {
-                if Instant::now() >= requested_resume {
-                    StartCause::ResumeTimeReached { start, requested_resume }
+                let actual_resume = Instant::now();
+                if actual_resume >= requested_resume {
+                    StartCause::ResumeTimeReached { start, requested_resume, actual_resume }
                 } else {
                     StartCause::WaitCancelled { start, requested_resume: Some(requested_resume) }
                 }
@@ -355,12 +478,24 @@ impl AppState {
             self.with_handler(|app, event_loop| app.proxy_wake_up(event_loop));
         }
 
-        let redraw = mem::take(&mut *self.pending_redraw.borrow_mut());
-        for window_id in redraw {
+        self.dispatch_coalesced_resizes();
+
+        let mut redraw = mem::take(&mut *self.pending_redraw.borrow_mut());
+        for window_id in self.scheduled_redraws.take_due(Instant::now()) {
+            if !redraw.iter().any(|&(id, _)| id == window_id) {
+                redraw.push((window_id, RedrawPriority::Normal));
+            }
+        }
+        // Deliver higher priority redraws first, keeping the request order otherwise.
+        redraw.sort_by_key(|&(_, priority)| Reverse(priority));
+        for (window_id, _) in redraw {
             self.with_handler(|app, event_loop| {
                 app.window_event(event_loop, window_id, WindowEvent::RedrawRequested);
             });
         }
+        if self.idle_timer.poll(Instant::now()) {
+            self.with_handler(|app, event_loop| app.idle_tick(event_loop));
+        }
         self.with_handler(|app, event_loop| {
             app.about_to_wait(event_loop);
         });
@@ -381,6 +516,8 @@ impl AppState {
             ControlFlow::Poll => Some(Instant::now()),
             ControlFlow::WaitUntil(instant) => Some(instant),
         };
+        let app_timeout = min_timeout(app_timeout, self.scheduled_redraws.deadline());
+        let app_timeout = min_timeout(app_timeout, self.idle_timer.deadline());
         self.waker.borrow_mut().start_at(min_timeout(wait_timeout, app_timeout));
     }
 }
@@ -388,6 +525,16 @@ impl AppState {
 /// Returns the minimum `Option<Instant>`, taking into account that `None`
 /// equates to an infinite timeout, not a zero timeout (so can't just use
 /// `Option::min`)
+fn monitors_with_uuid() -> Vec<(MonitorHandle, Option<[u8; 16]>)> {
+    monitor::available_monitors()
+        .into_iter()
+        .map(|monitor| {
+            let uuid = monitor.try_uuid();
+            (monitor, uuid)
+        })
+        .collect()
+}
+
 fn min_timeout(a: Option<Instant>, b: Option<Instant>) -> Option<Instant> {
     a.map_or(b, |a_timeout| b.map_or(Some(a_timeout), |b_timeout| Some(a_timeout.min(b_timeout))))
 }
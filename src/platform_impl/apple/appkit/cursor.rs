@@ -12,6 +12,7 @@ use objc2_foundation::{
 
 use crate::cursor::{CursorImage, OnlyCursorImageSource};
 use crate::error::RequestError;
+use crate::icon::premultiply_rgba;
 use crate::window::CursorIcon;
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -49,6 +50,10 @@ pub(crate) fn cursor_from_image(cursor: &CursorImage) -> Result<Retained<NSCurso
     }.ok_or_else(|| os_error!("parent view should be installed in a window"))?;
     let bitmap_data = unsafe { slice::from_raw_parts_mut(bitmap.bitmapData(), cursor.rgba.len()) };
     bitmap_data.copy_from_slice(&cursor.rgba);
+    // `NSBitmapImageRep` uses premultiplied alpha unless told otherwise.
+    if !cursor.premultiplied {
+        premultiply_rgba(bitmap_data);
+    }
 
     let image = unsafe {
         NSImage::initWithSize(NSImage::alloc(), NSSize::new(width.into(), height.into()))
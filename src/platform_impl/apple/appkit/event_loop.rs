@@ -12,8 +12,10 @@ use objc2::rc::{autoreleasepool, Retained};
 use objc2::runtime::ProtocolObject;
 use objc2::{available, msg_send, ClassType, MainThreadMarker};
 use objc2_app_kit::{
-    NSApplication, NSApplicationActivationPolicy, NSApplicationDidFinishLaunchingNotification,
-    NSApplicationWillTerminateNotification, NSWindow,
+    NSApplication, NSApplicationActivationPolicy,
+    NSApplicationDidChangeScreenParametersNotification,
+    NSApplicationDidFinishLaunchingNotification, NSApplicationWillTerminateNotification, NSColor,
+    NSColorSpace, NSSystemColorsDidChangeNotification, NSWindow,
 };
 use objc2_core_foundation::{
     kCFRunLoopCommonModes, CFIndex, CFRetained, CFRunLoopAddSource, CFRunLoopGetMain,
@@ -22,6 +24,7 @@ use objc2_core_foundation::{
 };
 use objc2_foundation::{NSNotificationCenter, NSObjectProtocol};
 use rwh_06::HasDisplayHandle;
+use smol_str::SmolStr;
 
 use super::super::notification_center::create_observer;
 use super::app::WinitApplication;
@@ -30,6 +33,7 @@ use super::cursor::CustomCursor;
 use super::event::dummy_event;
 use super::monitor;
 use super::observer::setup_control_flow_observers;
+use super::window_delegate::theme_to_appearance;
 use crate::application::ApplicationHandler;
 use crate::error::{EventLoopError, RequestError};
 use crate::event_loop::{
@@ -37,6 +41,7 @@ use crate::event_loop::{
     EventLoopProxy as CoreEventLoopProxy, EventLoopProxyProvider,
     OwnedDisplayHandle as CoreOwnedDisplayHandle,
 };
+use crate::keyboard::{ModifiersState, PhysicalKey};
 use crate::monitor::MonitorHandle as RootMonitorHandle;
 use crate::platform::macos::ActivationPolicy;
 use crate::platform::pump_events::PumpStatus;
@@ -138,6 +143,14 @@ impl RootActiveEventLoop for ActiveEventLoop {
         }
     }
 
+    fn accent_color(&self) -> Option<[u8; 4]> {
+        accent_color()
+    }
+
+    fn key_text(&self, _key: PhysicalKey, _modifiers: ModifiersState) -> Option<SmolStr> {
+        None
+    }
+
     fn set_control_flow(&self, control_flow: ControlFlow) {
         self.app_state.set_control_flow(control_flow)
     }
@@ -146,8 +159,25 @@ impl RootActiveEventLoop for ActiveEventLoop {
         self.app_state.control_flow()
     }
 
+    fn set_idle_interval(&self, interval: Option<Duration>) -> Result<(), RequestError> {
+        self.app_state.set_idle_interval(interval);
+        Ok(())
+    }
+
+    fn set_resize_coalescing(&self, enabled: bool) {
+        self.app_state.set_resize_coalescing(enabled)
+    }
+
+    fn scroll_lines_to_pixels(&self, lines: f32) -> f32 {
+        lines * 3.0 * 16.0
+    }
+
     fn exit(&self) {
-        self.app_state.exit()
+        self.exit_with_code(0)
+    }
+
+    fn exit_with_code(&self, code: i32) {
+        self.app_state.exit(code)
     }
 
     fn exiting(&self) -> bool {
@@ -170,6 +200,26 @@ impl rwh_06::HasDisplayHandle for ActiveEventLoop {
     }
 }
 
+pub(super) fn accent_color() -> Option<[u8; 4]> {
+    // The accent color was introduced in macOS 10.14
+    if !available!(macos = 10.14) {
+        return None;
+    }
+
+    let color = unsafe {
+        NSColor::controlAccentColor().colorUsingColorSpace(&NSColorSpace::sRGBColorSpace())
+    }?;
+    let component = |value: f64| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    Some(unsafe {
+        [
+            component(color.redComponent()),
+            component(color.greenComponent()),
+            component(color.blueComponent()),
+            component(color.alphaComponent()),
+        ]
+    })
+}
+
 pub struct EventLoop {
     /// Store a reference to the application for convenience.
     ///
@@ -187,6 +237,8 @@ pub struct EventLoop {
     // Though we do still need to keep the observers around to prevent them from being deallocated.
     _did_finish_launching_observer: Retained<ProtocolObject<dyn NSObjectProtocol>>,
     _will_terminate_observer: Retained<ProtocolObject<dyn NSObjectProtocol>>,
+    _system_colors_observer: Retained<ProtocolObject<dyn NSObjectProtocol>>,
+    _screen_parameters_observer: Retained<ProtocolObject<dyn NSObjectProtocol>>,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
@@ -194,11 +246,19 @@ pub(crate) struct PlatformSpecificEventLoopAttributes {
     pub(crate) activation_policy: Option<ActivationPolicy>,
     pub(crate) default_menu: bool,
     pub(crate) activate_ignoring_other_apps: bool,
+    pub(crate) reopen_events: bool,
+    pub(crate) theme: Option<Theme>,
 }
 
 impl Default for PlatformSpecificEventLoopAttributes {
     fn default() -> Self {
-        Self { activation_policy: None, default_menu: true, activate_ignoring_other_apps: true }
+        Self {
+            activation_policy: None,
+            default_menu: true,
+            activate_ignoring_other_apps: true,
+            reopen_events: false,
+            theme: None,
+        }
     }
 }
 
@@ -219,6 +279,10 @@ impl EventLoop {
             );
         }
 
+        if let Some(appearance) = theme_to_appearance(attributes.theme) {
+            unsafe { app.setAppearance(Some(&appearance)) };
+        }
+
         let activation_policy = match attributes.activation_policy {
             None => None,
             Some(ActivationPolicy::Regular) => Some(NSApplicationActivationPolicy::Regular),
@@ -231,6 +295,7 @@ impl EventLoop {
             activation_policy,
             attributes.default_menu,
             attributes.activate_ignoring_other_apps,
+            attributes.reopen_events,
         );
 
         let center = unsafe { NSNotificationCenter::defaultCenter() };
@@ -259,6 +324,29 @@ impl EventLoop {
             },
         );
 
+        let weak_app_state = Rc::downgrade(&app_state);
+        let _system_colors_observer = create_observer(
+            &center,
+            unsafe { NSSystemColorsDidChangeNotification },
+            move |notification| {
+                if let Some(app_state) = weak_app_state.upgrade() {
+                    app_state.system_colors_did_change(notification);
+                }
+            },
+        );
+
+        let weak_app_state = Rc::downgrade(&app_state);
+        let _screen_parameters_observer = create_observer(
+            &center,
+            // `applicationDidChangeScreenParameters:`
+            unsafe { NSApplicationDidChangeScreenParametersNotification },
+            move |notification| {
+                if let Some(app_state) = weak_app_state.upgrade() {
+                    app_state.screen_parameters_did_change(notification);
+                }
+            },
+        );
+
         let panic_info: Rc<PanicInfo> = Default::default();
         setup_control_flow_observers(mtm, Rc::downgrade(&panic_info));
 
@@ -269,6 +357,8 @@ impl EventLoop {
             panic_info,
             _did_finish_launching_observer,
             _will_terminate_observer,
+            _system_colors_observer,
+            _screen_parameters_observer,
         })
     }
 
@@ -319,7 +409,12 @@ impl EventLoop {
             })
         });
 
-        Ok(())
+        self.app_state.set_control_flow(ControlFlow::default());
+
+        match self.app_state.exit_code() {
+            Some(code) if code != 0 => Err(EventLoopError::ExitFailure(code)),
+            _ => Ok(()),
+        }
     }
 
     pub fn pump_app_events<A: ApplicationHandler>(
@@ -379,9 +474,9 @@ impl EventLoop {
                     resume_unwind(panic);
                 }
 
-                if self.app_state.exiting() {
+                if let Some(code) = self.app_state.exit_code() {
                     self.app_state.internal_exit();
-                    PumpStatus::Exit(0)
+                    PumpStatus::Exit(code)
                 } else {
                     PumpStatus::Continue
                 }
@@ -390,6 +485,12 @@ impl EventLoop {
     }
 }
 
+impl Drop for EventLoop {
+    fn drop(&mut self) {
+        self.app_state.event_loop_proxy().alive.store(false, AtomicOrdering::Relaxed);
+    }
+}
+
 pub(crate) struct OwnedDisplayHandle;
 
 impl HasDisplayHandle for OwnedDisplayHandle {
@@ -437,6 +538,7 @@ pub fn stop_app_on_panic<F: FnOnce() -> R + UnwindSafe, R>(
 #[derive(Debug)]
 pub struct EventLoopProxy {
     pub(crate) wake_up: AtomicBool,
+    pub(crate) alive: AtomicBool,
     source: CFRetained<CFRunLoopSource>,
 }
 
@@ -468,7 +570,7 @@ impl EventLoopProxy {
             CFRunLoopAddSource(&rl, Some(&source), kCFRunLoopCommonModes);
             CFRunLoopWakeUp(&rl);
 
-            EventLoopProxy { wake_up: AtomicBool::new(false), source }
+            EventLoopProxy { wake_up: AtomicBool::new(false), alive: AtomicBool::new(true), source }
         }
     }
 }
@@ -483,4 +585,8 @@ impl EventLoopProxyProvider for EventLoopProxy {
             CFRunLoopWakeUp(&rl);
         }
     }
+
+    fn is_alive(&self) -> bool {
+        self.alive.load(AtomicOrdering::Relaxed)
+    }
 }
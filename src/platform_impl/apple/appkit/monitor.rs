@@ -17,7 +17,7 @@ use objc2_core_graphics::{
     CGDirectDisplayID, CGDisplayBounds, CGDisplayCopyAllDisplayModes, CGDisplayCopyDisplayMode,
     CGDisplayMode, CGDisplayModeCopyPixelEncoding, CGDisplayModeGetPixelHeight,
     CGDisplayModeGetPixelWidth, CGDisplayModeGetRefreshRate, CGDisplayModelNumber,
-    CGGetActiveDisplayList, CGMainDisplayID,
+    CGDisplayScreenSize, CGGetActiveDisplayList, CGMainDisplayID,
 };
 #[allow(deprecated)]
 use objc2_core_video::{
@@ -28,7 +28,7 @@ use objc2_foundation::{ns_string, NSNumber, NSPoint, NSRect};
 
 use super::ffi;
 use super::util::cgerr;
-use crate::dpi::{LogicalPosition, PhysicalPosition, PhysicalSize};
+use crate::dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize};
 use crate::monitor::VideoMode;
 
 #[derive(Clone)]
@@ -107,9 +107,14 @@ pub struct MonitorHandle(CGDirectDisplayID);
 impl MonitorHandle {
     /// Internal comparisons of [`MonitorHandle`]s are done first requesting a UUID for the handle.
     fn uuid(&self) -> [u8; 16] {
-        let ptr = unsafe { ffi::CGDisplayCreateUUIDFromDisplayID(self.0) };
-        let cf_uuid = unsafe { CFRetained::from_raw(NonNull::new(ptr).unwrap()) };
-        unsafe { CFUUIDGetUUIDBytes(&cf_uuid) }.into()
+        self.try_uuid().unwrap()
+    }
+
+    /// The UUID of the display, which may not be available anymore once it's disconnected.
+    pub(super) fn try_uuid(&self) -> Option<[u8; 16]> {
+        let ptr = NonNull::new(unsafe { ffi::CGDisplayCreateUUIDFromDisplayID(self.0) })?;
+        let cf_uuid = unsafe { CFRetained::from_raw(ptr) };
+        Some(unsafe { CFUUIDGetUUIDBytes(&cf_uuid) }.into())
     }
 }
 
@@ -209,6 +214,19 @@ impl MonitorHandle {
         Some(position.to_physical(self.scale_factor()))
     }
 
+    pub fn work_area(&self) -> Option<(PhysicalPosition<i32>, PhysicalSize<u32>)> {
+        run_on_main(|mtm| {
+            let screen = self.ns_screen(mtm)?;
+            let scale_factor = screen.backingScaleFactor() as f64;
+            let frame = screen.visibleFrame();
+            let origin = flip_window_screen_coordinates(frame);
+            let position = LogicalPosition::new(origin.x, origin.y).to_physical(scale_factor);
+            let size =
+                LogicalSize::new(frame.size.width, frame.size.height).to_physical(scale_factor);
+            Some((position, size))
+        })
+    }
+
     pub fn scale_factor(&self) -> f64 {
         run_on_main(|mtm| {
             match self.ns_screen(mtm) {
@@ -224,6 +242,16 @@ impl MonitorHandle {
         refresh_rate_millihertz(self.0, &current_display_mode)
     }
 
+    pub fn physical_size_mm(&self) -> Option<(f64, f64)> {
+        let size = unsafe { CGDisplayScreenSize(self.0) };
+        (size.width > 0.0 && size.height > 0.0).then_some((size.width, size.height))
+    }
+
+    #[inline]
+    pub fn is_primary(&self) -> bool {
+        self.0 == unsafe { CGMainDisplayID() }
+    }
+
     pub fn current_video_mode(&self) -> Option<VideoMode> {
         let mode = NativeDisplayMode(unsafe { CGDisplayCopyDisplayMode(self.0) }.unwrap());
         let refresh_rate_millihertz = refresh_rate_millihertz(self.0, &mode);
@@ -8,8 +8,8 @@ use objc2::rc::Retained;
 use objc2::runtime::{AnyObject, Sel};
 use objc2::{define_class, msg_send, DefinedClass, MainThreadMarker};
 use objc2_app_kit::{
-    NSApplication, NSCursor, NSEvent, NSEventPhase, NSResponder, NSTextInputClient,
-    NSTrackingRectTag, NSView, NSWindow,
+    NSApplication, NSCursor, NSEvent, NSEventPhase, NSEventSubtype, NSEventType, NSResponder,
+    NSTextInputClient, NSTrackingRectTag, NSView, NSWindow,
 };
 use objc2_foundation::{
     NSArray, NSAttributedString, NSAttributedStringKey, NSCopying, NSMutableAttributedString,
@@ -632,6 +632,14 @@ define_class!(
             self.mouse_motion(event);
         }
 
+        // Pen changes that aren't part of a mouse event, like pressure changes while not moving.
+        #[unsafe(method(tabletPoint:))]
+        fn tablet_point(&self, event: &NSEvent) {
+            trace_scope!("tabletPoint:");
+
+            self.mouse_motion(event);
+        }
+
         #[unsafe(method(mouseEntered:))]
         fn mouse_entered(&self, event: &NSEvent) {
             trace_scope!("mouseEntered:");
@@ -815,10 +823,7 @@ impl WinitView {
     }
 
     fn queue_event(&self, event: WindowEvent) {
-        let window_id = window_id(&self.window());
-        self.ivars().app_state.maybe_queue_with_handler(move |app, event_loop| {
-            app.window_event(event_loop, window_id, event);
-        });
+        self.ivars().app_state.queue_window_event(window_id(&self.window()), event);
     }
 
     fn scale_factor(&self) -> f64 {
@@ -1031,6 +1036,8 @@ impl WinitView {
     fn mouse_click(&self, event: &NSEvent, button_state: ElementState) {
         let position = self.mouse_view_point(event).to_physical(self.scale_factor());
         let button = mouse_button(event);
+        // `clickCount` is valid for all mouse down and up events.
+        let click_count = unsafe { event.clickCount() }.max(1) as u32;
 
         self.update_modifiers(event, false);
 
@@ -1039,6 +1046,7 @@ impl WinitView {
             primary: true,
             state: button_state,
             position,
+            click_count,
             button: button.into(),
         });
     }
@@ -1065,7 +1073,7 @@ impl WinitView {
             device_id: None,
             primary: true,
             position: view_point.to_physical(self.scale_factor()),
-            source: PointerSource::Mouse,
+            source: pointer_source(event),
         });
     }
 
@@ -1094,6 +1102,35 @@ fn mouse_button(event: &NSEvent) -> MouseButton {
     }
 }
 
+/// Get the pen axes of tablet events, or [`PointerSource::Mouse`] for the other events.
+fn pointer_source(event: &NSEvent) -> PointerSource {
+    // Pens send mouse events with tablet data, and `subtype` isn't valid for all event types.
+    let is_tablet_point = match unsafe { event.r#type() } {
+        NSEventType::TabletPoint => true,
+        NSEventType::MouseMoved
+        | NSEventType::LeftMouseDragged
+        | NSEventType::RightMouseDragged
+        | NSEventType::OtherMouseDragged => {
+            let subtype = unsafe { event.subtype() };
+            subtype == NSEventSubtype::TabletPoint
+        },
+        _ => false,
+    };
+    if !is_tablet_point {
+        return PointerSource::Mouse;
+    }
+
+    // The tilt is scaled to `[-1, 1]` with positive values towards the right and the top, and the
+    // rotation is counterclockwise.
+    let tilt = unsafe { event.tilt() };
+    let rotation = unsafe { event.rotation() };
+    PointerSource::Pen {
+        pressure: Some(unsafe { event.pressure() }),
+        tilt: Some(((tilt.x * 90.0) as f32, (-tilt.y * 90.0) as f32)),
+        twist: Some((360.0 - rotation).rem_euclid(360.0)),
+    }
+}
+
 // NOTE: to get option as alt working we need to rewrite events
 // we're getting from the operating system, which makes it
 // impossible to provide such events as extra in `KeyEvent`.
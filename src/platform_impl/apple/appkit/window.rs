@@ -1,5 +1,7 @@
 #![allow(clippy::unnecessary_cast)]
 
+use std::time::Instant;
+
 use dispatch2::MainThreadBound;
 use dpi::{Position, Size};
 use objc2::rc::{autoreleasepool, Retained};
@@ -11,9 +13,10 @@ use super::event_loop::ActiveEventLoop;
 use super::window_delegate::WindowDelegate;
 use crate::error::RequestError;
 use crate::monitor::MonitorHandle as CoreMonitorHandle;
+use crate::platform::macos::WindowExtMacOS;
 use crate::window::{
-    Cursor, Fullscreen, Icon, ImePurpose, Theme, UserAttentionType, Window as CoreWindow,
-    WindowAttributes, WindowButtons, WindowId, WindowLevel,
+    Cursor, Fullscreen, Icon, ImePurpose, RedrawPriority, Theme, UserAttentionType,
+    Window as CoreWindow, WindowAttributes, WindowButtons, WindowId, WindowLevel,
 };
 
 pub(crate) struct Window {
@@ -100,6 +103,14 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.request_redraw());
     }
 
+    fn request_redraw_with_priority(&self, priority: RedrawPriority) {
+        self.maybe_wait_on_main(|delegate| delegate.request_redraw_with_priority(priority));
+    }
+
+    fn request_redraw_at(&self, deadline: Instant) {
+        self.maybe_wait_on_main(|delegate| delegate.request_redraw_at(deadline));
+    }
+
     fn pre_present_notify(&self) {
         self.maybe_wait_on_main(|delegate| delegate.pre_present_notify());
     }
@@ -144,6 +155,14 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.set_max_surface_size(max_size));
     }
 
+    fn min_surface_size(&self) -> Option<dpi::PhysicalSize<u32>> {
+        self.maybe_wait_on_main(|delegate| delegate.min_surface_size())
+    }
+
+    fn max_surface_size(&self) -> Option<dpi::PhysicalSize<u32>> {
+        self.maybe_wait_on_main(|delegate| delegate.max_surface_size())
+    }
+
     fn surface_resize_increments(&self) -> Option<dpi::PhysicalSize<u32>> {
         self.maybe_wait_on_main(|delegate| delegate.surface_resize_increments())
     }
@@ -156,6 +175,13 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.set_title(title));
     }
 
+    fn set_title_with_subtitle(&self, title: &str, subtitle: Option<&str>) {
+        self.maybe_wait_on_main(|delegate| {
+            delegate.set_title(title);
+            delegate.set_subtitle(subtitle.unwrap_or(""));
+        });
+    }
+
     fn set_transparent(&self, transparent: bool) {
         self.maybe_wait_on_main(|delegate| delegate.set_transparent(transparent));
     }
@@ -172,6 +198,10 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.is_visible())
     }
 
+    fn occlusion(&self) -> Option<f32> {
+        self.maybe_wait_on_main(|delegate| delegate.occlusion())
+    }
+
     fn set_resizable(&self, resizable: bool) {
         self.maybe_wait_on_main(|delegate| delegate.set_resizable(resizable))
     }
@@ -240,6 +270,8 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.set_ime_purpose(purpose));
     }
 
+    fn set_ime_theme(&self, _theme: Option<Theme>) {}
+
     fn focus_window(&self) {
         self.maybe_wait_on_main(|delegate| delegate.focus_window());
     }
@@ -260,8 +292,8 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.theme())
     }
 
-    fn set_content_protected(&self, protected: bool) {
-        self.maybe_wait_on_main(|delegate| delegate.set_content_protected(protected));
+    fn set_content_protected(&self, protected: bool) -> bool {
+        self.maybe_wait_on_main(|delegate| delegate.set_content_protected(protected))
     }
 
     fn title(&self) -> String {
@@ -280,6 +312,10 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.set_cursor_grab(mode))
     }
 
+    fn set_cursor_grab_restore_on_focus(&self, restore: bool) {
+        self.maybe_wait_on_main(|delegate| delegate.set_cursor_grab_restore_on_focus(restore))
+    }
+
     fn set_cursor_visible(&self, visible: bool) {
         self.maybe_wait_on_main(|delegate| delegate.set_cursor_visible(visible))
     }
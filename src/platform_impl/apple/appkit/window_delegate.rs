@@ -5,6 +5,7 @@ use std::ffi::c_void;
 use std::ptr;
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
 use objc2::rc::{autoreleasepool, Retained};
 use objc2::runtime::{AnyObject, ProtocolObject};
@@ -17,9 +18,10 @@ use objc2_app_kit::{
     NSAppearanceNameAqua, NSApplication, NSApplicationPresentationOptions, NSBackingStoreType,
     NSColor, NSDraggingDestination, NSDraggingInfo, NSFilenamesPboardType,
     NSRequestUserAttentionType, NSScreen, NSToolbar, NSView, NSViewFrameDidChangeNotification,
-    NSWindow, NSWindowButton, NSWindowDelegate, NSWindowFullScreenButton, NSWindowLevel,
-    NSWindowOcclusionState, NSWindowOrderingMode, NSWindowSharingType, NSWindowStyleMask,
-    NSWindowTabbingMode, NSWindowTitleVisibility, NSWindowToolbarStyle,
+    NSWindow, NSWindowButton, NSWindowCollectionBehavior, NSWindowDelegate,
+    NSWindowFullScreenButton, NSWindowLevel, NSWindowOcclusionState, NSWindowOrderingMode,
+    NSWindowSharingType, NSWindowStyleMask, NSWindowTabbingMode, NSWindowTitleVisibility,
+    NSWindowToolbarStyle,
 };
 use objc2_core_foundation::{CGFloat, CGPoint};
 use objc2_core_graphics::{
@@ -33,6 +35,8 @@ use objc2_foundation::{
     NSObjectNSDelayedPerforming, NSObjectNSKeyValueObserverRegistration, NSObjectProtocol, NSPoint,
     NSRect, NSSize, NSString,
 };
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use tracing::{trace, warn};
 
 use super::app_state::AppState;
@@ -48,14 +52,15 @@ use crate::dpi::{
     Position, Size,
 };
 use crate::error::{NotSupportedError, RequestError};
-use crate::event::{SurfaceSizeWriter, WindowEvent};
-use crate::platform::macos::{OptionAsAlt, WindowExtMacOS};
+use crate::event::{FullscreenState, SurfaceSizeWriter, WindowEvent};
+use crate::platform::macos::{CollectionBehavior, NativeWindowLevel, OptionAsAlt, WindowExtMacOS};
 use crate::window::{
-    Cursor, CursorGrabMode, Icon, ImePurpose, ResizeDirection, Theme, UserAttentionType,
-    WindowAttributes, WindowButtons, WindowId, WindowLevel,
+    Cursor, CursorGrabMode, Icon, ImePurpose, RedrawPriority, ResizeDirection, Theme,
+    UserAttentionType, WindowAttributes, WindowButtons, WindowId, WindowLevel,
 };
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default))]
 pub struct PlatformSpecificWindowAttributes {
     pub movable_by_window_background: bool,
     pub titlebar_transparent: bool,
@@ -71,6 +76,7 @@ pub struct PlatformSpecificWindowAttributes {
     pub borderless_game: bool,
     pub unified_titlebar: bool,
     pub panel: bool,
+    pub native_window_level: Option<NativeWindowLevel>,
 }
 
 impl Default for PlatformSpecificWindowAttributes {
@@ -91,6 +97,7 @@ impl Default for PlatformSpecificWindowAttributes {
             borderless_game: false,
             unified_titlebar: false,
             panel: false,
+            native_window_level: None,
         }
     }
 }
@@ -140,6 +147,12 @@ pub(crate) struct State {
     is_simple_fullscreen: Cell<bool>,
     saved_style: Cell<Option<NSWindowStyleMask>>,
     is_borderless_game: Cell<bool>,
+    /// The requested cursor grab, re-applied when the window becomes key again.
+    cursor_grab: Cell<CursorGrabMode>,
+    cursor_grab_restore: Cell<bool>,
+    /// The occlusion state from before `WindowExtMacOS::order_out`, used to hide the occlusion
+    /// changes caused by ordering the window out and back in.
+    occluded_before_order_out: Cell<Option<bool>>,
 }
 
 define_class!(
@@ -220,6 +233,13 @@ define_class!(
             trace_scope!("windowDidBecomeKey:");
             // TODO: center the cursor if the window had mouse grab when it
             // lost focus
+            let grab = self.ivars().cursor_grab.get();
+            if grab != CursorGrabMode::None && self.ivars().cursor_grab_restore.get() {
+                if let Err(err) = self.apply_cursor_grab(grab) {
+                    warn!("failed to restore the cursor grab: {err}");
+                }
+            }
+
             self.queue_event(WindowEvent::Focused(true));
         }
 
@@ -235,6 +255,17 @@ define_class!(
             // a synthetic ModifiersChanged event when we lose focus.
             self.view().reset_modifiers();
 
+            // The mouse association is global, don't keep it while another window is active.
+            if self.ivars().cursor_grab.get() != CursorGrabMode::None {
+                if let Err(err) = self.apply_cursor_grab(CursorGrabMode::None) {
+                    warn!("failed to release the cursor grab: {err}");
+                }
+
+                if !self.ivars().cursor_grab_restore.get() {
+                    self.ivars().cursor_grab.set(CursorGrabMode::None);
+                }
+            }
+
             self.queue_event(WindowEvent::Focused(false));
         }
 
@@ -262,6 +293,8 @@ define_class!(
                 },
             }
             self.ivars().in_fullscreen_transition.set(true);
+            drop(fullscreen);
+            self.queue_event(WindowEvent::FullscreenChanged { state: FullscreenState::Entering });
         }
 
         /// Invoked when before exit fullscreen
@@ -270,6 +303,7 @@ define_class!(
             trace_scope!("windowWillExitFullScreen:");
 
             self.ivars().in_fullscreen_transition.set(true);
+            self.queue_event(WindowEvent::FullscreenChanged { state: FullscreenState::Exiting });
         }
 
         #[unsafe(method(window:willUseFullScreenPresentationOptions:))]
@@ -304,6 +338,7 @@ define_class!(
             trace_scope!("windowDidEnterFullScreen:");
             self.ivars().initial_fullscreen.set(false);
             self.ivars().in_fullscreen_transition.set(false);
+            self.queue_event(WindowEvent::FullscreenChanged { state: FullscreenState::Entered });
             if let Some(target_fullscreen) = self.ivars().target_fullscreen.take() {
                 self.set_fullscreen(target_fullscreen);
             }
@@ -316,6 +351,7 @@ define_class!(
 
             self.restore_state_from_fullscreen();
             self.ivars().in_fullscreen_transition.set(false);
+            self.queue_event(WindowEvent::FullscreenChanged { state: FullscreenState::Exited });
             if let Some(target_fullscreen) = self.ivars().target_fullscreen.take() {
                 self.set_fullscreen(target_fullscreen);
             }
@@ -341,6 +377,7 @@ define_class!(
         fn window_did_fail_to_enter_fullscreen(&self, _: Option<&AnyObject>) {
             trace_scope!("windowDidFailToEnterFullScreen:");
             self.ivars().in_fullscreen_transition.set(false);
+            self.queue_event(WindowEvent::FullscreenChanged { state: FullscreenState::Exited });
             self.ivars().target_fullscreen.replace(None);
             if self.ivars().initial_fullscreen.get() {
                 unsafe {
@@ -360,6 +397,16 @@ define_class!(
         fn window_did_change_occlusion_state(&self, _: Option<&AnyObject>) {
             trace_scope!("windowDidChangeOcclusionState:");
             let visible = self.window().occlusionState().contains(NSWindowOcclusionState::Visible);
+            if let Some(occluded) = self.ivars().occluded_before_order_out.get() {
+                if !self.window().isVisible() {
+                    return;
+                }
+                // The window was ordered back in, only report a change from before ordering out.
+                self.ivars().occluded_before_order_out.set(None);
+                if occluded == !visible {
+                    return;
+                }
+            }
             self.queue_event(WindowEvent::Occluded(!visible));
         }
 
@@ -561,10 +608,16 @@ fn new_window(
         let frame = match &screen {
             Some(screen) => screen.frame(),
             None => {
-                let scale_factor = NSScreen::mainScreen(mtm)
+                let main_screen = NSScreen::mainScreen(mtm);
+                let scale_factor = main_screen
+                    .as_ref()
                     .map(|screen| screen.backingScaleFactor() as f64)
                     .unwrap_or(1.0);
-                let size = match attrs.surface_size {
+                let monitor_size = main_screen.map(|screen| {
+                    let size = screen.frame().size;
+                    LogicalSize::new(size.width, size.height).to_physical(scale_factor)
+                });
+                let size = match attrs.initial_surface_size(monitor_size) {
                     Some(size) => {
                         let size = size.to_logical(scale_factor);
                         NSSize::new(size.width, size.height)
@@ -804,6 +857,17 @@ impl WindowDelegate {
             None => (),
         }
 
+        if let Some(owner_id) = attrs.owner {
+            let app = NSApplication::sharedApplication(mtm);
+            match app.windows().iter().find(|owner| window_id(owner) == owner_id) {
+                // SAFETY: The window was just created, so it can't be an ancestor of its owner.
+                Some(owner) => unsafe {
+                    owner.addChildWindow_ordered(&window, NSWindowOrderingMode::Above)
+                },
+                None => warn!("owner window {owner_id:?} doesn't exist"),
+            }
+        }
+
         let surface_resize_increments = match attrs
             .surface_resize_increments
             .map(|i| i.to_logical(window.backingScaleFactor() as _))
@@ -838,6 +902,9 @@ impl WindowDelegate {
             is_simple_fullscreen: Cell::new(false),
             saved_style: Cell::new(None),
             is_borderless_game: Cell::new(attrs.platform_specific.borderless_game),
+            cursor_grab: Cell::new(CursorGrabMode::None),
+            cursor_grab_restore: Cell::new(true),
+            occluded_before_order_out: Cell::new(None),
         });
         let delegate: Retained<WindowDelegate> = unsafe { msg_send![super(delegate), init] };
 
@@ -866,7 +933,11 @@ impl WindowDelegate {
             delegate.set_max_surface_size(Some(dim));
         }
 
-        delegate.set_window_level(attrs.window_level);
+        if let Some(level) = attrs.platform_specific.native_window_level {
+            delegate.window().setLevel(native_window_level(level));
+        } else {
+            delegate.set_window_level(attrs.window_level);
+        }
 
         delegate.set_cursor(attrs.cursor);
 
@@ -909,10 +980,7 @@ impl WindowDelegate {
     }
 
     pub(crate) fn queue_event(&self, event: WindowEvent) {
-        let window_id = window_id(self.window());
-        self.ivars().app_state.maybe_queue_with_handler(move |app, event_loop| {
-            app.window_event(event_loop, window_id, event);
-        });
+        self.ivars().app_state.queue_window_event(window_id(self.window()), event);
     }
 
     fn handle_scale_factor_changed(&self, scale_factor: CGFloat) {
@@ -1007,8 +1075,21 @@ impl WindowDelegate {
         Some(self.window().isVisible())
     }
 
+    pub fn occlusion(&self) -> Option<f32> {
+        let visible = self.window().occlusionState().contains(NSWindowOcclusionState::Visible);
+        Some(if visible { 0.0 } else { 1.0 })
+    }
+
     pub fn request_redraw(&self) {
-        self.ivars().app_state.queue_redraw(window_id(self.window()));
+        self.request_redraw_with_priority(RedrawPriority::Normal);
+    }
+
+    pub fn request_redraw_with_priority(&self, priority: RedrawPriority) {
+        self.ivars().app_state.queue_redraw(window_id(self.window()), priority);
+    }
+
+    pub fn request_redraw_at(&self, deadline: Instant) {
+        self.ivars().app_state.queue_redraw_at(window_id(self.window()), deadline);
     }
 
     #[inline]
@@ -1141,6 +1222,24 @@ impl WindowDelegate {
         self.window().setContentSize(current_size);
     }
 
+    pub fn min_surface_size(&self) -> Option<PhysicalSize<u32>> {
+        // `set_min_surface_size(None)` sets a zero minimum.
+        let min_size = unsafe { self.window().contentMinSize() };
+        (min_size.width > 0.0 || min_size.height > 0.0).then(|| {
+            LogicalSize::new(min_size.width as f64, min_size.height as f64)
+                .to_physical(self.scale_factor())
+        })
+    }
+
+    pub fn max_surface_size(&self) -> Option<PhysicalSize<u32>> {
+        // `set_max_surface_size(None)` sets a maximum of `f32::MAX`.
+        let max_size = unsafe { self.window().contentMaxSize() };
+        (max_size.width < f32::MAX as CGFloat || max_size.height < f32::MAX as CGFloat).then(|| {
+            LogicalSize::new(max_size.width as f64, max_size.height as f64)
+                .to_physical(self.scale_factor())
+        })
+    }
+
     pub fn surface_resize_increments(&self) -> Option<PhysicalSize<u32>> {
         let increments = self.ivars().surface_resize_increments.get();
         let (w, h) = (increments.width, increments.height);
@@ -1258,6 +1357,17 @@ impl WindowDelegate {
 
     #[inline]
     pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), RequestError> {
+        self.apply_cursor_grab(mode)?;
+        self.ivars().cursor_grab.set(mode);
+        Ok(())
+    }
+
+    #[inline]
+    pub fn set_cursor_grab_restore_on_focus(&self, restore: bool) {
+        self.ivars().cursor_grab_restore.set(restore);
+    }
+
+    fn apply_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), RequestError> {
         let associate_mouse_cursor = match mode {
             CursorGrabMode::Locked => false,
             CursorGrabMode::None => true,
@@ -1793,12 +1903,13 @@ impl WindowDelegate {
     }
 
     #[inline]
-    pub fn set_content_protected(&self, protected: bool) {
+    pub fn set_content_protected(&self, protected: bool) -> bool {
         self.window().setSharingType(if protected {
             NSWindowSharingType::None
         } else {
             NSWindowSharingType::ReadOnly
-        })
+        });
+        true
     }
 
     pub fn title(&self) -> String {
@@ -2011,6 +2122,60 @@ impl WindowExtMacOS for WindowDelegate {
             window.toolbar().is_some() && window.toolbarStyle() == NSWindowToolbarStyle::Unified
         }
     }
+
+    #[inline]
+    fn set_subtitle(&self, subtitle: &str) {
+        if !available!(macos = 11.0) {
+            tracing::warn!("window subtitles are only available on macOS 11+");
+            return;
+        }
+        self.window().setSubtitle(&NSString::from_str(subtitle))
+    }
+
+    #[inline]
+    fn subtitle(&self) -> String {
+        if !available!(macos = 11.0) {
+            return String::new();
+        }
+        self.window().subtitle().to_string()
+    }
+
+    #[inline]
+    fn set_collection_behavior(&self, behavior: CollectionBehavior) {
+        self.window().setCollectionBehavior(NSWindowCollectionBehavior(behavior.bits() as usize))
+    }
+
+    #[inline]
+    fn collection_behavior(&self) -> CollectionBehavior {
+        CollectionBehavior::from_bits_retain(self.window().collectionBehavior().0 as u32)
+    }
+
+    #[inline]
+    fn order_out(&self) {
+        if self.window().isVisible() && self.ivars().occluded_before_order_out.get().is_none() {
+            let visible = self.window().occlusionState().contains(NSWindowOcclusionState::Visible);
+            self.ivars().occluded_before_order_out.set(Some(!visible));
+        }
+        self.window().orderOut(None);
+    }
+
+    #[inline]
+    fn order_front_regardless(&self) {
+        self.window().orderFrontRegardless();
+    }
+}
+
+fn native_window_level(level: NativeWindowLevel) -> NSWindowLevel {
+    match level {
+        NativeWindowLevel::Normal => ffi::kCGNormalWindowLevel as NSWindowLevel,
+        NativeWindowLevel::Floating => ffi::kCGFloatingWindowLevel as NSWindowLevel,
+        NativeWindowLevel::ModalPanel => ffi::kCGModalPanelWindowLevel as NSWindowLevel,
+        NativeWindowLevel::MainMenu => ffi::kCGMainMenuWindowLevel as NSWindowLevel,
+        NativeWindowLevel::Status => ffi::kCGStatusWindowLevel as NSWindowLevel,
+        NativeWindowLevel::PopUpMenu => ffi::kCGPopUpMenuWindowLevel as NSWindowLevel,
+        NativeWindowLevel::ScreenSaver => ffi::kCGScreenSaverWindowLevel as NSWindowLevel,
+        NativeWindowLevel::Raw(level) => level as NSWindowLevel,
+    }
 }
 
 const DEFAULT_STANDARD_FRAME: NSRect =
@@ -2039,7 +2204,7 @@ pub fn appearance_to_theme(appearance: &NSAppearance) -> Theme {
     }
 }
 
-fn theme_to_appearance(theme: Option<Theme>) -> Option<Retained<NSAppearance>> {
+pub(super) fn theme_to_appearance(theme: Option<Theme>) -> Option<Retained<NSAppearance>> {
     let appearance = match theme? {
         Theme::Light => unsafe { NSAppearance::appearanceNamed(NSAppearanceNameAqua) },
         Theme::Dark => NSAppearance::appearanceNamed(dark_appearance_name()),
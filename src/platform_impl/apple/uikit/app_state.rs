@@ -198,8 +198,9 @@ impl AppState {
                 StartCause::WaitCancelled { start, requested_resume: None }
             },
             (ControlFlow::WaitUntil(requested_resume), AppStateImpl::Waiting { start }) => {
-                if Instant::now() >= requested_resume {
-                    StartCause::ResumeTimeReached { start, requested_resume }
+                let actual_resume = Instant::now();
+                if actual_resume >= requested_resume {
+                    StartCause::ResumeTimeReached { start, requested_resume, actual_resume }
                 } else {
                     StartCause::WaitCancelled { start, requested_resume: Some(requested_resume) }
                 }
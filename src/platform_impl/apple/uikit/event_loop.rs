@@ -21,6 +21,7 @@ use objc2_ui_kit::{
     UIApplicationWillTerminateNotification, UIScreen,
 };
 use rwh_06::HasDisplayHandle;
+use smol_str::SmolStr;
 
 use super::super::notification_center::create_observer;
 use super::app_state::{send_occluded_event_for_all_windows, AppState};
@@ -32,6 +33,7 @@ use crate::event_loop::{
     EventLoopProxy as CoreEventLoopProxy, EventLoopProxyProvider,
     OwnedDisplayHandle as CoreOwnedDisplayHandle,
 };
+use crate::keyboard::{ModifiersState, PhysicalKey};
 use crate::monitor::MonitorHandle as RootMonitorHandle;
 use crate::platform_impl::Window;
 use crate::window::{CustomCursor, CustomCursorSource, Theme, Window as CoreWindow};
@@ -80,16 +82,34 @@ impl RootActiveEventLoop for ActiveEventLoop {
         None
     }
 
+    fn accent_color(&self) -> Option<[u8; 4]> {
+        None
+    }
+
+    fn key_text(&self, _key: PhysicalKey, _modifiers: ModifiersState) -> Option<SmolStr> {
+        None
+    }
+
     fn control_flow(&self) -> ControlFlow {
         AppState::get_mut(self.mtm).control_flow()
     }
 
+    fn set_resize_coalescing(&self, _enabled: bool) {}
+
+    fn scroll_lines_to_pixels(&self, lines: f32) -> f32 {
+        lines * 3.0 * 16.0
+    }
+
     fn exit(&self) {
         // https://developer.apple.com/library/archive/qa/qa1561/_index.html
         // it is not possible to quit an iOS app gracefully and programmatically
         tracing::warn!("`ControlFlow::Exit` ignored on iOS");
     }
 
+    fn exit_with_code(&self, _code: i32) {
+        self.exit()
+    }
+
     fn exiting(&self) -> bool {
         false
     }
@@ -331,6 +351,11 @@ impl EventLoopProxyProvider for EventLoopProxy {
             CFRunLoopWakeUp(&rl);
         }
     }
+
+    fn is_alive(&self) -> bool {
+        // `run_app` never returns, so the event loop lives until the process exits.
+        true
+    }
 }
 
 fn setup_control_flow_observers() {
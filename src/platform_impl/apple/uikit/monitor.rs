@@ -10,7 +10,7 @@ use objc2::{available, MainThreadMarker, Message};
 use objc2_foundation::NSInteger;
 use objc2_ui_kit::{UIScreen, UIScreenMode};
 
-use crate::dpi::PhysicalPosition;
+use crate::dpi::{PhysicalPosition, PhysicalSize};
 use crate::monitor::VideoMode;
 
 // Workaround for `MainThreadBound` implementing almost no traits
@@ -160,10 +160,26 @@ impl MonitorHandle {
         Some((bounds.origin.x as f64, bounds.origin.y as f64).into())
     }
 
+    pub fn work_area(&self) -> Option<(PhysicalPosition<i32>, PhysicalSize<u32>)> {
+        None
+    }
+
     pub fn scale_factor(&self) -> f64 {
         self.ui_screen.get_on_main(|ui_screen| ui_screen.nativeScale()) as f64
     }
 
+    pub fn physical_size_mm(&self) -> Option<(f64, f64)> {
+        None
+    }
+
+    pub fn is_primary(&self) -> bool {
+        run_on_main(|mtm| {
+            #[allow(deprecated)]
+            let main = UIScreen::mainScreen(mtm);
+            *self.ui_screen(mtm) == main
+        })
+    }
+
     pub fn current_video_mode(&self) -> Option<VideoMode> {
         Some(run_on_main(|mtm| {
             VideoModeHandle::new(
@@ -540,6 +540,7 @@ impl WinitView {
                             primary,
                             state: ElementState::Pressed,
                             position,
+                            click_count: 1,
                             button: if let UITouchType::Pencil = touch_type {
                                 ButtonSource::Unknown(0)
                             } else {
@@ -589,6 +590,7 @@ impl WinitView {
                                 primary,
                                 state: ElementState::Released,
                                 position,
+                                click_count: 1,
                                 button: if let UITouchType::Pencil = touch_type {
                                     ButtonSource::Unknown(0)
                                 } else {
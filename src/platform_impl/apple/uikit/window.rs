@@ -1,6 +1,7 @@
 #![allow(clippy::unnecessary_cast)]
 
 use std::collections::VecDeque;
+use std::time::Instant;
 
 use dispatch2::MainThreadBound;
 use objc2::rc::Retained;
@@ -11,6 +12,8 @@ use objc2_ui_kit::{
     UIApplication, UICoordinateSpace, UIEdgeInsets, UIResponder, UIScreen,
     UIScreenOverscanCompensation, UIViewController, UIWindow,
 };
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use tracing::{debug, warn};
 
 use super::app_state::EventWrapper;
@@ -28,8 +31,8 @@ use crate::icon::Icon;
 use crate::monitor::MonitorHandle as CoreMonitorHandle;
 use crate::platform::ios::{ScreenEdge, StatusBarStyle, ValidOrientations};
 use crate::window::{
-    CursorGrabMode, ImePurpose, ResizeDirection, Theme, UserAttentionType, Window as CoreWindow,
-    WindowAttributes, WindowButtons, WindowId, WindowLevel,
+    CursorGrabMode, ImePurpose, RedrawPriority, ResizeDirection, Theme, UserAttentionType,
+    Window as CoreWindow, WindowAttributes, WindowButtons, WindowId, WindowLevel,
 };
 
 define_class!(
@@ -438,7 +441,9 @@ impl Inner {
         None
     }
 
-    pub fn set_content_protected(&self, _protected: bool) {}
+    pub fn set_content_protected(&self, _protected: bool) -> bool {
+        false
+    }
 
     pub fn has_focus(&self) -> bool {
         self.window.isKeyWindow()
@@ -565,6 +570,14 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.request_redraw());
     }
 
+    fn request_redraw_with_priority(&self, _priority: RedrawPriority) {
+        self.request_redraw();
+    }
+
+    fn request_redraw_at(&self, _deadline: Instant) {
+        self.request_redraw();
+    }
+
     fn pre_present_notify(&self) {
         self.maybe_wait_on_main(|delegate| delegate.pre_present_notify());
     }
@@ -609,6 +622,14 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.set_max_surface_size(max_size));
     }
 
+    fn min_surface_size(&self) -> Option<PhysicalSize<u32>> {
+        None
+    }
+
+    fn max_surface_size(&self) -> Option<PhysicalSize<u32>> {
+        None
+    }
+
     fn surface_resize_increments(&self) -> Option<PhysicalSize<u32>> {
         self.maybe_wait_on_main(|delegate| delegate.surface_resize_increments())
     }
@@ -621,6 +642,10 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.set_title(title));
     }
 
+    fn set_title_with_subtitle(&self, title: &str, _subtitle: Option<&str>) {
+        self.set_title(title);
+    }
+
     fn set_transparent(&self, transparent: bool) {
         self.maybe_wait_on_main(|delegate| delegate.set_transparent(transparent));
     }
@@ -637,6 +662,10 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.is_visible())
     }
 
+    fn occlusion(&self) -> Option<f32> {
+        None
+    }
+
     fn set_resizable(&self, resizable: bool) {
         self.maybe_wait_on_main(|delegate| delegate.set_resizable(resizable))
     }
@@ -705,6 +734,8 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.set_ime_purpose(purpose));
     }
 
+    fn set_ime_theme(&self, _theme: Option<Theme>) {}
+
     fn focus_window(&self) {
         self.maybe_wait_on_main(|delegate| delegate.focus_window());
     }
@@ -725,8 +756,8 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.theme())
     }
 
-    fn set_content_protected(&self, protected: bool) {
-        self.maybe_wait_on_main(|delegate| delegate.set_content_protected(protected));
+    fn set_content_protected(&self, protected: bool) -> bool {
+        self.maybe_wait_on_main(|delegate| delegate.set_content_protected(protected))
     }
 
     fn title(&self) -> String {
@@ -745,6 +776,8 @@ impl CoreWindow for Window {
         Ok(self.maybe_wait_on_main(|delegate| delegate.set_cursor_grab(mode))?)
     }
 
+    fn set_cursor_grab_restore_on_focus(&self, _restore: bool) {}
+
     fn set_cursor_visible(&self, visible: bool) {
         self.maybe_wait_on_main(|delegate| delegate.set_cursor_visible(visible))
     }
@@ -874,6 +907,7 @@ impl Inner {
 }
 
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default))]
 pub struct PlatformSpecificWindowAttributes {
     pub scale_factor: Option<f64>,
     pub valid_orientations: ValidOrientations,
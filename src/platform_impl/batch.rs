@@ -0,0 +1,101 @@
+/// Create an object for each request while waiting for the windowing system only once.
+///
+/// - `send` sends the requests creating an object, without waiting for their outcome.
+/// - `sync` waits once for the windowing system to process the requests of all the objects.
+/// - `finish` checks the outcome of the requests of each object.
+///
+/// The results are in the order of `requests`. When `sync` fails, every object that was sent fails
+/// with the error made by `sync_failed`.
+pub fn create_batched<R, P, T, E>(
+    requests: Vec<R>,
+    send: impl FnMut(R) -> Result<P, E>,
+    sync: impl FnOnce(&[&P]) -> Result<(), E>,
+    finish: impl FnMut(P) -> Result<T, E>,
+    sync_failed: impl Fn(&E) -> E,
+) -> Vec<Result<T, E>> {
+    let sent: Vec<_> = requests.into_iter().map(send).collect();
+
+    let pending: Vec<_> = sent.iter().filter_map(|result| result.as_ref().ok()).collect();
+    let synced = if pending.is_empty() { Ok(()) } else { sync(&pending) };
+
+    let mut finish = finish;
+    sent.into_iter()
+        .map(|result| match (result, &synced) {
+            (Ok(pending), Ok(())) => finish(pending),
+            (Ok(_), Err(err)) => Err(sync_failed(err)),
+            (Err(err), _) => Err(err),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    #[test]
+    fn syncs_once_for_all_the_objects() {
+        let syncs = Cell::new(0);
+        let results = create_batched(
+            (0..16).collect(),
+            Ok::<_, String>,
+            |pending| {
+                assert_eq!(pending.len(), 16);
+                syncs.set(syncs.get() + 1);
+                Ok(())
+            },
+            |pending| Ok(pending * 2),
+            Clone::clone,
+        );
+
+        assert_eq!(syncs.get(), 1);
+        assert_eq!(results, (0..16).map(|i| Ok(i * 2)).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn failures_keep_their_position() {
+        let results = create_batched(
+            vec![0, 1, 2, 3],
+            |request| if request == 1 { Err("send") } else { Ok(request) },
+            |pending| {
+                assert_eq!(pending, [&0, &2, &3]);
+                Ok(())
+            },
+            |pending| if pending == 2 { Err("finish") } else { Ok(pending) },
+            |err| err,
+        );
+
+        assert_eq!(results, [Ok(0), Err("send"), Err("finish"), Ok(3)]);
+    }
+
+    #[test]
+    fn sync_failure_fails_the_sent_objects() {
+        let results = create_batched(
+            vec![0, 1, 2],
+            |request| if request == 0 { Err("send".to_owned()) } else { Ok(request) },
+            |_| Err("sync".to_owned()),
+            |_| -> Result<i32, _> { unreachable!("nothing is finished when the sync fails") },
+            |err| format!("{err} failed"),
+        );
+
+        assert_eq!(results, [
+            Err("send".to_owned()),
+            Err("sync failed".to_owned()),
+            Err("sync failed".to_owned())
+        ]);
+    }
+
+    #[test]
+    fn nothing_to_sync() {
+        let results: Vec<Result<i32, _>> = create_batched(
+            vec![0],
+            |_| Err("send"),
+            |_| unreachable!("there is nothing to wait for"),
+            Ok,
+            |err| err,
+        );
+
+        assert_eq!(results, [Err("send")]);
+    }
+}
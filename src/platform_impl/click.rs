@@ -0,0 +1,119 @@
+use std::cell::Cell;
+use std::time::Duration;
+
+use dpi::{PhysicalPosition, PhysicalSize};
+
+use crate::event::MouseButton;
+
+/// The interval used when the system one is unknown.
+pub const DEFAULT_DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How far the pointer may travel between two presses when the system distance is unknown.
+pub const DEFAULT_DOUBLE_CLICK_DISTANCE: PhysicalSize<f64> = PhysicalSize::new(4.0, 4.0);
+
+#[derive(Debug, Clone, Copy)]
+struct Click {
+    button: MouseButton,
+    position: PhysicalPosition<f64>,
+    /// The timestamp of the press, in milliseconds.
+    time: u32,
+    count: u32,
+}
+
+/// Computes the `click_count` of `WindowEvent::PointerButton` for the mouse.
+#[derive(Debug)]
+pub struct ClickCounter {
+    interval: Cell<Duration>,
+    distance: Cell<PhysicalSize<f64>>,
+    last: Cell<Option<Click>>,
+}
+
+impl Default for ClickCounter {
+    fn default() -> Self {
+        Self {
+            interval: Cell::new(DEFAULT_DOUBLE_CLICK_INTERVAL),
+            distance: Cell::new(DEFAULT_DOUBLE_CLICK_DISTANCE),
+            last: Cell::new(None),
+        }
+    }
+}
+
+impl ClickCounter {
+    /// The maximum time between two presses of the same click sequence.
+    #[cfg_attr(web_platform, allow(dead_code))]
+    pub fn set_interval(&self, interval: Duration) {
+        self.interval.set(interval);
+    }
+
+    /// The maximum distance, along each axis, between two presses of the same click sequence.
+    #[cfg_attr(not(windows_platform), allow(dead_code))]
+    pub fn set_distance(&self, distance: PhysicalSize<f64>) {
+        self.distance.set(distance);
+    }
+
+    /// Registers a press at the given timestamp, in milliseconds, and returns its click count.
+    ///
+    /// The timestamp may wrap around.
+    pub fn press(&self, button: MouseButton, position: PhysicalPosition<f64>, time: u32) -> u32 {
+        let count = match self.last.get() {
+            Some(last)
+                if last.button == button
+                    && Duration::from_millis(time.wrapping_sub(last.time).into())
+                        <= self.interval.get()
+                    && (last.position.x - position.x).abs() <= self.distance.get().width
+                    && (last.position.y - position.y).abs() <= self.distance.get().height =>
+            {
+                last.count.saturating_add(1)
+            },
+            _ => 1,
+        };
+
+        self.last.set(Some(Click { button, position, time, count }));
+        count
+    }
+
+    /// The click count of the press that the release of `button` ends.
+    pub fn release(&self, button: MouseButton) -> u32 {
+        match self.last.get() {
+            Some(last) if last.button == button => last.count,
+            _ => 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rapid_presses_are_counted() {
+        let counter = ClickCounter::default();
+        let position = PhysicalPosition::new(10.0, 10.0);
+
+        assert_eq!(counter.press(MouseButton::Left, position, 1000), 1);
+        assert_eq!(counter.release(MouseButton::Left), 1);
+        assert_eq!(counter.press(MouseButton::Left, position, 1200), 2);
+        assert_eq!(counter.release(MouseButton::Left), 2);
+        assert_eq!(counter.press(MouseButton::Left, PhysicalPosition::new(12.0, 9.0), 1400), 3);
+
+        // Too late.
+        assert_eq!(counter.press(MouseButton::Left, position, 2000), 1);
+        // Another button.
+        assert_eq!(counter.press(MouseButton::Right, position, 2100), 1);
+        assert_eq!(counter.release(MouseButton::Left), 1);
+        // Too far.
+        assert_eq!(counter.press(MouseButton::Right, PhysicalPosition::new(20.0, 10.0), 2200), 1);
+        assert_eq!(counter.press(MouseButton::Right, PhysicalPosition::new(20.0, 10.0), 2300), 2);
+
+        // The server time wrapped around.
+        counter.set_interval(Duration::from_millis(100));
+        assert_eq!(counter.press(MouseButton::Middle, position, u32::MAX - 50), 1);
+        assert_eq!(counter.press(MouseButton::Middle, position, 30), 2);
+
+        // A larger system distance.
+        counter.set_distance(PhysicalSize::new(8.0, 2.0));
+        assert_eq!(counter.press(MouseButton::Left, position, 100), 1);
+        assert_eq!(counter.press(MouseButton::Left, PhysicalPosition::new(17.0, 11.0), 150), 2);
+        assert_eq!(counter.press(MouseButton::Left, PhysicalPosition::new(17.0, 14.0), 200), 1);
+    }
+}
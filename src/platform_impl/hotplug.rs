@@ -0,0 +1,60 @@
+/// The monitors that changed between two enumerations, for backends that report hotplug by
+/// comparing the monitor list with the previous one.
+#[derive(Debug, PartialEq)]
+pub struct MonitorChanges<T> {
+    /// The monitors of the previous enumeration that are gone, as they were handed out before.
+    pub disconnected: Vec<T>,
+    /// The monitors of the new enumeration that weren't there before.
+    pub connected: Vec<T>,
+}
+
+impl<T: Clone> MonitorChanges<T> {
+    /// Compares the enumerations with `same_monitor`, since the handles of some backends compare
+    /// equal for distinct monitors.
+    pub fn new(previous: &[T], current: &[T], same_monitor: impl Fn(&T, &T) -> bool) -> Self {
+        let missing_from = |monitors: &[T], list: &[T]| -> Vec<T> {
+            monitors
+                .iter()
+                .filter(|monitor| !list.iter().any(|other| same_monitor(monitor, other)))
+                .cloned()
+                .collect()
+        };
+
+        Self {
+            disconnected: missing_from(previous, current),
+            connected: missing_from(current, previous),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unchanged_monitors_are_not_reported() {
+        let changes = MonitorChanges::new(&[1, 2], &[2, 1], |a, b| a == b);
+        assert_eq!(changes, MonitorChanges { disconnected: vec![], connected: vec![] });
+    }
+
+    #[test]
+    fn reports_connected_and_disconnected_monitors() {
+        let changes = MonitorChanges::new(&[1, 2, 3], &[2, 4], |a, b| a == b);
+        assert_eq!(changes, MonitorChanges { disconnected: vec![1, 3], connected: vec![4] });
+
+        // Everything is connected when the previous enumeration was empty.
+        let changes = MonitorChanges::new(&[], &[1], |a: &i32, b| a == b);
+        assert_eq!(changes, MonitorChanges { disconnected: vec![], connected: vec![1] });
+    }
+
+    #[test]
+    fn disconnected_monitors_keep_their_previous_handle() {
+        // An output plugged into the CRTC of another one is a different monitor.
+        let same_monitor = |a: &(u32, &str), b: &(u32, &str)| a == b;
+        let changes = MonitorChanges::new(&[(1, "DP-1")], &[(1, "HDMI-1")], same_monitor);
+        assert_eq!(changes, MonitorChanges {
+            disconnected: vec![(1, "DP-1")],
+            connected: vec![(1, "HDMI-1")]
+        });
+    }
+}
@@ -0,0 +1,72 @@
+use std::cell::Cell;
+#[cfg(not(web_platform))]
+use std::time::{Duration, Instant};
+
+#[cfg(web_platform)]
+use web_time::{Duration, Instant};
+
+/// Schedules the ticks requested with `ActiveEventLoop::set_idle_interval`.
+#[derive(Debug, Default)]
+pub struct IdleTimer {
+    interval: Cell<Option<Duration>>,
+    deadline: Cell<Option<Instant>>,
+}
+
+impl IdleTimer {
+    pub fn set_interval(&self, interval: Option<Duration>) {
+        self.interval.set(interval);
+        self.deadline.set(interval.map(|interval| Instant::now() + interval));
+    }
+
+    /// The time of the next tick, if any.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline.get()
+    }
+
+    /// The time left until the next tick, to be combined with the control flow timeout.
+    pub fn timeout(&self, now: Instant) -> Option<Duration> {
+        self.deadline().map(|deadline| deadline.saturating_duration_since(now))
+    }
+
+    /// Returns whether a tick is due, scheduling the next one if so.
+    pub fn poll(&self, now: Instant) -> bool {
+        match (self.interval.get(), self.deadline.get()) {
+            (Some(interval), Some(deadline)) if deadline <= now => {
+                // Skip missed ticks instead of delivering them in a burst.
+                let mut next = deadline + interval;
+                if next <= now {
+                    next = now + interval;
+                }
+                self.deadline.set(Some(next));
+                true
+            },
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn idle_timer_skips_missed_ticks() {
+        let timer = IdleTimer::default();
+        assert!(!timer.poll(Instant::now()));
+        assert_eq!(timer.timeout(Instant::now()), None);
+
+        let interval = Duration::from_secs(10);
+        timer.set_interval(Some(interval));
+        let start = Instant::now();
+        assert!(!timer.poll(start));
+
+        // Several intervals elapsed, but only a single tick is due.
+        let late = start + interval * 3;
+        assert!(timer.poll(late));
+        assert!(!timer.poll(late));
+        assert_eq!(timer.timeout(late), Some(interval));
+
+        timer.set_interval(None);
+        assert!(!timer.poll(late + interval * 2));
+    }
+}
@@ -1 +1,2 @@
+#[cfg(any(x11_platform, wayland_platform))]
 pub mod xkb;
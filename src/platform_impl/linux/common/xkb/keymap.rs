@@ -9,12 +9,14 @@ use x11_dl::xlib_xcb::xcb_connection_t;
 use xkb::XKB_MOD_INVALID;
 use xkbcommon_dl::{
     self as xkb, xkb_keycode_t, xkb_keymap, xkb_keymap_compile_flags, xkb_keysym_t,
-    xkb_layout_index_t, xkb_mod_index_t,
+    xkb_layout_index_t, xkb_mod_index_t, xkb_mod_mask_t,
 };
 #[cfg(wayland_platform)]
 use {memmap2::MmapOptions, std::os::unix::io::OwnedFd};
 
-use crate::keyboard::{Key, KeyCode, KeyLocation, NamedKey, NativeKey, NativeKeyCode, PhysicalKey};
+use crate::keyboard::{
+    Key, KeyCode, KeyLocation, ModifiersState, NamedKey, NativeKey, NativeKeyCode, PhysicalKey,
+};
 #[cfg(x11_platform)]
 use crate::platform_impl::common::xkb::XKBXH;
 use crate::platform_impl::common::xkb::{XkbContext, XKBH};
@@ -936,7 +938,7 @@ pub fn keysym_location(keysym: u32) -> KeyLocation {
 #[derive(Debug)]
 pub struct XkbKeymap {
     keymap: NonNull<xkb_keymap>,
-    _mods_indices: ModsIndices,
+    mods_indices: ModsIndices,
     pub _core_keyboard_id: i32,
 }
 
@@ -976,6 +978,27 @@ impl XkbKeymap {
         Some(Self::new_inner(keymap, core_keyboard_id))
     }
 
+    /// Compile the keymap for the given nul-terminated layout name, e.g. `b"us\0"`.
+    #[cfg(test)]
+    pub fn from_layout(context: &XkbContext, layout: &[u8]) -> Option<Self> {
+        let names = xkb::xkb_rule_names {
+            rules: ptr::null(),
+            model: ptr::null(),
+            layout: layout.as_ptr() as *const c_char,
+            variant: ptr::null(),
+            options: ptr::null(),
+        };
+        let keymap = unsafe {
+            (XKBH.xkb_keymap_new_from_names)(
+                context.as_ptr(),
+                &names,
+                xkb_keymap_compile_flags::XKB_KEYMAP_COMPILE_NO_FLAGS,
+            )
+        };
+        let keymap = NonNull::new(keymap)?;
+        Some(Self::new_inner(keymap, 0))
+    }
+
     fn new_inner(keymap: NonNull<xkb_keymap>, _core_keyboard_id: i32) -> Self {
         let mods_indices = ModsIndices {
             shift: mod_index_for_name(keymap, xkb::XKB_MOD_NAME_SHIFT),
@@ -988,12 +1011,12 @@ impl XkbKeymap {
             mod5: mod_index_for_name(keymap, b"Mod5\0"),
         };
 
-        Self { keymap, _mods_indices: mods_indices, _core_keyboard_id }
+        Self { keymap, mods_indices, _core_keyboard_id }
     }
 
     #[cfg(x11_platform)]
     pub fn mods_indices(&self) -> ModsIndices {
-        self._mods_indices
+        self.mods_indices
     }
 
     pub fn first_keysym_by_level(
@@ -1020,12 +1043,34 @@ impl XkbKeymap {
         }
     }
 
+    /// Get the xkb modifier mask matching the modifiers.
+    pub fn mods_mask(&self, mods: ModifiersState) -> xkb_mod_mask_t {
+        let indices = self.mods_indices;
+        [
+            (mods.shift_key(), indices.shift),
+            (mods.control_key(), indices.ctrl),
+            (mods.alt_key(), indices.alt),
+            (mods.super_key(), indices.logo),
+        ]
+        .into_iter()
+        .filter_map(|(active, index)| index.filter(|_| active))
+        .fold(0, |mask, index| mask | 1 << index)
+    }
+
     /// Check whether the given key repeats.
     pub fn key_repeats(&mut self, keycode: xkb_keycode_t) -> bool {
         unsafe { (XKBH.xkb_keymap_key_repeats)(self.keymap.as_ptr(), keycode) == 1 }
     }
 }
 
+#[cfg(x11_platform)]
+impl Clone for XkbKeymap {
+    fn clone(&self) -> Self {
+        unsafe { (XKBH.xkb_keymap_ref)(self.keymap.as_ptr()) };
+        Self { ..*self }
+    }
+}
+
 impl Drop for XkbKeymap {
     fn drop(&mut self) {
         unsafe {
@@ -15,7 +15,7 @@ use xkbcommon_dl::{
 use {x11_dl::xlib_xcb::xcb_connection_t, xkbcommon_dl::x11::xkbcommon_x11_handle};
 
 use crate::event::{ElementState, KeyEvent};
-use crate::keyboard::{Key, KeyLocation};
+use crate::keyboard::{Key, KeyLocation, ModifiersState, PhysicalKey};
 use crate::platform_impl::KeyEventExtra;
 use crate::utils::Lazy;
 
@@ -130,7 +130,7 @@ impl Context {
     #[cfg(wayland_platform)]
     pub fn set_keymap_from_fd(&mut self, fd: OwnedFd, size: usize) {
         let keymap = XkbKeymap::from_fd(&self.context, fd, size);
-        let state = keymap.as_ref().and_then(XkbState::new_wayland);
+        let state = keymap.as_ref().and_then(XkbState::new);
         if keymap.is_none() || state.is_none() {
             warn!("failed to update xkb keymap");
         }
@@ -149,6 +149,23 @@ impl Context {
         self.keymap = keymap;
     }
 
+    /// Text produced by the key with the given modifiers in the current layout.
+    #[cfg(any(wayland_platform, test))]
+    pub fn key_text(&mut self, key: PhysicalKey, mods: ModifiersState) -> Option<SmolStr> {
+        let state = self.state.as_mut()?;
+        let keymap = self.keymap.as_ref()?;
+        key_text(keymap, state, key, mods, &mut self.scratch_buffer)
+    }
+
+    /// The current keymap and state, which follows the updates of this context until the keymap
+    /// changes.
+    #[cfg(x11_platform)]
+    pub fn shared_keymap(&self) -> Option<SharedKeymap> {
+        let keymap = self.keymap.as_ref()?.clone();
+        let state = self.state.as_ref()?.share();
+        Some(SharedKeymap { keymap, state })
+    }
+
     /// Key builder context with the user provided xkb state.
     pub fn key_context(&mut self) -> Option<KeyContext<'_>> {
         let state = self.state.as_mut()?;
@@ -175,6 +192,40 @@ impl Context {
     }
 }
 
+/// The keymap and state of a [`Context`], to look up the text of keys outside of the event
+/// processor.
+#[cfg(x11_platform)]
+#[derive(Debug)]
+pub struct SharedKeymap {
+    keymap: XkbKeymap,
+    state: XkbState,
+}
+
+#[cfg(x11_platform)]
+impl SharedKeymap {
+    /// Text produced by the key with the given modifiers in the current layout.
+    pub fn key_text(&mut self, key: PhysicalKey, mods: ModifiersState) -> Option<SmolStr> {
+        key_text(&self.keymap, &mut self.state, key, mods, &mut Vec::new())
+    }
+}
+
+/// Look up the text of the key in the layout currently active in `state`, with only `mods` set.
+fn key_text(
+    keymap: &XkbKeymap,
+    state: &mut XkbState,
+    key: PhysicalKey,
+    mods: ModifiersState,
+    scratch_buffer: &mut Vec<u8>,
+) -> Option<SmolStr> {
+    let keycode = physicalkey_to_scancode(key)? + 8;
+    let layout = state.layout(keycode);
+
+    // Use a separate state to not disturb the modifiers of the real one.
+    let mut lookup_state = XkbState::new(keymap)?;
+    lookup_state.update_modifiers(keymap.mods_mask(mods), 0, 0, 0, 0, layout);
+    lookup_state.get_utf8_raw(keycode, scratch_buffer)
+}
+
 pub struct KeyContext<'a> {
     pub state: &'a mut XkbState,
     pub keymap: &'a mut XkbKeymap,
@@ -413,3 +464,71 @@ fn byte_slice_to_smol_str(bytes: &[u8]) -> Option<SmolStr> {
         })
         .ok()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keyboard::KeyCode;
+
+    /// The context with the layout, or `None` to skip the test when libxkbcommon or the layout
+    /// isn't installed.
+    fn context_with_layout(layout: &[u8]) -> Option<Context> {
+        let mut context = Context::new().ok()?;
+        let keymap = XkbKeymap::from_layout(&context.context, layout)?;
+        context.state = XkbState::new(&keymap);
+        context.keymap = Some(keymap);
+        Some(context)
+    }
+
+    fn text(context: &mut Context, code: KeyCode, mods: ModifiersState) -> Option<SmolStr> {
+        context.key_text(PhysicalKey::Code(code), mods)
+    }
+
+    #[test]
+    fn key_text_us() {
+        let Some(mut context) = context_with_layout(b"us\0") else {
+            return;
+        };
+        let none = ModifiersState::empty();
+        assert_eq!(text(&mut context, KeyCode::KeyA, none).as_deref(), Some("a"));
+        assert_eq!(text(&mut context, KeyCode::KeyA, ModifiersState::SHIFT).as_deref(), Some("A"));
+        assert_eq!(
+            text(&mut context, KeyCode::Digit2, ModifiersState::SHIFT).as_deref(),
+            Some("@")
+        );
+        assert_eq!(
+            text(&mut context, KeyCode::KeyA, ModifiersState::CONTROL).as_deref(),
+            Some("\u{1}")
+        );
+        assert_eq!(text(&mut context, KeyCode::ShiftLeft, none), None);
+    }
+
+    #[test]
+    fn key_text_de() {
+        let Some(mut context) = context_with_layout(b"de\0") else {
+            return;
+        };
+        let none = ModifiersState::empty();
+        assert_eq!(text(&mut context, KeyCode::KeyY, none).as_deref(), Some("z"));
+        assert_eq!(text(&mut context, KeyCode::KeyZ, none).as_deref(), Some("y"));
+        assert_eq!(text(&mut context, KeyCode::Semicolon, none).as_deref(), Some("ö"));
+        assert_eq!(
+            text(&mut context, KeyCode::Digit2, ModifiersState::SHIFT).as_deref(),
+            Some("\"")
+        );
+    }
+
+    #[test]
+    fn key_text_fr() {
+        let Some(mut context) = context_with_layout(b"fr\0") else {
+            return;
+        };
+        let none = ModifiersState::empty();
+        assert_eq!(text(&mut context, KeyCode::KeyQ, none).as_deref(), Some("a"));
+        assert_eq!(text(&mut context, KeyCode::Digit1, none).as_deref(), Some("&"));
+        assert_eq!(
+            text(&mut context, KeyCode::Digit1, ModifiersState::SHIFT).as_deref(),
+            Some("1")
+        );
+    }
+}
@@ -22,8 +22,7 @@ pub struct XkbState {
 }
 
 impl XkbState {
-    #[cfg(wayland_platform)]
-    pub fn new_wayland(keymap: &XkbKeymap) -> Option<Self> {
+    pub fn new(keymap: &XkbKeymap) -> Option<Self> {
         let state = NonNull::new(unsafe { (XKBH.xkb_state_new)(keymap.as_ptr()) })?;
         Some(Self::new_inner(state))
     }
@@ -44,6 +43,13 @@ impl XkbState {
         this
     }
 
+    /// Another handle to the same state, which follows its updates.
+    #[cfg(x11_platform)]
+    pub fn share(&self) -> Self {
+        unsafe { (XKBH.xkb_state_ref)(self.state.as_ptr()) };
+        Self { ..*self }
+    }
+
     pub fn get_one_sym_raw(&mut self, keycode: xkb_keycode_t) -> xkb_keysym_t {
         unsafe { (XKBH.xkb_state_key_get_one_sym)(self.state.as_ptr(), keycode) }
     }
@@ -1,7 +1,7 @@
 #![cfg(free_unix)]
 
-#[cfg(all(not(x11_platform), not(wayland_platform)))]
-compile_error!("Please select a feature to build for unix: `x11`, `wayland`");
+#[cfg(all(not(x11_platform), not(wayland_platform), not(null_platform)))]
+compile_error!("Please select a feature to build for unix: `x11`, `wayland`, `null`");
 
 use std::env;
 use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd, RawFd};
@@ -9,18 +9,23 @@ use std::time::Duration;
 #[cfg(x11_platform)]
 use std::{ffi::CStr, mem::MaybeUninit, os::raw::*, sync::Arc, sync::Mutex};
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use smol_str::SmolStr;
 
+#[cfg(any(x11_platform, wayland_platform))]
 pub(crate) use self::common::xkb::{physicalkey_to_scancode, scancode_to_physicalkey};
 #[cfg(x11_platform)]
 use self::x11::{XConnection, XError, XNotSupported};
 use crate::application::ApplicationHandler;
-pub(crate) use crate::cursor::OnlyCursorImageSource as PlatformCustomCursorSource;
-use crate::dpi::PhysicalPosition;
+use crate::cursor::{BadImage, CursorImage, CustomCursor as RootCustomCursor};
 #[cfg(x11_platform)]
 use crate::dpi::Size;
+use crate::dpi::{PhysicalPosition, PhysicalSize};
 use crate::error::{EventLoopError, NotSupportedError};
 use crate::event_loop::ActiveEventLoop;
+#[cfg(x11_platform)]
+use crate::icon::Icon;
 pub(crate) use crate::icon::RgbaIcon as PlatformIcon;
 use crate::keyboard::Key;
 use crate::monitor::VideoMode;
@@ -32,6 +37,8 @@ use crate::utils::Lazy;
 use crate::window::ActivationToken;
 
 pub(crate) mod common;
+#[cfg(null_platform)]
+pub(crate) mod null;
 #[cfg(wayland_platform)]
 pub(crate) mod wayland;
 #[cfg(x11_platform)]
@@ -43,62 +50,89 @@ pub(crate) enum Backend {
     X,
     #[cfg(wayland_platform)]
     Wayland,
+    #[cfg(null_platform)]
+    Null,
 }
 
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
 pub(crate) struct PlatformSpecificEventLoopAttributes {
     pub(crate) forced_backend: Option<Backend>,
     pub(crate) any_thread: bool,
+    #[cfg_attr(not(wayland_platform), allow(dead_code))]
+    pub(crate) double_click_interval: Option<Duration>,
+}
+
+impl PlatformSpecificEventLoopAttributes {
+    /// Whether the headless backend is forced. It doesn't use the main thread nor any
+    /// process-wide resource, so it can be created on any thread and several times.
+    #[cfg(null_platform)]
+    pub(crate) fn is_headless(&self) -> bool {
+        self.forced_backend == Some(Backend::Null)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ApplicationName {
     pub general: String,
     pub instance: String,
 }
 
 impl ApplicationName {
+    #[cfg(any(x11_platform, wayland_platform))]
     pub fn new(general: String, instance: String) -> Self {
         Self { general, instance }
     }
 }
 
-#[derive(Clone, Debug, PartialEq)]
+/// The activation token, and the X11 ids and icon are skipped by `serde`, as they only make sense
+/// for the current session.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default))]
 pub struct PlatformSpecificWindowAttributes {
     pub name: Option<ApplicationName>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub activation_token: Option<ActivationToken>,
     #[cfg(x11_platform)]
     pub x11: X11WindowAttributes,
 }
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default))]
 #[cfg(x11_platform)]
 pub struct X11WindowAttributes {
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub visual_id: Option<x11rb::protocol::xproto::Visualid>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub screen_id: Option<i32>,
     pub base_size: Option<Size>,
     pub override_redirect: bool,
     pub x11_window_types: Vec<XWindowType>,
 
     /// The parent window to embed this window into.
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub embed_window: Option<x11rb::protocol::xproto::Window>,
+
+    /// The fraction of the window that must be on another monitor to adopt its scale factor.
+    pub scale_factor_hysteresis: Option<f64>,
+
+    /// The larger icon listed after the window icon in `_NET_WM_ICON`.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub taskbar_icon: Option<Icon>,
 }
 
-#[cfg_attr(not(x11_platform), allow(clippy::derivable_impls))]
-impl Default for PlatformSpecificWindowAttributes {
+#[cfg(x11_platform)]
+impl Default for X11WindowAttributes {
     fn default() -> Self {
         Self {
-            name: None,
-            activation_token: None,
-            #[cfg(x11_platform)]
-            x11: X11WindowAttributes {
-                visual_id: None,
-                screen_id: None,
-                base_size: None,
-                override_redirect: false,
-                x11_window_types: vec![XWindowType::Normal],
-                embed_window: None,
-            },
+            visual_id: None,
+            screen_id: None,
+            base_size: None,
+            override_redirect: false,
+            x11_window_types: vec![XWindowType::Normal],
+            embed_window: None,
+            scale_factor_hysteresis: None,
+            taskbar_icon: None,
         }
     }
 }
@@ -113,6 +147,8 @@ pub enum MonitorHandle {
     X(x11::MonitorHandle),
     #[cfg(wayland_platform)]
     Wayland(wayland::MonitorHandle),
+    #[cfg(null_platform)]
+    Null(null::MonitorHandle),
 }
 
 /// `x11_or_wayland!(match expr; Enum(foo) => foo.something())`
@@ -131,6 +167,8 @@ macro_rules! x11_or_wayland {
             $enum::X($($c1)*) => $enum2::X($x),
             #[cfg(wayland_platform)]
             $enum::Wayland($($c1)*) => $enum2::Wayland($x),
+            #[cfg(null_platform)]
+            $enum::Null($($c1)*) => $enum2::Null($x),
         }
     };
     (match $what:expr; $enum:ident ( $($c1:tt)* ) => $x:expr) => {
@@ -139,6 +177,8 @@ macro_rules! x11_or_wayland {
             $enum::X($($c1)*) => $x,
             #[cfg(wayland_platform)]
             $enum::Wayland($($c1)*) => $x,
+            #[cfg(null_platform)]
+            $enum::Null($($c1)*) => $x,
         }
     };
 }
@@ -149,6 +189,7 @@ impl MonitorHandle {
         x11_or_wayland!(match self; MonitorHandle(m) => m.name())
     }
 
+    #[cfg(any(x11_platform, wayland_platform))]
     #[inline]
     pub fn native_identifier(&self) -> u32 {
         x11_or_wayland!(match self; MonitorHandle(m) => m.native_identifier())
@@ -159,11 +200,26 @@ impl MonitorHandle {
         x11_or_wayland!(match self; MonitorHandle(m) => m.position())
     }
 
+    #[inline]
+    pub fn work_area(&self) -> Option<(PhysicalPosition<i32>, PhysicalSize<u32>)> {
+        x11_or_wayland!(match self; MonitorHandle(m) => m.work_area())
+    }
+
     #[inline]
     pub fn scale_factor(&self) -> f64 {
         x11_or_wayland!(match self; MonitorHandle(m) => m.scale_factor() as _)
     }
 
+    #[inline]
+    pub fn physical_size_mm(&self) -> Option<(f64, f64)> {
+        x11_or_wayland!(match self; MonitorHandle(m) => m.physical_size_mm())
+    }
+
+    #[inline]
+    pub fn is_primary(&self) -> bool {
+        x11_or_wayland!(match self; MonitorHandle(m) => m.is_primary())
+    }
+
     #[inline]
     pub fn current_video_mode(&self) -> Option<VideoMode> {
         x11_or_wayland!(match self; MonitorHandle(m) => m.current_video_mode())
@@ -189,6 +245,31 @@ pub(crate) enum PlatformCustomCursor {
     X(x11::CustomCursor),
 }
 
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub(crate) enum PlatformCustomCursorSource {
+    Image(CursorImage),
+    /// Created with `CustomCursorExtX11::from_animation`, only animated on X11.
+    #[cfg_attr(not(x11_platform), allow(dead_code))]
+    Animation {
+        duration: Duration,
+        cursors: Vec<RootCustomCursor>,
+    },
+}
+
+impl PlatformCustomCursorSource {
+    pub(crate) fn from_rgba(
+        rgba: Vec<u8>,
+        width: u16,
+        height: u16,
+        hotspot_x: u16,
+        hotspot_y: u16,
+        premultiplied: bool,
+    ) -> Result<Self, BadImage> {
+        CursorImage::from_rgba(rgba, width, height, hotspot_x, hotspot_y, premultiplied)
+            .map(Self::Image)
+    }
+}
+
 /// Hooks for X11 errors.
 #[cfg(x11_platform)]
 pub(crate) static XLIB_ERROR_HOOKS: Mutex<Vec<XlibErrorHook>> = Mutex::new(Vec::new());
@@ -245,13 +326,19 @@ pub enum EventLoop {
     Wayland(Box<wayland::EventLoop>),
     #[cfg(x11_platform)]
     X(x11::EventLoop),
+    #[cfg(null_platform)]
+    Null(null::EventLoop),
 }
 
 impl EventLoop {
     pub(crate) fn new(
         attributes: &PlatformSpecificEventLoopAttributes,
     ) -> Result<Self, EventLoopError> {
-        if !attributes.any_thread && !is_main_thread() {
+        #[cfg(null_platform)]
+        let any_thread = attributes.any_thread || attributes.is_headless();
+        #[cfg(not(null_platform))]
+        let any_thread = attributes.any_thread;
+        if !any_thread && !is_main_thread() {
             panic!(
                 "Initializing the event loop outside of the main thread is a significant \
                  cross-platform compatibility hazard. If you absolutely need to create an \
@@ -299,15 +386,19 @@ impl EventLoop {
         // Create the display based on the backend.
         match backend {
             #[cfg(wayland_platform)]
-            Backend::Wayland => EventLoop::new_wayland_any_thread().map_err(Into::into),
+            Backend::Wayland => EventLoop::new_wayland_any_thread(attributes).map_err(Into::into),
             #[cfg(x11_platform)]
             Backend::X => EventLoop::new_x11_any_thread().map_err(Into::into),
+            #[cfg(null_platform)]
+            Backend::Null => null::EventLoop::new().map(EventLoop::Null),
         }
     }
 
     #[cfg(wayland_platform)]
-    fn new_wayland_any_thread() -> Result<EventLoop, EventLoopError> {
-        wayland::EventLoop::new().map(|evlp| EventLoop::Wayland(Box::new(evlp)))
+    fn new_wayland_any_thread(
+        attributes: &PlatformSpecificEventLoopAttributes,
+    ) -> Result<EventLoop, EventLoopError> {
+        wayland::EventLoop::new(attributes).map(|evlp| EventLoop::Wayland(Box::new(evlp)))
     }
 
     #[cfg(x11_platform)]
@@ -320,16 +411,34 @@ impl EventLoop {
         Ok(EventLoop::X(x11::EventLoop::new(xconn)))
     }
 
+    #[cfg(wayland_platform)]
     #[inline]
     pub fn is_wayland(&self) -> bool {
         match *self {
             #[cfg(wayland_platform)]
             EventLoop::Wayland(_) => true,
+            #[cfg(any(x11_platform, null_platform))]
+            _ => false,
+        }
+    }
+
+    #[cfg(x11_platform)]
+    #[inline]
+    pub fn is_x11(&self) -> bool {
+        match *self {
             #[cfg(x11_platform)]
+            EventLoop::X(_) => true,
+            #[cfg(any(wayland_platform, null_platform))]
             _ => false,
         }
     }
 
+    #[cfg(null_platform)]
+    #[inline]
+    pub fn is_null(&self) -> bool {
+        matches!(self, EventLoop::Null(_))
+    }
+
     pub fn run_app<A: ApplicationHandler>(self, app: A) -> Result<(), EventLoopError> {
         x11_or_wayland!(match self; EventLoop(evlp) => evlp.run_app(app))
     }
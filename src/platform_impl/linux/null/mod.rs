@@ -0,0 +1,705 @@
+//! A backend that doesn't connect to any display server.
+//!
+//! Windows only exist in memory, and the only events are the ones injected through an
+//! `EventInjector`, redraw requests, wake ups and window destruction.
+
+use std::cell::Cell;
+use std::cmp::{Ordering as CmpOrdering, Reverse};
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::mem;
+use std::num::{NonZeroU16, NonZeroU32};
+use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd, RawFd};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
+
+use calloop::ping::Ping;
+use calloop::EventLoop as Loop;
+use smol_str::SmolStr;
+
+use crate::application::ApplicationHandler;
+use crate::dpi::{PhysicalPosition, PhysicalSize};
+use crate::error::{EventLoopError, NotSupportedError, RequestError};
+use crate::event::{StartCause, WindowEvent};
+use crate::event_loop::{
+    ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEvents,
+    EventLoopProxy as CoreEventLoopProxy, EventLoopProxyProvider,
+    OwnedDisplayHandle as CoreOwnedDisplayHandle,
+};
+use crate::keyboard::{ModifiersState, PhysicalKey};
+use crate::monitor::VideoMode;
+use crate::platform::pump_events::PumpStatus;
+use crate::platform_impl::idle::IdleTimer;
+use crate::platform_impl::platform::min_timeout;
+use crate::platform_impl::redraw::RedrawSchedule;
+use crate::window::{
+    CustomCursor as RootCustomCursor, CustomCursorSource, RedrawPriority, Theme,
+    Window as CoreWindow, WindowAttributes, WindowId,
+};
+
+mod window;
+
+pub use window::Window;
+use window::WindowState;
+
+pub struct EventLoop {
+    loop_running: bool,
+    event_loop: Loop<'static, EventLoopState>,
+    window_target: ActiveEventLoop,
+
+    /// The current state of the event loop.
+    state: EventLoopState,
+}
+
+struct EventLoopState {
+    /// User requested a wake up.
+    proxy_wake_up: bool,
+}
+
+impl EventLoop {
+    pub(crate) fn new() -> Result<Self, EventLoopError> {
+        let event_loop = Loop::<EventLoopState>::try_new()
+            .map_err(|err| os_error!(std::io::Error::from(err)))?;
+
+        let (waker, waker_source) = calloop::ping::make_ping().map_err(|err| os_error!(err))?;
+        event_loop
+            .handle()
+            .insert_source(waker_source, move |_, _, _| {
+                // No extra handling is required, we just need to wake-up.
+            })
+            .map_err(|err| os_error!(std::io::Error::from(err.error)))?;
+
+        let (user_waker, user_waker_source) =
+            calloop::ping::make_ping().map_err(|err| os_error!(err))?;
+        event_loop
+            .handle()
+            .insert_source(user_waker_source, move |_, _, state| {
+                state.proxy_wake_up = true;
+            })
+            .map_err(|err| os_error!(std::io::Error::from(err.error)))?;
+
+        let window_target = ActiveEventLoop {
+            control_flow: Cell::new(ControlFlow::default()),
+            exit: Cell::new(None),
+            idle_timer: IdleTimer::default(),
+            shared: Arc::new(Shared::new(waker)),
+            event_loop_proxy: Arc::new(EventLoopProxy::new(user_waker)),
+        };
+
+        Ok(Self {
+            loop_running: false,
+            event_loop,
+            window_target,
+            state: EventLoopState { proxy_wake_up: false },
+        })
+    }
+
+    pub(crate) fn window_target(&self) -> &dyn RootActiveEventLoop {
+        &self.window_target
+    }
+
+    pub(crate) fn shared(&self) -> &Arc<Shared> {
+        &self.window_target.shared
+    }
+
+    pub fn run_app<A: ApplicationHandler>(mut self, app: A) -> Result<(), EventLoopError> {
+        self.run_app_on_demand(app)
+    }
+
+    pub fn run_app_on_demand<A: ApplicationHandler>(
+        &mut self,
+        mut app: A,
+    ) -> Result<(), EventLoopError> {
+        self.window_target.clear_exit();
+        let exit = loop {
+            match self.pump_app_events(None, &mut app) {
+                PumpStatus::Exit(0) => break Ok(()),
+                PumpStatus::Exit(code) => break Err(EventLoopError::ExitFailure(code)),
+                PumpStatus::Continue => continue,
+            }
+        };
+
+        self.window_target.control_flow.set(ControlFlow::default());
+
+        exit
+    }
+
+    pub fn pump_app_events<A: ApplicationHandler>(
+        &mut self,
+        timeout: Option<Duration>,
+        mut app: A,
+    ) -> PumpStatus {
+        if !self.loop_running {
+            self.loop_running = true;
+
+            // run the initial loop iteration
+            self.single_iteration(&mut app, StartCause::Init);
+        }
+
+        // Consider the possibility that the `StartCause::Init` iteration could
+        // request to Exit.
+        if !self.window_target.exiting() {
+            self.poll_events_with_timeout(timeout, &mut app);
+        }
+        if let Some(code) = self.window_target.exit.get() {
+            self.loop_running = false;
+
+            app.exiting(&self.window_target);
+
+            PumpStatus::Exit(code)
+        } else {
+            PumpStatus::Continue
+        }
+    }
+
+    fn has_pending(&self) -> bool {
+        self.state.proxy_wake_up || self.window_target.shared.has_pending()
+    }
+
+    fn poll_events_with_timeout<A: ApplicationHandler>(
+        &mut self,
+        mut timeout: Option<Duration>,
+        app: &mut A,
+    ) {
+        let start = Instant::now();
+
+        timeout = if self.has_pending() {
+            // If we already have work to do then we don't want to block on the next poll.
+            Some(Duration::ZERO)
+        } else {
+            let control_flow_timeout = match self.window_target.control_flow() {
+                ControlFlow::Wait => None,
+                ControlFlow::Poll => Some(Duration::ZERO),
+                ControlFlow::WaitUntil(wait_deadline) => {
+                    Some(wait_deadline.saturating_duration_since(start))
+                },
+            };
+
+            let idle_timeout = self.window_target.idle_timer.timeout(start);
+            let redraw_timeout = self.window_target.shared.scheduled_redraws.timeout(start);
+            let timeout = min_timeout(min_timeout(control_flow_timeout, idle_timeout), timeout);
+            min_timeout(redraw_timeout, timeout)
+        };
+
+        if let Err(error) =
+            self.event_loop.dispatch(timeout, &mut self.state).map_err(std::io::Error::from)
+        {
+            tracing::error!("Failed to poll for events: {error:?}");
+            let exit_code = error.raw_os_error().unwrap_or(1);
+            self.window_target.exit.set(Some(exit_code));
+            return;
+        }
+
+        let cause = match self.window_target.control_flow() {
+            ControlFlow::Poll => StartCause::Poll,
+            ControlFlow::Wait => StartCause::WaitCancelled { start, requested_resume: None },
+            ControlFlow::WaitUntil(deadline) => {
+                let actual_resume = Instant::now();
+                if actual_resume < deadline {
+                    StartCause::WaitCancelled { start, requested_resume: Some(deadline) }
+                } else {
+                    StartCause::ResumeTimeReached {
+                        start,
+                        requested_resume: deadline,
+                        actual_resume,
+                    }
+                }
+            },
+        };
+
+        // Don't run an iteration for a spurious wake up.
+        if !self.has_pending()
+            && !matches!(&cause, StartCause::ResumeTimeReached { .. } | StartCause::Poll)
+            && self.window_target.idle_timer.timeout(Instant::now()) != Some(Duration::ZERO)
+            && self.window_target.shared.scheduled_redraws.timeout(Instant::now())
+                != Some(Duration::ZERO)
+        {
+            return;
+        }
+
+        self.single_iteration(app, cause);
+    }
+
+    fn single_iteration<A: ApplicationHandler>(&mut self, app: &mut A, cause: StartCause) {
+        let target = &self.window_target;
+        app.new_events(target, cause);
+
+        if cause == StartCause::Init {
+            app.can_create_surfaces(target);
+        }
+
+        // Only deliver the events queued so far, so that an application re-injecting events
+        // can't starve the loop.
+        let events = mem::take(&mut *target.shared.events.lock().unwrap());
+        for (window_id, event) in events {
+            if let Some(window) = target.shared.window(window_id) {
+                window.apply_event(&event);
+            }
+            app.window_event(target, window_id, event);
+        }
+
+        if mem::take(&mut self.state.proxy_wake_up) {
+            app.proxy_wake_up(target);
+        }
+
+        let mut redraws = mem::take(&mut *target.shared.redraws.lock().unwrap());
+        for window_id in target.shared.scheduled_redraws.take_due(Instant::now()) {
+            redraws.entry(window_id).or_insert(RedrawPriority::Normal);
+        }
+
+        // Deliver higher priority redraws first.
+        let mut redraws: Vec<_> = redraws.into_iter().collect();
+        redraws.sort_by_key(|&(_, priority)| Reverse(priority));
+        for (window_id, _) in redraws {
+            app.window_event(target, window_id, WindowEvent::RedrawRequested);
+        }
+
+        if target.idle_timer.poll(Instant::now()) {
+            app.idle_tick(target);
+        }
+
+        // This is always the last event we dispatch before poll again
+        app.about_to_wait(target);
+    }
+}
+
+impl AsFd for EventLoop {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.event_loop.as_fd()
+    }
+}
+
+impl AsRawFd for EventLoop {
+    fn as_raw_fd(&self) -> RawFd {
+        self.event_loop.as_raw_fd()
+    }
+}
+
+impl Drop for EventLoop {
+    fn drop(&mut self) {
+        self.window_target.event_loop_proxy.alive.store(false, Ordering::Relaxed);
+    }
+}
+
+/// The state shared between the event loop, its windows and the event injectors.
+#[derive(Debug)]
+pub(crate) struct Shared {
+    waker: Ping,
+    monitor: Mutex<MonitorHandle>,
+    events: Mutex<VecDeque<(WindowId, WindowEvent)>>,
+    redraws: Mutex<HashMap<WindowId, RedrawPriority>>,
+    scheduled_redraws: RedrawSchedule,
+    windows: Mutex<HashMap<WindowId, Weak<WindowState>>>,
+}
+
+impl Shared {
+    fn new(waker: Ping) -> Self {
+        Self {
+            waker,
+            monitor: Mutex::new(MonitorHandle::default()),
+            events: Default::default(),
+            redraws: Default::default(),
+            scheduled_redraws: Default::default(),
+            windows: Default::default(),
+        }
+    }
+
+    /// Queue an event for the window and wake up the event loop.
+    pub(crate) fn send_event(&self, window_id: WindowId, event: WindowEvent) {
+        self.events.lock().unwrap().push_back((window_id, event));
+        self.waker.ping();
+    }
+
+    pub(crate) fn set_monitor(&self, size: PhysicalSize<u32>, scale_factor: f64) {
+        *self.monitor.lock().unwrap() = MonitorHandle { size, scale_factor };
+    }
+
+    fn monitor(&self) -> MonitorHandle {
+        self.monitor.lock().unwrap().clone()
+    }
+
+    fn request_redraw(&self, window_id: WindowId, priority: RedrawPriority) {
+        let mut redraws = self.redraws.lock().unwrap();
+        let pending = redraws.entry(window_id).or_insert(priority);
+        *pending = priority.max(*pending);
+        drop(redraws);
+        self.waker.ping();
+    }
+
+    fn request_redraw_at(&self, window_id: WindowId, deadline: Instant) {
+        if self.scheduled_redraws.schedule(window_id, deadline) {
+            self.waker.ping();
+        }
+    }
+
+    fn window(&self, window_id: WindowId) -> Option<Arc<WindowState>> {
+        self.windows.lock().unwrap().get(&window_id).and_then(Weak::upgrade)
+    }
+
+    fn has_pending(&self) -> bool {
+        !self.events.lock().unwrap().is_empty() || !self.redraws.lock().unwrap().is_empty()
+    }
+}
+
+pub struct ActiveEventLoop {
+    control_flow: Cell<ControlFlow>,
+    exit: Cell<Option<i32>>,
+    idle_timer: IdleTimer,
+    shared: Arc<Shared>,
+    event_loop_proxy: Arc<EventLoopProxy>,
+}
+
+impl ActiveEventLoop {
+    pub(crate) fn shared(&self) -> &Arc<Shared> {
+        &self.shared
+    }
+
+    fn clear_exit(&self) {
+        self.exit.set(None)
+    }
+}
+
+impl RootActiveEventLoop for ActiveEventLoop {
+    fn create_proxy(&self) -> CoreEventLoopProxy {
+        CoreEventLoopProxy::new(self.event_loop_proxy.clone())
+    }
+
+    fn create_window(
+        &self,
+        window_attributes: WindowAttributes,
+    ) -> Result<Box<dyn CoreWindow>, RequestError> {
+        Ok(Box::new(Window::new(self, window_attributes)))
+    }
+
+    fn create_custom_cursor(
+        &self,
+        _: CustomCursorSource,
+    ) -> Result<RootCustomCursor, RequestError> {
+        Err(NotSupportedError::new("custom cursors are not supported without a display").into())
+    }
+
+    fn available_monitors(&self) -> Box<dyn Iterator<Item = crate::monitor::MonitorHandle>> {
+        Box::new(std::iter::once(mkmonitor(self.shared.monitor())))
+    }
+
+    fn primary_monitor(&self) -> Option<crate::monitor::MonitorHandle> {
+        Some(mkmonitor(self.shared.monitor()))
+    }
+
+    fn system_theme(&self) -> Option<Theme> {
+        None
+    }
+
+    fn accent_color(&self) -> Option<[u8; 4]> {
+        None
+    }
+
+    fn key_text(&self, _: PhysicalKey, _: ModifiersState) -> Option<SmolStr> {
+        None
+    }
+
+    fn listen_device_events(&self, _: DeviceEvents) {}
+
+    fn set_control_flow(&self, control_flow: ControlFlow) {
+        self.control_flow.set(control_flow)
+    }
+
+    fn control_flow(&self) -> ControlFlow {
+        self.control_flow.get()
+    }
+
+    fn set_idle_interval(&self, interval: Option<Duration>) -> Result<(), RequestError> {
+        self.idle_timer.set_interval(interval);
+        Ok(())
+    }
+
+    fn set_resize_coalescing(&self, _: bool) {}
+
+    fn scroll_lines_to_pixels(&self, lines: f32) -> f32 {
+        lines * 3.0 * 16.0
+    }
+
+    fn exit(&self) {
+        self.exit_with_code(0)
+    }
+
+    fn exit_with_code(&self, code: i32) {
+        self.exit.set(Some(code))
+    }
+
+    fn exiting(&self) -> bool {
+        self.exit.get().is_some()
+    }
+
+    fn owned_display_handle(&self) -> CoreOwnedDisplayHandle {
+        CoreOwnedDisplayHandle::new(Arc::new(NoDisplayHandle))
+    }
+
+    fn rwh_06_handle(&self) -> &dyn rwh_06::HasDisplayHandle {
+        &NoDisplayHandle
+    }
+}
+
+/// There's no display to hand out.
+struct NoDisplayHandle;
+
+impl rwh_06::HasDisplayHandle for NoDisplayHandle {
+    fn display_handle(&self) -> Result<rwh_06::DisplayHandle<'_>, rwh_06::HandleError> {
+        Err(rwh_06::HandleError::NotSupported)
+    }
+}
+
+pub struct EventLoopProxy {
+    ping: Ping,
+    alive: AtomicBool,
+}
+
+impl EventLoopProxyProvider for EventLoopProxy {
+    fn wake_up(&self) {
+        self.ping.ping();
+    }
+
+    fn is_alive(&self) -> bool {
+        self.alive.load(Ordering::Relaxed)
+    }
+}
+
+impl EventLoopProxy {
+    fn new(ping: Ping) -> Self {
+        Self { ping, alive: AtomicBool::new(true) }
+    }
+}
+
+/// The single virtual monitor, located at the origin.
+#[derive(Debug, Clone)]
+pub struct MonitorHandle {
+    size: PhysicalSize<u32>,
+    scale_factor: f64,
+}
+
+impl Default for MonitorHandle {
+    fn default() -> Self {
+        Self { size: PhysicalSize::new(1920, 1080), scale_factor: 1.0 }
+    }
+}
+
+impl MonitorHandle {
+    pub fn name(&self) -> Option<String> {
+        Some(String::from("Virtual"))
+    }
+
+    #[cfg(any(x11_platform, wayland_platform))]
+    pub fn native_identifier(&self) -> u32 {
+        0
+    }
+
+    pub fn position(&self) -> Option<PhysicalPosition<i32>> {
+        Some(PhysicalPosition::new(0, 0))
+    }
+
+    pub fn work_area(&self) -> Option<(PhysicalPosition<i32>, PhysicalSize<u32>)> {
+        Some((PhysicalPosition::new(0, 0), self.size))
+    }
+
+    pub fn scale_factor(&self) -> f64 {
+        self.scale_factor
+    }
+
+    pub fn physical_size_mm(&self) -> Option<(f64, f64)> {
+        None
+    }
+
+    pub fn is_primary(&self) -> bool {
+        true
+    }
+
+    pub fn current_video_mode(&self) -> Option<VideoMode> {
+        Some(VideoMode {
+            size: self.size,
+            bit_depth: NonZeroU16::new(32),
+            refresh_rate_millihertz: NonZeroU32::new(60_000),
+        })
+    }
+
+    pub fn video_modes(&self) -> impl Iterator<Item = VideoMode> {
+        self.current_video_mode().into_iter()
+    }
+
+    pub(crate) fn size(&self) -> PhysicalSize<u32> {
+        self.size
+    }
+
+    fn key(&self) -> (PhysicalSize<u32>, u64) {
+        (self.size, self.scale_factor.to_bits())
+    }
+}
+
+impl PartialEq for MonitorHandle {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for MonitorHandle {}
+
+impl PartialOrd for MonitorHandle {
+    fn partial_cmp(&self, other: &Self) -> Option<CmpOrdering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for MonitorHandle {
+    fn cmp(&self, other: &Self) -> CmpOrdering {
+        let (size, scale) = self.key();
+        let (other_size, other_scale) = other.key();
+        (size.width, size.height, scale).cmp(&(other_size.width, other_size.height, other_scale))
+    }
+}
+
+impl Hash for MonitorHandle {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+fn mkmonitor(monitor: MonitorHandle) -> crate::monitor::MonitorHandle {
+    crate::monitor::MonitorHandle { inner: crate::platform_impl::MonitorHandle::Null(monitor) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dpi::LogicalSize;
+
+    #[derive(Default)]
+    struct App {
+        attributes: WindowAttributes,
+        window: Option<Box<dyn CoreWindow>>,
+        events: Vec<WindowEvent>,
+    }
+
+    impl ApplicationHandler for App {
+        fn can_create_surfaces(&mut self, event_loop: &dyn RootActiveEventLoop) {
+            self.window = Some(event_loop.create_window(self.attributes.clone()).unwrap());
+        }
+
+        fn window_event(
+            &mut self,
+            _: &dyn RootActiveEventLoop,
+            window_id: WindowId,
+            event: WindowEvent,
+        ) {
+            assert_eq!(self.window.as_ref().map_or(window_id, |window| window.id()), window_id);
+            if event == WindowEvent::CloseRequested {
+                self.window = None;
+            }
+            self.events.push(event);
+        }
+    }
+
+    fn pump(event_loop: &mut EventLoop, app: &mut App) -> Vec<WindowEvent> {
+        assert!(matches!(
+            event_loop.pump_app_events(Some(Duration::ZERO), &mut *app),
+            PumpStatus::Continue
+        ));
+        mem::take(&mut app.events)
+    }
+
+    #[test]
+    fn injected_events_update_windows() {
+        let mut event_loop = EventLoop::new().unwrap();
+        let mut app = App {
+            attributes: WindowAttributes::default().with_surface_size(PhysicalSize::new(640, 480)),
+            ..Default::default()
+        };
+
+        // The window is drawn once it is created.
+        assert_eq!(pump(&mut event_loop, &mut app), [WindowEvent::RedrawRequested]);
+        let window_id = app.window.as_ref().unwrap().id();
+        assert_eq!(app.window.as_ref().unwrap().surface_size(), PhysicalSize::new(640, 480));
+
+        let resized = WindowEvent::SurfaceResized(PhysicalSize::new(800, 600));
+        event_loop.shared().send_event(window_id, resized.clone());
+        assert_eq!(pump(&mut event_loop, &mut app), [resized]);
+        assert_eq!(app.window.as_ref().unwrap().surface_size(), PhysicalSize::new(800, 600));
+
+        // Dropping the window on close destroys it on the next iteration.
+        event_loop.shared().send_event(window_id, WindowEvent::CloseRequested);
+        assert_eq!(pump(&mut event_loop, &mut app), [WindowEvent::CloseRequested]);
+        assert!(app.window.is_none());
+        assert_eq!(pump(&mut event_loop, &mut app), [WindowEvent::Destroyed]);
+        assert!(event_loop.shared().window(window_id).is_none());
+    }
+
+    #[test]
+    fn scheduled_redraw_wakes_up_the_loop() {
+        let mut event_loop = EventLoop::new().unwrap();
+        let mut app = App::default();
+        pump(&mut event_loop, &mut app);
+
+        let deadline = Instant::now() + Duration::from_millis(30);
+        app.window.as_ref().unwrap().request_redraw_at(deadline);
+        assert_eq!(pump(&mut event_loop, &mut app), []);
+
+        // Even with `ControlFlow::Wait`, the loop waits for the deadline only.
+        event_loop.pump_app_events(None, &mut app);
+        assert!(Instant::now() >= deadline);
+        assert_eq!(mem::take(&mut app.events), [WindowEvent::RedrawRequested]);
+    }
+
+    #[test]
+    fn virtual_monitor_is_configurable() {
+        let mut event_loop = EventLoop::new().unwrap();
+        event_loop.shared().set_monitor(PhysicalSize::new(3840, 2160), 2.0);
+        let mut app = App {
+            attributes: WindowAttributes::default().with_surface_size(LogicalSize::new(400, 300)),
+            ..Default::default()
+        };
+        pump(&mut event_loop, &mut app);
+
+        let window = app.window.as_ref().unwrap();
+        assert_eq!(window.scale_factor(), 2.0);
+        assert_eq!(window.surface_size(), PhysicalSize::new(800, 600));
+
+        let monitor = event_loop.window_target().primary_monitor().unwrap();
+        assert_eq!(monitor.scale_factor(), 2.0);
+        assert_eq!(monitor.current_video_mode().unwrap().size(), PhysicalSize::new(3840, 2160));
+    }
+
+    #[test]
+    fn proxy_outlives_the_event_loop() {
+        let event_loop = EventLoop::new().unwrap();
+        let proxy = event_loop.window_target().create_proxy();
+        assert!(proxy.is_alive());
+
+        drop(event_loop);
+        assert!(!proxy.is_alive());
+        // Waking up a dead loop does nothing.
+        proxy.wake_up();
+    }
+
+    #[test]
+    fn run_on_demand_resets_control_flow_on_exit() {
+        struct ExitApp(Vec<ControlFlow>);
+
+        impl ApplicationHandler for ExitApp {
+            fn can_create_surfaces(&mut self, event_loop: &dyn RootActiveEventLoop) {
+                self.0.push(event_loop.control_flow());
+                event_loop.set_control_flow(ControlFlow::Poll);
+                event_loop.exit();
+            }
+
+            fn window_event(&mut self, _: &dyn RootActiveEventLoop, _: WindowId, _: WindowEvent) {}
+        }
+
+        let mut event_loop = EventLoop::new().unwrap();
+        let wait_until = ControlFlow::WaitUntil(Instant::now() + Duration::from_secs(60));
+        event_loop.window_target().set_control_flow(wait_until);
+
+        let mut app = ExitApp(Vec::new());
+        event_loop.run_app_on_demand(&mut app).unwrap();
+        event_loop.run_app_on_demand(&mut app).unwrap();
+        assert_eq!(app.0, [wait_until, ControlFlow::default()]);
+    }
+}
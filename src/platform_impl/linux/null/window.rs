@@ -0,0 +1,388 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Instant;
+
+use super::{mkmonitor, ActiveEventLoop, NoDisplayHandle, Shared};
+use crate::dpi::{LogicalSize, PhysicalInsets, PhysicalPosition, PhysicalSize, Position, Size};
+use crate::error::{NotSupportedError, RequestError};
+use crate::event::WindowEvent;
+use crate::monitor::MonitorHandle as CoreMonitorHandle;
+use crate::window::{
+    Cursor, CursorGrabMode, Fullscreen, Icon, ImePurpose, RedrawPriority, ResizeDirection, Theme,
+    UserAttentionType, Window as CoreWindow, WindowAttributes, WindowButtons, WindowId,
+    WindowLevel,
+};
+
+/// The next window ID, shared by all event loops so that IDs are never reused.
+static NEXT_WINDOW_ID: AtomicUsize = AtomicUsize::new(1);
+
+#[derive(Debug)]
+pub struct Window {
+    state: Arc<WindowState>,
+    shared: Arc<Shared>,
+}
+
+/// The state of a window, which is also updated by the events delivered to it.
+#[derive(Debug)]
+pub(crate) struct WindowState {
+    id: WindowId,
+    inner: Mutex<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    scale_factor: f64,
+    surface_size: PhysicalSize<u32>,
+    min_surface_size: Option<PhysicalSize<u32>>,
+    max_surface_size: Option<PhysicalSize<u32>>,
+    surface_resize_increments: Option<PhysicalSize<u32>>,
+    outer_position: PhysicalPosition<i32>,
+    title: String,
+    visible: bool,
+    resizable: bool,
+    enabled_buttons: WindowButtons,
+    minimized: bool,
+    maximized: bool,
+    fullscreen: Option<Fullscreen>,
+    decorated: bool,
+    has_focus: bool,
+    theme: Option<Theme>,
+}
+
+impl Inner {
+    fn constrain(&self, size: PhysicalSize<u32>) -> PhysicalSize<u32> {
+        let mut size = size;
+        if let Some(min) = self.min_surface_size {
+            size = PhysicalSize::new(size.width.max(min.width), size.height.max(min.height));
+        }
+        if let Some(max) = self.max_surface_size {
+            size = PhysicalSize::new(size.width.min(max.width), size.height.min(max.height));
+        }
+        size
+    }
+}
+
+impl WindowState {
+    /// Update the state to reflect an event that is about to be delivered.
+    pub(crate) fn apply_event(&self, event: &WindowEvent) {
+        let mut inner = self.inner.lock().unwrap();
+        match *event {
+            WindowEvent::SurfaceResized(size) => inner.surface_size = size,
+            WindowEvent::Moved(position) => inner.outer_position = position,
+            WindowEvent::Focused(focused) => inner.has_focus = focused,
+            _ => {},
+        }
+    }
+}
+
+impl Window {
+    pub(crate) fn new(event_loop: &ActiveEventLoop, attributes: WindowAttributes) -> Self {
+        let shared = Arc::clone(event_loop.shared());
+        let monitor = shared.monitor();
+        let scale_factor = monitor.scale_factor();
+
+        let to_physical = |size: Size| size.to_physical::<u32>(scale_factor);
+        let surface_size = attributes
+            .initial_surface_size(Some(monitor.size()))
+            .unwrap_or_else(|| LogicalSize::new(800, 600).into());
+        let mut inner = Inner {
+            scale_factor,
+            surface_size: to_physical(surface_size),
+            min_surface_size: attributes.min_surface_size.map(to_physical),
+            max_surface_size: attributes.max_surface_size.map(to_physical),
+            surface_resize_increments: attributes.surface_resize_increments.map(to_physical),
+            outer_position: attributes
+                .position
+                .map(|position| position.to_physical(scale_factor))
+                .unwrap_or_default(),
+            title: attributes.title,
+            visible: attributes.visible,
+            resizable: attributes.resizable,
+            enabled_buttons: attributes.enabled_buttons,
+            minimized: false,
+            maximized: attributes.maximized,
+            fullscreen: attributes.fullscreen,
+            decorated: attributes.decorations,
+            has_focus: false,
+            theme: attributes.preferred_theme,
+        };
+        inner.surface_size = inner.constrain(inner.surface_size);
+
+        let id = WindowId::from_raw(NEXT_WINDOW_ID.fetch_add(1, Ordering::Relaxed));
+        let state = Arc::new(WindowState { id, inner: Mutex::new(inner) });
+        shared.windows.lock().unwrap().insert(id, Arc::downgrade(&state));
+
+        // Like an expose event, draw the window once it is shown.
+        if attributes.visible {
+            shared.request_redraw(id, RedrawPriority::Normal);
+        }
+
+        Self { state, shared }
+    }
+
+    fn inner(&self) -> MutexGuard<'_, Inner> {
+        self.state.inner.lock().unwrap()
+    }
+
+    fn to_physical(&self, size: Size) -> PhysicalSize<u32> {
+        size.to_physical(self.scale_factor())
+    }
+}
+
+impl Drop for Window {
+    fn drop(&mut self) {
+        self.shared.windows.lock().unwrap().remove(&self.state.id);
+        self.shared.scheduled_redraws.cancel(self.state.id);
+        self.shared.send_event(self.state.id, WindowEvent::Destroyed);
+    }
+}
+
+impl CoreWindow for Window {
+    fn id(&self) -> WindowId {
+        self.state.id
+    }
+
+    fn scale_factor(&self) -> f64 {
+        self.inner().scale_factor
+    }
+
+    fn request_redraw(&self) {
+        self.request_redraw_with_priority(RedrawPriority::Normal)
+    }
+
+    fn request_redraw_with_priority(&self, priority: RedrawPriority) {
+        self.shared.request_redraw(self.state.id, priority)
+    }
+
+    fn request_redraw_at(&self, deadline: Instant) {
+        self.shared.request_redraw_at(self.state.id, deadline)
+    }
+
+    fn pre_present_notify(&self) {}
+
+    fn reset_dead_keys(&self) {}
+
+    fn surface_position(&self) -> PhysicalPosition<i32> {
+        PhysicalPosition::new(0, 0)
+    }
+
+    fn outer_position(&self) -> Result<PhysicalPosition<i32>, RequestError> {
+        Ok(self.inner().outer_position)
+    }
+
+    fn set_outer_position(&self, position: Position) {
+        let position = position.to_physical(self.scale_factor());
+        self.inner().outer_position = position;
+    }
+
+    fn surface_size(&self) -> PhysicalSize<u32> {
+        self.inner().surface_size
+    }
+
+    fn request_surface_size(&self, size: Size) -> Option<PhysicalSize<u32>> {
+        let size = self.to_physical(size);
+        let mut inner = self.inner();
+        inner.surface_size = inner.constrain(size);
+        Some(inner.surface_size)
+    }
+
+    fn outer_size(&self) -> PhysicalSize<u32> {
+        self.surface_size()
+    }
+
+    fn safe_area(&self) -> PhysicalInsets<u32> {
+        PhysicalInsets::new(0, 0, 0, 0)
+    }
+
+    fn set_min_surface_size(&self, min_size: Option<Size>) {
+        let min_size = min_size.map(|size| self.to_physical(size));
+        self.inner().min_surface_size = min_size;
+    }
+
+    fn set_max_surface_size(&self, max_size: Option<Size>) {
+        let max_size = max_size.map(|size| self.to_physical(size));
+        self.inner().max_surface_size = max_size;
+    }
+
+    fn min_surface_size(&self) -> Option<PhysicalSize<u32>> {
+        self.inner().min_surface_size
+    }
+
+    fn max_surface_size(&self) -> Option<PhysicalSize<u32>> {
+        self.inner().max_surface_size
+    }
+
+    fn surface_resize_increments(&self) -> Option<PhysicalSize<u32>> {
+        self.inner().surface_resize_increments
+    }
+
+    fn set_surface_resize_increments(&self, increments: Option<Size>) {
+        let increments = increments.map(|size| self.to_physical(size));
+        self.inner().surface_resize_increments = increments;
+    }
+
+    fn set_title(&self, title: &str) {
+        self.inner().title = title.to_owned();
+    }
+
+    fn set_title_with_subtitle(&self, title: &str, _: Option<&str>) {
+        self.set_title(title);
+    }
+
+    fn set_transparent(&self, _: bool) {}
+
+    fn set_blur(&self, _: bool) {}
+
+    fn set_visible(&self, visible: bool) {
+        self.inner().visible = visible;
+    }
+
+    fn is_visible(&self) -> Option<bool> {
+        Some(self.inner().visible)
+    }
+
+    fn occlusion(&self) -> Option<f32> {
+        None
+    }
+
+    fn set_resizable(&self, resizable: bool) {
+        self.inner().resizable = resizable;
+    }
+
+    fn is_resizable(&self) -> bool {
+        self.inner().resizable
+    }
+
+    fn set_enabled_buttons(&self, buttons: WindowButtons) {
+        self.inner().enabled_buttons = buttons;
+    }
+
+    fn enabled_buttons(&self) -> WindowButtons {
+        self.inner().enabled_buttons
+    }
+
+    fn set_minimized(&self, minimized: bool) {
+        self.inner().minimized = minimized;
+    }
+
+    fn is_minimized(&self) -> Option<bool> {
+        Some(self.inner().minimized)
+    }
+
+    fn set_maximized(&self, maximized: bool) {
+        self.inner().maximized = maximized;
+    }
+
+    fn is_maximized(&self) -> bool {
+        self.inner().maximized
+    }
+
+    fn set_fullscreen(&self, fullscreen: Option<Fullscreen>) {
+        self.inner().fullscreen = fullscreen;
+    }
+
+    fn fullscreen(&self) -> Option<Fullscreen> {
+        self.inner().fullscreen.clone()
+    }
+
+    fn set_decorations(&self, decorations: bool) {
+        self.inner().decorated = decorations;
+    }
+
+    fn is_decorated(&self) -> bool {
+        self.inner().decorated
+    }
+
+    fn set_window_level(&self, _: WindowLevel) {}
+
+    fn set_window_icon(&self, _: Option<Icon>) {}
+
+    fn set_ime_cursor_area(&self, _: Position, _: Size) {}
+
+    fn set_ime_allowed(&self, _: bool) {}
+
+    fn set_ime_purpose(&self, _: ImePurpose) {}
+
+    fn set_ime_theme(&self, _: Option<Theme>) {}
+
+    fn focus_window(&self) {}
+
+    fn has_focus(&self) -> bool {
+        self.inner().has_focus
+    }
+
+    fn request_user_attention(&self, _: Option<UserAttentionType>) {}
+
+    fn set_theme(&self, theme: Option<Theme>) {
+        self.inner().theme = theme;
+    }
+
+    fn theme(&self) -> Option<Theme> {
+        self.inner().theme
+    }
+
+    fn set_content_protected(&self, _: bool) -> bool {
+        false
+    }
+
+    fn title(&self) -> String {
+        self.inner().title.clone()
+    }
+
+    fn set_cursor(&self, _: Cursor) {}
+
+    fn set_cursor_position(&self, _: Position) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("there is no cursor without a display").into())
+    }
+
+    fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), RequestError> {
+        match mode {
+            CursorGrabMode::None => Ok(()),
+            _ => Err(NotSupportedError::new("there is no cursor without a display").into()),
+        }
+    }
+
+    fn set_cursor_grab_restore_on_focus(&self, _: bool) {}
+
+    fn set_cursor_visible(&self, _: bool) {}
+
+    fn drag_window(&self) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("drag_window is not supported without a display").into())
+    }
+
+    fn drag_resize_window(&self, _: ResizeDirection) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("drag_resize_window is not supported without a display").into())
+    }
+
+    fn show_window_menu(&self, _: Position) {}
+
+    fn set_cursor_hittest(&self, _: bool) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("set_cursor_hittest is not supported without a display").into())
+    }
+
+    fn current_monitor(&self) -> Option<CoreMonitorHandle> {
+        Some(mkmonitor(self.shared.monitor()))
+    }
+
+    fn available_monitors(&self) -> Box<dyn Iterator<Item = CoreMonitorHandle>> {
+        Box::new(std::iter::once(mkmonitor(self.shared.monitor())))
+    }
+
+    fn primary_monitor(&self) -> Option<CoreMonitorHandle> {
+        Some(mkmonitor(self.shared.monitor()))
+    }
+
+    fn rwh_06_display_handle(&self) -> &dyn rwh_06::HasDisplayHandle {
+        &NoDisplayHandle
+    }
+
+    fn rwh_06_window_handle(&self) -> &dyn rwh_06::HasWindowHandle {
+        self
+    }
+}
+
+impl rwh_06::HasWindowHandle for Window {
+    fn window_handle(&self) -> Result<rwh_06::WindowHandle<'_>, rwh_06::HandleError> {
+        Err(rwh_06::HandleError::NotSupported)
+    }
+}
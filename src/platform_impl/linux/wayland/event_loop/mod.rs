@@ -10,19 +10,24 @@ use std::time::{Duration, Instant};
 
 use sctk::reexports::calloop_wayland_source::WaylandSource;
 use sctk::reexports::client::{globals, Connection, QueueHandle};
+use smol_str::SmolStr;
 
 use crate::application::ApplicationHandler;
 use crate::cursor::OnlyCursorImage;
 use crate::dpi::LogicalSize;
 use crate::error::{EventLoopError, OsError, RequestError};
-use crate::event::{Event, StartCause, SurfaceSizeWriter, WindowEvent};
+use crate::event::{Event, FullscreenState, StartCause, SurfaceSizeWriter, WindowEvent};
 use crate::event_loop::{
     ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEvents,
     OwnedDisplayHandle as CoreOwnedDisplayHandle,
 };
+use crate::keyboard::{ModifiersState, PhysicalKey};
 use crate::platform::pump_events::PumpStatus;
-use crate::platform_impl::platform::min_timeout;
-use crate::platform_impl::PlatformCustomCursor;
+use crate::platform_impl::click::DEFAULT_DOUBLE_CLICK_INTERVAL;
+use crate::platform_impl::idle::IdleTimer;
+use crate::platform_impl::platform::{min_timeout, PlatformSpecificEventLoopAttributes};
+use crate::platform_impl::redraw::RedrawSchedule;
+use crate::platform_impl::{batch, PlatformCustomCursor, PlatformCustomCursorSource};
 use crate::window::{CustomCursor as RootCustomCursor, CustomCursorSource, Theme};
 
 mod proxy;
@@ -63,7 +68,9 @@ pub struct EventLoop {
 }
 
 impl EventLoop {
-    pub fn new() -> Result<EventLoop, EventLoopError> {
+    pub fn new(
+        attributes: &PlatformSpecificEventLoopAttributes,
+    ) -> Result<EventLoop, EventLoopError> {
         let connection = Connection::connect_to_env().map_err(|err| os_error!(err))?;
 
         let (globals, mut event_queue) =
@@ -73,12 +80,18 @@ impl EventLoop {
         let event_loop =
             calloop::EventLoop::<WinitState>::try_new().map_err(|err| os_error!(err))?;
 
-        let mut winit_state = WinitState::new(&globals, &queue_handle, event_loop.handle())?;
+        let double_click_interval =
+            attributes.double_click_interval.unwrap_or(DEFAULT_DOUBLE_CLICK_INTERVAL);
+        let mut winit_state =
+            WinitState::new(&globals, &queue_handle, event_loop.handle(), double_click_interval)?;
 
         // NOTE: do a roundtrip after binding the globals to prevent potential
         // races with the server.
         event_queue.roundtrip(&mut winit_state).map_err(|err| os_error!(err))?;
 
+        // The outputs announced so far were there before the event loop started.
+        winit_state.events_sink.clear_monitor_connected();
+
         // Register Wayland source.
         let wayland_source = WaylandSource::new(connection.clone(), event_queue);
         let wayland_dispatcher =
@@ -125,10 +138,12 @@ impl EventLoop {
             handle: handle.clone(),
             wayland_dispatcher: wayland_dispatcher.clone(),
             event_loop_awakener,
-            event_loop_proxy: EventLoopProxy::new(ping).into(),
+            event_loop_proxy: Arc::new(EventLoopProxy::new(ping)),
             queue_handle,
             control_flow: Cell::new(ControlFlow::default()),
             exit: Cell::new(None),
+            idle_timer: IdleTimer::default(),
+            scheduled_redraws: Default::default(),
             state: RefCell::new(winit_state),
         };
 
@@ -169,6 +184,8 @@ impl EventLoop {
             }
         };
 
+        self.active_event_loop.control_flow.set(ControlFlow::default());
+
         // Applications aren't allowed to carry windows between separate
         // `run_on_demand` calls but if they have only just dropped their
         // windows we need to make sure those last requests are sent to the
@@ -222,7 +239,10 @@ impl EventLoop {
                         Some(wait_deadline.saturating_duration_since(start))
                     },
                 };
-                min_timeout(control_flow_timeout, timeout)
+                let idle_timeout = self.active_event_loop.idle_timer.timeout(start);
+                let redraw_timeout = self.active_event_loop.scheduled_redraws.timeout(start);
+                let timeout = min_timeout(min_timeout(control_flow_timeout, idle_timeout), timeout);
+                min_timeout(redraw_timeout, timeout)
             };
 
             // NOTE Ideally we should flush as the last thing we do before polling
@@ -255,17 +275,30 @@ impl EventLoop {
                 ControlFlow::Poll => StartCause::Poll,
                 ControlFlow::Wait => StartCause::WaitCancelled { start, requested_resume: None },
                 ControlFlow::WaitUntil(deadline) => {
-                    if Instant::now() < deadline {
+                    let actual_resume = Instant::now();
+                    if actual_resume < deadline {
                         StartCause::WaitCancelled { start, requested_resume: Some(deadline) }
                     } else {
-                        StartCause::ResumeTimeReached { start, requested_resume: deadline }
+                        StartCause::ResumeTimeReached {
+                            start,
+                            requested_resume: deadline,
+                            actual_resume,
+                        }
                     }
                 },
             };
 
             // Reduce spurious wake-ups.
             let dispatched_events = self.with_state(|state| state.dispatched_events);
-            if matches!(cause, StartCause::WaitCancelled { .. }) && !dispatched_events {
+            let idle_tick_due =
+                self.active_event_loop.idle_timer.timeout(Instant::now()) == Some(Duration::ZERO);
+            let redraw_due = self.active_event_loop.scheduled_redraws.timeout(Instant::now())
+                == Some(Duration::ZERO);
+            if matches!(cause, StartCause::WaitCancelled { .. })
+                && !dispatched_events
+                && !idle_tick_due
+                && !redraw_due
+            {
                 continue;
             }
 
@@ -369,6 +402,13 @@ impl EventLoop {
                 app.window_event(&self.active_event_loop, window_id, event);
             }
 
+            if let Some(fullscreen) = compositor_update.fullscreen {
+                let state =
+                    if fullscreen { FullscreenState::Entered } else { FullscreenState::Exited };
+                let event = WindowEvent::FullscreenChanged { state };
+                app.window_event(&self.active_event_loop, window_id, event);
+            }
+
             if compositor_update.close_window {
                 app.window_event(&self.active_event_loop, window_id, WindowEvent::CloseRequested);
             }
@@ -402,10 +442,27 @@ impl EventLoop {
                 Event::DeviceEvent { device_id, event } => {
                     app.device_event(&self.active_event_loop, device_id, event)
                 },
-                _ => unreachable!("event which is neither device nor window event."),
+                Event::MonitorConnected(monitor) => {
+                    app.monitor_connected(&self.active_event_loop, monitor)
+                },
+                Event::MonitorDisconnected(monitor) => {
+                    app.monitor_disconnected(&self.active_event_loop, monitor)
+                },
+                _ => unreachable!("event which is neither device, window nor monitor event."),
             }
         }
 
+        // Turn the scheduled redraws that are due into redraw requests.
+        let due_redraws = self.active_event_loop.scheduled_redraws.take_due(Instant::now());
+        self.with_state(|state| {
+            let window_requests = state.window_requests.get_mut();
+            for window_id in due_redraws {
+                if let Some(window_requests) = window_requests.get(&window_id) {
+                    window_requests.redraw_requested.store(true, Ordering::Relaxed);
+                }
+            }
+        });
+
         // Collect the window ids
         self.with_state(|state| {
             window_ids.extend(state.window_requests.get_mut().keys());
@@ -448,6 +505,10 @@ impl EventLoop {
             state.dispatched_events = false;
         });
 
+        if self.active_event_loop.idle_timer.poll(Instant::now()) {
+            app.idle_tick(&self.active_event_loop);
+        }
+
         // This is always the last event we dispatch before poll again
         app.about_to_wait(&self.active_event_loop);
 
@@ -542,9 +603,15 @@ impl AsRawFd for EventLoop {
     }
 }
 
+impl Drop for EventLoop {
+    fn drop(&mut self) {
+        self.active_event_loop.event_loop_proxy.close();
+    }
+}
+
 pub struct ActiveEventLoop {
     /// Event loop proxy
-    event_loop_proxy: CoreEventLoopProxy,
+    event_loop_proxy: Arc<EventLoopProxy>,
 
     /// The event loop wakeup source.
     pub event_loop_awakener: calloop::ping::Ping,
@@ -558,6 +625,12 @@ pub struct ActiveEventLoop {
     /// The application's exit state.
     pub(crate) exit: Cell<Option<i32>>,
 
+    /// The ticks requested with `set_idle_interval`.
+    pub(crate) idle_timer: IdleTimer,
+
+    /// The redraws requested with `request_redraw_at`.
+    pub(crate) scheduled_redraws: Arc<RedrawSchedule>,
+
     // TODO remove that RefCell once we can pass `&mut` in `Window::new`.
     /// Winit state.
     pub state: RefCell<WinitState>,
@@ -571,7 +644,7 @@ pub struct ActiveEventLoop {
 
 impl RootActiveEventLoop for ActiveEventLoop {
     fn create_proxy(&self) -> CoreEventLoopProxy {
-        self.event_loop_proxy.clone()
+        CoreEventLoopProxy::new(self.event_loop_proxy.clone())
     }
 
     fn set_control_flow(&self, control_flow: ControlFlow) {
@@ -582,8 +655,28 @@ impl RootActiveEventLoop for ActiveEventLoop {
         self.control_flow.get()
     }
 
+    fn set_idle_interval(&self, interval: Option<Duration>) -> Result<(), RequestError> {
+        self.idle_timer.set_interval(interval);
+        Ok(())
+    }
+
+    fn set_resize_coalescing(&self, _enabled: bool) {
+        // Resizes are already coalesced within an iteration.
+    }
+
+    fn scroll_lines_to_pixels(&self, lines: f32) -> f32 {
+        match self.state.borrow().scroll_step {
+            Some(step) => lines * step as f32,
+            None => lines * 3.0 * 16.0,
+        }
+    }
+
     fn exit(&self) {
-        self.exit.set(Some(0))
+        self.exit_with_code(0)
+    }
+
+    fn exit_with_code(&self, code: i32) {
+        self.exit.set(Some(code))
     }
 
     fn exiting(&self) -> bool {
@@ -597,9 +690,15 @@ impl RootActiveEventLoop for ActiveEventLoop {
         &self,
         cursor: CustomCursorSource,
     ) -> Result<RootCustomCursor, RequestError> {
-        Ok(RootCustomCursor {
-            inner: PlatformCustomCursor::Wayland(OnlyCursorImage(Arc::from(cursor.inner.0))),
-        })
+        let inner = match cursor.inner {
+            PlatformCustomCursorSource::Image(image) => {
+                PlatformCustomCursor::Wayland(OnlyCursorImage(Arc::from(image)))
+            },
+            // Animations aren't supported, fall back to the first frame.
+            PlatformCustomCursorSource::Animation { cursors, .. } => cursors[0].inner.clone(),
+        };
+
+        Ok(RootCustomCursor { inner })
     }
 
     #[inline]
@@ -607,6 +706,14 @@ impl RootActiveEventLoop for ActiveEventLoop {
         None
     }
 
+    fn accent_color(&self) -> Option<[u8; 4]> {
+        super::portal::accent_color()
+    }
+
+    fn key_text(&self, key: PhysicalKey, modifiers: ModifiersState) -> Option<SmolStr> {
+        self.state.borrow_mut().seats.values_mut().find_map(|seat| seat.key_text(key, modifiers))
+    }
+
     fn create_window(
         &self,
         window_attributes: crate::window::WindowAttributes,
@@ -615,6 +722,22 @@ impl RootActiveEventLoop for ActiveEventLoop {
         Ok(Box::new(window))
     }
 
+    fn create_windows(
+        &self,
+        window_attributes: Vec<crate::window::WindowAttributes>,
+    ) -> Vec<Result<Box<dyn crate::window::Window>, RequestError>> {
+        use crate::platform_impl::wayland::Window;
+
+        batch::create_batched(
+            window_attributes,
+            |attributes| Window::new_unconfigured(self, attributes),
+            |windows| Window::wait_for_configure(self, windows),
+            |window| Ok(Box::new(window) as Box<dyn crate::window::Window>),
+            // Every window shares the failure of the connection.
+            |err| os_error!(err.to_string()).into(),
+        )
+    }
+
     fn available_monitors(&self) -> Box<dyn Iterator<Item = crate::monitor::MonitorHandle>> {
         Box::new(
             self.state
@@ -653,6 +776,11 @@ impl ActiveEventLoop {
     fn exit_code(&self) -> Option<i32> {
         self.exit.get()
     }
+
+    pub(crate) fn is_cursor_lock_raw(&self) -> bool {
+        let state = self.state.borrow();
+        state.relative_pointer.is_some() && state.pointer_constraints.is_some()
+    }
 }
 
 impl rwh_06::HasDisplayHandle for ActiveEventLoop {
@@ -1,30 +1,34 @@
 //! An event loop proxy.
 
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use sctk::reexports::calloop::ping::Ping;
 
-use crate::event_loop::{EventLoopProxy as CoreEventLoopProxy, EventLoopProxyProvider};
+use crate::event_loop::EventLoopProxyProvider;
 
 /// A handle that can be sent across the threads and used to wake up the `EventLoop`.
 pub struct EventLoopProxy {
     ping: Ping,
+    alive: AtomicBool,
 }
 
 impl EventLoopProxyProvider for EventLoopProxy {
     fn wake_up(&self) {
         self.ping.ping();
     }
+
+    fn is_alive(&self) -> bool {
+        self.alive.load(Ordering::Relaxed)
+    }
 }
 
 impl EventLoopProxy {
     pub fn new(ping: Ping) -> Self {
-        Self { ping }
+        Self { ping, alive: AtomicBool::new(true) }
     }
-}
 
-impl From<EventLoopProxy> for CoreEventLoopProxy {
-    fn from(value: EventLoopProxy) -> Self {
-        CoreEventLoopProxy::new(Arc::new(value))
+    /// Mark the event loop as gone.
+    pub fn close(&self) {
+        self.alive.store(false, Ordering::Relaxed);
     }
 }
@@ -3,6 +3,7 @@
 use std::vec::Drain;
 
 use crate::event::{DeviceEvent, Event, WindowEvent};
+use crate::platform_impl::wayland::output::MonitorHandle;
 use crate::window::WindowId;
 
 /// An event loop's sink to deliver events from the Wayland event callbacks
@@ -35,6 +36,23 @@ impl EventSink {
         self.window_events.push(Event::WindowEvent { event, window_id });
     }
 
+    /// Add new monitor connection event to a queue.
+    #[inline]
+    pub fn push_monitor_connected(&mut self, monitor: MonitorHandle) {
+        self.window_events.push(Event::MonitorConnected(root_monitor(monitor)));
+    }
+
+    /// Add new monitor disconnection event to a queue.
+    #[inline]
+    pub fn push_monitor_disconnected(&mut self, monitor: MonitorHandle) {
+        self.window_events.push(Event::MonitorDisconnected(root_monitor(monitor)));
+    }
+
+    /// Drop the monitor connection events, e.g. for the monitors present on startup.
+    pub fn clear_monitor_connected(&mut self) {
+        self.window_events.retain(|event| !matches!(event, Event::MonitorConnected(_)));
+    }
+
     #[inline]
     pub fn append(&mut self, other: &mut Self) {
         self.window_events.append(&mut other.window_events);
@@ -45,3 +63,7 @@ impl EventSink {
         self.window_events.drain(..)
     }
 }
+
+fn root_monitor(monitor: MonitorHandle) -> crate::monitor::MonitorHandle {
+    crate::monitor::MonitorHandle { inner: crate::platform_impl::MonitorHandle::Wayland(monitor) }
+}
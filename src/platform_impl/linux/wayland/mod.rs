@@ -7,11 +7,12 @@ use sctk::reexports::client::Proxy;
 pub use window::Window;
 
 pub(super) use crate::cursor::OnlyCursorImage as CustomCursor;
-use crate::dpi::{LogicalSize, PhysicalSize};
+use crate::dpi::{LogicalSize, PhysicalSize, Size};
 use crate::window::WindowId;
 
 mod event_loop;
 mod output;
+mod portal;
 mod seat;
 mod state;
 mod types;
@@ -25,7 +26,5 @@ fn make_wid(surface: &WlSurface) -> WindowId {
 
 /// The default routine does floor, but we need round on Wayland.
 fn logical_to_physical_rounded(size: LogicalSize<u32>, scale_factor: f64) -> PhysicalSize<u32> {
-    let width = size.width as f64 * scale_factor;
-    let height = size.height as f64 * scale_factor;
-    (width.round(), height.round()).into()
+    Size::from(size).to_physical_rounded(scale_factor)
 }
@@ -4,7 +4,7 @@ use sctk::output::{Mode, OutputData};
 use sctk::reexports::client::protocol::wl_output::WlOutput;
 use sctk::reexports::client::Proxy;
 
-use crate::dpi::{LogicalPosition, PhysicalPosition};
+use crate::dpi::{LogicalPosition, PhysicalPosition, PhysicalSize};
 use crate::monitor::VideoMode;
 
 #[derive(Clone, Debug)]
@@ -47,12 +47,31 @@ impl MonitorHandle {
         }))
     }
 
+    #[inline]
+    pub fn work_area(&self) -> Option<(PhysicalPosition<i32>, PhysicalSize<u32>)> {
+        // There's no protocol to query the area left free by panels.
+        None
+    }
+
     #[inline]
     pub fn scale_factor(&self) -> i32 {
         let output_data = self.proxy.data::<OutputData>().unwrap();
         output_data.scale_factor()
     }
 
+    #[inline]
+    pub fn physical_size_mm(&self) -> Option<(f64, f64)> {
+        let output_data = self.proxy.data::<OutputData>().unwrap();
+        let (width, height) = output_data.with_output_info(|info| info.physical_size);
+        (width > 0 && height > 0).then_some((width as f64, height as f64))
+    }
+
+    #[inline]
+    pub fn is_primary(&self) -> bool {
+        // There's no primary monitor on Wayland.
+        false
+    }
+
     #[inline]
     pub fn current_video_mode(&self) -> Option<VideoMode> {
         let output_data = self.proxy.data::<OutputData>().unwrap();
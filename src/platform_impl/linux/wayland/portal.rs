@@ -0,0 +1,68 @@
+//! Settings read from the XDG desktop portal.
+
+use std::process::Command;
+
+/// Get the accent color from the `org.freedesktop.appearance` namespace of the settings portal.
+///
+/// Like the `sctk-adwaita` decorations, this asks `dbus-send`, so it's `None` when it isn't
+/// installed or when the portal doesn't answer in time.
+pub(crate) fn accent_color() -> Option<[u8; 4]> {
+    let output = Command::new("dbus-send")
+        .arg("--reply-timeout=100")
+        .arg("--print-reply=literal")
+        .arg("--dest=org.freedesktop.portal.Desktop")
+        .arg("/org/freedesktop/portal/desktop")
+        .arg("org.freedesktop.portal.Settings.Read")
+        .arg("string:org.freedesktop.appearance")
+        .arg("string:accent-color")
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    parse_accent_color(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Parse the `(ddd)` reply of `dbus-send`, which looks like
+/// `variant variant struct { double 0.2 double 0.5 double 1 }`.
+fn parse_accent_color(reply: &str) -> Option<[u8; 4]> {
+    let mut tokens = reply.split_whitespace();
+    let mut components = Vec::with_capacity(3);
+    while tokens.any(|token| token == "double") {
+        components.push(tokens.next()?.parse::<f64>().ok()?);
+    }
+
+    // Values outside of `0.0..=1.0` mean that there is no accent color.
+    match components[..] {
+        [red, green, blue]
+            if [red, green, blue].iter().all(|component| (0.0..=1.0).contains(component)) =>
+        {
+            let component = |value: f64| (value * 255.0).round() as u8;
+            Some([component(red), component(green), component(blue), 255])
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accent_color_reply() {
+        let reply = "   variant       variant          struct {\n         double 0.2\n         \
+                     double 0.5\n         double 1\n      }\n";
+        assert_eq!(parse_accent_color(reply), Some([51, 128, 255, 255]));
+    }
+
+    #[test]
+    fn unset_accent_color() {
+        let reply = "   variant       variant          struct {\n         double -1\n         \
+                     double -1\n         double -1\n      }\n";
+        assert_eq!(parse_accent_color(reply), None);
+        assert_eq!(parse_accent_color(""), None);
+        assert_eq!(parse_accent_color("   variant       variant          uint32 1\n"), None);
+    }
+}
@@ -8,23 +8,27 @@ use sctk::reexports::client::protocol::wl_seat::WlSeat;
 use sctk::reexports::client::protocol::wl_touch::WlTouch;
 use sctk::reexports::client::{Connection, Proxy, QueueHandle};
 use sctk::reexports::protocols::wp::relative_pointer::zv1::client::zwp_relative_pointer_v1::ZwpRelativePointerV1;
+use sctk::reexports::protocols::wp::tablet::zv2::client::zwp_tablet_seat_v2::ZwpTabletSeatV2;
 use sctk::reexports::protocols::wp::text_input::zv3::client::zwp_text_input_v3::ZwpTextInputV3;
 use sctk::seat::pointer::{ThemeSpec, ThemedPointer};
 use sctk::seat::{Capability as SeatCapability, SeatHandler, SeatState};
+use smol_str::SmolStr;
 use tracing::warn;
 
 use crate::event::WindowEvent;
-use crate::keyboard::ModifiersState;
+use crate::keyboard::{ModifiersState, PhysicalKey};
 use crate::platform_impl::wayland::state::WinitState;
 
 mod keyboard;
 mod pointer;
+mod tablet;
 mod text_input;
 mod touch;
 
 use keyboard::{KeyboardData, KeyboardState};
 pub use pointer::relative_pointer::RelativePointerState;
 pub use pointer::{PointerConstraintsState, WinitPointerData, WinitPointerDataExt};
+pub use tablet::TabletManagerState;
 use text_input::TextInputData;
 pub use text_input::{TextInputState, ZwpTextInputV3Ext};
 use touch::TouchPoint;
@@ -49,6 +53,9 @@ pub struct WinitSeatState {
     /// The relative pointer bound on the seat.
     relative_pointer: Option<ZwpRelativePointerV1>,
 
+    /// The tablets of the seat.
+    tablet_seat: Option<ZwpTabletSeatV2>,
+
     /// The keyboard bound on the seat.
     keyboard_state: Option<KeyboardState>,
 
@@ -63,6 +70,11 @@ impl WinitSeatState {
     pub fn new() -> Self {
         Default::default()
     }
+
+    /// Text produced by the key on the keyboard of this seat.
+    pub fn key_text(&mut self, key: PhysicalKey, mods: ModifiersState) -> Option<SmolStr> {
+        self.keyboard_state.as_mut()?.xkb_context.key_text(key, mods)
+    }
 }
 
 impl SeatHandler for WinitState {
@@ -97,7 +109,7 @@ impl SeatHandler for WinitState {
             SeatCapability::Pointer if seat_state.pointer.is_none() => {
                 let surface = self.compositor_state.create_surface(queue_handle);
                 let surface_id = surface.id();
-                let pointer_data = WinitPointerData::new(seat.clone());
+                let pointer_data = WinitPointerData::new(seat.clone(), self.double_click_interval);
                 let themed_pointer = self
                     .seat_state
                     .get_pointer_with_theme_and_data(
@@ -137,6 +149,16 @@ impl SeatHandler for WinitState {
                 TextInputData::default(),
             )));
         }
+
+        if let Some(tablet_manager) =
+            seat_state.tablet_seat.is_none().then_some(self.tablet_manager.as_ref()).flatten()
+        {
+            seat_state.tablet_seat = Some(tablet_manager.get_tablet_seat(
+                &seat,
+                queue_handle,
+                sctk::globals::GlobalData,
+            ));
+        }
     }
 
     fn remove_capability(
@@ -210,7 +232,11 @@ impl SeatHandler for WinitState {
         _queue_handle: &QueueHandle<Self>,
         seat: WlSeat,
     ) {
-        let _ = self.seats.remove(&seat.id());
+        if let Some(tablet_seat) =
+            self.seats.remove(&seat.id()).and_then(|seat_state| seat_state.tablet_seat)
+        {
+            tablet_seat.destroy();
+        }
         self.on_keyboard_destroy(&seat.id());
     }
 }
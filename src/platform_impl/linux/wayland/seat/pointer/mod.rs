@@ -32,6 +32,7 @@ use crate::event::{
     WindowEvent,
 };
 
+use crate::platform_impl::click::ClickCounter;
 use crate::platform_impl::wayland::state::WinitState;
 use crate::platform_impl::wayland::{self, WindowId};
 
@@ -167,23 +168,33 @@ impl PointerHandler for WinitState {
                         window_id,
                     );
                 },
-                ref kind @ PointerEventKind::Press { button, serial, .. }
-                | ref kind @ PointerEventKind::Release { button, serial, .. } => {
-                    // Update the last button serial.
-                    pointer.winit_data().inner.lock().unwrap().latest_button_serial = serial;
-
+                ref kind @ PointerEventKind::Press { button, serial, time }
+                | ref kind @ PointerEventKind::Release { button, serial, time } => {
                     let button = wayland_button_to_winit(button);
                     let state = if matches!(kind, PointerEventKind::Press { .. }) {
                         ElementState::Pressed
                     } else {
                         ElementState::Released
                     };
+
+                    let mut pointer_data = pointer.winit_data().inner.lock().unwrap();
+                    // Update the last button serial.
+                    pointer_data.latest_button_serial = serial;
+                    let click_count = match state {
+                        ElementState::Pressed => {
+                            pointer_data.click_counter.press(button, position, time)
+                        },
+                        ElementState::Released => pointer_data.click_counter.release(button),
+                    };
+                    drop(pointer_data);
+
                     self.events_sink.push_window_event(
                         WindowEvent::PointerButton {
                             primary: true,
                             device_id: None,
                             state,
                             position,
+                            click_count,
                             button: button.into(),
                         },
                         window_id,
@@ -212,6 +223,16 @@ impl PointerHandler for WinitState {
 
                     // Update the phase.
                     pointer_data.phase = phase;
+                    drop(pointer_data);
+
+                    // Remember how far the compositor scrolls for a wheel step.
+                    if vertical.discrete != 0 && vertical.absolute != 0. {
+                        self.scroll_step =
+                            Some((vertical.absolute / vertical.discrete as f64).abs());
+                    } else if horizontal.discrete != 0 && horizontal.absolute != 0. {
+                        self.scroll_step =
+                            Some((horizontal.absolute / horizontal.discrete as f64).abs());
+                    }
 
                     // Mice events have both pixel and discrete delta's at the same time. So prefer
                     // the descrite values if they are present.
@@ -249,11 +270,10 @@ pub struct WinitPointerData {
 }
 
 impl WinitPointerData {
-    pub fn new(seat: WlSeat) -> Self {
-        Self {
-            inner: Mutex::new(WinitPointerDataInner::default()),
-            sctk_data: PointerData::new(seat),
-        }
+    pub fn new(seat: WlSeat, double_click_interval: Duration) -> Self {
+        let inner = WinitPointerDataInner::default();
+        inner.click_counter.set_interval(double_click_interval);
+        Self { inner: Mutex::new(inner), sctk_data: PointerData::new(seat) }
     }
 
     pub fn lock_pointer(
@@ -357,6 +377,9 @@ pub struct WinitPointerDataInner {
 
     /// Current axis phase.
     phase: TouchPhase,
+
+    /// Counts the presses in quick succession.
+    click_counter: ClickCounter,
 }
 
 impl Drop for WinitPointerDataInner {
@@ -379,12 +402,13 @@ impl Default for WinitPointerDataInner {
             confined_pointer: None,
             latest_button_serial: 0,
             phase: TouchPhase::Ended,
+            click_counter: ClickCounter::default(),
         }
     }
 }
 
 /// Convert the Wayland button into winit.
-fn wayland_button_to_winit(button: u32) -> MouseButton {
+pub(super) fn wayland_button_to_winit(button: u32) -> MouseButton {
     // These values are coming from <linux/input-event-codes.h>.
     const BTN_LEFT: u32 = 0x110;
     const BTN_RIGHT: u32 = 0x111;
@@ -66,9 +66,10 @@ impl Dispatch<ZwpRelativePointerV1, GlobalData, WinitState> for RelativePointerS
             },
             _ => return,
         };
-        state
-            .events_sink
-            .push_device_event(DeviceEvent::PointerMotion { delta: (dx_unaccel, dy_unaccel) });
+        state.events_sink.push_device_event(DeviceEvent::PointerMotion {
+            delta: (dx_unaccel, dy_unaccel),
+            raw: true,
+        });
     }
 }
 
@@ -0,0 +1,381 @@
+//! The tablet tool events.
+
+use std::ops::Deref;
+use std::sync::Mutex;
+
+use sctk::compositor::SurfaceData;
+use sctk::globals::GlobalData;
+use sctk::reexports::client::globals::{BindError, GlobalList};
+use sctk::reexports::client::protocol::wl_surface::WlSurface;
+use sctk::reexports::client::{
+    delegate_dispatch, event_created_child, Connection, Dispatch, Proxy, QueueHandle, WEnum,
+};
+use sctk::reexports::protocols::wp::tablet::zv2::client::zwp_tablet_manager_v2::ZwpTabletManagerV2;
+use sctk::reexports::protocols::wp::tablet::zv2::client::zwp_tablet_pad_group_v2::{
+    self, ZwpTabletPadGroupV2,
+};
+use sctk::reexports::protocols::wp::tablet::zv2::client::zwp_tablet_pad_ring_v2::ZwpTabletPadRingV2;
+use sctk::reexports::protocols::wp::tablet::zv2::client::zwp_tablet_pad_strip_v2::ZwpTabletPadStripV2;
+use sctk::reexports::protocols::wp::tablet::zv2::client::zwp_tablet_pad_v2::{
+    self, ZwpTabletPadV2,
+};
+use sctk::reexports::protocols::wp::tablet::zv2::client::zwp_tablet_seat_v2::{
+    self, ZwpTabletSeatV2,
+};
+use sctk::reexports::protocols::wp::tablet::zv2::client::zwp_tablet_tool_v2::{
+    ButtonState, Capability, Event as ToolEvent, Type as ToolType, ZwpTabletToolV2,
+};
+use sctk::reexports::protocols::wp::tablet::zv2::client::zwp_tablet_v2::{self, ZwpTabletV2};
+
+use super::pointer::wayland_button_to_winit;
+use crate::dpi::LogicalPosition;
+use crate::event::{ElementState, MouseButton, PointerKind, PointerSource, WindowEvent};
+use crate::platform_impl::click::ClickCounter;
+use crate::platform_impl::wayland::state::WinitState;
+use crate::platform_impl::wayland::{self, WindowId};
+
+/// Wrapper around the tablet manager.
+pub struct TabletManagerState {
+    manager: ZwpTabletManagerV2,
+}
+
+impl TabletManagerState {
+    /// Create new tablet manager.
+    pub fn new(
+        globals: &GlobalList,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> Result<Self, BindError> {
+        // The pad dials of version 2 aren't handled.
+        let manager = globals.bind(queue_handle, 1..=1, GlobalData)?;
+        Ok(Self { manager })
+    }
+}
+
+impl Deref for TabletManagerState {
+    type Target = ZwpTabletManagerV2;
+
+    fn deref(&self) -> &Self::Target {
+        &self.manager
+    }
+}
+
+/// The data associated with a tablet tool.
+#[derive(Default)]
+pub struct TabletToolData {
+    inner: Mutex<TabletToolDataInner>,
+}
+
+#[derive(Default)]
+struct TabletToolDataInner {
+    /// Whether the tool is used like a pen, rather than like a mouse.
+    is_pen: bool,
+
+    /// The window the tool is in proximity of.
+    window_id: Option<WindowId>,
+
+    /// The surface-local position of the tool.
+    position: (f64, f64),
+
+    /// The axes of the tool, [`None`] when it doesn't have them.
+    pressure: Option<f32>,
+    tilt: Option<(f32, f32)>,
+    twist: Option<f32>,
+
+    /// The changes accumulated until the next `frame`.
+    pending: PendingToolFrame,
+
+    click_counter: ClickCounter,
+}
+
+#[derive(Default)]
+struct PendingToolFrame {
+    entered: Option<WlSurface>,
+    left: bool,
+    moved: bool,
+    buttons: Vec<(MouseButton, ElementState)>,
+}
+
+impl TabletToolDataInner {
+    fn kind(&self) -> PointerKind {
+        if self.is_pen {
+            PointerKind::Pen
+        } else {
+            PointerKind::Mouse
+        }
+    }
+
+    fn source(&self) -> PointerSource {
+        if self.is_pen {
+            PointerSource::Pen { pressure: self.pressure, tilt: self.tilt, twist: self.twist }
+        } else {
+            PointerSource::Mouse
+        }
+    }
+}
+
+impl Dispatch<ZwpTabletToolV2, TabletToolData, WinitState> for TabletManagerState {
+    fn event(
+        state: &mut WinitState,
+        tool: &ZwpTabletToolV2,
+        event: <ZwpTabletToolV2 as Proxy>::Event,
+        data: &TabletToolData,
+        _conn: &Connection,
+        _qhandle: &QueueHandle<WinitState>,
+    ) {
+        let mut data = data.inner.lock().unwrap();
+        match event {
+            ToolEvent::Type { tool_type: WEnum::Value(tool_type) } => {
+                data.is_pen = !matches!(tool_type, ToolType::Mouse | ToolType::Lens);
+            },
+            // The axes the tool doesn't advertise are reported as `None`.
+            ToolEvent::Capability { capability: WEnum::Value(capability) } => match capability {
+                Capability::Pressure => data.pressure = Some(0.0),
+                Capability::Tilt => data.tilt = Some((0.0, 0.0)),
+                Capability::Rotation => data.twist = Some(0.0),
+                _ => (),
+            },
+            ToolEvent::Removed => tool.destroy(),
+            ToolEvent::ProximityIn { surface, .. } => data.pending.entered = Some(surface),
+            ToolEvent::ProximityOut => data.pending.left = true,
+            ToolEvent::Down { .. } => {
+                data.pending.buttons.push((MouseButton::Left, ElementState::Pressed))
+            },
+            ToolEvent::Up => data.pending.buttons.push((MouseButton::Left, ElementState::Released)),
+            ToolEvent::Motion { x, y } => {
+                data.position = (x, y);
+                data.pending.moved = true;
+            },
+            ToolEvent::Pressure { pressure } => {
+                data.pressure = Some(pressure as f32 / 65535.0);
+                data.pending.moved = true;
+            },
+            ToolEvent::Tilt { tilt_x, tilt_y } => {
+                data.tilt = Some((tilt_x as f32, tilt_y as f32));
+                data.pending.moved = true;
+            },
+            ToolEvent::Rotation { degrees } => {
+                data.twist = Some((degrees as f32).rem_euclid(360.0));
+                data.pending.moved = true;
+            },
+            ToolEvent::Button { button, state: WEnum::Value(button_state), .. } => {
+                let state = match button_state {
+                    ButtonState::Pressed => ElementState::Pressed,
+                    _ => ElementState::Released,
+                };
+                data.pending.buttons.push((tablet_button_to_winit(button), state));
+            },
+            ToolEvent::Frame { time } => {
+                let data = &mut *data;
+                let pending = std::mem::take(&mut data.pending);
+                let entered = pending.entered.is_some();
+                if let Some(surface) = pending.entered {
+                    // Client side decorations only handle the pointer.
+                    let is_main_surface = surface
+                        .data::<SurfaceData>()
+                        .is_some_and(|data| data.parent_surface().is_none());
+                    data.window_id = is_main_surface.then(|| wayland::make_wid(&surface));
+                }
+
+                let window_id = match data.window_id {
+                    Some(window_id) => window_id,
+                    None => return,
+                };
+                let scale_factor = match state.windows.get_mut().get(&window_id) {
+                    Some(window) => window.lock().unwrap().scale_factor(),
+                    None => return,
+                };
+                let position = LogicalPosition::new(data.position.0, data.position.1)
+                    .to_physical(scale_factor);
+
+                let events_sink = &mut state.events_sink;
+                if entered {
+                    events_sink.push_window_event(
+                        WindowEvent::PointerEntered {
+                            device_id: None,
+                            primary: true,
+                            position,
+                            kind: data.kind(),
+                        },
+                        window_id,
+                    );
+                }
+
+                if pending.moved {
+                    events_sink.push_window_event(
+                        WindowEvent::PointerMoved {
+                            device_id: None,
+                            primary: true,
+                            position,
+                            source: data.source(),
+                        },
+                        window_id,
+                    );
+                }
+
+                for (button, state) in pending.buttons {
+                    let click_count = match state {
+                        ElementState::Pressed => data.click_counter.press(button, position, time),
+                        ElementState::Released => data.click_counter.release(button),
+                    };
+                    events_sink.push_window_event(
+                        WindowEvent::PointerButton {
+                            device_id: None,
+                            primary: true,
+                            state,
+                            position,
+                            click_count,
+                            button: button.into(),
+                        },
+                        window_id,
+                    );
+                }
+
+                if pending.left {
+                    data.window_id = None;
+                    events_sink.push_window_event(
+                        WindowEvent::PointerLeft {
+                            device_id: None,
+                            primary: true,
+                            position: Some(position),
+                            kind: data.kind(),
+                        },
+                        window_id,
+                    );
+                }
+            },
+            _ => (),
+        }
+    }
+}
+
+/// Map the buttons of the pen barrel like the X11 drivers do, and the other ones like a mouse.
+fn tablet_button_to_winit(button: u32) -> MouseButton {
+    // These values are coming from <linux/input-event-codes.h>.
+    const BTN_STYLUS: u32 = 0x14b;
+    const BTN_STYLUS2: u32 = 0x14c;
+    const BTN_STYLUS3: u32 = 0x149;
+
+    match button {
+        BTN_STYLUS => MouseButton::Middle,
+        BTN_STYLUS2 => MouseButton::Right,
+        BTN_STYLUS3 => MouseButton::Back,
+        button => wayland_button_to_winit(button),
+    }
+}
+
+impl Dispatch<ZwpTabletManagerV2, GlobalData, WinitState> for TabletManagerState {
+    fn event(
+        _state: &mut WinitState,
+        _proxy: &ZwpTabletManagerV2,
+        _event: <ZwpTabletManagerV2 as Proxy>::Event,
+        _data: &GlobalData,
+        _conn: &Connection,
+        _qhandle: &QueueHandle<WinitState>,
+    ) {
+    }
+}
+
+impl Dispatch<ZwpTabletSeatV2, GlobalData, WinitState> for TabletManagerState {
+    fn event(
+        _state: &mut WinitState,
+        _proxy: &ZwpTabletSeatV2,
+        _event: <ZwpTabletSeatV2 as Proxy>::Event,
+        _data: &GlobalData,
+        _conn: &Connection,
+        _qhandle: &QueueHandle<WinitState>,
+    ) {
+    }
+
+    event_created_child!(WinitState, ZwpTabletSeatV2, [
+        zwp_tablet_seat_v2::EVT_TABLET_ADDED_OPCODE => (ZwpTabletV2, GlobalData),
+        zwp_tablet_seat_v2::EVT_TOOL_ADDED_OPCODE => (ZwpTabletToolV2, Default::default()),
+        zwp_tablet_seat_v2::EVT_PAD_ADDED_OPCODE => (ZwpTabletPadV2, GlobalData),
+    ]);
+}
+
+// The tablets and pads must be handled for the tools to be announced, but their events aren't
+// used.
+
+impl Dispatch<ZwpTabletV2, GlobalData, WinitState> for TabletManagerState {
+    fn event(
+        _state: &mut WinitState,
+        tablet: &ZwpTabletV2,
+        event: <ZwpTabletV2 as Proxy>::Event,
+        _data: &GlobalData,
+        _conn: &Connection,
+        _qhandle: &QueueHandle<WinitState>,
+    ) {
+        if let zwp_tablet_v2::Event::Removed = event {
+            tablet.destroy();
+        }
+    }
+}
+
+impl Dispatch<ZwpTabletPadV2, GlobalData, WinitState> for TabletManagerState {
+    fn event(
+        _state: &mut WinitState,
+        pad: &ZwpTabletPadV2,
+        event: <ZwpTabletPadV2 as Proxy>::Event,
+        _data: &GlobalData,
+        _conn: &Connection,
+        _qhandle: &QueueHandle<WinitState>,
+    ) {
+        if let zwp_tablet_pad_v2::Event::Removed = event {
+            pad.destroy();
+        }
+    }
+
+    event_created_child!(WinitState, ZwpTabletPadV2, [
+        zwp_tablet_pad_v2::EVT_GROUP_OPCODE => (ZwpTabletPadGroupV2, GlobalData),
+    ]);
+}
+
+impl Dispatch<ZwpTabletPadGroupV2, GlobalData, WinitState> for TabletManagerState {
+    fn event(
+        _state: &mut WinitState,
+        _proxy: &ZwpTabletPadGroupV2,
+        _event: <ZwpTabletPadGroupV2 as Proxy>::Event,
+        _data: &GlobalData,
+        _conn: &Connection,
+        _qhandle: &QueueHandle<WinitState>,
+    ) {
+    }
+
+    event_created_child!(WinitState, ZwpTabletPadGroupV2, [
+        zwp_tablet_pad_group_v2::EVT_RING_OPCODE => (ZwpTabletPadRingV2, GlobalData),
+        zwp_tablet_pad_group_v2::EVT_STRIP_OPCODE => (ZwpTabletPadStripV2, GlobalData),
+    ]);
+}
+
+impl Dispatch<ZwpTabletPadRingV2, GlobalData, WinitState> for TabletManagerState {
+    fn event(
+        _state: &mut WinitState,
+        _proxy: &ZwpTabletPadRingV2,
+        _event: <ZwpTabletPadRingV2 as Proxy>::Event,
+        _data: &GlobalData,
+        _conn: &Connection,
+        _qhandle: &QueueHandle<WinitState>,
+    ) {
+    }
+}
+
+impl Dispatch<ZwpTabletPadStripV2, GlobalData, WinitState> for TabletManagerState {
+    fn event(
+        _state: &mut WinitState,
+        _proxy: &ZwpTabletPadStripV2,
+        _event: <ZwpTabletPadStripV2 as Proxy>::Event,
+        _data: &GlobalData,
+        _conn: &Connection,
+        _qhandle: &QueueHandle<WinitState>,
+    ) {
+    }
+}
+
+delegate_dispatch!(WinitState: [ZwpTabletManagerV2: GlobalData] => TabletManagerState);
+delegate_dispatch!(WinitState: [ZwpTabletSeatV2: GlobalData] => TabletManagerState);
+delegate_dispatch!(WinitState: [ZwpTabletToolV2: TabletToolData] => TabletManagerState);
+delegate_dispatch!(WinitState: [ZwpTabletV2: GlobalData] => TabletManagerState);
+delegate_dispatch!(WinitState: [ZwpTabletPadV2: GlobalData] => TabletManagerState);
+delegate_dispatch!(WinitState: [ZwpTabletPadGroupV2: GlobalData] => TabletManagerState);
+delegate_dispatch!(WinitState: [ZwpTabletPadRingV2: GlobalData] => TabletManagerState);
+delegate_dispatch!(WinitState: [ZwpTabletPadStripV2: GlobalData] => TabletManagerState);
@@ -65,6 +65,7 @@ impl TouchHandler for WinitState {
                 primary,
                 state: ElementState::Pressed,
                 position,
+                click_count: 1,
                 button: ButtonSource::Touch { finger_id, force: None },
             },
             window_id,
@@ -118,6 +119,7 @@ impl TouchHandler for WinitState {
                 primary,
                 state: ElementState::Released,
                 position,
+                click_count: 1,
                 button: ButtonSource::Touch { finger_id, force: None },
             },
             window_id,
@@ -1,6 +1,7 @@
 use std::cell::RefCell;
 use std::sync::atomic::Ordering;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use ahash::AHashMap;
 use sctk::compositor::{CompositorHandler, CompositorState};
@@ -25,13 +26,14 @@ use crate::error::OsError;
 use crate::platform_impl::wayland::event_loop::sink::EventSink;
 use crate::platform_impl::wayland::output::MonitorHandle;
 use crate::platform_impl::wayland::seat::{
-    PointerConstraintsState, RelativePointerState, TextInputState, WinitPointerData,
-    WinitPointerDataExt, WinitSeatState,
+    PointerConstraintsState, RelativePointerState, TabletManagerState, TextInputState,
+    WinitPointerData, WinitPointerDataExt, WinitSeatState,
 };
 use crate::platform_impl::wayland::types::kwin_blur::KWinBlurManager;
 use crate::platform_impl::wayland::types::wp_fractional_scaling::FractionalScalingManager;
 use crate::platform_impl::wayland::types::wp_viewporter::ViewporterState;
 use crate::platform_impl::wayland::types::xdg_activation::XdgActivationState;
+use crate::platform_impl::wayland::types::xdg_toplevel_icon::XdgToplevelIconManager;
 use crate::platform_impl::wayland::window::{WindowRequests, WindowState};
 use crate::platform_impl::wayland::WindowId;
 
@@ -92,9 +94,15 @@ pub struct WinitState {
     /// Xdg activation.
     pub xdg_activation: Option<XdgActivationState>,
 
+    /// Toplevel icon manager, used for window icons.
+    pub xdg_toplevel_icon_manager: Option<XdgToplevelIconManager>,
+
     /// Relative pointer.
     pub relative_pointer: Option<RelativePointerState>,
 
+    /// Tablet manager, used for pens.
+    pub tablet_manager: Option<TabletManagerState>,
+
     /// Pointer constraints to handle pointer locking and confining.
     pub pointer_constraints: Option<Arc<PointerConstraintsState>>,
 
@@ -116,6 +124,12 @@ pub struct WinitState {
 
     /// Whether the user initiated a wake up.
     pub proxy_wake_up: bool,
+
+    /// The distance in logical pixels of the latest wheel step, as sent by the compositor.
+    pub scroll_step: Option<f64>,
+
+    /// The maximum time between two presses of the same click sequence.
+    pub double_click_interval: Duration,
 }
 
 impl WinitState {
@@ -123,6 +137,7 @@ impl WinitState {
         globals: &GlobalList,
         queue_handle: &QueueHandle<Self>,
         loop_handle: LoopHandle<'static, WinitState>,
+        double_click_interval: Duration,
     ) -> Result<Self, OsError> {
         let registry_state = RegistryState::new(globals);
         let compositor_state =
@@ -170,6 +185,7 @@ impl WinitState {
 
             xdg_shell: XdgShell::bind(globals, queue_handle).map_err(|err| os_error!(err))?,
             xdg_activation: XdgActivationState::bind(globals, queue_handle).ok(),
+            xdg_toplevel_icon_manager: XdgToplevelIconManager::new(globals, queue_handle).ok(),
 
             windows: Default::default(),
             window_requests: Default::default(),
@@ -183,6 +199,7 @@ impl WinitState {
             text_input_state: TextInputState::new(globals, queue_handle).ok(),
 
             relative_pointer: RelativePointerState::new(globals, queue_handle).ok(),
+            tablet_manager: TabletManagerState::new(globals, queue_handle).ok(),
             pointer_constraints: PointerConstraintsState::new(globals, queue_handle)
                 .map(Arc::new)
                 .ok(),
@@ -194,6 +211,8 @@ impl WinitState {
             // Make it true by default.
             dispatched_events: true,
             proxy_wake_up: false,
+            scroll_step: None,
+            double_click_interval,
         })
     }
 
@@ -284,15 +303,25 @@ impl WindowHandler for WinitState {
             self.window_compositor_updates.len() - 1
         };
 
-        // Populate the configure to the window.
-        self.window_compositor_updates[pos].resized |= self
+        let mut window = self
             .windows
             .get_mut()
             .get_mut(&window_id)
             .expect("got configure for dead window.")
             .lock()
-            .unwrap()
-            .configure(configure, &self.shm, &self.subcompositor_state);
+            .unwrap();
+
+        let was_fullscreen =
+            window.last_configure.as_ref().is_some_and(|configure| configure.is_fullscreen());
+        let is_fullscreen = configure.is_fullscreen();
+        if was_fullscreen != is_fullscreen {
+            self.window_compositor_updates[pos].fullscreen = Some(is_fullscreen);
+        }
+
+        // Populate the configure to the window.
+        self.window_compositor_updates[pos].resized |=
+            window.configure(configure, &self.shm, &self.subcompositor_state);
+        drop(window);
 
         // NOTE: configure demands wl_surface::commit, however winit doesn't commit on behalf of the
         // users, since it can break a lot of things, thus it'll ask users to redraw instead.
@@ -314,7 +343,9 @@ impl OutputHandler for WinitState {
     }
 
     fn new_output(&mut self, _: &Connection, _: &QueueHandle<Self>, output: WlOutput) {
-        self.monitors.lock().unwrap().push(MonitorHandle::new(output));
+        let monitor = MonitorHandle::new(output);
+        self.monitors.lock().unwrap().push(monitor.clone());
+        self.events_sink.push_monitor_connected(monitor);
     }
 
     fn update_output(&mut self, _: &Connection, _: &QueueHandle<Self>, updated: WlOutput) {
@@ -333,6 +364,8 @@ impl OutputHandler for WinitState {
         if let Some(pos) = monitors.iter().position(|output| output == &removed) {
             monitors.remove(pos);
         }
+        drop(monitors);
+        self.events_sink.push_monitor_disconnected(removed);
     }
 }
 
@@ -420,11 +453,20 @@ pub struct WindowCompositorUpdate {
 
     /// Close the window.
     pub close_window: bool,
+
+    /// New fullscreen state.
+    pub fullscreen: Option<bool>,
 }
 
 impl WindowCompositorUpdate {
     fn new(window_id: WindowId) -> Self {
-        Self { window_id, resized: false, scale_changed: false, close_window: false }
+        Self {
+            window_id,
+            resized: false,
+            scale_changed: false,
+            close_window: false,
+            fullscreen: None,
+        }
     }
 }
 
@@ -3,6 +3,7 @@ use sctk::reexports::client::protocol::wl_shm::Format;
 use sctk::shm::slot::{Buffer, SlotPool};
 
 use crate::cursor::CursorImage;
+use crate::icon::premultiply_rgba;
 
 #[derive(Debug)]
 pub enum SelectedCursor {
@@ -36,16 +37,12 @@ impl CustomCursor {
             )
             .unwrap();
 
-        for (canvas_chunk, rgba) in canvas.chunks_exact_mut(4).zip(image.rgba.chunks_exact(4)) {
-            // Alpha in buffer is premultiplied.
-            let alpha = rgba[3] as f32 / 255.;
-            let r = (rgba[0] as f32 * alpha) as u32;
-            let g = (rgba[1] as f32 * alpha) as u32;
-            let b = (rgba[2] as f32 * alpha) as u32;
-            let color = ((rgba[3] as u32) << 24) + (r << 16) + (g << 8) + b;
-            let array: &mut [u8; 4] = canvas_chunk.try_into().unwrap();
-            *array = color.to_le_bytes();
+        // Alpha in buffer is premultiplied.
+        let mut rgba = image.rgba.clone();
+        if !image.premultiplied {
+            premultiply_rgba(&mut rgba);
         }
+        write_argb8888(canvas, &rgba);
 
         CustomCursor {
             buffer,
@@ -56,3 +53,13 @@ impl CustomCursor {
         }
     }
 }
+
+/// Write premultiplied RGBA pixels in the `Argb8888` format of `wl_shm`.
+pub(crate) fn write_argb8888(canvas: &mut [u8], rgba: &[u8]) {
+    for (canvas_chunk, rgba) in canvas.chunks_exact_mut(4).zip(rgba.chunks_exact(4)) {
+        let [r, g, b, a] = [rgba[0], rgba[1], rgba[2], rgba[3]].map(u32::from);
+        let color = (a << 24) + (r << 16) + (g << 8) + b;
+        let array: &mut [u8; 4] = canvas_chunk.try_into().unwrap();
+        *array = color.to_le_bytes();
+    }
+}
@@ -0,0 +1,146 @@
+//! Handling of xdg toplevel icons, which are used for window icons.
+
+use sctk::globals::GlobalData;
+use sctk::reexports::client::globals::{BindError, GlobalList};
+use sctk::reexports::client::protocol::wl_shm::Format;
+use sctk::reexports::client::{delegate_dispatch, Connection, Dispatch, Proxy, QueueHandle};
+use sctk::reexports::protocols::xdg::shell::client::xdg_toplevel::XdgToplevel;
+use sctk::reexports::protocols::xdg::toplevel_icon::v1::client::xdg_toplevel_icon_manager_v1::XdgToplevelIconManagerV1;
+use sctk::reexports::protocols::xdg::toplevel_icon::v1::client::xdg_toplevel_icon_v1::XdgToplevelIconV1;
+use sctk::shm::slot::{Buffer, SlotPool};
+
+use super::cursor::write_argb8888;
+use crate::icon::{premultiply_rgba, RgbaIcon, PIXEL_SIZE};
+use crate::platform_impl::wayland::state::WinitState;
+
+/// Toplevel icon manager.
+#[derive(Debug, Clone)]
+pub struct XdgToplevelIconManager {
+    manager: XdgToplevelIconManagerV1,
+}
+
+impl XdgToplevelIconManager {
+    pub fn new(
+        globals: &GlobalList,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> Result<Self, BindError> {
+        let manager = globals.bind(queue_handle, 1..=1, GlobalData)?;
+        Ok(Self { manager })
+    }
+
+    pub(crate) fn create_icon(
+        &self,
+        pool: &mut SlotPool,
+        icon: &RgbaIcon,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> ToplevelIcon {
+        let (side, pixels) = square_argb8888(icon);
+        let side = side as i32;
+        let (buffer, canvas) =
+            pool.create_buffer(side, side, side * PIXEL_SIZE as i32, Format::Argb8888).unwrap();
+        canvas.copy_from_slice(&pixels);
+
+        let toplevel_icon = self.manager.create_icon(queue_handle, ());
+        toplevel_icon.add_buffer(buffer.wl_buffer(), 1);
+
+        ToplevelIcon { icon: toplevel_icon, _buffer: buffer }
+    }
+
+    /// Set the icon of the toplevel, or reset it to the default one.
+    ///
+    /// Takes effect on the next commit of the surface.
+    pub fn set_icon(&self, toplevel: &XdgToplevel, icon: Option<&ToplevelIcon>) {
+        self.manager.set_icon(toplevel, icon.map(|icon| &icon.icon));
+    }
+}
+
+/// An icon along with the buffer holding its pixels, which must outlive it.
+#[derive(Debug)]
+pub struct ToplevelIcon {
+    icon: XdgToplevelIconV1,
+    _buffer: Buffer,
+}
+
+impl Drop for ToplevelIcon {
+    fn drop(&mut self) {
+        self.icon.destroy();
+    }
+}
+
+/// Convert the icon to the square `Argb8888` image required by the protocol, centering it.
+fn square_argb8888(icon: &RgbaIcon) -> (u32, Vec<u8>) {
+    let side = icon.width.max(icon.height);
+    let (offset_x, offset_y) = ((side - icon.width) / 2, (side - icon.height) / 2);
+
+    let mut rgba = icon.rgba.clone();
+    if !icon.premultiplied {
+        premultiply_rgba(&mut rgba);
+    }
+
+    let stride = side as usize * PIXEL_SIZE;
+    let mut pixels = vec![0; stride * side as usize];
+    let row_len = icon.width as usize * PIXEL_SIZE;
+    for (y, row) in rgba.chunks_exact(row_len).enumerate() {
+        let start = (offset_y as usize + y) * stride + offset_x as usize * PIXEL_SIZE;
+        write_argb8888(&mut pixels[start..start + row_len], row);
+    }
+
+    (side, pixels)
+}
+
+impl Dispatch<XdgToplevelIconManagerV1, GlobalData, WinitState> for XdgToplevelIconManager {
+    fn event(
+        _: &mut WinitState,
+        _: &XdgToplevelIconManagerV1,
+        _: <XdgToplevelIconManagerV1 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        // The preferred sizes aren't useful, the icon is only provided at its own size.
+    }
+}
+
+impl Dispatch<XdgToplevelIconV1, (), WinitState> for XdgToplevelIconManager {
+    fn event(
+        _: &mut WinitState,
+        _: &XdgToplevelIconV1,
+        _: <XdgToplevelIconV1 as Proxy>::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        unreachable!("no events defined for xdg_toplevel_icon_v1");
+    }
+}
+
+delegate_dispatch!(WinitState: [XdgToplevelIconManagerV1: GlobalData] => XdgToplevelIconManager);
+delegate_dispatch!(WinitState: [XdgToplevelIconV1: ()] => XdgToplevelIconManager);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn icon_is_centered_in_square_buffer() {
+        // A 1x2 icon with an opaque red pixel above a half-transparent white one.
+        let icon =
+            RgbaIcon::from_rgba(vec![255, 0, 0, 255, 255, 255, 255, 128], 1, 2, false).unwrap();
+
+        let (side, pixels) = square_argb8888(&icon);
+        assert_eq!(side, 2);
+        assert_eq!(pixels.len(), 2 * 2 * PIXEL_SIZE);
+
+        // Little-endian `Argb8888` is stored as BGRA, with premultiplied alpha.
+        #[rustfmt::skip]
+        assert_eq!(pixels, [
+            0, 0, 255, 255,    0, 0, 0, 0,
+            128, 128, 128, 128, 0, 0, 0, 0,
+        ]);
+
+        // Premultiplied icons are used as is.
+        let rgba = vec![255, 0, 0, 255, 128, 128, 128, 128];
+        let premultiplied = RgbaIcon::from_rgba(rgba, 1, 2, true).unwrap();
+        assert_eq!(square_argb8888(&premultiplied), (side, pixels));
+    }
+}
@@ -2,13 +2,17 @@
 
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
 use sctk::compositor::{CompositorState, Region, SurfaceData};
 use sctk::reexports::client::protocol::wl_display::WlDisplay;
 use sctk::reexports::client::protocol::wl_surface::WlSurface;
 use sctk::reexports::client::{Proxy, QueueHandle};
 use sctk::reexports::protocols::xdg::activation::v1::client::xdg_activation_v1::XdgActivationV1;
+use sctk::reexports::protocols::xdg::shell::client::xdg_surface::XdgSurface as XdgSurfaceProxy;
+use sctk::reexports::protocols::xdg::shell::client::xdg_toplevel::XdgToplevel;
 use sctk::shell::xdg::window::{Window as SctkWindow, WindowDecorations};
+use sctk::shell::xdg::XdgSurface;
 use sctk::shell::WaylandSurface;
 use tracing::warn;
 
@@ -22,11 +26,12 @@ use crate::error::{NotSupportedError, RequestError};
 use crate::event::{Ime, WindowEvent};
 use crate::event_loop::AsyncRequestSerial;
 use crate::monitor::MonitorHandle as CoreMonitorHandle;
+use crate::platform_impl::redraw::RedrawSchedule;
 use crate::platform_impl::{Fullscreen, MonitorHandle as PlatformMonitorHandle};
 use crate::window::{
-    Cursor, CursorGrabMode, Fullscreen as CoreFullscreen, ImePurpose, ResizeDirection, Theme,
-    UserAttentionType, Window as CoreWindow, WindowAttributes, WindowButtons, WindowId,
-    WindowLevel,
+    ActivationToken, Cursor, CursorGrabMode, Fullscreen as CoreFullscreen, ImePurpose,
+    PhysicalRect, RedrawPriority, ResizeConstraints, ResizeDirection, Theme, UserAttentionType,
+    Window as CoreWindow, WindowAttributes, WindowButtons, WindowId, WindowLevel,
 };
 
 pub(crate) mod state;
@@ -69,6 +74,9 @@ pub struct Window {
     /// Source to wake-up the event-loop for window requests.
     event_loop_awakener: calloop::ping::Ping,
 
+    /// Redraws scheduled with `request_redraw_at`, shared with the event loop.
+    scheduled_redraws: Arc<RedrawSchedule>,
+
     /// The event sink to deliver synthetic events.
     window_events_sink: Arc<Mutex<EventSink>>,
 }
@@ -77,6 +85,16 @@ impl Window {
     pub(crate) fn new(
         event_loop_window_target: &ActiveEventLoop,
         attributes: WindowAttributes,
+    ) -> Result<Self, RequestError> {
+        let window = Self::new_unconfigured(event_loop_window_target, attributes)?;
+        Self::wait_for_configure(event_loop_window_target, &[&window])?;
+        Ok(window)
+    }
+
+    /// Create the window without waiting for the compositor to configure it.
+    pub(crate) fn new_unconfigured(
+        event_loop_window_target: &ActiveEventLoop,
+        attributes: WindowAttributes,
     ) -> Result<Self, RequestError> {
         let queue_handle = event_loop_window_target.queue_handle.clone();
         let mut state = event_loop_window_target.state.borrow_mut();
@@ -116,6 +134,10 @@ impl Window {
 
         window_state.set_blur(attributes.blur);
 
+        if let Some(icon) = attributes.window_icon.as_ref() {
+            window_state.set_window_icon(Some(&icon.inner));
+        }
+
         // Set the decorations hint.
         window_state.set_decorate(attributes.decorations);
 
@@ -127,12 +149,20 @@ impl Window {
         // Set the window title.
         window_state.set_title(attributes.title);
 
+        // Set the owner, which must be one of our windows.
+        let owner = attributes.owner.and_then(|owner| state.windows.get_mut().get(&owner).cloned());
+        if let Some(owner) = owner {
+            window.set_parent(Some(&owner.lock().unwrap().window));
+        }
+
         // Set the min and max sizes. We must set the hints upon creating a window, so
         // we use the default `1.` scaling...
         let min_size = attributes.min_surface_size.map(|size| size.to_logical(1.));
         let max_size = attributes.max_surface_size.map(|size| size.to_logical(1.));
         window_state.set_min_surface_size(min_size);
         window_state.set_max_surface_size(max_size);
+        window_state.set_aspect_ratio(attributes.surface_aspect_ratio);
+        window_state.set_surface_resize_increments(attributes.surface_resize_increments);
 
         // Non-resizable implies that the min and max sizes are set to the same value.
         window_state.set_resizable(attributes.resizable);
@@ -142,12 +172,14 @@ impl Window {
             Some(Fullscreen::Exclusive(..)) => {
                 warn!("`Fullscreen::Exclusive` is ignored on Wayland");
             },
-            #[cfg_attr(not(x11_platform), allow(clippy::bind_instead_of_map))]
+            #[cfg_attr(not(any(x11_platform, null_platform)), allow(clippy::bind_instead_of_map))]
             Some(Fullscreen::Borderless(monitor)) => {
                 let output = monitor.and_then(|monitor| match monitor {
                     PlatformMonitorHandle::Wayland(monitor) => Some(monitor.proxy),
                     #[cfg(x11_platform)]
                     PlatformMonitorHandle::X(_) => None,
+                    #[cfg(null_platform)]
+                    PlatformMonitorHandle::Null(_) => None,
                 });
 
                 window.set_fullscreen(output.as_ref())
@@ -186,20 +218,8 @@ impl Window {
         // Setup the event sync to insert `WindowEvents` right from the window.
         let window_events_sink = state.window_events_sink.clone();
 
-        let mut wayland_source = event_loop_window_target.wayland_dispatcher.as_source_mut();
-        let event_queue = wayland_source.queue();
-
-        // Do a roundtrip.
-        event_queue.roundtrip(&mut state).map_err(|err| os_error!(err))?;
-
-        // XXX Wait for the initial configure to arrive.
-        while !window_state.lock().unwrap().is_configured() {
-            event_queue.blocking_dispatch(&mut state).map_err(|err| os_error!(err))?;
-        }
-
-        // Wake-up event loop, so it'll send initial redraw requested.
         let event_loop_awakener = event_loop_window_target.event_loop_awakener.clone();
-        event_loop_awakener.ping();
+        let scheduled_redraws = event_loop_window_target.scheduled_redraws.clone();
 
         Ok(Self {
             window,
@@ -212,10 +232,34 @@ impl Window {
             xdg_activation,
             attention_requested: Arc::new(AtomicBool::new(false)),
             event_loop_awakener,
+            scheduled_redraws,
             window_requests,
             window_events_sink,
         })
     }
+
+    /// Wait for the initial configure of all the given windows.
+    pub(crate) fn wait_for_configure(
+        event_loop_window_target: &ActiveEventLoop,
+        windows: &[&Self],
+    ) -> Result<(), RequestError> {
+        let mut state = event_loop_window_target.state.borrow_mut();
+        let mut wayland_source = event_loop_window_target.wayland_dispatcher.as_source_mut();
+        let event_queue = wayland_source.queue();
+
+        // Do a roundtrip.
+        event_queue.roundtrip(&mut state).map_err(|err| os_error!(err))?;
+
+        // XXX Wait for the initial configure to arrive.
+        while !windows.iter().all(|window| window.window_state.lock().unwrap().is_configured()) {
+            event_queue.blocking_dispatch(&mut state).map_err(|err| os_error!(err))?;
+        }
+
+        // Wake-up event loop, so it'll send initial redraw requested.
+        event_loop_window_target.event_loop_awakener.ping();
+
+        Ok(())
+    }
 }
 
 impl Window {
@@ -235,15 +279,51 @@ impl Window {
         Ok(serial)
     }
 
+    pub fn focus_window_with_token(&self, token: ActivationToken) -> Result<(), RequestError> {
+        let xdg_activation = match self.xdg_activation.as_ref() {
+            Some(xdg_activation) => xdg_activation,
+            None => return Err(NotSupportedError::new("xdg_activation_v1 is not available").into()),
+        };
+
+        xdg_activation.activate(token.token, self.surface());
+        Ok(())
+    }
+
     #[inline]
     pub fn surface(&self) -> &WlSurface {
         self.window.wl_surface()
     }
+
+    #[inline]
+    pub fn xdg_surface(&self) -> &XdgSurfaceProxy {
+        self.window.xdg_surface()
+    }
+
+    #[inline]
+    pub fn xdg_toplevel(&self) -> &XdgToplevel {
+        self.window.xdg_toplevel()
+    }
+
+    #[inline]
+    pub fn fractional_scale(&self) -> Option<f64> {
+        self.window_state.lock().unwrap().fractional_scale()
+    }
+
+    #[inline]
+    pub fn set_csd_theme(&self, theme: Theme) -> bool {
+        self.window_state.lock().unwrap().set_csd_theme(theme)
+    }
+
+    #[inline]
+    pub fn set_app_id(&self, app_id: &str) {
+        self.window_state.lock().unwrap().set_app_id(app_id.to_owned());
+    }
 }
 
 impl Drop for Window {
     fn drop(&mut self) {
         self.window_requests.closed.store(true, Ordering::Relaxed);
+        self.scheduled_redraws.cancel(self.window_id);
         self.event_loop_awakener.ping();
     }
 }
@@ -290,6 +370,17 @@ impl CoreWindow for Window {
         }
     }
 
+    fn request_redraw_with_priority(&self, _priority: RedrawPriority) {
+        self.request_redraw();
+    }
+
+    fn request_redraw_at(&self, deadline: Instant) {
+        // Wake up the loop so that it waits for the new deadline.
+        if self.scheduled_redraws.schedule(self.window_id, deadline) {
+            self.event_loop_awakener.ping();
+        }
+    }
+
     #[inline]
     fn title(&self) -> String {
         self.window_state.lock().unwrap().title().to_owned()
@@ -304,7 +395,8 @@ impl CoreWindow for Window {
     }
 
     fn surface_position(&self) -> PhysicalPosition<i32> {
-        (0, 0).into()
+        let window_state = self.window_state.lock().unwrap();
+        window_state.surface_position().to_physical(window_state.scale_factor())
     }
 
     fn outer_position(&self) -> Result<PhysicalPosition<i32>, RequestError> {
@@ -357,12 +449,32 @@ impl CoreWindow for Window {
         self.request_redraw();
     }
 
+    fn min_surface_size(&self) -> Option<PhysicalSize<u32>> {
+        let window_state = self.window_state.lock().unwrap();
+        let scale_factor = window_state.scale_factor();
+        Some(super::logical_to_physical_rounded(window_state.min_surface_size(), scale_factor))
+    }
+
+    fn max_surface_size(&self) -> Option<PhysicalSize<u32>> {
+        let window_state = self.window_state.lock().unwrap();
+        let scale_factor = window_state.scale_factor();
+        window_state
+            .max_surface_size()
+            .map(|size| super::logical_to_physical_rounded(size, scale_factor))
+    }
+
+    fn set_resize_constraints(&self, constraints: ResizeConstraints) {
+        self.window_state.lock().unwrap().set_aspect_ratio(constraints.aspect_ratio);
+        self.set_min_surface_size(constraints.min);
+        self.set_max_surface_size(constraints.max);
+    }
+
     fn surface_resize_increments(&self) -> Option<PhysicalSize<u32>> {
-        None
+        self.window_state.lock().unwrap().surface_resize_increments()
     }
 
-    fn set_surface_resize_increments(&self, _increments: Option<Size>) {
-        warn!("`set_surface_resize_increments` is not implemented for Wayland");
+    fn set_surface_resize_increments(&self, increments: Option<Size>) {
+        self.window_state.lock().unwrap().set_surface_resize_increments(increments);
     }
 
     fn set_title(&self, title: &str) {
@@ -370,6 +482,10 @@ impl CoreWindow for Window {
         self.window_state.lock().unwrap().set_title(new_title);
     }
 
+    fn set_title_with_subtitle(&self, title: &str, _subtitle: Option<&str>) {
+        self.set_title(title);
+    }
+
     #[inline]
     fn set_transparent(&self, transparent: bool) {
         self.window_state.lock().unwrap().set_transparent(transparent);
@@ -383,6 +499,10 @@ impl CoreWindow for Window {
         None
     }
 
+    fn occlusion(&self) -> Option<f32> {
+        None
+    }
+
     fn set_resizable(&self, resizable: bool) {
         if self.window_state.lock().unwrap().set_resizable(resizable) {
             // NOTE: Requires commit to be applied.
@@ -441,12 +561,14 @@ impl CoreWindow for Window {
             Some(CoreFullscreen::Exclusive(..)) => {
                 warn!("`Fullscreen::Exclusive` is ignored on Wayland");
             },
-            #[cfg_attr(not(x11_platform), allow(clippy::bind_instead_of_map))]
+            #[cfg_attr(not(any(x11_platform, null_platform)), allow(clippy::bind_instead_of_map))]
             Some(CoreFullscreen::Borderless(monitor)) => {
                 let output = monitor.and_then(|monitor| match monitor.inner {
                     PlatformMonitorHandle::Wayland(monitor) => Some(monitor.proxy),
                     #[cfg(x11_platform)]
                     PlatformMonitorHandle::X(_) => None,
+                    #[cfg(null_platform)]
+                    PlatformMonitorHandle::Null(_) => None,
                 });
 
                 self.window.set_fullscreen(output.as_ref())
@@ -495,7 +617,10 @@ impl CoreWindow for Window {
 
     fn set_window_level(&self, _level: WindowLevel) {}
 
-    fn set_window_icon(&self, _window_icon: Option<crate::window::Icon>) {}
+    fn set_window_icon(&self, window_icon: Option<crate::window::Icon>) {
+        let icon = window_icon.as_ref().map(|icon| &icon.inner);
+        self.window_state.lock().unwrap().set_window_icon(icon);
+    }
 
     #[inline]
     fn set_ime_cursor_area(&self, position: Position, size: Size) {
@@ -524,6 +649,8 @@ impl CoreWindow for Window {
         self.window_state.lock().unwrap().set_ime_purpose(purpose);
     }
 
+    fn set_ime_theme(&self, _theme: Option<Theme>) {}
+
     fn focus_window(&self) {}
 
     fn has_focus(&self) -> bool {
@@ -564,7 +691,9 @@ impl CoreWindow for Window {
         self.window_state.lock().unwrap().theme()
     }
 
-    fn set_content_protected(&self, _protected: bool) {}
+    fn set_content_protected(&self, _protected: bool) -> bool {
+        false
+    }
 
     fn set_cursor(&self, cursor: Cursor) {
         let window_state = &mut self.window_state.lock().unwrap();
@@ -590,6 +719,10 @@ impl CoreWindow for Window {
         self.window_state.lock().unwrap().set_cursor_grab(mode)
     }
 
+    fn set_cursor_grab_restore_on_focus(&self, _restore: bool) {
+        // The compositor restores the pointer constraints by itself.
+    }
+
     fn set_cursor_visible(&self, visible: bool) {
         self.window_state.lock().unwrap().set_cursor_visible(visible);
     }
@@ -622,6 +755,29 @@ impl CoreWindow for Window {
         }
     }
 
+    fn set_cursor_hittest_region(
+        &self,
+        regions: Option<Vec<PhysicalRect>>,
+    ) -> Result<(), RequestError> {
+        let surface = self.window.wl_surface();
+
+        let Some(regions) = regions else {
+            surface.set_input_region(None);
+            return Ok(());
+        };
+
+        // The input region is in surface local coordinates.
+        let scale_factor = self.scale_factor();
+        let region = Region::new(&*self.compositor).map_err(|err| os_error!(err))?;
+        for rect in regions {
+            let origin = rect.origin.to_logical::<i32>(scale_factor);
+            let size = rect.size.to_logical::<i32>(scale_factor);
+            region.add(origin.x, origin.y, size.width, size.height);
+        }
+        surface.set_input_region(Some(region.wl_region()));
+        Ok(())
+    }
+
     fn current_monitor(&self) -> Option<CoreMonitorHandle> {
         let data = self.window.wl_surface().data::<SurfaceData>()?;
         data.outputs()
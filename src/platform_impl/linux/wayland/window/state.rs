@@ -25,12 +25,13 @@ use sctk::shell::WaylandSurface;
 use sctk::shm::slot::SlotPool;
 use sctk::shm::Shm;
 use sctk::subcompositor::SubcompositorState;
-use tracing::{info, warn};
+use tracing::{debug, info, warn};
 use wayland_protocols_plasma::blur::client::org_kde_kwin_blur::OrgKdeKwinBlur;
 
 use crate::cursor::CustomCursor as RootCustomCursor;
 use crate::dpi::{LogicalPosition, LogicalSize, PhysicalSize, Size};
 use crate::error::{NotSupportedError, RequestError};
+use crate::icon::RgbaIcon;
 use crate::platform_impl::wayland::event_loop::OwnedDisplayHandle;
 use crate::platform_impl::wayland::logical_to_physical_rounded;
 use crate::platform_impl::wayland::seat::{
@@ -39,6 +40,9 @@ use crate::platform_impl::wayland::seat::{
 use crate::platform_impl::wayland::state::{WindowCompositorUpdate, WinitState};
 use crate::platform_impl::wayland::types::cursor::{CustomCursor, SelectedCursor};
 use crate::platform_impl::wayland::types::kwin_blur::KWinBlurManager;
+use crate::platform_impl::wayland::types::xdg_toplevel_icon::{
+    ToplevelIcon, XdgToplevelIconManager,
+};
 use crate::platform_impl::PlatformCustomCursor;
 use crate::window::{CursorGrabMode, CursorIcon, ImePurpose, ResizeDirection, Theme, WindowId};
 
@@ -122,10 +126,16 @@ pub struct WindowState {
     /// Whether we should decorate the frame.
     decorate: bool,
 
-    /// Min size.
+    /// The min and max sizes of the surface itself, without the borders of the frame.
     min_surface_size: LogicalSize<u32>,
     max_surface_size: Option<LogicalSize<u32>>,
 
+    /// The aspect ratio the surface is shrunk to when the compositor suggests a size.
+    aspect_ratio: Option<(u32, u32)>,
+
+    /// The increments the surface is shrunk to when the compositor suggests a size.
+    surface_resize_increments: Option<Size>,
+
     /// The size of the window when no states were applied to it. The primary use for it
     /// is to fallback to original window size, before it was maximized, if the compositor
     /// sends `None` for the new size in the configure.
@@ -140,14 +150,21 @@ pub struct WindowState {
 
     viewport: Option<WpViewport>,
     fractional_scale: Option<WpFractionalScaleV1>,
+    /// The last preferred scale sent through `wp_fractional_scale_v1`.
+    preferred_fractional_scale: Option<f64>,
     blur: Option<OrgKdeKwinBlur>,
     blur_manager: Option<KWinBlurManager>,
+    toplevel_icon: Option<ToplevelIcon>,
+    toplevel_icon_manager: Option<XdgToplevelIconManager>,
 
     /// Whether the client side decorations have pending move operations.
     ///
     /// The value is the serial of the event triggered moved.
     has_pending_move: Option<u32>,
 
+    /// The application ID requested before the window got its first configure.
+    pending_app_id: Option<String>,
+
     /// The underlying SCTK window.
     pub window: Window,
 
@@ -183,6 +200,8 @@ impl WindowState {
         Self {
             blur: None,
             blur_manager: winit_state.kwin_blur_manager.clone(),
+            toplevel_icon: None,
+            toplevel_icon_manager: winit_state.xdg_toplevel_icon_manager.clone(),
             compositor,
             handle,
             csd_fails: false,
@@ -191,14 +210,18 @@ impl WindowState {
             cursor_visible: true,
             decorate: true,
             fractional_scale,
+            preferred_fractional_scale: None,
             frame: None,
             frame_callback_state: FrameCallbackState::None,
             seat_focus: Default::default(),
             has_pending_move: None,
+            pending_app_id: None,
             ime_allowed: false,
             ime_purpose: ImePurpose::Normal,
             last_configure: None,
             max_surface_size: None,
+            aspect_ratio: None,
+            surface_resize_increments: None,
             min_surface_size: MIN_WINDOW_SIZE,
             pointer_constraints,
             pointers: Default::default(),
@@ -271,6 +294,10 @@ impl WindowState {
             self.stateless_size = self.size;
         }
 
+        if let Some(app_id) = self.pending_app_id.take() {
+            self.window.set_app_id(app_id);
+        }
+
         if let Some(subcompositor) = subcompositor.as_ref().filter(|_| {
             configure.decoration_mode == DecorationMode::Client
                 && self.frame.is_none()
@@ -338,6 +365,15 @@ impl WindowState {
                 .unwrap_or(new_size.height);
         }
 
+        // The size must match exactly when the window is maximized, fullscreen or tiled.
+        if stateless {
+            let increments = self
+                .surface_resize_increments
+                .map(|increments| increments.to_logical(self.scale_factor()));
+            new_size =
+                constrain_size(new_size, self.aspect_ratio, increments, self.min_surface_size);
+        }
+
         let new_state = configure.state;
         let old_state = self.last_configure.as_ref().map(|configure| configure.state);
 
@@ -504,13 +540,7 @@ impl WindowState {
         }
 
         self.resizable = resizable;
-        if resizable {
-            // Restore min/max sizes of the window.
-            self.reload_min_max_hints();
-        } else {
-            self.set_min_surface_size(Some(self.size));
-            self.set_max_surface_size(Some(self.size));
-        }
+        self.reload_min_max_hints();
 
         // Reload the state on the frame as well.
         if let Some(frame) = self.frame.as_mut() {
@@ -568,6 +598,14 @@ impl WindowState {
             .unwrap_or(self.size)
     }
 
+    /// Get the position of the surface inside the window, which is offset by the client side
+    /// decorations.
+    #[inline]
+    pub fn surface_position(&self) -> LogicalPosition<i32> {
+        let (x, y) = self.frame.as_ref().map(|frame| frame.location()).unwrap_or_default();
+        LogicalPosition::new(-x, -y)
+    }
+
     /// Register pointer on the top-level.
     pub fn pointer_entered(&mut self, added: Weak<ThemedPointer<WinitPointerData>>) {
         self.pointers.push(added);
@@ -753,35 +791,38 @@ impl WindowState {
         });
     }
 
-    /// Set maximum inner window size.
+    /// Set minimum inner window size.
     pub fn set_min_surface_size(&mut self, size: Option<LogicalSize<u32>>) {
         // Ensure that the window has the right minimum size.
         let mut size = size.unwrap_or(MIN_WINDOW_SIZE);
         size.width = size.width.max(MIN_WINDOW_SIZE.width);
         size.height = size.height.max(MIN_WINDOW_SIZE.height);
-
-        // Add the borders.
-        let size = self
-            .frame
-            .as_ref()
-            .map(|frame| frame.add_borders(size.width, size.height).into())
-            .unwrap_or(size);
-
         self.min_surface_size = size;
-        self.window.set_min_size(Some(size.into()));
+        self.reload_min_max_hints();
     }
 
     /// Set maximum inner window size.
     pub fn set_max_surface_size(&mut self, size: Option<LogicalSize<u32>>) {
-        let size = size.map(|size| {
-            self.frame
-                .as_ref()
-                .map(|frame| frame.add_borders(size.width, size.height).into())
-                .unwrap_or(size)
-        });
-
         self.max_surface_size = size;
-        self.window.set_max_size(size.map(Into::into));
+        self.reload_min_max_hints();
+    }
+
+    /// Set the aspect ratio applied to the sizes suggested by the compositor.
+    #[inline]
+    pub fn set_aspect_ratio(&mut self, aspect_ratio: Option<(u32, u32)>) {
+        self.aspect_ratio = aspect_ratio;
+    }
+
+    /// Set the resize increments applied to the sizes suggested by the compositor.
+    #[inline]
+    pub fn set_surface_resize_increments(&mut self, increments: Option<Size>) {
+        self.surface_resize_increments = increments;
+    }
+
+    /// Get the resize increments of the surface.
+    #[inline]
+    pub fn surface_resize_increments(&self) -> Option<PhysicalSize<u32>> {
+        self.surface_resize_increments.map(|increments| increments.to_physical(self.scale_factor()))
     }
 
     /// Set the CSD theme.
@@ -793,6 +834,20 @@ impl WindowState {
         }
     }
 
+    /// Set the CSD theme, returning `false` when the decorations can't be themed.
+    pub fn set_csd_theme(&mut self, theme: Theme) -> bool {
+        let server_side = self
+            .last_configure
+            .as_ref()
+            .is_some_and(|configure| configure.decoration_mode == DecorationMode::Server);
+        if !cfg!(feature = "sctk-adwaita") || server_side || self.csd_fails {
+            return false;
+        }
+
+        self.set_theme(Some(theme));
+        true
+    }
+
     /// The current theme for CSD decorations.
     #[inline]
     pub fn theme(&self) -> Option<Theme> {
@@ -813,8 +868,29 @@ impl WindowState {
 
     /// Reload the hints for minimum and maximum sizes.
     pub fn reload_min_max_hints(&mut self) {
-        self.set_min_surface_size(Some(self.min_surface_size));
-        self.set_max_surface_size(self.max_surface_size);
+        let fixed_size = (!self.resizable).then_some(self.size);
+        let (min_size, max_size) =
+            size_hints(self.min_surface_size, self.max_surface_size, fixed_size, |size| {
+                self.frame
+                    .as_ref()
+                    .map(|frame| frame.add_borders(size.width, size.height).into())
+                    .unwrap_or(size)
+            });
+
+        self.window.set_min_size(Some(min_size.into()));
+        self.window.set_max_size(max_size.map(Into::into));
+    }
+
+    /// The minimum surface size, without the borders of the frame.
+    #[inline]
+    pub fn min_surface_size(&self) -> LogicalSize<u32> {
+        self.min_surface_size
+    }
+
+    /// The maximum surface size, without the borders of the frame.
+    #[inline]
+    pub fn max_surface_size(&self) -> Option<LogicalSize<u32>> {
+        self.max_surface_size
     }
 
     /// Set the grabbing state on the surface.
@@ -987,6 +1063,12 @@ impl WindowState {
         }
     }
 
+    /// The preferred scale from `wp_fractional_scale_v1`, if the compositor sent one.
+    #[inline]
+    pub fn fractional_scale(&self) -> Option<f64> {
+        self.preferred_fractional_scale
+    }
+
     /// Get the IME purpose.
     pub fn ime_purpose(&self) -> ImePurpose {
         self.ime_purpose
@@ -1000,6 +1082,8 @@ impl WindowState {
         // NOTE: When fractional scaling is not used update the buffer scale.
         if self.fractional_scale.is_none() {
             let _ = self.window.set_buffer_scale(self.scale_factor as _);
+        } else {
+            self.preferred_fractional_scale = Some(scale_factor);
         }
 
         if let Some(frame) = self.frame.as_mut() {
@@ -1024,6 +1108,27 @@ impl WindowState {
         }
     }
 
+    /// Set the window icon through `xdg_toplevel_icon_v1`.
+    pub(crate) fn set_window_icon(&mut self, window_icon: Option<&RgbaIcon>) {
+        let icon_manager = match self.toplevel_icon_manager.as_ref() {
+            Some(icon_manager) => icon_manager,
+            None => {
+                debug!("Toplevel icon manager unavailable, unable to set the window icon");
+                return;
+            },
+        };
+
+        let icon = window_icon.map(|icon| {
+            let mut pool = self.custom_cursor_pool.lock().unwrap();
+            icon_manager.create_icon(&mut pool, icon, &self.queue_handle)
+        });
+        icon_manager.set_icon(self.window.xdg_toplevel(), icon.as_ref());
+        self.window.wl_surface().commit();
+
+        // The previous icon is destroyed now that it's replaced.
+        self.toplevel_icon = icon;
+    }
+
     /// Set the window title to a new value.
     ///
     /// This will automatically truncate the title to something meaningful.
@@ -1047,6 +1152,15 @@ impl WindowState {
         self.title = title;
     }
 
+    /// Set the application ID, deferring it until the window is configured.
+    pub fn set_app_id(&mut self, app_id: String) {
+        if self.last_configure.is_some() {
+            self.window.set_app_id(app_id);
+        } else {
+            self.pending_app_id = Some(app_id);
+        }
+    }
+
     /// Mark the window as transparent.
     #[inline]
     pub fn set_transparent(&mut self, transparent: bool) {
@@ -1148,3 +1262,178 @@ fn into_sctk_adwaita_config(theme: Option<Theme>) -> sctk_adwaita::FrameConfig {
         None => sctk_adwaita::FrameConfig::auto(),
     }
 }
+
+/// Apply the aspect ratio and the resize increments to the size picked for a floating window.
+///
+/// Both shrink the size, which then grows back to the minimum size if it went below it.
+fn constrain_size(
+    mut size: LogicalSize<u32>,
+    aspect_ratio: Option<(u32, u32)>,
+    increments: Option<LogicalSize<u32>>,
+    min_size: LogicalSize<u32>,
+) -> LogicalSize<u32> {
+    if aspect_ratio.is_none() && increments.is_none() {
+        return size;
+    }
+
+    if let Some(aspect_ratio) = aspect_ratio {
+        size = constrain_to_aspect_ratio(size, aspect_ratio);
+    }
+    if let Some(increments) = increments {
+        size = constrain_to_increments(size, increments);
+    }
+    grow_to_min_size(size, min_size, aspect_ratio.unwrap_or((0, 0)))
+}
+
+/// Shrink the size to the largest one with the given aspect ratio that fits into it.
+fn constrain_to_aspect_ratio(
+    size: LogicalSize<u32>,
+    (ratio_width, ratio_height): (u32, u32),
+) -> LogicalSize<u32> {
+    if ratio_width == 0 || ratio_height == 0 {
+        return size;
+    }
+
+    let (width, height) = (size.width as u64, size.height as u64);
+    let (ratio_width, ratio_height) = (ratio_width as u64, ratio_height as u64);
+    if width * ratio_height > height * ratio_width {
+        let width = (height * ratio_width / ratio_height).max(1);
+        LogicalSize::new(width as u32, size.height)
+    } else {
+        let height = (width * ratio_height / ratio_width).max(1);
+        LogicalSize::new(size.width, height as u32)
+    }
+}
+
+/// Shrink the size to the largest multiple of the increments that fits into it.
+fn constrain_to_increments(
+    size: LogicalSize<u32>,
+    increments: LogicalSize<u32>,
+) -> LogicalSize<u32> {
+    let snap = |length: u32, increment: u32| match increment {
+        // Keep the size when it doesn't fit a single increment.
+        increment if increment == 0 || length < increment => length,
+        increment => length - length % increment,
+    };
+    LogicalSize::new(snap(size.width, increments.width), snap(size.height, increments.height))
+}
+
+/// The min and max size hints of the toplevel, including the borders of the frame.
+///
+/// A window that isn't resizable is pinned to its `fixed_size`, which leaves the requested bounds
+/// in place for when it becomes resizable again.
+fn size_hints(
+    min_size: LogicalSize<u32>,
+    max_size: Option<LogicalSize<u32>>,
+    fixed_size: Option<LogicalSize<u32>>,
+    add_borders: impl Fn(LogicalSize<u32>) -> LogicalSize<u32>,
+) -> (LogicalSize<u32>, Option<LogicalSize<u32>>) {
+    let (min_size, max_size) = match fixed_size {
+        Some(size) => (size, Some(size)),
+        None => (min_size, max_size),
+    };
+    (add_borders(min_size), max_size.map(add_borders))
+}
+
+/// Grow the size to the minimum size, keeping the given aspect ratio.
+fn grow_to_min_size(
+    size: LogicalSize<u32>,
+    min_size: LogicalSize<u32>,
+    (ratio_width, ratio_height): (u32, u32),
+) -> LogicalSize<u32> {
+    if size.width >= min_size.width && size.height >= min_size.height {
+        return size;
+    }
+
+    let size = LogicalSize::new(size.width.max(min_size.width), size.height.max(min_size.height));
+    if ratio_width == 0 || ratio_height == 0 {
+        return size;
+    }
+
+    let (width, height) = (size.width as u64, size.height as u64);
+    let (ratio_width, ratio_height) = (ratio_width as u64, ratio_height as u64);
+    if width * ratio_height > height * ratio_width {
+        let height = (width * ratio_height).div_ceil(ratio_width);
+        LogicalSize::new(size.width, height as u32)
+    } else {
+        let width = (height * ratio_width).div_ceil(ratio_height);
+        LogicalSize::new(width as u32, size.height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aspect_ratio_shrinks_to_fit() {
+        let size = LogicalSize::new(1000, 400);
+        assert_eq!(constrain_to_aspect_ratio(size, (16, 9)), LogicalSize::new(711, 400));
+        assert_eq!(constrain_to_aspect_ratio(size, (1, 1)), LogicalSize::new(400, 400));
+        assert_eq!(constrain_to_aspect_ratio(size, (4, 1)), LogicalSize::new(1000, 250));
+        assert_eq!(constrain_to_aspect_ratio(size, (0, 1)), size);
+        assert_eq!(constrain_to_aspect_ratio(LogicalSize::new(1, 1), (1, 1000)).height, 1);
+    }
+
+    #[test]
+    fn increments_shrink_to_fit() {
+        let size = LogicalSize::new(1000, 405);
+        let increments =
+            |width, height| constrain_to_increments(size, LogicalSize::new(width, height));
+        assert_eq!(increments(9, 18), LogicalSize::new(999, 396));
+        assert_eq!(increments(0, 10), LogicalSize::new(1000, 400));
+        assert_eq!(increments(2000, 1), size);
+    }
+
+    #[test]
+    fn size_hints_reload_requested_sizes() {
+        let borders = |size: LogicalSize<u32>| LogicalSize::new(size.width + 10, size.height + 40);
+        let min_size = LogicalSize::new(200, 100);
+        let max_size = Some(LogicalSize::new(800, 600));
+
+        let hints = size_hints(min_size, max_size, None, borders);
+        assert_eq!(hints, (LogicalSize::new(210, 140), Some(LogicalSize::new(810, 640))));
+
+        // Pinning the size of a window that isn't resizable keeps the requested sizes, so
+        // reloading them adds the borders only once.
+        let fixed_size = Some(LogicalSize::new(500, 400));
+        let pinned = size_hints(min_size, max_size, fixed_size, borders);
+        assert_eq!(pinned, (LogicalSize::new(510, 440), Some(LogicalSize::new(510, 440))));
+        assert_eq!(size_hints(min_size, max_size, None, borders), hints);
+
+        assert_eq!(size_hints(min_size, None, None, borders).1, None);
+    }
+
+    #[test]
+    fn snapped_size_grows_back_to_min_size() {
+        let min_size = LogicalSize::new(300, 200);
+        let size = constrain_to_increments(LogicalSize::new(320, 210), LogicalSize::new(100, 100));
+        assert_eq!(size, LogicalSize::new(300, 200));
+        assert_eq!(grow_to_min_size(size, min_size, (0, 0)), size);
+
+        let size = LogicalSize::new(300, 100);
+        assert_eq!(grow_to_min_size(size, min_size, (0, 0)), LogicalSize::new(300, 200));
+        assert_eq!(grow_to_min_size(size, min_size, (16, 9)), LogicalSize::new(356, 200));
+        assert_eq!(grow_to_min_size(size, min_size, (1, 1)), LogicalSize::new(300, 300));
+    }
+
+    #[test]
+    fn constrained_size_keeps_min_size() {
+        let min_size = LogicalSize::new(300, 200);
+        let size = LogicalSize::new(1000, 210);
+
+        // The aspect ratio alone shrinks the width below the minimum.
+        let constrained = constrain_size(size, Some((1, 1)), None, min_size);
+        assert_eq!(constrained, LogicalSize::new(300, 300));
+        assert_eq!(constrain_size(size, Some((16, 9)), None, min_size), LogicalSize::new(373, 210));
+
+        let increments = Some(LogicalSize::new(100, 100));
+        assert_eq!(constrain_size(size, None, increments, min_size), LogicalSize::new(1000, 200));
+        let constrained = constrain_size(size, Some((1, 1)), increments, min_size);
+        assert_eq!(constrained, LogicalSize::new(300, 300));
+
+        // Without any constraint, the size is left as is.
+        let size = LogicalSize::new(100, 100);
+        assert_eq!(constrain_size(size, None, None, min_size), size);
+    }
+}
@@ -137,6 +137,13 @@ impl XConnection {
     }
 }
 
+/// Extract the timestamp of the user interaction from the `_TIME` suffix of a token.
+///
+/// Tokens without one, like the ones not following the recommended format, have no timestamp.
+pub(crate) fn activation_token_timestamp(token: &str) -> Option<xproto::Timestamp> {
+    token.rsplit_once("_TIME").and_then(|(_, time)| time.parse().ok())
+}
+
 /// Quote a literal string as per the startup notification specification.
 fn quote_string(s: &str, target: &mut Vec<u8>) {
     let total_len = s.len().checked_add(3).expect("quote string overflow");
@@ -191,4 +198,12 @@ mod tests {
         assert_eq("foo", b"\"foo\"");
         assert_eq("foo\"bar", b"\"foo\\\"bar\"");
     }
+
+    #[test]
+    fn reads_token_timestamp() {
+        assert_eq!(activation_token_timestamp("host+1234_TIME5678"), Some(5678));
+        assert_eq!(activation_token_timestamp("a_TIME1_TIME42"), Some(42));
+        assert_eq!(activation_token_timestamp("host+1234"), None);
+        assert_eq!(activation_token_timestamp("host_TIMEnow"), None);
+    }
 }
@@ -53,6 +53,7 @@ atom_manager! {
     _NET_WM_STATE,
     _NET_WM_STATE_ABOVE,
     _NET_WM_STATE_BELOW,
+    _NET_WM_STATE_DEMANDS_ATTENTION,
     _NET_WM_STATE_FULLSCREEN,
     _NET_WM_STATE_HIDDEN,
     _NET_WM_STATE_MAXIMIZED_HORZ,
@@ -94,14 +95,21 @@ atom_manager! {
     TextUriList: b"text/uri-list",
     None: b"None",
 
+    // XInput2 Valuator Labels
+    AbsPressure: b"Abs Pressure",
+    AbsTiltX: b"Abs Tilt X",
+    AbsTiltY: b"Abs Tilt Y",
+
     // Miscellaneous Atoms
     _GTK_THEME_VARIANT,
     _MOTIF_WM_HINTS,
     _NET_ACTIVE_WINDOW,
     _NET_CLIENT_LIST,
+    _NET_CURRENT_DESKTOP,
     _NET_FRAME_EXTENTS,
     _NET_SUPPORTED,
     _NET_SUPPORTING_WM_CHECK,
+    _NET_WORKAREA,
     _XEMBED,
     _XSETTINGS_SETTINGS
 }
@@ -1,12 +1,12 @@
 use std::cell::{Cell, RefCell};
 use std::collections::{HashMap, VecDeque};
 use std::os::raw::{c_char, c_int, c_long, c_ulong};
-use std::slice;
 use std::sync::{Arc, Mutex};
+use std::{mem, slice};
 
 use x11_dl::xinput2::{
     self, XIDeviceEvent, XIEnterEvent, XIFocusInEvent, XIFocusOutEvent, XIHierarchyEvent,
-    XILeaveEvent, XIModifierState, XIRawEvent,
+    XILeaveEvent, XIModifierState, XIRawEvent, XIValuatorState,
 };
 use x11_dl::xlib::{
     self, Display as XDisplay, Window as XWindow, XAnyEvent, XClientMessageEvent, XConfigureEvent,
@@ -22,20 +22,22 @@ use xkbcommon_dl::xkb_mod_mask_t;
 
 use crate::dpi::{PhysicalPosition, PhysicalSize};
 use crate::event::{
-    ButtonSource, DeviceEvent, DeviceId, ElementState, Event, FingerId, Ime, MouseButton,
-    MouseScrollDelta, PointerKind, PointerSource, RawKeyEvent, SurfaceSizeWriter, TouchPhase,
-    WindowEvent,
+    ButtonSource, DeviceEvent, DeviceId, ElementState, Event, FingerId, FullscreenState, Ime,
+    MouseButton, MouseScrollDelta, PointerKind, PointerSource, RawKeyEvent, SurfaceSizeWriter,
+    TouchPhase, WindowEvent,
 };
 use crate::keyboard::ModifiersState;
+use crate::platform_impl::click::ClickCounter;
 use crate::platform_impl::common::xkb::{self, XkbState};
+use crate::platform_impl::hotplug::MonitorChanges;
 use crate::platform_impl::platform::common::xkb::Context;
 use crate::platform_impl::platform::x11::ime::{ImeEvent, ImeEventReceiver, ImeRequest};
 use crate::platform_impl::platform::x11::ActiveEventLoop;
 use crate::platform_impl::x11::atoms::*;
 use crate::platform_impl::x11::util::cookie::GenericEventCookie;
 use crate::platform_impl::x11::{
-    mkdid, mkwid, util, CookieResultExt, Device, DeviceInfo, Dnd, DndState, ImeReceiver,
-    ScrollOrientation, UnownedWindow, WindowId,
+    mkdid, mkmonitor, mkwid, util, CookieResultExt, Device, DeviceInfo, Dnd, DndState, ImeReceiver,
+    MonitorHandle, ScrollOrientation, UnownedWindow, WindowId,
 };
 
 /// The maximum amount of X modifiers to replay.
@@ -69,6 +71,10 @@ pub struct EventProcessor {
     pub xfiltered_modifiers: VecDeque<c_ulong>,
     pub xmodmap: util::ModifierKeymap,
     pub is_composing: bool,
+    pub click_counter: ClickCounter,
+    /// The monitors reported so far, starting with the ones connected when the event loop was
+    /// created, to report hotplug.
+    pub monitors: Vec<MonitorHandle>,
 }
 
 impl EventProcessor {
@@ -340,7 +346,8 @@ impl EventProcessor {
         let mut devices = self.devices.borrow_mut();
         if let Some(info) = DeviceInfo::get(&self.target.xconn, device as _) {
             for info in info.iter() {
-                devices.insert(mkdid(info.deviceid as xinput::DeviceId), Device::new(info));
+                let device = Device::new(info, self.target.xconn.atoms());
+                devices.insert(mkdid(info.deviceid as xinput::DeviceId), device);
             }
         }
     }
@@ -700,12 +707,23 @@ impl EventProcessor {
                 let monitor = self
                     .target
                     .xconn
-                    .get_monitor_for_window(Some(window_rect))
+                    .get_monitor_for_window(Some(window_rect.clone()))
                     .expect("Failed to find monitor for window");
 
                 if monitor.is_dummy() {
                     // Avoid updating monitor using a dummy monitor handle
                     last_scale_factor
+                } else if monitor != shared_state_lock.last_monitor
+                    && !window.should_switch_monitor(
+                        &window_rect,
+                        &shared_state_lock.last_monitor,
+                        &monitor,
+                    )
+                {
+                    // Stay on the previous monitor until most of the window moved over. This isn't
+                    // debounced: the last `ConfigureNotify` of a drag may arrive within any delay,
+                    // and nothing would apply the switch afterwards.
+                    last_scale_factor
                 } else {
                     shared_state_lock.last_monitor = monitor.clone();
                     monitor.scale_factor
@@ -849,11 +867,33 @@ impl EventProcessor {
     {
         let atoms = self.target.x_connection().atoms();
         let atom = xev.atom as xproto::Atom;
+        let is_xsettings = atom == atoms[_XSETTINGS_SETTINGS];
 
-        if atom == xproto::Atom::from(xproto::AtomEnum::RESOURCE_MANAGER)
-            || atom == atoms[_XSETTINGS_SETTINGS]
-        {
+        if atom == xproto::Atom::from(xproto::AtomEnum::RESOURCE_MANAGER) || is_xsettings {
             self.process_dpi_change(&mut callback);
+            if is_xsettings {
+                self.process_accent_color_change(&mut callback);
+                self.update_double_click_interval();
+            }
+        } else if atom == atoms[_NET_WM_STATE] {
+            let xwindow = xev.window as xproto::Window;
+            let changed = self.with_window(xwindow, |window| {
+                let fullscreen = window.is_wm_fullscreen();
+                let mut shared_state_lock = window.shared_state_lock();
+                let changed = shared_state_lock.wm_fullscreen != fullscreen;
+                shared_state_lock.wm_fullscreen = fullscreen;
+                changed.then_some(fullscreen)
+            });
+
+            if let Some(Some(fullscreen)) = changed {
+                let state =
+                    if fullscreen { FullscreenState::Entered } else { FullscreenState::Exited };
+                let event = Event::WindowEvent {
+                    window_id: mkwid(xwindow),
+                    event: WindowEvent::FullscreenChanged { state },
+                };
+                callback(&self.target, event);
+            }
         }
     }
 
@@ -1052,67 +1092,49 @@ impl EventProcessor {
 
         let position = PhysicalPosition::new(event.event_x, event.event_y);
 
-        let event = match event.detail as u32 {
-            xlib::Button1 => WindowEvent::PointerButton {
-                device_id,
-                primary: true,
-                state,
-                position,
-                button: MouseButton::Left.into(),
-            },
-            xlib::Button2 => WindowEvent::PointerButton {
-                device_id,
-                primary: true,
-                state,
-                position,
-                button: MouseButton::Middle.into(),
-            },
-
-            xlib::Button3 => WindowEvent::PointerButton {
-                device_id,
-                primary: true,
-                state,
-                position,
-                button: MouseButton::Right.into(),
-            },
+        let button = match event.detail as u32 {
+            xlib::Button1 => MouseButton::Left,
+            xlib::Button2 => MouseButton::Middle,
+            xlib::Button3 => MouseButton::Right,
 
             // Suppress emulated scroll wheel clicks, since we handle the real motion events for
             // those. In practice, even clicky scroll wheels appear to be reported by
             // evdev (and XInput2 in turn) as axis motion, so we don't otherwise
             // special-case these button presses.
-            4..=7 => WindowEvent::MouseWheel {
-                device_id,
-                delta: match event.detail {
-                    4 => MouseScrollDelta::LineDelta(0.0, 1.0),
-                    5 => MouseScrollDelta::LineDelta(0.0, -1.0),
-                    6 => MouseScrollDelta::LineDelta(1.0, 0.0),
-                    7 => MouseScrollDelta::LineDelta(-1.0, 0.0),
-                    _ => unreachable!(),
-                },
-                phase: TouchPhase::Moved,
-            },
-            8 => WindowEvent::PointerButton {
-                device_id,
-                primary: true,
-                state,
-                position,
-                button: MouseButton::Back.into(),
+            4..=7 => {
+                let event = WindowEvent::MouseWheel {
+                    device_id,
+                    delta: match event.detail {
+                        4 => MouseScrollDelta::LineDelta(0.0, 1.0),
+                        5 => MouseScrollDelta::LineDelta(0.0, -1.0),
+                        6 => MouseScrollDelta::LineDelta(1.0, 0.0),
+                        7 => MouseScrollDelta::LineDelta(-1.0, 0.0),
+                        _ => unreachable!(),
+                    },
+                    phase: TouchPhase::Moved,
+                };
+                callback(&self.target, Event::WindowEvent { window_id, event });
+                return;
             },
+            8 => MouseButton::Back,
+            9 => MouseButton::Forward,
+            x => MouseButton::Other(x as u16),
+        };
 
-            9 => WindowEvent::PointerButton {
-                device_id,
-                primary: true,
-                state,
-                position,
-                button: MouseButton::Forward.into(),
-            },
-            x => WindowEvent::PointerButton {
-                device_id,
-                primary: true,
-                state,
-                position,
-                button: MouseButton::Other(x as u16).into(),
+        let click_count = match state {
+            ElementState::Pressed => {
+                self.click_counter.press(button, position, event.time as xproto::Timestamp)
             },
+            ElementState::Released => self.click_counter.release(button),
+        };
+
+        let event = WindowEvent::PointerButton {
+            device_id,
+            primary: true,
+            state,
+            position,
+            click_count,
+            button: button.into(),
         };
 
         let event = Event::WindowEvent { window_id, event };
@@ -1131,22 +1153,33 @@ impl EventProcessor {
         let window_id = mkwid(window);
         let new_cursor_pos = (event.event_x, event.event_y);
 
+        let valuators = unsafe { valuator_values(&event.valuators) };
+        let physical_device_id = mkdid(event.sourceid as xinput::DeviceId);
+
+        // The devices must not be borrowed while running the callback, which may query them.
+        let (source, pen_changed) = {
+            let mut devices = self.devices.borrow_mut();
+            match devices.get_mut(&physical_device_id).and_then(|device| device.pen_axes.as_mut()) {
+                Some(pen_axes) => {
+                    let changed = pen_axes.update(&valuators);
+                    (pen_axes.source(), changed)
+                },
+                None => (PointerSource::Mouse, false),
+            }
+        };
+
         let cursor_moved = self.with_window(window, |window| {
             let mut shared_state_lock = window.shared_state_lock();
             util::maybe_change(&mut shared_state_lock.cursor_pos, new_cursor_pos)
         });
 
-        if cursor_moved == Some(true) {
+        // Pens also report changes of pressure and tilt while not moving.
+        if cursor_moved == Some(true) || (cursor_moved.is_some() && pen_changed) {
             let position = PhysicalPosition::new(event.event_x, event.event_y);
 
             let event = Event::WindowEvent {
                 window_id,
-                event: WindowEvent::PointerMoved {
-                    device_id,
-                    primary: true,
-                    position,
-                    source: PointerSource::Mouse,
-                },
+                event: WindowEvent::PointerMoved { device_id, primary: true, position, source },
             };
             callback(&self.target, event);
         } else if cursor_moved.is_none() {
@@ -1154,26 +1187,16 @@ impl EventProcessor {
         }
 
         // More gymnastics, for self.devices
-        let mask = unsafe {
-            slice::from_raw_parts(event.valuators.mask, event.valuators.mask_len as usize)
-        };
         let mut devices = self.devices.borrow_mut();
-        let physical_device = match devices.get_mut(&mkdid(event.sourceid as xinput::DeviceId)) {
+        let physical_device = match devices.get_mut(&physical_device_id) {
             Some(device) => device,
             None => return,
         };
 
         let mut events = Vec::new();
-        let mut value = event.valuators.values;
-        for i in 0..event.valuators.mask_len * 8 {
-            if !xinput2::XIMaskIsSet(mask, i) {
-                continue;
-            }
-
-            let x = unsafe { *value };
-
+        for (i, x) in valuators {
             if let Some(&mut (_, ref mut info)) =
-                physical_device.scroll_axes.iter_mut().find(|&&mut (axis, _)| axis == i as _)
+                physical_device.scroll_axes.iter_mut().find(|&&mut (axis, _)| axis == i)
             {
                 let delta = (x - info.position) / info.increment;
                 info.position = x;
@@ -1188,9 +1211,8 @@ impl EventProcessor {
                 let event = WindowEvent::MouseWheel { device_id, delta, phase: TouchPhase::Moved };
                 events.push(Event::WindowEvent { window_id, event });
             }
-
-            value = unsafe { value.offset(1) };
         }
+        drop(devices);
 
         for event in events {
             callback(&self.target, event);
@@ -1235,7 +1257,7 @@ impl EventProcessor {
                     device_id,
                     primary: true,
                     position,
-                    kind: PointerKind::Mouse,
+                    kind: self.pointer_kind(event.sourceid),
                 },
             };
             callback(&self.target, event);
@@ -1260,7 +1282,7 @@ impl EventProcessor {
                     device_id: Some(mkdid(event.deviceid as xinput::DeviceId)),
                     primary: true,
                     position: Some(PhysicalPosition::new(event.event_x, event.event_y)),
-                    kind: PointerKind::Mouse,
+                    kind: self.pointer_kind(event.sourceid),
                 },
             };
             callback(&self.target, event);
@@ -1293,6 +1315,7 @@ impl EventProcessor {
 
         if let Some(window) = self.with_window(window, Arc::clone) {
             window.shared_state_lock().has_focus = true;
+            window.cursor_grab_focus_changed(true);
         }
 
         let event = Event::WindowEvent { window_id, event: WindowEvent::Focused(true) };
@@ -1373,6 +1396,7 @@ impl EventProcessor {
 
             if let Some(window) = self.with_window(window, Arc::clone) {
                 window.shared_state_lock().has_focus = false;
+                window.cursor_grab_focus_changed(false);
             }
 
             let event = Event::WindowEvent { window_id, event: WindowEvent::Focused(false) };
@@ -1432,6 +1456,7 @@ impl EventProcessor {
                             primary: is_first_touch,
                             state: ElementState::Pressed,
                             position,
+                            click_count: 1,
                             button: ButtonSource::Touch { finger_id, force: None },
                         },
                     };
@@ -1457,6 +1482,7 @@ impl EventProcessor {
                             primary: is_first_touch,
                             state: ElementState::Released,
                             position,
+                            click_count: 1,
                             button: ButtonSource::Touch { finger_id, force: None },
                         },
                     };
@@ -1529,7 +1555,7 @@ impl EventProcessor {
         if let Some(mouse_delta) = mouse_delta.consume() {
             let event = Event::DeviceEvent {
                 device_id: did,
-                event: DeviceEvent::PointerMotion { delta: mouse_delta },
+                event: DeviceEvent::PointerMotion { delta: mouse_delta, raw: true },
             };
             callback(&self.target, event);
         }
@@ -1601,6 +1627,7 @@ impl EventProcessor {
                 {
                     let xcb = self.target.xconn.xcb_connection().get_raw_xcb_connection();
                     self.xkb_context.set_keymap_from_x11(xcb);
+                    *self.target.xkb_keymap.borrow_mut() = self.xkb_context.shared_keymap();
                     self.xmodmap.reload_from_x_connection(&self.target.xconn);
 
                     let window_id = match self.active_window.map(super::mkwid) {
@@ -1617,6 +1644,7 @@ impl EventProcessor {
             xlib::XkbMapNotify => {
                 let xcb = self.target.xconn.xcb_connection().get_raw_xcb_connection();
                 self.xkb_context.set_keymap_from_x11(xcb);
+                *self.target.xkb_keymap.borrow_mut() = self.xkb_context.shared_keymap();
                 self.xmodmap.reload_from_x_connection(&self.target.xconn);
                 let window_id = match self.active_window.map(super::mkwid) {
                     Some(window_id) => window_id,
@@ -1854,22 +1882,25 @@ impl EventProcessor {
         }
     }
 
-    fn process_dpi_change<F>(&self, callback: &mut F)
+    fn process_dpi_change<F>(&mut self, callback: &mut F)
     where
         F: FnMut(&ActiveEventLoop, Event),
     {
         self.target.xconn.reload_database().expect("failed to reload Xft database");
 
-        // In the future, it would be quite easy to emit monitor hotplug events.
-        let prev_list = {
-            let prev_list = self.target.xconn.invalidate_cached_monitor_list();
-            match prev_list {
-                Some(prev_list) => prev_list,
-                None => return,
-            }
-        };
-
+        self.target.xconn.invalidate_cached_monitor_list();
         let new_list = self.target.xconn.available_monitors().expect("Failed to get monitor list");
+        let prev_list = mem::replace(&mut self.monitors, new_list.clone());
+
+        // A CRTC can be reused by another output, so match the output name as well.
+        let changes = MonitorChanges::new(&prev_list, &new_list, |a, b| a == b && a.name == b.name);
+        for monitor in changes.disconnected {
+            callback(&self.target, Event::MonitorDisconnected(mkmonitor(monitor)));
+        }
+        for monitor in changes.connected {
+            callback(&self.target, Event::MonitorConnected(mkmonitor(monitor)));
+        }
+
         for new_monitor in new_list {
             // Previous list may be empty, in case of disconnecting and
             // reconnecting the only one monitor. We still need to emit events in
@@ -1888,9 +1919,52 @@ impl EventProcessor {
         }
     }
 
+    /// Reads the double-click interval from XSettings, keeping the current one when it's unset.
+    pub(crate) fn update_double_click_interval(&self) {
+        let Some(xsettings_screen) = self.target.xconn.xsettings_screen() else {
+            return;
+        };
+
+        match self.target.xconn.xsettings_double_click_time(xsettings_screen) {
+            Ok(Some(interval)) => self.click_counter.set_interval(interval),
+            Ok(None) => {},
+            Err(err) => tracing::warn!("failed to fetch XSettings: {err}"),
+        }
+    }
+
+    fn process_accent_color_change<F>(&self, callback: &mut F)
+    where
+        F: FnMut(&ActiveEventLoop, Event),
+    {
+        let accent_color = self.target.xconn.xsettings_accent_color();
+        if self.target.accent_color.replace(accent_color) == accent_color {
+            return;
+        }
+
+        if let Some(accent_color) = accent_color {
+            let window_ids: Vec<_> = self.target.windows.borrow().keys().copied().collect();
+            for window_id in window_ids {
+                let event = Event::WindowEvent {
+                    window_id,
+                    event: WindowEvent::AccentColorChanged(accent_color),
+                };
+                callback(&self.target, event);
+            }
+        }
+    }
+
     fn window_exists(&self, window_id: xproto::Window) -> bool {
         self.with_window(window_id, |_| ()).is_some()
     }
+
+    /// The kind of pointer driven by the given physical device.
+    fn pointer_kind(&self, sourceid: c_int) -> PointerKind {
+        let devices = self.devices.borrow();
+        match devices.get(&mkdid(sourceid as xinput::DeviceId)) {
+            Some(device) if device.pen_axes.is_some() => PointerKind::Pen,
+            _ => PointerKind::Mouse,
+        }
+    }
 }
 
 fn is_first_touch(first: &mut Option<u32>, num: &mut u32, id: u32, phase: i32) -> bool {
@@ -1912,3 +1986,63 @@ fn is_first_touch(first: &mut Option<u32>, num: &mut u32, id: u32, phase: i32) -
 
     *first == Some(id)
 }
+
+/// Collect the `(axis, value)` pairs of the valuators set in `valuators`.
+///
+/// `values` only holds an entry for each bit set in the mask, in increasing axis order.
+///
+/// # Safety
+///
+/// `valuators` must point to a mask of `mask_len` bytes and to as many values as it has set bits.
+unsafe fn valuator_values(valuators: &XIValuatorState) -> Vec<(i32, f64)> {
+    let mask = unsafe { slice::from_raw_parts(valuators.mask, valuators.mask_len as usize) };
+    let mut value = valuators.values;
+    let mut values = Vec::new();
+    for i in 0..valuators.mask_len * 8 {
+        if xinput2::XIMaskIsSet(mask, i) {
+            values.push((i, unsafe { *value }));
+            value = unsafe { value.offset(1) };
+        }
+    }
+
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valuators_skip_unset_axes() {
+        let mut mask = [0b0000_0101u8, 0b0000_0001];
+        let mut values = [1.5, -2.0, 42.0];
+        let valuators = XIValuatorState {
+            mask_len: mask.len() as _,
+            mask: mask.as_mut_ptr(),
+            values: values.as_mut_ptr(),
+        };
+
+        let parsed = unsafe { valuator_values(&valuators) };
+        assert_eq!(parsed, [(0, 1.5), (2, -2.0), (8, 42.0)]);
+    }
+
+    #[test]
+    fn pen_axes_are_normalized() {
+        use crate::platform_impl::x11::{PenAxes, PenAxis};
+
+        let axis = |number, min, max| Some(PenAxis { number, min, max, value: 0.0 });
+        let mut pen = PenAxes {
+            pressure: axis(2, 0.0, 2048.0),
+            tilt_x: axis(3, -64.0, 63.0),
+            tilt_y: axis(4, -64.0, 63.0),
+        };
+
+        assert!(pen.update(&[(0, 10.0), (2, 512.0), (3, -20.0), (4, 100.0)]));
+        assert!(!pen.update(&[(0, 11.0), (2, 512.0)]));
+        assert_eq!(pen.source(), PointerSource::Pen {
+            pressure: Some(0.25),
+            tilt: Some((-20.0, 90.0)),
+            twist: None,
+        });
+    }
+}
@@ -1,6 +1,6 @@
 use std::error::Error;
 use std::ffi::CStr;
-use std::os::raw::c_short;
+use std::os::raw::{c_int, c_short};
 use std::sync::Arc;
 use std::{fmt, mem, ptr};
 
@@ -146,8 +146,13 @@ extern "C" fn preedit_caret_callback(
     let client_data = unsafe { &mut *(client_data as *mut ImeContextClientData) };
     let call_data = unsafe { &mut *(call_data as *mut XIMPreeditCaretCallbackStruct) };
 
-    if call_data.direction == ffi::XIMCaretDirection::XIMAbsolutePosition {
-        client_data.cursor_pos = call_data.position as usize;
+    let position = call_data.position.max(0) as usize;
+    if let Some(cursor_pos) =
+        move_caret(&client_data.text, client_data.cursor_pos, call_data.direction, position)
+    {
+        client_data.cursor_pos = cursor_pos;
+        // The input method expects the resulting position back.
+        call_data.position = cursor_pos as c_int;
         let cursor_byte_pos = calc_byte_position(&client_data.text, client_data.cursor_pos);
 
         client_data
@@ -160,6 +165,29 @@ extern "C" fn preedit_caret_callback(
     }
 }
 
+/// Compute the new caret position in the preedit text, in characters.
+///
+/// Returns `None` for movements that don't make sense for a single line of preedit text.
+fn move_caret(
+    text: &[char],
+    caret: usize,
+    direction: ffi::XIMCaretDirection,
+    position: usize,
+) -> Option<usize> {
+    use ffi::XIMCaretDirection::*;
+    let caret = match direction {
+        XIMForwardChar => caret + 1,
+        XIMBackwardChar => caret.saturating_sub(1),
+        XIMLineStart => 0,
+        XIMLineEnd => text.len(),
+        XIMAbsolutePosition => position,
+        XIMDontChange => caret,
+        XIMForwardWord | XIMBackwardWord | XIMCaretUp | XIMCaretDown | XIMNextLine
+        | XIMPreviousLine => return None,
+    };
+    Some(caret.min(text.len()))
+}
+
 /// Struct to simplify callback creation and latter passing into Xlib XIM.
 struct PreeditCallbacks {
     start_callback: ffi::XIMCallback,
@@ -384,3 +412,28 @@ impl ImeContext {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ffi::XIMCaretDirection::*;
+    use super::*;
+
+    #[test]
+    fn caret_moves_within_preedit() {
+        let text: Vec<char> = "日本語".chars().collect();
+
+        assert_eq!(move_caret(&text, 3, XIMBackwardChar, 0), Some(2));
+        assert_eq!(move_caret(&text, 0, XIMBackwardChar, 0), Some(0));
+        assert_eq!(move_caret(&text, 2, XIMForwardChar, 0), Some(3));
+        assert_eq!(move_caret(&text, 3, XIMForwardChar, 0), Some(3));
+        assert_eq!(move_caret(&text, 2, XIMLineStart, 0), Some(0));
+        assert_eq!(move_caret(&text, 0, XIMLineEnd, 0), Some(3));
+        assert_eq!(move_caret(&text, 0, XIMAbsolutePosition, 1), Some(1));
+        assert_eq!(move_caret(&text, 0, XIMAbsolutePosition, 7), Some(3));
+        assert_eq!(move_caret(&text, 1, XIMDontChange, 0), Some(1));
+        assert_eq!(move_caret(&text, 1, XIMCaretUp, 0), None);
+
+        // Positions are reported in bytes.
+        assert_eq!(calc_byte_position(&text, 2), 6);
+    }
+}
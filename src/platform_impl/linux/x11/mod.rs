@@ -1,10 +1,12 @@
 use std::cell::{Cell, RefCell};
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::cmp::Reverse;
+use std::collections::{HashMap, VecDeque};
 use std::ffi::CStr;
 use std::mem::MaybeUninit;
 use std::ops::Deref;
 use std::os::raw::*;
 use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd, RawFd};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
 use std::sync::{Arc, Weak};
 use std::time::{Duration, Instant};
@@ -14,6 +16,7 @@ use calloop::generic::Generic;
 use calloop::ping::Ping;
 use calloop::{EventLoop as Loop, Readiness};
 use libc::{setlocale, LC_CTYPE};
+use smol_str::SmolStr;
 use tracing::warn;
 use x11rb::connection::RequestConnection;
 use x11rb::errors::{ConnectError, ConnectionError, IdsExhausted, ReplyError};
@@ -24,20 +27,25 @@ use x11rb::xcb_ffi::ReplyOrIdError;
 
 use crate::application::ApplicationHandler;
 use crate::error::{EventLoopError, RequestError};
-use crate::event::{DeviceId, Event, StartCause, WindowEvent};
+use crate::event::{DeviceId, Event, PointerSource, StartCause, WindowEvent};
 use crate::event_loop::{
     ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEvents,
     EventLoopProxy as CoreEventLoopProxy, EventLoopProxyProvider,
     OwnedDisplayHandle as CoreOwnedDisplayHandle,
 };
+use crate::keyboard::{ModifiersState, PhysicalKey};
 use crate::platform::pump_events::PumpStatus;
-use crate::platform_impl::common::xkb::Context;
+use crate::platform_impl::click::ClickCounter;
+use crate::platform_impl::common::xkb::{Context, SharedKeymap};
+use crate::platform_impl::idle::IdleTimer;
 use crate::platform_impl::platform::min_timeout;
+use crate::platform_impl::redraw::RedrawSchedule;
+use crate::platform_impl::resize::ResizeCoalescer;
 use crate::platform_impl::x11::window::Window;
 use crate::platform_impl::PlatformCustomCursor;
 use crate::window::{
-    CustomCursor as RootCustomCursor, CustomCursorSource, Theme, Window as CoreWindow,
-    WindowAttributes, WindowId,
+    CustomCursor as RootCustomCursor, CustomCursorSource, RedrawPriority, Theme,
+    Window as CoreWindow, WindowAttributes, WindowId,
 };
 
 mod activation;
@@ -135,12 +143,18 @@ pub struct ActiveEventLoop {
     ime_sender: ImeSender,
     control_flow: Cell<ControlFlow>,
     exit: Cell<Option<i32>>,
+    accent_color: Cell<Option<[u8; 4]>>,
+    /// The keymap of the event processor, for `key_text`.
+    xkb_keymap: RefCell<Option<SharedKeymap>>,
+    idle_timer: IdleTimer,
+    resize_coalescer: ResizeCoalescer,
     root: xproto::Window,
     ime: Option<RefCell<Ime>>,
     windows: RefCell<HashMap<WindowId, Weak<UnownedWindow>>>,
-    redraw_sender: WakeSender<WindowId>,
+    redraw_sender: WakeSender<(WindowId, RedrawPriority)>,
+    scheduled_redraws: Arc<RedrawSchedule>,
     activation_sender: WakeSender<ActivationToken>,
-    event_loop_proxy: CoreEventLoopProxy,
+    event_loop_proxy: Arc<EventLoopProxy>,
     device_events: Cell<DeviceEvents>,
 }
 
@@ -148,7 +162,7 @@ pub struct EventLoop {
     loop_running: bool,
     event_loop: Loop<'static, EventLoopState>,
     event_processor: EventProcessor,
-    redraw_receiver: PeekableReceiver<WindowId>,
+    redraw_receiver: PeekableReceiver<(WindowId, RedrawPriority)>,
     activation_receiver: PeekableReceiver<ActivationToken>,
 
     /// The current state of the event loop.
@@ -280,7 +294,7 @@ impl EventLoop {
                 state.proxy_wake_up = true;
             })
             .expect("Failed to register the event loop waker source");
-        let event_loop_proxy = EventLoopProxy::new(user_waker);
+        let event_loop_proxy = Arc::new(EventLoopProxy::new(user_waker));
 
         let xkb_context =
             Context::from_x11_xkb(xconn.xcb_connection().get_raw_xcb_connection()).unwrap();
@@ -293,6 +307,10 @@ impl EventLoop {
             root,
             control_flow: Cell::new(ControlFlow::default()),
             exit: Cell::new(None),
+            accent_color: Cell::new(xconn.xsettings_accent_color()),
+            xkb_keymap: RefCell::new(xkb_context.shared_keymap()),
+            idle_timer: IdleTimer::default(),
+            resize_coalescer: ResizeCoalescer::default(),
             windows: Default::default(),
             ime_sender,
             xconn,
@@ -303,17 +321,24 @@ impl EventLoop {
                 sender: redraw_sender, // not used again so no clone
                 waker: waker.clone(),
             },
+            scheduled_redraws: Default::default(),
             activation_sender: WakeSender {
                 sender: activation_token_sender, // not used again so no clone
                 waker: waker.clone(),
             },
-            event_loop_proxy: event_loop_proxy.into(),
+            event_loop_proxy,
             device_events: Default::default(),
         };
 
         // Set initial device event filter.
         window_target.update_listen_device_events(true);
 
+        // The monitors that are already connected aren't reported as hotplugged.
+        let monitors = window_target.xconn.available_monitors().unwrap_or_else(|err| {
+            warn!("failed to get the monitor list: {err}");
+            Vec::new()
+        });
+
         let event_processor = EventProcessor {
             target: window_target,
             dnd,
@@ -332,7 +357,10 @@ impl EventLoop {
             active_window: None,
             modifiers: Default::default(),
             is_composing: false,
+            click_counter: ClickCounter::default(),
+            monitors,
         };
+        event_processor.update_double_click_interval();
 
         // Register for device hotplug events
         // (The request buffer is flushed during `init_device`)
@@ -396,6 +424,8 @@ impl EventLoop {
             }
         };
 
+        self.event_processor.target.control_flow.set(ControlFlow::default());
+
         // Applications aren't allowed to carry windows between separate
         // `run_on_demand` calls but if they have only just dropped their
         // windows we need to make sure those last requests are sent to the
@@ -464,7 +494,10 @@ impl EventLoop {
                 },
             };
 
-            min_timeout(control_flow_timeout, timeout)
+            let idle_timeout = self.event_processor.target.idle_timer.timeout(start);
+            let redraw_timeout = self.event_processor.target.scheduled_redraws.timeout(start);
+            let timeout = min_timeout(min_timeout(control_flow_timeout, idle_timeout), timeout);
+            min_timeout(redraw_timeout, timeout)
         };
 
         self.state.x11_readiness = Readiness::EMPTY;
@@ -483,10 +516,15 @@ impl EventLoop {
             ControlFlow::Poll => StartCause::Poll,
             ControlFlow::Wait => StartCause::WaitCancelled { start, requested_resume: None },
             ControlFlow::WaitUntil(deadline) => {
-                if Instant::now() < deadline {
+                let actual_resume = Instant::now();
+                if actual_resume < deadline {
                     StartCause::WaitCancelled { start, requested_resume: Some(deadline) }
                 } else {
-                    StartCause::ResumeTimeReached { start, requested_resume: deadline }
+                    StartCause::ResumeTimeReached {
+                        start,
+                        requested_resume: deadline,
+                        actual_resume,
+                    }
                 }
             },
         };
@@ -501,6 +539,10 @@ impl EventLoop {
         // If we don't have any pending `_receiver`
         if !self.has_pending()
             && !matches!(&cause, StartCause::ResumeTimeReached { .. } | StartCause::Poll)
+            && self.event_processor.target.idle_timer.timeout(Instant::now())
+                != Some(Duration::ZERO)
+            && self.event_processor.target.scheduled_redraws.timeout(Instant::now())
+                != Some(Duration::ZERO)
         {
             return;
         }
@@ -550,13 +592,23 @@ impl EventLoop {
 
         // Empty the redraw requests
         {
-            let mut windows = HashSet::new();
+            let mut windows = HashMap::new();
+
+            let scheduled_redraws = &self.event_processor.target.scheduled_redraws;
+            for window_id in scheduled_redraws.take_due(Instant::now()) {
+                windows.insert(window_id, RedrawPriority::Normal);
+            }
 
-            while let Ok(window_id) = self.redraw_receiver.try_recv() {
-                windows.insert(window_id);
+            while let Ok((window_id, priority)) = self.redraw_receiver.try_recv() {
+                let pending = windows.entry(window_id).or_insert(priority);
+                *pending = priority.max(*pending);
             }
 
-            for window_id in windows {
+            // Deliver higher priority redraws first.
+            let mut windows: Vec<_> = windows.into_iter().collect();
+            windows.sort_by_key(|&(_, priority)| Reverse(priority));
+
+            for (window_id, _) in windows {
                 app.window_event(
                     &self.event_processor.target,
                     window_id,
@@ -565,6 +617,10 @@ impl EventLoop {
             }
         }
 
+        if self.event_processor.target.idle_timer.poll(Instant::now()) {
+            app.idle_tick(&self.event_processor.target);
+        }
+
         // This is always the last event we dispatch before poll again
         app.about_to_wait(&self.event_processor.target);
     }
@@ -577,20 +633,37 @@ impl EventLoop {
             self.event_processor.process_event(&mut xev, |window_target, event: Event| {
                 if let Event::WindowEvent { window_id, event: WindowEvent::RedrawRequested } = event
                 {
-                    window_target.redraw_sender.send(window_id);
+                    window_target.redraw_sender.send((window_id, RedrawPriority::Normal));
                 } else {
                     match event {
                         Event::WindowEvent { window_id, event } => {
-                            app.window_event(window_target, window_id, event)
+                            if let Some(event) =
+                                window_target.resize_coalescer.coalesce(window_id, event)
+                            {
+                                app.window_event(window_target, window_id, event)
+                            }
                         },
                         Event::DeviceEvent { device_id, event } => {
                             app.device_event(window_target, device_id, event)
                         },
-                        _ => unreachable!("event which is neither device nor window event."),
+                        Event::MonitorConnected(monitor) => {
+                            app.monitor_connected(window_target, monitor)
+                        },
+                        Event::MonitorDisconnected(monitor) => {
+                            app.monitor_disconnected(window_target, monitor)
+                        },
+                        _ => {
+                            unreachable!("event which is neither device, window nor monitor event.")
+                        },
                     }
                 }
             });
         }
+
+        let target = &self.event_processor.target;
+        for (window_id, size) in target.resize_coalescer.take() {
+            app.window_event(target, window_id, WindowEvent::SurfaceResized(size));
+        }
     }
 
     fn control_flow(&self) -> ControlFlow {
@@ -622,6 +695,12 @@ impl AsRawFd for EventLoop {
     }
 }
 
+impl Drop for EventLoop {
+    fn drop(&mut self) {
+        self.event_processor.target.event_loop_proxy.alive.store(false, Ordering::Relaxed);
+    }
+}
+
 impl ActiveEventLoop {
     /// Returns the `XConnection` of this events loop.
     #[inline]
@@ -663,7 +742,7 @@ impl ActiveEventLoop {
 
 impl RootActiveEventLoop for ActiveEventLoop {
     fn create_proxy(&self) -> CoreEventLoopProxy {
-        self.event_loop_proxy.clone()
+        CoreEventLoopProxy::new(self.event_loop_proxy.clone())
     }
 
     fn create_window(
@@ -673,6 +752,16 @@ impl RootActiveEventLoop for ActiveEventLoop {
         Ok(Box::new(Window::new(self, window_attributes)?))
     }
 
+    fn create_windows(
+        &self,
+        window_attributes: Vec<WindowAttributes>,
+    ) -> Vec<Result<Box<dyn CoreWindow>, RequestError>> {
+        Window::new_batch(self, window_attributes)
+            .into_iter()
+            .map(|window| window.map(|window| Box::new(window) as Box<dyn CoreWindow>))
+            .collect()
+    }
+
     fn create_custom_cursor(
         &self,
         custom_cursor: CustomCursorSource,
@@ -683,28 +772,25 @@ impl RootActiveEventLoop for ActiveEventLoop {
     }
 
     fn available_monitors(&self) -> Box<dyn Iterator<Item = crate::monitor::MonitorHandle>> {
-        Box::new(
-            self.xconn
-                .available_monitors()
-                .into_iter()
-                .flatten()
-                .map(crate::platform_impl::MonitorHandle::X)
-                .map(|inner| crate::monitor::MonitorHandle { inner }),
-        )
+        Box::new(self.xconn.available_monitors().into_iter().flatten().map(mkmonitor))
     }
 
     fn primary_monitor(&self) -> Option<crate::monitor::MonitorHandle> {
-        self.xconn
-            .primary_monitor()
-            .ok()
-            .map(crate::platform_impl::MonitorHandle::X)
-            .map(|inner| crate::monitor::MonitorHandle { inner })
+        self.xconn.primary_monitor().ok().map(mkmonitor)
     }
 
     fn system_theme(&self) -> Option<Theme> {
         None
     }
 
+    fn accent_color(&self) -> Option<[u8; 4]> {
+        self.accent_color.get()
+    }
+
+    fn key_text(&self, key: PhysicalKey, modifiers: ModifiersState) -> Option<SmolStr> {
+        self.xkb_keymap.borrow_mut().as_mut()?.key_text(key, modifiers)
+    }
+
     fn listen_device_events(&self, allowed: DeviceEvents) {
         self.device_events.set(allowed);
     }
@@ -717,8 +803,25 @@ impl RootActiveEventLoop for ActiveEventLoop {
         self.control_flow.get()
     }
 
+    fn set_idle_interval(&self, interval: Option<Duration>) -> Result<(), RequestError> {
+        self.idle_timer.set_interval(interval);
+        Ok(())
+    }
+
+    fn set_resize_coalescing(&self, enabled: bool) {
+        self.resize_coalescer.set_enabled(enabled)
+    }
+
+    fn scroll_lines_to_pixels(&self, lines: f32) -> f32 {
+        lines * 3.0 * 16.0
+    }
+
     fn exit(&self) {
-        self.exit.set(Some(0))
+        self.exit_with_code(0)
+    }
+
+    fn exit_with_code(&self, code: i32) {
+        self.exit.set(Some(code))
     }
 
     fn exiting(&self) -> bool {
@@ -777,26 +880,24 @@ impl Deref for DeviceInfo<'_> {
     }
 }
 
-#[derive(Clone)]
 pub struct EventLoopProxy {
     ping: Ping,
+    alive: AtomicBool,
 }
 
 impl EventLoopProxyProvider for EventLoopProxy {
     fn wake_up(&self) {
         self.ping.ping();
     }
-}
 
-impl EventLoopProxy {
-    fn new(ping: Ping) -> Self {
-        Self { ping }
+    fn is_alive(&self) -> bool {
+        self.alive.load(Ordering::Relaxed)
     }
 }
 
-impl From<EventLoopProxy> for CoreEventLoopProxy {
-    fn from(value: EventLoopProxy) -> Self {
-        CoreEventLoopProxy::new(Arc::new(value))
+impl EventLoopProxy {
+    fn new(ping: Ping) -> Self {
+        Self { ping, alive: AtomicBool::new(true) }
     }
 }
 
@@ -966,11 +1067,15 @@ fn mkwid(w: xproto::Window) -> crate::window::WindowId {
 fn mkdid(w: xinput::DeviceId) -> DeviceId {
     DeviceId::from_raw(w as i64)
 }
+fn mkmonitor(monitor: MonitorHandle) -> crate::monitor::MonitorHandle {
+    crate::monitor::MonitorHandle { inner: crate::platform_impl::MonitorHandle::X(monitor) }
+}
 
 #[derive(Debug)]
 pub struct Device {
     _name: String,
     scroll_axes: Vec<(i32, ScrollAxis)>,
+    pen_axes: Option<PenAxes>,
     // For master devices, this is the paired device (pointer <-> keyboard).
     // For slave devices, this is the master.
     attachment: c_int,
@@ -989,16 +1094,87 @@ enum ScrollOrientation {
     Horizontal,
 }
 
+/// The valuators of a pen, identified by their label.
+#[derive(Debug, Default, Copy, Clone)]
+struct PenAxes {
+    pressure: Option<PenAxis>,
+    tilt_x: Option<PenAxis>,
+    tilt_y: Option<PenAxis>,
+}
+
+#[derive(Debug, Copy, Clone)]
+struct PenAxis {
+    number: i32,
+    min: f64,
+    max: f64,
+    value: f64,
+}
+
+impl PenAxes {
+    fn axis_mut(&mut self, number: i32) -> Option<&mut PenAxis> {
+        [&mut self.pressure, &mut self.tilt_x, &mut self.tilt_y]
+            .into_iter()
+            .flatten()
+            .find(|axis| axis.number == number)
+    }
+
+    /// Store the new valuator values, returns whether any of the pen axes changed.
+    fn update(&mut self, valuators: &[(i32, f64)]) -> bool {
+        let mut changed = false;
+        for &(number, value) in valuators {
+            if let Some(axis) = self.axis_mut(number).filter(|axis| axis.value != value) {
+                axis.value = value;
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    fn source(&self) -> PointerSource {
+        let pressure = self.pressure.map(|axis| {
+            let range = axis.max - axis.min;
+            if range > 0.0 {
+                ((axis.value - axis.min) / range).clamp(0.0, 1.0) as f32
+            } else {
+                0.0
+            }
+        });
+        // The drivers report the tilt in degrees.
+        let tilt = self
+            .tilt_x
+            .zip(self.tilt_y)
+            .map(|(x, y)| (x.value.clamp(-90.0, 90.0) as f32, y.value.clamp(-90.0, 90.0) as f32));
+        PointerSource::Pen { pressure, tilt, twist: None }
+    }
+}
+
 impl Device {
-    fn new(info: &ffi::XIDeviceInfo) -> Self {
+    fn new(info: &ffi::XIDeviceInfo, atoms: &Atoms) -> Self {
         let name = unsafe { CStr::from_ptr(info.name).to_string_lossy() };
         let mut scroll_axes = Vec::new();
+        let mut pen_axes = PenAxes::default();
 
         if Device::physical_device(info) {
-            // Identify scroll axes
+            // Identify scroll and pen axes
             for &class_ptr in Device::classes(info) {
                 let ty = unsafe { (*class_ptr)._type };
-                if ty == ffi::XIScrollClass {
+                if ty == ffi::XIValuatorClass {
+                    let info = unsafe { &*(class_ptr as *const ffi::XIValuatorClassInfo) };
+                    let axis = Some(PenAxis {
+                        number: info.number,
+                        min: info.min,
+                        max: info.max,
+                        value: info.value,
+                    });
+                    let label = info.label as xproto::Atom;
+                    if label == atoms[AbsPressure] {
+                        pen_axes.pressure = axis;
+                    } else if label == atoms[AbsTiltX] {
+                        pen_axes.tilt_x = axis;
+                    } else if label == atoms[AbsTiltY] {
+                        pen_axes.tilt_y = axis;
+                    }
+                } else if ty == ffi::XIScrollClass {
                     let info = unsafe { &*(class_ptr as *const ffi::XIScrollClassInfo) };
                     scroll_axes.push((info.number, ScrollAxis {
                         increment: info.increment,
@@ -1013,8 +1189,11 @@ impl Device {
             }
         }
 
+        let is_pen = pen_axes.pressure.is_some() || pen_axes.tilt_x.is_some();
+        let pen_axes = is_pen.then_some(pen_axes);
+
         let mut device =
-            Device { _name: name.into_owned(), scroll_axes, attachment: info.attachment };
+            Device { _name: name.into_owned(), scroll_axes, pen_axes, attachment: info.attachment };
         device.reset_scroll_position(info);
         device
     }
@@ -5,7 +5,7 @@ use x11rb::protocol::randr::{self, ConnectionExt as _};
 use x11rb::protocol::xproto;
 
 use super::{util, X11Error, XConnection};
-use crate::dpi::PhysicalPosition;
+use crate::dpi::{PhysicalPosition, PhysicalSize};
 use crate::monitor::VideoMode;
 
 // Used for testing. This should always be committed as false.
@@ -42,10 +42,14 @@ pub struct MonitorHandle {
     pub(crate) position: (i32, i32),
     /// If the monitor is the primary one
     primary: bool,
+    /// The physical size of the monitor in millimeters
+    physical_size: Option<(u32, u32)>,
     /// The DPI scale factor
     pub(crate) scale_factor: f64,
     /// Used to determine which windows are on this monitor
     pub(crate) rect: util::AaRect,
+    /// The part of the monitor not covered by panels
+    work_area: util::AaRect,
     /// Supported video modes on this monitor
     pub(crate) video_modes: Vec<VideoModeHandle>,
 }
@@ -96,13 +100,28 @@ impl MonitorHandle {
         crtc: &randr::GetCrtcInfoReply,
         primary: bool,
     ) -> Option<Self> {
-        let (name, scale_factor, video_modes) = xconn.get_output_info(resources, crtc)?;
+        let util::OutputInfo { name, scale_factor, video_modes, physical_size } =
+            xconn.get_output_info(resources, crtc)?;
         let dimensions = (crtc.width as u32, crtc.height as u32);
         let position = (crtc.x as i32, crtc.y as i32);
 
         let rect = util::AaRect::new(position, dimensions);
-
-        Some(MonitorHandle { id, name, scale_factor, position, primary, rect, video_modes })
+        let work_area = xconn
+            .get_work_area()
+            .and_then(|work_area| work_area.intersection(&rect))
+            .unwrap_or_else(|| rect.clone());
+
+        Some(MonitorHandle {
+            id,
+            name,
+            scale_factor,
+            position,
+            primary,
+            physical_size,
+            rect,
+            work_area,
+            video_modes,
+        })
     }
 
     pub fn dummy() -> Self {
@@ -112,7 +131,9 @@ impl MonitorHandle {
             scale_factor: 1.0,
             position: (0, 0),
             primary: true,
+            physical_size: None,
             rect: util::AaRect::new((0, 0), (1, 1)),
+            work_area: util::AaRect::new((0, 0), (1, 1)),
             video_modes: Vec::new(),
         }
     }
@@ -135,11 +156,26 @@ impl MonitorHandle {
         Some(self.position.into())
     }
 
+    #[inline]
+    pub fn work_area(&self) -> Option<(PhysicalPosition<i32>, PhysicalSize<u32>)> {
+        Some((self.work_area.position().into(), self.work_area.size().into()))
+    }
+
     #[inline]
     pub fn scale_factor(&self) -> f64 {
         self.scale_factor
     }
 
+    #[inline]
+    pub fn physical_size_mm(&self) -> Option<(f64, f64)> {
+        self.physical_size.map(|(width, height)| (width as f64, height as f64))
+    }
+
+    #[inline]
+    pub fn is_primary(&self) -> bool {
+        self.primary
+    }
+
     #[inline]
     pub fn current_video_mode(&self) -> Option<VideoMode> {
         self.video_modes.iter().find(|mode| mode.current).cloned().map(Into::into)
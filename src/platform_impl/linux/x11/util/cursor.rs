@@ -2,6 +2,7 @@ use std::collections::hash_map::Entry;
 use std::hash::{Hash, Hasher};
 use std::iter;
 use std::sync::Arc;
+use std::time::Duration;
 
 use x11rb::connection::Connection;
 use x11rb::protocol::render::{self, ConnectionExt as _};
@@ -9,8 +10,12 @@ use x11rb::protocol::xproto;
 
 use super::super::ActiveEventLoop;
 use super::*;
+use crate::cursor::{CursorImage, CustomCursor as RootCustomCursor};
+#[cfg(wayland_platform)]
+use crate::error::NotSupportedError;
 use crate::error::RequestError;
-use crate::platform_impl::PlatformCustomCursorSource;
+use crate::icon::premultiply_rgba;
+use crate::platform_impl::{PlatformCustomCursor, PlatformCustomCursorSource};
 use crate::window::CursorIcon;
 
 impl XConnection {
@@ -95,6 +100,16 @@ impl XConnection {
         Ok(cursor)
     }
 
+    /// Create a cursor cycling through the frames of an animation.
+    fn create_animated_cursor(
+        &self,
+        frames: &[render::Animcursorelt],
+    ) -> Result<xproto::Cursor, X11Error> {
+        let cursor = self.xcb_connection().generate_id()?;
+        self.xcb_connection().render_create_anim_cursor(cursor, frames)?.check()?;
+        Ok(cursor)
+    }
+
     /// Find the render format that corresponds to ARGB32.
     fn find_argb32_format(&self) -> Result<render::Pictformat, X11Error> {
         macro_rules! direct {
@@ -136,15 +151,22 @@ impl XConnection {
         )?
         .reply()?;
 
+        // Animated cursors like `wait` are loaded with all of their frames, but themes often only
+        // ship them under one of the alternative names, so keep looking when a name is missing
+        // from the theme instead of settling for no cursor at all.
         let mut last_error = None;
         for &name in iter::once(&cursor.name()).chain(cursor.alt_names().iter()) {
             match handle.load_cursor(self.xcb_connection(), name) {
+                Ok(x11rb::NONE) => (),
                 Ok(cursor) => return Ok(cursor),
                 Err(err) => last_error = Some(err.into()),
             }
         }
 
-        Err(last_error.unwrap())
+        match last_error {
+            Some(err) => Err(err),
+            None => Ok(x11rb::NONE),
+        }
     }
 
     fn update_cursor(
@@ -192,10 +214,41 @@ impl Eq for CustomCursor {}
 impl CustomCursor {
     pub(crate) fn new(
         event_loop: &ActiveEventLoop,
-        mut cursor: PlatformCustomCursorSource,
+        cursor: PlatformCustomCursorSource,
     ) -> Result<CustomCursor, RequestError> {
+        let (cursor, animation) = match cursor {
+            PlatformCustomCursorSource::Image(image) => {
+                (Self::create_from_image(event_loop, image)?, false)
+            },
+            PlatformCustomCursorSource::Animation { duration, cursors } => {
+                (Self::create_animation(event_loop, duration, &cursors)?, true)
+            },
+        };
+
+        Ok(Self {
+            inner: Arc::new(CustomCursorInner {
+                xconn: event_loop.xconn.clone(),
+                cursor,
+                animation,
+            }),
+        })
+    }
+
+    pub(crate) fn is_animation(&self) -> bool {
+        self.inner.animation
+    }
+
+    fn create_from_image(
+        event_loop: &ActiveEventLoop,
+        mut image: CursorImage,
+    ) -> Result<xproto::Cursor, RequestError> {
+        // The ARGB32 render format uses premultiplied alpha.
+        if !image.premultiplied {
+            premultiply_rgba(&mut image.rgba);
+        }
+
         // Reverse RGBA order to BGRA.
-        cursor.0.rgba.chunks_mut(4).for_each(|chunk| {
+        image.rgba.chunks_mut(4).for_each(|chunk| {
             let chunk: &mut [u8; 4] = chunk.try_into().unwrap();
             chunk[0..3].reverse();
 
@@ -206,18 +259,41 @@ impl CustomCursor {
             }
         });
 
-        let cursor = event_loop
+        event_loop
             .xconn
             .create_cursor_from_image(
-                cursor.0.width,
-                cursor.0.height,
-                cursor.0.hotspot_x,
-                cursor.0.hotspot_y,
-                &cursor.0.rgba,
+                image.width,
+                image.height,
+                image.hotspot_x,
+                image.hotspot_y,
+                &image.rgba,
             )
-            .map_err(|err| os_error!(err))?;
+            .map_err(|err| os_error!(err).into())
+    }
+
+    fn create_animation(
+        event_loop: &ActiveEventLoop,
+        duration: Duration,
+        cursors: &[RootCustomCursor],
+    ) -> Result<xproto::Cursor, RequestError> {
+        // The frames are shown for equal parts of the animation. A delay of zero would stop the
+        // animation on the first frame, so frames last at least a millisecond.
+        let delay = (duration.as_millis() / cursors.len() as u128).try_into().unwrap_or(u32::MAX);
+        let delay = delay.max(1);
+        let frames = cursors
+            .iter()
+            .map(|cursor| match &cursor.inner {
+                PlatformCustomCursor::X(cursor) => {
+                    Ok(render::Animcursorelt { cursor: cursor.inner.cursor, delay })
+                },
+                #[cfg(wayland_platform)]
+                PlatformCustomCursor::Wayland(_) => {
+                    Err(NotSupportedError::new("animation frames must be X11 cursors").into())
+                },
+            })
+            .collect::<Result<Vec<_>, RequestError>>()?;
 
-        Ok(Self { inner: Arc::new(CustomCursorInner { xconn: event_loop.xconn.clone(), cursor }) })
+        event_loop.xconn.create_animated_cursor(&frames).map_err(|err| os_error!(err).into())
     }
 }
 
@@ -225,6 +301,7 @@ impl CustomCursor {
 struct CustomCursorInner {
     xconn: Arc<XConnection>,
     cursor: xproto::Cursor,
+    animation: bool,
 }
 
 impl Drop for CustomCursorInner {
@@ -33,6 +33,41 @@ impl AaRect {
         );
         x_overlap * y_overlap
     }
+
+    /// The part of this rectangle that lies within `other`, if any.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let x = cmp::max(self.x, other.x);
+        let y = cmp::max(self.y, other.y);
+        let width = cmp::min(self.x + self.width, other.x + other.width) - x;
+        let height = cmp::min(self.y + self.height, other.y + other.height) - y;
+        (width > 0 && height > 0).then_some(AaRect { x, y, width, height })
+    }
+
+    pub fn position(&self) -> (i32, i32) {
+        (self.x as i32, self.y as i32)
+    }
+
+    pub fn size(&self) -> (u32, u32) {
+        (self.width as u32, self.height as u32)
+    }
+
+    /// The fraction of this rectangle's area that lies within `other`.
+    pub fn overlap_ratio(&self, other: &Self) -> f64 {
+        let area = self.width * self.height;
+        if area == 0 {
+            return 0.0;
+        }
+
+        self.get_overlapping_area(other) as f64 / area as f64
+    }
+
+    /// Whether a window with this rectangle should leave the monitor at `current` for the one at
+    /// `target`, once at least `threshold` of its area is on `target`.
+    ///
+    /// A window that isn't on `current` anymore always leaves it.
+    pub fn should_switch_monitor(&self, current: &Self, target: &Self, threshold: f64) -> bool {
+        self.get_overlapping_area(current) == 0 || self.overlap_ratio(target) >= threshold
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -124,6 +159,24 @@ impl XConnection {
         self.xcb_connection().get_geometry(window)?.reply().map_err(Into::into)
     }
 
+    /// The work area of the current desktop, as set by the window manager.
+    pub fn get_work_area(&self) -> Option<AaRect> {
+        let atoms = self.atoms();
+        let root = self.default_root().root;
+        let cardinal = xproto::Atom::from(xproto::AtomEnum::CARDINAL);
+
+        let desktop = self
+            .get_property::<u32>(root, atoms[_NET_CURRENT_DESKTOP], cardinal)
+            .ok()
+            .and_then(|desktop| desktop.first().copied())
+            .unwrap_or(0);
+
+        // There's one `x, y, width, height` rectangle per desktop.
+        let work_areas = self.get_property::<u32>(root, atoms[_NET_WORKAREA], cardinal).ok()?;
+        let area = work_areas.chunks_exact(4).nth(desktop as usize)?;
+        Some(AaRect::new((area[0] as i32, area[1] as i32), (area[2], area[3])))
+    }
+
     fn get_frame_extents(&self, window: xproto::Window) -> Option<FrameExtents> {
         let atoms = self.atoms();
         let extents_atom = atoms[_NET_FRAME_EXTENTS];
@@ -281,3 +334,55 @@ impl XConnection {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn work_area_within_monitor() {
+        let monitor = AaRect::new((1920, 0), (1280, 1024));
+
+        // A work area spanning several monitors, with a panel at the top.
+        let work_area = AaRect::new((0, 32), (3200, 992));
+        let clipped = work_area.intersection(&monitor).unwrap();
+        assert_eq!(clipped.position(), (1920, 32));
+        assert_eq!(clipped.size(), (1280, 992));
+        assert_eq!(clipped.overlap_ratio(&monitor), 1.0);
+
+        assert_eq!(AaRect::new((0, 0), (1920, 1080)).intersection(&monitor), None);
+    }
+
+    #[test]
+    fn overlap_ratio() {
+        let monitor = AaRect::new((0, 0), (1920, 1080));
+
+        assert_eq!(AaRect::new((100, 100), (800, 600)).overlap_ratio(&monitor), 1.0);
+        assert_eq!(AaRect::new((1520, 0), (800, 600)).overlap_ratio(&monitor), 0.5);
+        assert_eq!(AaRect::new((1720, 980), (800, 400)).overlap_ratio(&monitor), 0.0625);
+        assert_eq!(AaRect::new((1920, 0), (800, 600)).overlap_ratio(&monitor), 0.0);
+        // An empty window isn't on any monitor.
+        assert_eq!(AaRect::new((100, 100), (0, 600)).overlap_ratio(&monitor), 0.0);
+    }
+
+    #[test]
+    fn should_switch_monitor() {
+        let left = AaRect::new((0, 0), (1920, 1080));
+        let right = AaRect::new((1920, 0), (2560, 1440));
+
+        // 60% of the window is on the right monitor.
+        let window = AaRect::new((1600, 0), (800, 600));
+        assert!(window.should_switch_monitor(&left, &right, 0.5));
+        assert!(window.should_switch_monitor(&left, &right, 0.6));
+        assert!(!window.should_switch_monitor(&left, &right, 0.75));
+
+        // Moving back to the left monitor requires the same share of the window to be on it.
+        assert!(!window.should_switch_monitor(&right, &left, 0.6));
+        let window = AaRect::new((1440, 0), (800, 600));
+        assert!(window.should_switch_monitor(&right, &left, 0.6));
+
+        // A window that left the current monitor always switches.
+        let window = AaRect::new((2000, 0), (800, 600));
+        assert!(window.should_switch_monitor(&left, &right, 1.0));
+    }
+}
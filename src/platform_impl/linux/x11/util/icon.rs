@@ -1,7 +1,7 @@
 #![allow(clippy::assertions_on_constants)]
 
 use super::*;
-use crate::icon::{Pixel, RgbaIcon, PIXEL_SIZE};
+use crate::icon::{unpremultiply_rgba, Pixel, RgbaIcon, PIXEL_SIZE};
 
 impl Pixel {
     pub fn to_packed_argb(&self) -> Cardinal {
@@ -26,7 +26,12 @@ impl RgbaIcon {
         let mut data = Vec::with_capacity(pixel_count);
         data.push(self.width as Cardinal);
         data.push(self.height as Cardinal);
-        let pixels = self.rgba.as_ptr() as *const Pixel;
+        // `_NET_WM_ICON` uses straight alpha.
+        let mut rgba = self.rgba.clone();
+        if self.premultiplied {
+            unpremultiply_rgba(&mut rgba);
+        }
+        let pixels = rgba.as_ptr() as *const Pixel;
         for pixel_index in 0..pixel_count {
             let pixel = unsafe { &*pixels.add(pixel_index) };
             data.push(pixel.to_packed_argb());
@@ -34,3 +39,35 @@ impl RgbaIcon {
         data
     }
 }
+
+/// Encodes icons for `_NET_WM_ICON`, which holds them back to back so that the window manager and
+/// the taskbar can each pick the size that fits best.
+pub(crate) fn icons_to_cardinals<'a>(
+    icons: impl IntoIterator<Item = &'a RgbaIcon>,
+) -> Vec<Cardinal> {
+    icons.into_iter().flat_map(RgbaIcon::to_cardinals).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn icons_are_concatenated() {
+        let small = RgbaIcon::from_rgba(vec![0; 16 * 16 * PIXEL_SIZE], 16, 16, false).unwrap();
+        let large = RgbaIcon::from_rgba(vec![0; 256 * 256 * PIXEL_SIZE], 256, 256, false).unwrap();
+
+        let data = icons_to_cardinals([&small, &large]);
+        assert_eq!(data.len(), 2 + 16 * 16 + 2 + 256 * 256);
+        assert_eq!(data[..2], [16, 16]);
+        assert_eq!(data[2 + 16 * 16..][..2], [256, 256]);
+
+        assert!(icons_to_cardinals([]).is_empty());
+    }
+
+    #[test]
+    fn premultiplied_icons_use_straight_alpha() {
+        let icon = RgbaIcon::from_rgba(vec![0, 128, 0, 128], 1, 1, true).unwrap();
+        assert_eq!(icon.to_cardinals(), [1, 1, 0x8000ff00]);
+    }
+}
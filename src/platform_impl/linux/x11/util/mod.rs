@@ -25,8 +25,10 @@ use x11rb::protocol::xproto::{self, ConnectionExt as _};
 pub use self::cursor::*;
 pub use self::geometry::*;
 pub use self::hint::*;
+pub(crate) use self::icon::icons_to_cardinals;
 pub use self::input::*;
 pub use self::mouse::*;
+pub use self::randr::OutputInfo;
 pub use self::window_property::*;
 pub use self::wm::*;
 pub use self::xmodmap::ModifierKeymap;
@@ -38,6 +38,17 @@ pub fn calc_dpi_factor(
     }
 }
 
+/// The information about a RandR output used to create a [`MonitorHandle`].
+///
+/// [`MonitorHandle`]: monitor::MonitorHandle
+pub struct OutputInfo {
+    pub name: String,
+    pub scale_factor: f64,
+    pub video_modes: Vec<VideoModeHandle>,
+    /// The physical size in millimeters, if known.
+    pub physical_size: Option<(u32, u32)>,
+}
+
 impl XConnection {
     // Retrieve DPI from Xft.dpi property
     pub fn get_xft_dpi(&self) -> Option<f64> {
@@ -59,7 +70,7 @@ impl XConnection {
         &self,
         resources: &monitor::ScreenResources,
         crtc: &randr::GetCrtcInfoReply,
-    ) -> Option<(String, f64, Vec<VideoModeHandle>)> {
+    ) -> Option<OutputInfo> {
         let output_info = match self
             .xcb_connection()
             .randr_get_output_info(crtc.outputs[0], x11rb::CURRENT_TIME)
@@ -158,7 +169,11 @@ impl XConnection {
             },
         };
 
-        Some((name, scale_factor, modes))
+        // RandR reports a zero size when it's unknown, e.g. for projectors.
+        let physical_size = (output_info.mm_width > 0 && output_info.mm_height > 0)
+            .then_some((output_info.mm_width, output_info.mm_height));
+
+        Some(OutputInfo { name, scale_factor, video_modes: modes, physical_size })
     }
 
     pub fn set_crtc_config(
@@ -4,37 +4,45 @@ use std::num::NonZeroU32;
 use std::ops::Deref;
 use std::os::raw::*;
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Instant;
 use std::{cmp, env};
 
 use tracing::{debug, info, warn};
 use x11rb::connection::{Connection, RequestConnection};
-use x11rb::properties::{WmHints, WmSizeHints, WmSizeHintsSpecification};
+use x11rb::cookie::Cookie;
+use x11rb::properties::{AspectRatio, WmHints, WmSizeHints, WmSizeHintsSpecification};
 use x11rb::protocol::shape::SK;
 use x11rb::protocol::sync::{ConnectionExt as _, Int64};
 use x11rb::protocol::xfixes::{ConnectionExt, RegionWrapper};
 use x11rb::protocol::xproto::{self, ConnectionExt as _, Rectangle};
 use x11rb::protocol::{randr, xinput};
 
+use super::activation::activation_token_timestamp;
 use super::util::{self, SelectedCursor};
 use super::{
-    ffi, ActiveEventLoop, CookieResultExt, ImeRequest, ImeSender, VoidCookie, XConnection,
+    ffi, ActiveEventLoop, CookieResultExt, ImeRequest, ImeSender, VoidCookie, X11rbConnection,
+    XConnection,
 };
 use crate::cursor::{Cursor, CustomCursor as RootCustomCursor};
 use crate::dpi::{PhysicalInsets, PhysicalPosition, PhysicalSize, Position, Size};
 use crate::error::{NotSupportedError, RequestError};
-use crate::event::{Event, SurfaceSizeWriter, WindowEvent};
+use crate::event::{Event, MouseButton, SurfaceSizeWriter, WindowEvent};
 use crate::event_loop::AsyncRequestSerial;
-use crate::platform::x11::WindowType;
+use crate::platform::x11::{AttentionMechanism, WindowType};
+use crate::platform_impl::redraw::RedrawSchedule;
 use crate::platform_impl::x11::atoms::*;
 use crate::platform_impl::x11::{
     xinput_fp1616_to_float, MonitorHandle as X11MonitorHandle, WakeSender, X11Error,
 };
 use crate::platform_impl::{
-    common, Fullscreen, MonitorHandle as PlatformMonitorHandle, PlatformCustomCursor, PlatformIcon,
+    batch, common, Fullscreen, MonitorHandle as PlatformMonitorHandle, PlatformCustomCursor,
+    PlatformIcon,
 };
 use crate::window::{
-    CursorGrabMode, ImePurpose, ResizeDirection, Theme, UserAttentionType, Window as CoreWindow,
+    ActivationToken, Anchor, CursorGrabMode, ImePurpose, PhysicalRect, RedrawPriority,
+    ResizeConstraints, ResizeDirection, Theme, UserAttentionType, Window as CoreWindow,
     WindowAttributes, WindowButtons, WindowId, WindowLevel,
 };
 
@@ -58,6 +66,21 @@ impl Window {
         event_loop.windows.borrow_mut().insert(window.id(), Arc::downgrade(&window));
         Ok(Window(window))
     }
+
+    /// Create several windows, waiting for the X server only once.
+    pub(crate) fn new_batch(
+        event_loop: &ActiveEventLoop,
+        attributes: Vec<WindowAttributes>,
+    ) -> Vec<Result<Self, RequestError>> {
+        UnownedWindow::new_batch(event_loop, attributes)
+            .into_iter()
+            .map(|window| {
+                let window = Arc::new(window?);
+                event_loop.windows.borrow_mut().insert(window.id(), Arc::downgrade(&window));
+                Ok(Window(window))
+            })
+            .collect()
+    }
 }
 
 impl CoreWindow for Window {
@@ -73,6 +96,14 @@ impl CoreWindow for Window {
         self.0.request_redraw()
     }
 
+    fn request_redraw_with_priority(&self, priority: RedrawPriority) {
+        self.0.request_redraw_with_priority(priority)
+    }
+
+    fn request_redraw_at(&self, deadline: Instant) {
+        self.0.request_redraw_at(deadline)
+    }
+
     fn pre_present_notify(&self) {
         self.0.pre_present_notify()
     }
@@ -101,6 +132,14 @@ impl CoreWindow for Window {
         self.0.request_surface_size(size)
     }
 
+    fn request_surface_size_anchored(
+        &self,
+        size: Size,
+        anchor: Anchor,
+    ) -> Option<PhysicalSize<u32>> {
+        self.0.request_surface_size_anchored(size, anchor)
+    }
+
     fn outer_size(&self) -> PhysicalSize<u32> {
         self.0.outer_size()
     }
@@ -117,6 +156,20 @@ impl CoreWindow for Window {
         self.0.set_max_surface_size(max_size)
     }
 
+    fn min_surface_size(&self) -> Option<PhysicalSize<u32>> {
+        self.0.min_surface_size()
+    }
+
+    fn max_surface_size(&self) -> Option<PhysicalSize<u32>> {
+        self.0.max_surface_size()
+    }
+
+    fn set_resize_constraints(&self, constraints: ResizeConstraints) {
+        self.0.set_min_surface_size(constraints.min);
+        self.0.set_max_surface_size(constraints.max);
+        self.0.set_surface_aspect_ratio(constraints.aspect_ratio);
+    }
+
     fn surface_resize_increments(&self) -> Option<PhysicalSize<u32>> {
         self.0.surface_resize_increments()
     }
@@ -129,6 +182,10 @@ impl CoreWindow for Window {
         self.0.set_title(title);
     }
 
+    fn set_title_with_subtitle(&self, title: &str, _subtitle: Option<&str>) {
+        self.0.set_title(title);
+    }
+
     fn set_transparent(&self, transparent: bool) {
         self.0.set_transparent(transparent);
     }
@@ -145,6 +202,10 @@ impl CoreWindow for Window {
         self.0.is_visible()
     }
 
+    fn occlusion(&self) -> Option<f32> {
+        None
+    }
+
     fn set_resizable(&self, resizable: bool) {
         self.0.set_resizable(resizable);
     }
@@ -213,6 +274,8 @@ impl CoreWindow for Window {
         self.0.set_ime_purpose(purpose);
     }
 
+    fn set_ime_theme(&self, _theme: Option<Theme>) {}
+
     fn focus_window(&self) {
         self.0.focus_window();
     }
@@ -233,8 +296,8 @@ impl CoreWindow for Window {
         self.0.theme()
     }
 
-    fn set_content_protected(&self, protected: bool) {
-        self.0.set_content_protected(protected);
+    fn set_content_protected(&self, protected: bool) -> bool {
+        self.0.set_content_protected(protected)
     }
 
     fn title(&self) -> String {
@@ -253,6 +316,10 @@ impl CoreWindow for Window {
         self.0.set_cursor_grab(mode)
     }
 
+    fn set_cursor_grab_restore_on_focus(&self, restore: bool) {
+        self.0.set_cursor_grab_restore_on_focus(restore);
+    }
+
     fn set_cursor_visible(&self, visible: bool) {
         self.0.set_cursor_visible(visible);
     }
@@ -265,6 +332,22 @@ impl CoreWindow for Window {
         self.0.drag_resize_window(direction)
     }
 
+    fn drag_window_with_button(&self, button: MouseButton) -> Result<(), RequestError> {
+        self.0.drag_window_with_button(button)
+    }
+
+    fn drag_resize_window_with_button(
+        &self,
+        direction: ResizeDirection,
+        button: MouseButton,
+    ) -> Result<(), RequestError> {
+        self.0.drag_resize_window_with_button(direction, button)
+    }
+
+    fn resize_window_with_keyboard(&self) -> Result<(), RequestError> {
+        self.0.resize_window_with_keyboard()
+    }
+
     fn show_window_menu(&self, position: Position) {
         self.0.show_window_menu(position);
     }
@@ -273,6 +356,13 @@ impl CoreWindow for Window {
         self.0.set_cursor_hittest(hittest)
     }
 
+    fn set_cursor_hittest_region(
+        &self,
+        regions: Option<Vec<PhysicalRect>>,
+    ) -> Result<(), RequestError> {
+        self.0.set_cursor_hittest_region(regions)
+    }
+
     fn current_monitor(&self) -> Option<crate::monitor::MonitorHandle> {
         self.0
             .current_monitor()
@@ -335,6 +425,9 @@ impl Drop for Window {
         {
             c.ignore_error();
         }
+
+        // The X server may reuse the id for another window.
+        window.scheduled_redraws.cancel(window.id());
     }
 }
 
@@ -363,8 +456,13 @@ pub struct SharedState {
     pub base_size: Option<Size>,
     pub visibility: Visibility,
     pub has_focus: bool,
+    // Fullscreen state last reported by the window manager through `_NET_WM_STATE`.
+    pub wm_fullscreen: bool,
     // Use `Option` to not apply hittest logic when it was never requested.
     pub cursor_hittest: Option<bool>,
+    pub attention_mechanism: AttentionMechanism,
+    pub(crate) window_icon: Option<PlatformIcon>,
+    pub(crate) taskbar_icon: Option<PlatformIcon>,
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -402,7 +500,16 @@ impl SharedState {
             surface_resize_increments: None,
             base_size: None,
             has_focus: false,
+            wm_fullscreen: false,
             cursor_hittest: None,
+            attention_mechanism: AttentionMechanism::default(),
+            window_icon: window_attributes.window_icon.as_ref().map(|icon| icon.inner.clone()),
+            taskbar_icon: window_attributes
+                .platform_specific
+                .x11
+                .taskbar_icon
+                .as_ref()
+                .map(|icon| icon.inner.clone()),
         })
     }
 }
@@ -411,21 +518,24 @@ unsafe impl Send for UnownedWindow {}
 unsafe impl Sync for UnownedWindow {}
 
 pub struct UnownedWindow {
-    pub(crate) xconn: Arc<XConnection>, // never changes
-    xwindow: xproto::Window,            // never changes
-    #[allow(dead_code)]
-    visual: u32, // never changes
-    root: xproto::Window,               // never changes
-    #[allow(dead_code)]
-    screen_id: i32, // never changes
-    sync_counter_id: Option<NonZeroU32>, // never changes
+    pub(crate) xconn: Arc<XConnection>,   // never changes
+    xwindow: xproto::Window,              // never changes
+    visual: u32,                          // never changes
+    root: xproto::Window,                 // never changes
+    screen_id: i32,                       // never changes
+    sync_counter_id: Option<NonZeroU32>,  // never changes
+    scale_factor_hysteresis: Option<f64>, // never changes
     selected_cursor: Mutex<SelectedCursor>,
     cursor_grabbed_mode: Mutex<CursorGrabMode>,
+    /// The grab mode requested by the user, re-applied when the window regains focus.
+    cursor_grab_requested: Mutex<CursorGrabMode>,
+    cursor_grab_restore: AtomicBool,
     #[allow(clippy::mutex_atomic)]
     cursor_visible: Mutex<bool>,
     ime_sender: Mutex<ImeSender>,
     pub shared_state: Mutex<SharedState>,
-    redraw_sender: WakeSender<WindowId>,
+    redraw_sender: WakeSender<(WindowId, RedrawPriority)>,
+    scheduled_redraws: Arc<RedrawSchedule>,
     activation_sender: WakeSender<super::ActivationToken>,
 }
 macro_rules! leap {
@@ -434,14 +544,86 @@ macro_rules! leap {
     };
 }
 
+/// A window whose creation requests were sent, but not checked yet.
+struct PendingWindow<'a> {
+    window: UnownedWindow,
+    /// The requests that must succeed for the window to be usable.
+    checks: Vec<VoidCookie<'a>>,
+    /// The attributes of the window, when its visual is copied from the parent.
+    attributes: Option<Cookie<'a, X11rbConnection, xproto::GetWindowAttributesReply>>,
+}
+
+impl PendingWindow<'_> {
+    /// Check the outcome of the creation requests, once the X server processed them.
+    fn finish(self) -> Result<UnownedWindow, RequestError> {
+        // The window is destroyed when dropped on failure.
+        let PendingWindow { mut window, checks, attributes } = self;
+        for cookie in checks {
+            leap!(cookie.check());
+        }
+
+        // The COPY_FROM_PARENT is a special value for the visual used to copy
+        // the visual from the parent window, thus we have to query the visual
+        // we've got when we built the window.
+        if let Some(attributes) = attributes {
+            window.visual = leap!(attributes.reply()).visual;
+        }
+
+        Ok(window)
+    }
+}
+
 impl UnownedWindow {
-    #[allow(clippy::unnecessary_cast)]
     pub(crate) fn new(
         event_loop: &ActiveEventLoop,
         window_attrs: WindowAttributes,
     ) -> Result<UnownedWindow, RequestError> {
+        Self::new_batch(event_loop, vec![window_attrs]).remove(0)
+    }
+
+    /// Create several windows, sending the requests of all of them before waiting for the X
+    /// server.
+    pub(crate) fn new_batch(
+        event_loop: &ActiveEventLoop,
+        attributes: Vec<WindowAttributes>,
+    ) -> Vec<Result<UnownedWindow, RequestError>> {
+        let xconn = &event_loop.xconn;
+
+        // Attempt to make keyboard input repeat detectable, which applies to the whole display.
+        let mut supported_ptr = ffi::False;
+        unsafe {
+            (xconn.xlib.XkbSetDetectableAutoRepeat)(xconn.display, ffi::True, &mut supported_ptr);
+        }
+        if supported_ptr == ffi::False {
+            return attributes
+                .iter()
+                .map(|_| Err(os_error!("`XkbSetDetectableAutoRepeat` failed").into()))
+                .collect();
+        }
+
+        // The windows are created at once, so the pointer only needs to be queried once per root.
+        let mut pointer_positions = Vec::new();
+        batch::create_batched(
+            attributes,
+            |window_attrs| Self::send_requests(event_loop, window_attrs, &mut pointer_positions),
+            // We never want to give the user a broken window, since by then, it's too late to
+            // handle.
+            |_| xconn.sync_with_server().map_err(|err| os_error!(err).into()),
+            PendingWindow::finish,
+            |err| os_error!(err.to_string()).into(),
+        )
+    }
+
+    /// Send the requests creating a window, without waiting for the X server.
+    #[allow(clippy::unnecessary_cast)]
+    fn send_requests<'a>(
+        event_loop: &'a ActiveEventLoop,
+        window_attrs: WindowAttributes,
+        pointer_positions: &mut Vec<(xproto::Window, Option<(i64, i64)>)>,
+    ) -> Result<PendingWindow<'a>, RequestError> {
         let xconn = &event_loop.xconn;
         let atoms = xconn.atoms();
+        let mut checks = Vec::new();
 
         let screen_id = match window_attrs.platform_specific.x11.screen_id {
             Some(id) => id,
@@ -467,12 +649,20 @@ impl UnownedWindow {
         let guessed_monitor = if monitors.is_empty() {
             X11MonitorHandle::dummy()
         } else {
-            xconn
-                .query_pointer(root, util::VIRTUAL_CORE_POINTER)
-                .ok()
-                .and_then(|pointer_state| {
-                    let (x, y) = (pointer_state.root_x as i64, pointer_state.root_y as i64);
-
+            let pointer_position =
+                match pointer_positions.iter().find(|&&(window, _)| window == root) {
+                    Some(&(_, position)) => position,
+                    None => {
+                        let position = xconn
+                            .query_pointer(root, util::VIRTUAL_CORE_POINTER)
+                            .ok()
+                            .map(|state| (state.root_x as i64, state.root_y as i64));
+                        pointer_positions.push((root, position));
+                        position
+                    },
+                };
+            pointer_position
+                .and_then(|(x, y)| {
                     for i in 0..monitors.len() {
                         if monitors[i].rect.contains_point(x, y) {
                             return Some(monitors.swap_remove(i));
@@ -495,11 +685,13 @@ impl UnownedWindow {
         let position =
             window_attrs.position.map(|position| position.to_physical::<i32>(scale_factor));
 
+        let monitor_size =
+            (!guessed_monitor.is_dummy()).then(|| guessed_monitor.rect.size().into());
         let dimensions = {
             // x11 only applies constraints when the window is actively resized
             // by the user, so we have to manually apply the initial constraints
             let mut dimensions: (u32, u32) = window_attrs
-                .surface_size
+                .initial_surface_size(monitor_size)
                 .map(|size| size.to_physical::<u32>(scale_factor))
                 .or_else(|| Some((800, 600).into()))
                 .map(Into::into)
@@ -550,7 +742,7 @@ impl UnownedWindow {
                 },
                 _ => (None, x11rb::COPY_FROM_PARENT as _, false),
             };
-        let mut visual = visualtype.map_or(x11rb::COPY_FROM_PARENT, |v| v.visual_id);
+        let visual = visualtype.map_or(x11rb::COPY_FROM_PARENT, |v| v.visual_id);
 
         let window_attributes = {
             use xproto::EventMask;
@@ -616,21 +808,16 @@ impl UnownedWindow {
                 visual,
                 &window_attributes,
             );
-            leap!(leap!(result).check());
+            checks.push(leap!(result));
 
             wid
         };
 
-        // The COPY_FROM_PARENT is a special value for the visual used to copy
-        // the visual from the parent window, thus we have to query the visual
-        // we've got when we built the window above.
-        if visual == x11rb::COPY_FROM_PARENT {
-            visual = leap!(leap!(xconn
-                .xcb_connection()
-                .get_window_attributes(xwindow as xproto::Window))
-            .reply())
-            .visual;
-        }
+        let attributes = if visual == x11rb::COPY_FROM_PARENT {
+            Some(leap!(xconn.xcb_connection().get_window_attributes(xwindow as xproto::Window)))
+        } else {
+            None
+        };
 
         #[allow(clippy::mutex_atomic)]
         let mut window = UnownedWindow {
@@ -640,12 +827,16 @@ impl UnownedWindow {
             root,
             screen_id,
             sync_counter_id: None,
+            scale_factor_hysteresis: window_attrs.platform_specific.x11.scale_factor_hysteresis,
             selected_cursor: Default::default(),
             cursor_grabbed_mode: Mutex::new(CursorGrabMode::None),
+            cursor_grab_requested: Mutex::new(CursorGrabMode::None),
+            cursor_grab_restore: AtomicBool::new(true),
             cursor_visible: Mutex::new(true),
             ime_sender: Mutex::new(event_loop.ime_sender.clone()),
             shared_state: SharedState::new(guessed_monitor, &window_attrs),
             redraw_sender: event_loop.redraw_sender.clone(),
+            scheduled_redraws: Arc::clone(&event_loop.scheduled_redraws),
             activation_sender: event_loop.activation_sender.clone(),
         };
 
@@ -661,7 +852,13 @@ impl UnownedWindow {
 
         // Embed the window if needed.
         if window_attrs.platform_specific.x11.embed_window.is_some() {
-            window.embed_window()?;
+            checks.push(leap!(xconn.change_property(
+                window.xwindow,
+                atoms[_XEMBED],
+                atoms[_XEMBED],
+                xproto::PropMode::REPLACE,
+                &[0u32, 1u32],
+            )));
         }
 
         {
@@ -697,15 +894,7 @@ impl UnownedWindow {
                     (instance, class)
                 };
 
-                let class = format!("{instance}\0{class}\0");
-                leap!(xconn.change_property(
-                    window.xwindow,
-                    xproto::Atom::from(xproto::AtomEnum::WM_CLASS),
-                    xproto::Atom::from(xproto::AtomEnum::STRING),
-                    xproto::PropMode::REPLACE,
-                    class.as_bytes(),
-                ))
-                .ignore_error();
+                leap!(window.set_wm_class_inner(&class, &instance)).ignore_error();
             }
 
             if let Some(flusher) = leap!(window.set_pid()) {
@@ -715,6 +904,10 @@ impl UnownedWindow {
             leap!(window.set_window_types(window_attrs.platform_specific.x11.x11_window_types))
                 .ignore_error();
 
+            if let Some(owner) = window_attrs.owner {
+                leap!(window.set_transient_for(owner.into_raw() as xproto::Window)).ignore_error();
+            }
+
             // Set size hints.
             let mut min_surface_size =
                 window_attrs.min_surface_size.map(|size| size.to_physical::<u32>(scale_factor));
@@ -755,19 +948,21 @@ impl UnownedWindow {
                     .x11
                     .base_size
                     .map(|size| cast_size_to_hint(size, scale_factor)),
-                aspect: None,
+                aspect: window_attrs.surface_aspect_ratio.and_then(cast_aspect_ratio_to_hint),
                 win_gravity: None,
             };
-            leap!(leap!(normal_hints.set(
+            checks.push(leap!(normal_hints.set(
                 xconn.xcb_connection(),
                 window.xwindow as xproto::Window,
                 xproto::AtomEnum::WM_NORMAL_HINTS,
-            ))
-            .check());
+            )));
 
             // Set window icons
-            if let Some(icon) = window_attrs.window_icon {
-                leap!(window.set_icon_inner(icon.inner)).ignore_error();
+            {
+                let shared_state = window.shared_state_lock();
+                if shared_state.window_icon.is_some() || shared_state.taskbar_icon.is_some() {
+                    leap!(window.set_icons_inner(&shared_state)).ignore_error();
+                }
             }
 
             // Opt into handling window close and resize synchronization
@@ -832,19 +1027,6 @@ impl UnownedWindow {
                 .ignore_error();
             }
 
-            // Attempt to make keyboard input repeat detectable
-            unsafe {
-                let mut supported_ptr = ffi::False;
-                (xconn.xlib.XkbSetDetectableAutoRepeat)(
-                    xconn.display,
-                    ffi::True,
-                    &mut supported_ptr,
-                );
-                if supported_ptr == ffi::False {
-                    return Err(os_error!("`XkbSetDetectableAutoRepeat` failed").into());
-                }
-            }
-
             // Try to create input context for the window.
             if let Some(ime) = event_loop.ime.as_ref() {
                 ime.borrow_mut()
@@ -882,25 +1064,7 @@ impl UnownedWindow {
             leap!(xconn.remove_activation_token(xwindow, &startup.token));
         }
 
-        // We never want to give the user a broken window, since by then, it's too late to handle.
-        let window = leap!(xconn.sync_with_server().map(|_| window));
-
-        Ok(window)
-    }
-
-    /// Embed this window into a parent window.
-    pub(super) fn embed_window(&self) -> Result<(), RequestError> {
-        let atoms = self.xconn.atoms();
-        leap!(leap!(self.xconn.change_property(
-            self.xwindow,
-            atoms[_XEMBED],
-            atoms[_XEMBED],
-            xproto::PropMode::REPLACE,
-            &[0u32, 1u32],
-        ))
-        .check());
-
-        Ok(())
+        Ok(PendingWindow { window, checks, attributes })
     }
 
     pub(super) fn shared_state_lock(&self) -> MutexGuard<'_, SharedState> {
@@ -949,6 +1113,16 @@ impl UnownedWindow {
         )
     }
 
+    fn set_transient_for(&self, owner: xproto::Window) -> Result<VoidCookie<'_>, X11Error> {
+        self.xconn.change_property(
+            self.xwindow,
+            xproto::Atom::from(xproto::AtomEnum::WM_TRANSIENT_FOR),
+            xproto::Atom::from(xproto::AtomEnum::WINDOW),
+            xproto::PropMode::REPLACE,
+            &[owner],
+        )
+    }
+
     pub fn set_theme_inner(&self, theme: Option<Theme>) -> Result<VoidCookie<'_>, X11Error> {
         let atoms = self.xconn.atoms();
         let hint_atom = atoms[_GTK_THEME_VARIANT];
@@ -1077,7 +1251,7 @@ impl UnownedWindow {
                     Fullscreen::Borderless(None) => {
                         (None, self.shared_state_lock().last_monitor.clone())
                     },
-                    #[cfg(wayland_platform)]
+                    #[cfg(any(wayland_platform, null_platform))]
                     _ => unreachable!(),
                 };
 
@@ -1308,6 +1482,38 @@ impl UnownedWindow {
         }
     }
 
+    /// Whether the window should adopt the scale factor of `monitor`, the one it overlaps the most,
+    /// instead of staying on `current`.
+    pub(crate) fn should_switch_monitor(
+        &self,
+        window_rect: &util::AaRect,
+        current: &X11MonitorHandle,
+        monitor: &X11MonitorHandle,
+    ) -> bool {
+        let threshold = match self.scale_factor_hysteresis {
+            Some(threshold) => threshold,
+            None => return true,
+        };
+
+        current.is_dummy()
+            || window_rect.should_switch_monitor(&current.rect, &monitor.rect, threshold)
+    }
+
+    /// Whether the window manager lists the window as fullscreen in `_NET_WM_STATE`.
+    pub(crate) fn is_wm_fullscreen(&self) -> bool {
+        let atoms = self.xconn.atoms();
+        let state = self.xconn.get_property(
+            self.xwindow,
+            atoms[_NET_WM_STATE],
+            xproto::Atom::from(xproto::AtomEnum::ATOM),
+        );
+        let fullscreen_atom = atoms[_NET_WM_STATE_FULLSCREEN];
+        match state {
+            Ok(atoms) => atoms.contains(&fullscreen_atom),
+            _ => false,
+        }
+    }
+
     fn set_maximized_inner(&self, maximized: bool) -> Result<VoidCookie<'_>, X11Error> {
         let atoms = self.xconn.atoms();
         let horz_atom = atoms[_NET_WM_STATE_MAXIMIZED_HORZ];
@@ -1353,6 +1559,29 @@ impl UnownedWindow {
         self.xconn.flush_requests().expect("Failed to set window title");
     }
 
+    fn set_wm_class_inner(
+        &self,
+        general: &str,
+        instance: &str,
+    ) -> Result<VoidCookie<'_>, X11Error> {
+        let class = format!("{instance}\0{general}\0");
+        self.xconn.change_property(
+            self.xwindow,
+            xproto::Atom::from(xproto::AtomEnum::WM_CLASS),
+            xproto::Atom::from(xproto::AtomEnum::STRING),
+            xproto::PropMode::REPLACE,
+            class.as_bytes(),
+        )
+    }
+
+    #[inline]
+    pub fn set_wm_class(&self, general: &str, instance: &str) {
+        self.set_wm_class_inner(general, instance)
+            .expect_then_ignore_error("Failed to set window class");
+
+        self.xconn.flush_requests().expect("Failed to set window class");
+    }
+
     #[inline]
     pub fn set_transparent(&self, _transparent: bool) {}
 
@@ -1407,10 +1636,12 @@ impl UnownedWindow {
         self.xconn.flush_requests().expect("Failed to set window-level state");
     }
 
-    fn set_icon_inner(&self, icon: PlatformIcon) -> Result<VoidCookie<'_>, X11Error> {
+    /// Write both the window and taskbar icons to `_NET_WM_ICON`, removing it if there are none.
+    fn set_icons_inner(&self, shared_state: &SharedState) -> Result<VoidCookie<'_>, X11Error> {
         let atoms = self.xconn.atoms();
         let icon_atom = atoms[_NET_WM_ICON];
-        let data = icon.to_cardinals();
+        let icons = [&shared_state.window_icon, &shared_state.taskbar_icon];
+        let data = util::icons_to_cardinals(icons.into_iter().flatten());
         self.xconn.change_property(
             self.xwindow,
             icon_atom,
@@ -1420,26 +1651,22 @@ impl UnownedWindow {
         )
     }
 
-    fn unset_icon_inner(&self) -> Result<VoidCookie<'_>, X11Error> {
-        let atoms = self.xconn.atoms();
-        let icon_atom = atoms[_NET_WM_ICON];
-        let empty_data: [util::Cardinal; 0] = [];
-        self.xconn.change_property(
-            self.xwindow,
-            icon_atom,
-            xproto::Atom::from(xproto::AtomEnum::CARDINAL),
-            xproto::PropMode::REPLACE,
-            &empty_data,
-        )
+    #[inline]
+    pub(crate) fn set_window_icon(&self, icon: Option<PlatformIcon>) {
+        let mut shared_state = self.shared_state_lock();
+        shared_state.window_icon = icon;
+        self.set_icons_inner(&shared_state).expect_then_ignore_error("Failed to set icons");
+        drop(shared_state);
+
+        self.xconn.flush_requests().expect("Failed to set icons");
     }
 
     #[inline]
-    pub(crate) fn set_window_icon(&self, icon: Option<PlatformIcon>) {
-        match icon {
-            Some(icon) => self.set_icon_inner(icon),
-            None => self.unset_icon_inner(),
-        }
-        .expect_then_ignore_error("Failed to set icons");
+    pub(crate) fn set_taskbar_icon(&self, icon: Option<PlatformIcon>) {
+        let mut shared_state = self.shared_state_lock();
+        shared_state.taskbar_icon = icon;
+        self.set_icons_inner(&shared_state).expect_then_ignore_error("Failed to set icons");
+        drop(shared_state);
 
         self.xconn.flush_requests().expect("Failed to set icons");
     }
@@ -1536,24 +1763,28 @@ impl UnownedWindow {
         }
     }
 
-    pub(crate) fn set_position_inner(
-        &self,
-        mut x: i32,
-        mut y: i32,
-    ) -> Result<VoidCookie<'_>, X11Error> {
+    /// The position to configure the window at for the outer position `(x, y)`.
+    fn configure_position(&self, x: i32, y: i32) -> (i32, i32) {
         // There are a few WMs that set client area position rather than window position, so
         // we'll translate for consistency.
         if util::wm_name_is_one_of(&["Enlightenment", "FVWM"]) {
             let extents = self.shared_state_lock().frame_extents.clone();
             if let Some(extents) = extents {
-                x += cast_dimension_to_hint(extents.frame_extents.left);
-                y += cast_dimension_to_hint(extents.frame_extents.top);
+                return (
+                    x + cast_dimension_to_hint(extents.frame_extents.left),
+                    y + cast_dimension_to_hint(extents.frame_extents.top),
+                );
             } else {
                 self.update_cached_frame_extents();
-                return self.set_position_inner(x, y);
+                return self.configure_position(x, y);
             }
         }
 
+        (x, y)
+    }
+
+    pub(crate) fn set_position_inner(&self, x: i32, y: i32) -> Result<VoidCookie<'_>, X11Error> {
+        let (x, y) = self.configure_position(x, y);
         self.xconn
             .xcb_connection()
             .configure_window(self.xwindow, &xproto::ConfigureWindowAux::new().x(x).y(y))
@@ -1601,12 +1832,13 @@ impl UnownedWindow {
     }
 
     pub(crate) fn request_surface_size_physical(&self, width: u32, height: u32) {
+        self.configure_surface_size(xproto::ConfigureWindowAux::new().width(width).height(height));
+    }
+
+    fn configure_surface_size(&self, aux: xproto::ConfigureWindowAux) {
         self.xconn
             .xcb_connection()
-            .configure_window(
-                self.xwindow,
-                &xproto::ConfigureWindowAux::new().width(width).height(height),
-            )
+            .configure_window(self.xwindow, &aux)
             .expect_then_ignore_error("Failed to call `xcb_configure_window`");
         self.xconn.flush_requests().expect("Failed to call XResizeWindow");
         // cursor_hittest needs to be reapplied after each window resize.
@@ -1619,6 +1851,40 @@ impl UnownedWindow {
     pub fn request_surface_size(&self, size: Size) -> Option<PhysicalSize<u32>> {
         let scale_factor = self.scale_factor();
         let size = size.to_physical::<u32>(scale_factor).into();
+        self.update_fixed_size_hints(size);
+        self.request_surface_size_physical(size.0 as u32, size.1 as u32);
+
+        None
+    }
+
+    #[inline]
+    pub fn request_surface_size_anchored(
+        &self,
+        size: Size,
+        anchor: Anchor,
+    ) -> Option<PhysicalSize<u32>> {
+        let Ok(position) = self.outer_position() else {
+            return self.request_surface_size(size);
+        };
+
+        let new_size = size.to_physical::<u32>(self.scale_factor());
+        let (dx, dy) = anchor.offset(self.surface_size(), new_size);
+        let (x, y) = self.configure_position(position.x - dx, position.y - dy);
+        self.update_fixed_size_hints(new_size.into());
+        // Move and resize in a single request, so that they are applied together.
+        self.configure_surface_size(
+            xproto::ConfigureWindowAux::new()
+                .x(x)
+                .y(y)
+                .width(new_size.width)
+                .height(new_size.height),
+        );
+
+        None
+    }
+
+    /// Pins the size hints to the requested size while the window isn't resizable.
+    fn update_fixed_size_hints(&self, size: (i32, i32)) {
         if !self.shared_state_lock().is_resizable {
             self.update_normal_hints(|normal_hints| {
                 normal_hints.min_size = Some(size);
@@ -1626,9 +1892,6 @@ impl UnownedWindow {
             })
             .expect("Failed to call `XSetWMNormalHints`");
         }
-        self.request_surface_size_physical(size.0 as u32, size.1 as u32);
-
-        None
     }
 
     fn update_normal_hints<F>(&self, callback: F) -> Result<(), X11Error>
@@ -1685,6 +1948,26 @@ impl UnownedWindow {
         self.set_max_surface_size_physical(physical_dimensions);
     }
 
+    #[inline]
+    pub fn min_surface_size(&self) -> Option<PhysicalSize<u32>> {
+        let scale_factor = self.scale_factor();
+        self.shared_state_lock().min_surface_size.map(|size| size.to_physical(scale_factor))
+    }
+
+    #[inline]
+    pub fn max_surface_size(&self) -> Option<PhysicalSize<u32>> {
+        let scale_factor = self.scale_factor();
+        self.shared_state_lock().max_surface_size.map(|size| size.to_physical(scale_factor))
+    }
+
+    #[inline]
+    pub fn set_surface_aspect_ratio(&self, aspect_ratio: Option<(u32, u32)>) {
+        self.update_normal_hints(|normal_hints| {
+            normal_hints.aspect = aspect_ratio.and_then(cast_aspect_ratio_to_hint)
+        })
+        .expect("Failed to call `XSetWMNormalHints`");
+    }
+
     #[inline]
     pub fn surface_resize_increments(&self) -> Option<PhysicalSize<u32>> {
         WmSizeHints::get(
@@ -1795,6 +2078,16 @@ impl UnownedWindow {
         self.xwindow as ffi::Window
     }
 
+    #[inline]
+    pub fn visual_id(&self) -> u32 {
+        self.visual
+    }
+
+    #[inline]
+    pub fn screen_id(&self) -> i32 {
+        self.screen_id
+    }
+
     #[inline]
     pub fn set_cursor(&self, cursor: Cursor) {
         match cursor {
@@ -1836,6 +2129,46 @@ impl UnownedWindow {
             return Err(NotSupportedError::new("locked cursor is not implemented on X11").into());
         }
 
+        let mut requested_lock = self.cursor_grab_requested.lock().unwrap();
+        let result = self.apply_cursor_grab(mode);
+        if result.is_ok() {
+            *requested_lock = mode;
+        }
+
+        result
+    }
+
+    #[inline]
+    pub fn set_cursor_grab_restore_on_focus(&self, restore: bool) {
+        self.cursor_grab_restore.store(restore, Ordering::Relaxed);
+    }
+
+    /// Release the pointer grab when the window loses focus, so that it doesn't keep the pointer
+    /// confined while another window is active, and re-apply it once focus comes back.
+    pub(crate) fn cursor_grab_focus_changed(&self, focused: bool) {
+        let mut requested_lock = self.cursor_grab_requested.lock().unwrap();
+        if *requested_lock == CursorGrabMode::None {
+            return;
+        }
+
+        if focused {
+            if self.cursor_grab_restore.load(Ordering::Relaxed) {
+                if let Err(err) = self.apply_cursor_grab(*requested_lock) {
+                    warn!("Failed to restore the cursor grab: {err}");
+                }
+            }
+        } else {
+            if let Err(err) = self.apply_cursor_grab(CursorGrabMode::None) {
+                warn!("Failed to release the cursor grab: {err}");
+            }
+
+            if !self.cursor_grab_restore.load(Ordering::Relaxed) {
+                *requested_lock = CursorGrabMode::None;
+            }
+        }
+    }
+
+    fn apply_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), RequestError> {
         let mut grabbed_lock = self.cursor_grabbed_mode.lock().unwrap();
         if mode == *grabbed_lock {
             return Ok(());
@@ -1969,19 +2302,52 @@ impl UnownedWindow {
                 height: size.height as u16,
             })
         }
-        let region = RegionWrapper::create_region(self.xconn.xcb_connection(), &rectangles)
+        self.set_input_shape(&rectangles)?;
+        self.shared_state_lock().cursor_hittest = Some(hittest);
+        Ok(())
+    }
+
+    pub fn set_cursor_hittest_region(
+        &self,
+        regions: Option<Vec<PhysicalRect>>,
+    ) -> Result<(), RequestError> {
+        let Some(regions) = regions else {
+            return self.set_cursor_hittest(true);
+        };
+
+        let rectangles: Vec<Rectangle> = regions
+            .iter()
+            .map(|rect| Rectangle {
+                x: rect.origin.x.clamp(i16::MIN.into(), i16::MAX.into()) as i16,
+                y: rect.origin.y.clamp(i16::MIN.into(), i16::MAX.into()) as i16,
+                width: rect.size.width.min(u16::MAX.into()) as u16,
+                height: rect.size.height.min(u16::MAX.into()) as u16,
+            })
+            .collect();
+        self.set_input_shape(&rectangles)?;
+        // The regions don't follow the surface size, so there's nothing to reapply on resize.
+        self.shared_state_lock().cursor_hittest = None;
+        Ok(())
+    }
+
+    fn set_input_shape(&self, rectangles: &[Rectangle]) -> Result<(), RequestError> {
+        let region = RegionWrapper::create_region(self.xconn.xcb_connection(), rectangles)
             .map_err(|_e| RequestError::Ignored)?;
         self.xconn
             .xcb_connection()
             .xfixes_set_window_shape_region(self.xwindow, SK::INPUT, 0, 0, region.region())
             .map_err(|_e| RequestError::Ignored)?;
-        self.shared_state_lock().cursor_hittest = Some(hittest);
         Ok(())
     }
 
     /// Moves the window while it is being dragged.
     pub fn drag_window(&self) -> Result<(), RequestError> {
-        self.drag_initiate(util::MOVERESIZE_MOVE)
+        self.drag_window_with_button(MouseButton::Left)
+    }
+
+    /// Moves the window while it is being dragged with the given button.
+    pub fn drag_window_with_button(&self, button: MouseButton) -> Result<(), RequestError> {
+        self.drag_initiate(util::MOVERESIZE_MOVE, Some(button))
     }
 
     #[inline]
@@ -1989,7 +2355,16 @@ impl UnownedWindow {
 
     /// Resizes the window while it is being dragged.
     pub fn drag_resize_window(&self, direction: ResizeDirection) -> Result<(), RequestError> {
-        self.drag_initiate(match direction {
+        self.drag_resize_window_with_button(direction, MouseButton::Left)
+    }
+
+    /// Resizes the window while it is being dragged with the given button.
+    pub fn drag_resize_window_with_button(
+        &self,
+        direction: ResizeDirection,
+        button: MouseButton,
+    ) -> Result<(), RequestError> {
+        let action = match direction {
             ResizeDirection::East => util::MOVERESIZE_RIGHT,
             ResizeDirection::North => util::MOVERESIZE_TOP,
             ResizeDirection::NorthEast => util::MOVERESIZE_TOPRIGHT,
@@ -1998,11 +2373,33 @@ impl UnownedWindow {
             ResizeDirection::SouthEast => util::MOVERESIZE_BOTTOMRIGHT,
             ResizeDirection::SouthWest => util::MOVERESIZE_BOTTOMLEFT,
             ResizeDirection::West => util::MOVERESIZE_LEFT,
-        })
+        };
+        self.drag_initiate(action, Some(button))
+    }
+
+    /// Resizes the window with the keyboard.
+    pub fn resize_window_with_keyboard(&self) -> Result<(), RequestError> {
+        self.drag_initiate(util::MOVERESIZE_SIZE_KEYBOARD, None)
     }
 
-    /// Initiates a drag operation while the left mouse button is pressed.
-    fn drag_initiate(&self, action: isize) -> Result<(), RequestError> {
+    /// Initiates a drag operation while the given mouse button is pressed, or with the keyboard
+    /// without a button.
+    fn drag_initiate(
+        &self,
+        action: isize,
+        button: Option<MouseButton>,
+    ) -> Result<(), RequestError> {
+        // The inverse of the button mapping in the event processor.
+        let button = match button {
+            None => 0,
+            Some(MouseButton::Left) => 1,
+            Some(MouseButton::Middle) => 2,
+            Some(MouseButton::Right) => 3,
+            Some(MouseButton::Back) => 8,
+            Some(MouseButton::Forward) => 9,
+            Some(MouseButton::Other(button)) => button as u32,
+        };
+
         let pointer = self
             .xconn
             .query_pointer(self.xwindow, util::VIRTUAL_CORE_POINTER)
@@ -2038,7 +2435,7 @@ impl UnownedWindow {
                     (window_position.0 + xinput_fp1616_to_float(pointer.win_x) as i32) as u32,
                     (window_position.1 + xinput_fp1616_to_float(pointer.win_y) as i32) as u32,
                     action.try_into().unwrap(),
-                    1, // Button 1
+                    button,
                     1,
                 ],
             )
@@ -2080,6 +2477,12 @@ impl UnownedWindow {
 
     #[inline]
     pub fn focus_window(&self) {
+        self.activate(x11rb::CURRENT_TIME);
+    }
+
+    /// Ask the window manager to activate the window, with the timestamp of the user interaction
+    /// that led to it.
+    fn activate(&self, timestamp: xproto::Timestamp) {
         let atoms = self.xconn.atoms();
         let state_atom = atoms[WM_STATE];
         let state_type_atom = atoms[CARD32];
@@ -2105,7 +2508,7 @@ impl UnownedWindow {
                         xproto::EventMask::SUBSTRUCTURE_REDIRECT
                             | xproto::EventMask::SUBSTRUCTURE_NOTIFY,
                     ),
-                    [1, x11rb::CURRENT_TIME, 0, 0, 0],
+                    [1, timestamp, 0, 0, 0],
                 )
                 .expect_then_ignore_error("Failed to send client message");
             if let Err(e) = self.xconn.flush_requests() {
@@ -2119,17 +2522,32 @@ impl UnownedWindow {
 
     #[inline]
     pub fn request_user_attention(&self, request_type: Option<UserAttentionType>) {
-        let mut wm_hints =
-            WmHints::get(self.xconn.xcb_connection(), self.xwindow as xproto::Window)
-                .ok()
-                .and_then(|cookie| cookie.reply().ok())
-                .flatten()
-                .unwrap_or_default();
+        let mechanism = self.shared_state_lock().attention_mechanism;
+
+        if mechanism.urgency_hint {
+            let mut wm_hints =
+                WmHints::get(self.xconn.xcb_connection(), self.xwindow as xproto::Window)
+                    .ok()
+                    .and_then(|cookie| cookie.reply().ok())
+                    .flatten()
+                    .unwrap_or_default();
+
+            wm_hints.urgent = request_type.is_some();
+            wm_hints
+                .set(self.xconn.xcb_connection(), self.xwindow as xproto::Window)
+                .expect_then_ignore_error("Failed to set WM hints");
+        }
+
+        if mechanism.net_wm_state {
+            self.toggle_atom(_NET_WM_STATE_DEMANDS_ATTENTION, request_type.is_some())
+                .expect_then_ignore_error("Failed to set demands attention state");
+            self.xconn.flush_requests().expect("Failed to set demands attention state");
+        }
+    }
 
-        wm_hints.urgent = request_type.is_some();
-        wm_hints
-            .set(self.xconn.xcb_connection(), self.xwindow as xproto::Window)
-            .expect_then_ignore_error("Failed to set WM hints");
+    #[inline]
+    pub fn set_attention_mechanism(&self, mechanism: AttentionMechanism) {
+        self.shared_state_lock().attention_mechanism = mechanism;
     }
 
     #[inline]
@@ -2158,6 +2576,17 @@ impl UnownedWindow {
         Ok(serial)
     }
 
+    #[inline]
+    pub fn focus_window_with_token(&self, token: ActivationToken) -> Result<(), RequestError> {
+        // Complete the startup sequence of the token on this window, then activate it with the
+        // time of the user interaction the token was created for.
+        self.xconn
+            .remove_activation_token(self.xwindow, &token.token)
+            .map_err(|err| os_error!(err))?;
+        self.activate(activation_token_timestamp(&token.token).unwrap_or(x11rb::CURRENT_TIME));
+        Ok(())
+    }
+
     #[inline]
     pub fn id(&self) -> WindowId {
         WindowId::from_raw(self.xwindow as _)
@@ -2169,7 +2598,21 @@ impl UnownedWindow {
 
     #[inline]
     pub fn request_redraw(&self) {
-        self.redraw_sender.send(WindowId::from_raw(self.xwindow as _));
+        self.request_redraw_with_priority(RedrawPriority::Normal);
+    }
+
+    #[inline]
+    pub fn request_redraw_with_priority(&self, priority: RedrawPriority) {
+        self.redraw_sender.send((WindowId::from_raw(self.xwindow as _), priority));
+    }
+
+    #[inline]
+    pub fn request_redraw_at(&self, deadline: Instant) {
+        let window_id = WindowId::from_raw(self.xwindow as _);
+        if self.scheduled_redraws.schedule(window_id, deadline) {
+            // Wake up the event loop so that it waits for the new deadline.
+            self.redraw_sender.waker.ping();
+        }
     }
 
     #[inline]
@@ -2203,7 +2646,9 @@ impl UnownedWindow {
         None
     }
 
-    pub fn set_content_protected(&self, _protected: bool) {}
+    pub fn set_content_protected(&self, _protected: bool) -> bool {
+        false
+    }
 
     #[inline]
     pub fn has_focus(&self) -> bool {
@@ -2233,3 +2678,13 @@ fn cast_size_to_hint(size: Size, scale_factor: f64) -> (i32, i32) {
         Size::Logical(size) => size.to_physical::<i32>(scale_factor).into(),
     }
 }
+
+/// Use the above strategy to cast an aspect ratio into the same minimum and maximum hinted aspect.
+fn cast_aspect_ratio_to_hint((width, height): (u32, u32)) -> Option<(AspectRatio, AspectRatio)> {
+    if width == 0 || height == 0 {
+        return None;
+    }
+
+    let aspect = AspectRatio::new(cast_dimension_to_hint(width), cast_dimension_to_hint(height));
+    Some((aspect, aspect))
+}
@@ -6,15 +6,19 @@
 
 use std::iter;
 use std::num::NonZeroUsize;
+use std::time::Duration;
 
 use x11rb::protocol::xproto::{self, ConnectionExt};
 
 use super::atoms::*;
 use super::XConnection;
+use crate::platform::x11::XSettingValue;
 
 type Result<T> = core::result::Result<T, ParserError>;
 
 const DPI_NAME: &[u8] = b"Xft/DPI";
+const DOUBLE_CLICK_TIME_NAME: &[u8] = b"Net/DoubleClickTime";
+const COLOR_SCHEME_NAME: &[u8] = b"Gtk/ColorScheme";
 const DPI_MULTIPLIER: f64 = 1024.0;
 const LITTLE_ENDIAN: u8 = b'l';
 const BIG_ENDIAN: u8 = b'B';
@@ -25,37 +29,138 @@ impl XConnection {
         &self,
         xsettings_screen: xproto::Atom,
     ) -> core::result::Result<Option<f64>, super::X11Error> {
+        let data = self.xsettings_data(xsettings_screen)?;
+        let dpi = find_integer(&data, DPI_NAME)?;
+        Ok(dpi.map(|dpi| dpi as f64 / DPI_MULTIPLIER))
+    }
+
+    /// Get the double-click interval from XSettings.
+    pub(crate) fn xsettings_double_click_time(
+        &self,
+        xsettings_screen: xproto::Atom,
+    ) -> core::result::Result<Option<Duration>, super::X11Error> {
+        let data = self.xsettings_data(xsettings_screen)?;
+        let time = find_integer(&data, DOUBLE_CLICK_TIME_NAME)?;
+        Ok(time.map(|time| Duration::from_millis(time.max(0) as u64)))
+    }
+
+    /// Get the accent color from XSettings.
+    pub(crate) fn xsettings_accent_color(&self) -> Option<[u8; 4]> {
+        let xsettings_screen = self.xsettings_screen()?;
+        let data = match self.xsettings_data(xsettings_screen) {
+            Ok(data) => data,
+            Err(err) => {
+                tracing::warn!("failed to fetch XSettings: {err}");
+                return None;
+            },
+        };
+        accent_color(&data).unwrap_or_else(|err| {
+            tracing::warn!("failed to parse XSettings: {err:?}");
+            None
+        })
+    }
+
+    /// Get the setting with the given name from XSettings.
+    pub(crate) fn xsettings(&self, name: &str) -> Option<XSettingValue> {
+        let xsettings_screen = self.xsettings_screen()?;
+        let data = match self.xsettings_data(xsettings_screen) {
+            Ok(data) => data,
+            Err(err) => {
+                tracing::warn!("failed to fetch XSettings: {err}");
+                return None;
+            },
+        };
+        find_value(&data, name.as_bytes()).unwrap_or_else(|err| {
+            tracing::warn!("failed to parse XSettings: {err:?}");
+            None
+        })
+    }
+
+    fn xsettings_data(
+        &self,
+        xsettings_screen: xproto::Atom,
+    ) -> core::result::Result<Vec<u8>, super::X11Error> {
         let atoms = self.atoms();
 
         // Get the current owner of the screen's settings.
         let owner = self.xcb_connection().get_selection_owner(xsettings_screen)?.reply()?;
 
         // Read the _XSETTINGS_SETTINGS property.
-        let data: Vec<u8> =
-            self.get_property(owner.owner, atoms[_XSETTINGS_SETTINGS], atoms[_XSETTINGS_SETTINGS])?;
-
-        // Parse the property.
-        let dpi_setting = read_settings(&data)?
-            .find(|res| res.as_ref().map_or(true, |s| s.name == DPI_NAME))
-            .transpose()?;
-        if let Some(dpi_setting) = dpi_setting {
-            let base_dpi = match dpi_setting.data {
-                SettingData::Integer(dpi) => dpi as f64,
-                SettingData::String(_) => {
-                    return Err(ParserError::BadType(SettingType::String).into())
-                },
-                SettingData::Color(_) => {
-                    return Err(ParserError::BadType(SettingType::Color).into())
-                },
-            };
-
-            Ok(Some(base_dpi / DPI_MULTIPLIER))
-        } else {
-            Ok(None)
-        }
+        Ok(self.get_property(
+            owner.owner,
+            atoms[_XSETTINGS_SETTINGS],
+            atoms[_XSETTINGS_SETTINGS],
+        )?)
+    }
+}
+
+/// Find the setting with the given name.
+fn find_setting<'a>(data: &'a [u8], name: &[u8]) -> Result<Option<Setting<'a>>> {
+    read_settings(data)?.find(|res| res.as_ref().map_or(true, |s| s.name == name)).transpose()
+}
+
+/// Find the value of the setting with the given name.
+fn find_value(data: &[u8], name: &[u8]) -> Result<Option<XSettingValue>> {
+    Ok(find_setting(data, name)?.map(|setting| match setting.data {
+        SettingData::Integer(value) => XSettingValue::Integer(value),
+        SettingData::String(value) => {
+            XSettingValue::String(String::from_utf8_lossy(value).into_owned())
+        },
+        SettingData::Color(color) => XSettingValue::Color(color),
+    }))
+}
+
+/// Find the integer setting with the given name.
+fn find_integer(data: &[u8], name: &[u8]) -> Result<Option<i32>> {
+    match find_setting(data, name)?.map(|setting| setting.data) {
+        Some(SettingData::Integer(value)) => Ok(Some(value)),
+        Some(SettingData::String(_)) => Err(ParserError::BadType(SettingType::String)),
+        Some(SettingData::Color(_)) => Err(ParserError::BadType(SettingType::Color)),
+        None => Ok(None),
     }
 }
 
+/// Read the accent color, which is the `selected_bg_color` of the GTK color scheme.
+fn accent_color(data: &[u8]) -> Result<Option<[u8; 4]>> {
+    let scheme = match find_setting(data, COLOR_SCHEME_NAME)?.map(|setting| setting.data) {
+        Some(SettingData::String(scheme)) => scheme,
+        Some(SettingData::Integer(_)) => return Err(ParserError::BadType(SettingType::Integer)),
+        Some(SettingData::Color(_)) => return Err(ParserError::BadType(SettingType::Color)),
+        None => return Ok(None),
+    };
+
+    // The scheme is a list of `name:color` pairs, separated by new lines or semicolons.
+    let color = scheme
+        .split(|&b| b == b'\n' || b == b';')
+        .filter_map(|entry| {
+            let entry = std::str::from_utf8(entry).ok()?;
+            let (name, color) = entry.split_once(':')?;
+            (name.trim() == "selected_bg_color").then(|| color.trim())
+        })
+        .next();
+    Ok(color.and_then(parse_hex_color))
+}
+
+/// Parse a `#rgb`, `#rrggbb` or `#rrrrggggbbbb` color.
+fn parse_hex_color(color: &str) -> Option<[u8; 4]> {
+    let hex = color.strip_prefix('#')?;
+    let digits = match hex.len() {
+        3 | 6 | 12 => hex.len() / 3,
+        _ => return None,
+    };
+
+    let mut rgba = [0, 0, 0, u8::MAX];
+    for (channel, chunk) in rgba.iter_mut().zip(hex.as_bytes().chunks(digits)) {
+        let value = u16::from_str_radix(std::str::from_utf8(chunk).ok()?, 16).ok()?;
+        *channel = match digits {
+            1 => value as u8 * 0x11,
+            2 => value as u8,
+            _ => (value >> 8) as u8,
+        };
+    }
+    Some(rgba)
+}
+
 /// Read over the settings in the block of data.
 fn read_settings(data: &[u8]) -> Result<impl Iterator<Item = Result<Setting<'_>>> + '_> {
     // Create a parser. This automatically parses the first 8 bytes for metadata.
@@ -81,8 +186,8 @@ struct Setting<'a> {
 /// The data contained in a setting.
 enum SettingData<'a> {
     Integer(i32),
-    String(#[allow(dead_code)] &'a [u8]),
-    Color(#[allow(dead_code)] [i16; 4]),
+    String(&'a [u8]),
+    Color([u16; 4]),
 }
 
 impl<'a> Setting<'a> {
@@ -118,11 +223,11 @@ impl<'a> Setting<'a> {
             },
 
             SettingType::Color => {
-                // Read i16's of color.
-                let (red, blue, green, alpha) =
-                    (parser.i16()?, parser.i16()?, parser.i16()?, parser.i16()?);
+                // Read u16's of color.
+                let (red, green, blue, alpha) =
+                    (parser.u16()?, parser.u16()?, parser.u16()?, parser.u16()?);
 
-                SettingData::Color([red, blue, green, alpha])
+                SettingData::Color([red, green, blue, alpha])
             },
         };
 
@@ -202,11 +307,16 @@ impl<'a> Parser<'a> {
 
     /// Get two bytes.
     fn i16(&mut self) -> Result<i16> {
+        self.u16().map(|value| value as i16)
+    }
+
+    /// Get two unsigned bytes.
+    fn u16(&mut self) -> Result<u16> {
         self.advance(2).map(|s| {
             let bytes: &[u8; 2] = s.try_into().unwrap();
             match self.endianness {
-                Endianness::Big => i16::from_be_bytes(*bytes),
-                Endianness::Little => i16::from_le_bytes(*bytes),
+                Endianness::Big => u16::from_be_bytes(*bytes),
+                Endianness::Little => u16::from_le_bytes(*bytes),
             }
         })
     }
@@ -307,6 +417,68 @@ mod tests {
         assert_string(&rgba.data, "rgb");
         let lcd = settings.iter().find(|s| s.name == b"Xft/Lcdfilter").unwrap();
         assert_string(&lcd.data, "lcddefault");
+
+        assert!(find_integer(&data, DOUBLE_CLICK_TIME_NAME).unwrap().is_some());
+        assert_eq!(find_value(&data, DPI_NAME).unwrap(), Some(XSettingValue::Integer(96 * 1024)));
+        assert_eq!(
+            find_value(&data, b"Gtk/CursorThemeName").unwrap(),
+            Some(XSettingValue::String("DMZ-White".into()))
+        );
+        assert_eq!(
+            find_value(&data, b"Net/ThemeName").unwrap(),
+            Some(XSettingValue::String("Greybird".into()))
+        );
+        assert_eq!(find_value(&data, b"Net/Missing").unwrap(), None);
+        assert_eq!(accent_color(&data).unwrap(), None);
+    }
+
+    #[test]
+    fn accent_color_from_color_scheme() {
+        let data = string_setting(
+            COLOR_SCHEME_NAME,
+            b"fg_color:#000000\nselected_bg_color:#3584e4;bg_color:#ffffff",
+        );
+        assert_eq!(accent_color(&data).unwrap(), Some([0x35, 0x84, 0xe4, 0xff]));
+
+        let data = string_setting(COLOR_SCHEME_NAME, b"selected_bg_color: #35358484e4e4\n");
+        assert_eq!(accent_color(&data).unwrap(), Some([0x35, 0x84, 0xe4, 0xff]));
+
+        let data = string_setting(COLOR_SCHEME_NAME, b"bg_color:#fff");
+        assert_eq!(accent_color(&data).unwrap(), None);
+
+        let data = string_setting(b"Net/ThemeName", b"Adwaita");
+        assert_eq!(accent_color(&data).unwrap(), None);
+    }
+
+    #[test]
+    fn hex_colors() {
+        assert_eq!(parse_hex_color("#f0a"), Some([0xff, 0x00, 0xaa, 0xff]));
+        assert_eq!(parse_hex_color("#12ab34"), Some([0x12, 0xab, 0x34, 0xff]));
+        assert_eq!(parse_hex_color("#1234abcd5678"), Some([0x12, 0xab, 0x56, 0xff]));
+        assert_eq!(parse_hex_color("12ab34"), None);
+        assert_eq!(parse_hex_color("#12ab3"), None);
+        assert_eq!(parse_hex_color("#12ab3g"), None);
+        assert_eq!(parse_hex_color("blue"), None);
+    }
+
+    /// Build the settings data for a single string setting.
+    fn string_setting(name: &[u8], value: &[u8]) -> Vec<u8> {
+        fn pad(data: &mut Vec<u8>) {
+            data.resize(data.len().next_multiple_of(4), 0);
+        }
+
+        let mut data = vec![LITTLE_ENDIAN, 0, 0, 0];
+        data.extend_from_slice(&0i32.to_le_bytes()); // Serial.
+        data.extend_from_slice(&1i32.to_le_bytes()); // Number of settings.
+        data.extend_from_slice(&[1, 0]); // String type and padding.
+        data.extend_from_slice(&(name.len() as i16).to_le_bytes());
+        data.extend_from_slice(name);
+        pad(&mut data);
+        data.extend_from_slice(&0i32.to_le_bytes()); // Last change serial.
+        data.extend_from_slice(&(value.len() as i32).to_le_bytes());
+        data.extend_from_slice(value);
+        pad(&mut data);
+        data
     }
 
     fn assert_string(dat: &SettingData<'_>, s: &str) {
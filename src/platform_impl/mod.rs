@@ -6,9 +6,28 @@ mod android;
 #[cfg(target_vendor = "apple")]
 mod apple;
 #[cfg(any(x11_platform, wayland_platform))]
+mod batch;
+#[cfg(any(x11_platform, wayland_platform, windows_platform, web_platform))]
+mod click;
+#[cfg(any(x11_platform, macos_platform, web_platform))]
+mod hotplug;
+#[cfg(any(
+    x11_platform,
+    wayland_platform,
+    null_platform,
+    windows_platform,
+    macos_platform,
+    web_platform
+))]
+mod idle;
+#[cfg(any(x11_platform, wayland_platform, null_platform))]
 mod linux;
 #[cfg(orbital_platform)]
 mod orbital;
+#[cfg(any(x11_platform, wayland_platform, null_platform, windows_platform, macos_platform))]
+mod redraw;
+#[cfg(any(x11_platform, windows_platform, macos_platform, web_platform))]
+mod resize;
 #[cfg(web_platform)]
 mod web;
 #[cfg(windows_platform)]
@@ -18,7 +37,7 @@ mod windows;
 use self::android as platform;
 #[cfg(target_vendor = "apple")]
 use self::apple as platform;
-#[cfg(any(x11_platform, wayland_platform))]
+#[cfg(any(x11_platform, wayland_platform, null_platform))]
 use self::linux as platform;
 #[cfg(orbital_platform)]
 use self::orbital as platform;
@@ -70,6 +89,7 @@ impl From<Fullscreen> for RootFullscreen {
     not(android_platform),
     not(x11_platform),
     not(wayland_platform),
+    not(null_platform),
     not(web_platform),
     not(orbital_platform),
 ))]
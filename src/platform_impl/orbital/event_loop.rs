@@ -1,5 +1,6 @@
 use std::cell::Cell;
 use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{mpsc, Arc, Mutex};
 use std::time::Instant;
 use std::{mem, slice};
@@ -304,12 +305,16 @@ impl EventLoop {
             windows: Vec::new(),
             window_target: ActiveEventLoop {
                 control_flow: Cell::new(ControlFlow::default()),
-                exit: Cell::new(false),
+                exit: Cell::new(None),
                 creates: Mutex::new(VecDeque::new()),
                 redraws: Arc::new(Mutex::new(VecDeque::new())),
                 destroys: Arc::new(Mutex::new(VecDeque::new())),
                 event_socket,
-                event_loop_proxy: Arc::new(EventLoopProxy { wake_socket, user_events_sender }),
+                event_loop_proxy: Arc::new(EventLoopProxy {
+                    wake_socket,
+                    user_events_sender,
+                    alive: AtomicBool::new(true),
+                }),
             },
             user_events_receiver,
         })
@@ -414,6 +419,7 @@ impl EventLoop {
             EventOption::MouseRelative(MouseRelativeEvent { dx, dy }) => {
                 app.device_event(window_target, None, event::DeviceEvent::PointerMotion {
                     delta: (dx as f64, dy as f64),
+                    raw: false,
                 });
             },
             EventOption::Button(ButtonEvent { left, middle, right }) => {
@@ -423,6 +429,7 @@ impl EventLoop {
                         primary: true,
                         state,
                         position: dpi::PhysicalPosition::default(),
+                        click_count: 1,
                         button: button.into(),
                     });
                 }
@@ -641,11 +648,15 @@ impl EventLoop {
             self.window_target.event_socket.read(&mut event).unwrap();
 
             // TODO: handle spurious wakeups (redraw caused wakeup but redraw already handled)
+            let actual_resume = Instant::now();
             match requested_resume {
-                Some(requested_resume) if event.id == timeout_socket.0.fd => {
-                    // If the event is from the special timeout socket, report that resume
-                    // time was reached.
-                    start_cause = StartCause::ResumeTimeReached { start, requested_resume };
+                Some(requested_resume)
+                    if event.id == timeout_socket.0.fd || actual_resume >= requested_resume =>
+                {
+                    // If the event is from the special timeout socket, or another event raced
+                    // with it after the deadline, report that resume time was reached.
+                    start_cause =
+                        StartCause::ResumeTimeReached { start, requested_resume, actual_resume };
                 },
                 _ => {
                     // Normal window event or spurious timeout.
@@ -656,7 +667,10 @@ impl EventLoop {
 
         app.exiting(&self.window_target);
 
-        Ok(())
+        match self.window_target.exit.get() {
+            Some(code) if code != 0 => Err(EventLoopError::ExitFailure(code)),
+            _ => Ok(()),
+        }
     }
 
     pub fn window_target(&self) -> &dyn RootActiveEventLoop {
@@ -664,9 +678,16 @@ impl EventLoop {
     }
 }
 
+impl Drop for EventLoop {
+    fn drop(&mut self) {
+        self.window_target.event_loop_proxy.alive.store(false, Ordering::Relaxed);
+    }
+}
+
 pub struct EventLoopProxy {
     user_events_sender: mpsc::SyncSender<()>,
     pub(super) wake_socket: TimeSocket,
+    alive: AtomicBool,
 }
 
 impl EventLoopProxyProvider for EventLoopProxy {
@@ -677,13 +698,17 @@ impl EventLoopProxyProvider for EventLoopProxy {
             self.wake_socket.wake().unwrap();
         }
     }
+
+    fn is_alive(&self) -> bool {
+        self.alive.load(Ordering::Relaxed)
+    }
 }
 
 impl Unpin for EventLoopProxy {}
 
 pub struct ActiveEventLoop {
     control_flow: Cell<ControlFlow>,
-    exit: Cell<bool>,
+    exit: Cell<Option<i32>>,
     pub(super) creates: Mutex<VecDeque<Arc<RedoxSocket>>>,
     pub(super) redraws: Arc<Mutex<VecDeque<WindowId>>>,
     pub(super) destroys: Arc<Mutex<VecDeque<WindowId>>>,
@@ -720,6 +745,14 @@ impl RootActiveEventLoop for ActiveEventLoop {
         None
     }
 
+    fn accent_color(&self) -> Option<[u8; 4]> {
+        None
+    }
+
+    fn key_text(&self, _key: PhysicalKey, _modifiers: ModifiersState) -> Option<SmolStr> {
+        None
+    }
+
     fn primary_monitor(&self) -> Option<crate::monitor::MonitorHandle> {
         Some(crate::monitor::MonitorHandle { inner: MonitorHandle })
     }
@@ -734,12 +767,22 @@ impl RootActiveEventLoop for ActiveEventLoop {
         self.control_flow.get()
     }
 
+    fn set_resize_coalescing(&self, _enabled: bool) {}
+
+    fn scroll_lines_to_pixels(&self, lines: f32) -> f32 {
+        lines * 3.0 * 16.0
+    }
+
     fn exit(&self) {
-        self.exit.set(true);
+        self.exit_with_code(0);
+    }
+
+    fn exit_with_code(&self, code: i32) {
+        self.exit.set(Some(code));
     }
 
     fn exiting(&self) -> bool {
-        self.exit.get()
+        self.exit.get().is_some()
     }
 
     fn owned_display_handle(&self) -> CoreOwnedDisplayHandle {
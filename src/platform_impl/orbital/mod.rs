@@ -2,11 +2,13 @@
 
 use std::{fmt, str};
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use smol_str::SmolStr;
 
 pub(crate) use self::event_loop::{ActiveEventLoop, EventLoop};
 pub use self::window::Window;
-use crate::dpi::PhysicalPosition;
+use crate::dpi::{PhysicalPosition, PhysicalSize};
 use crate::keyboard::Key;
 use crate::monitor::VideoMode;
 
@@ -100,6 +102,7 @@ impl TimeSocket {
 pub(crate) struct PlatformSpecificEventLoopAttributes {}
 
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PlatformSpecificWindowAttributes;
 
 struct WindowProperties<'a> {
@@ -147,10 +150,22 @@ impl MonitorHandle {
         None
     }
 
+    pub fn work_area(&self) -> Option<(PhysicalPosition<i32>, PhysicalSize<u32>)> {
+        None
+    }
+
     pub fn scale_factor(&self) -> f64 {
         1.0 // TODO
     }
 
+    pub fn physical_size_mm(&self) -> Option<(f64, f64)> {
+        None
+    }
+
+    pub fn is_primary(&self) -> bool {
+        true
+    }
+
     pub fn current_video_mode(&self) -> Option<VideoMode> {
         // (it is guaranteed to support 32 bit color though)
         None
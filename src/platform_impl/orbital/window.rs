@@ -1,5 +1,6 @@
 use std::collections::VecDeque;
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
 use super::event_loop::EventLoopProxy;
 use super::{ActiveEventLoop, MonitorHandle, RedoxSocket, WindowProperties};
@@ -189,6 +190,16 @@ impl CoreWindow for Window {
         }
     }
 
+    #[inline]
+    fn request_redraw_with_priority(&self, _priority: window::RedrawPriority) {
+        self.request_redraw();
+    }
+
+    #[inline]
+    fn request_redraw_at(&self, _deadline: Instant) {
+        self.request_redraw();
+    }
+
     #[inline]
     fn pre_present_notify(&self) {}
 
@@ -249,6 +260,16 @@ impl CoreWindow for Window {
     #[inline]
     fn set_max_surface_size(&self, _: Option<Size>) {}
 
+    #[inline]
+    fn min_surface_size(&self) -> Option<PhysicalSize<u32>> {
+        None
+    }
+
+    #[inline]
+    fn max_surface_size(&self) -> Option<PhysicalSize<u32>> {
+        None
+    }
+
     #[inline]
     fn title(&self) -> String {
         let mut buf: [u8; 4096] = [0; 4096];
@@ -262,6 +283,11 @@ impl CoreWindow for Window {
         self.window_socket.write(format!("T,{title}").as_bytes()).expect("failed to set title");
     }
 
+    #[inline]
+    fn set_title_with_subtitle(&self, title: &str, _subtitle: Option<&str>) {
+        self.set_title(title);
+    }
+
     #[inline]
     fn set_transparent(&self, transparent: bool) {
         let _ = self.set_flag(ORBITAL_FLAG_TRANSPARENT, transparent);
@@ -280,6 +306,11 @@ impl CoreWindow for Window {
         Some(!self.get_flag(ORBITAL_FLAG_HIDDEN).unwrap_or(false))
     }
 
+    #[inline]
+    fn occlusion(&self) -> Option<f32> {
+        None
+    }
+
     #[inline]
     fn surface_resize_increments(&self) -> Option<PhysicalSize<u32>> {
         None
@@ -360,6 +391,9 @@ impl CoreWindow for Window {
     #[inline]
     fn set_ime_purpose(&self, _purpose: ImePurpose) {}
 
+    #[inline]
+    fn set_ime_theme(&self, _theme: Option<window::Theme>) {}
+
     #[inline]
     fn focus_window(&self) {}
 
@@ -390,6 +424,9 @@ impl CoreWindow for Window {
         Ok(())
     }
 
+    #[inline]
+    fn set_cursor_grab_restore_on_focus(&self, _restore: bool) {}
+
     #[inline]
     fn set_cursor_visible(&self, visible: bool) {
         let _ = self.window_socket.write(format!("M,C,{}", if visible { 1 } else { 0 }).as_bytes());
@@ -448,7 +485,9 @@ impl CoreWindow for Window {
     #[inline]
     fn set_theme(&self, _theme: Option<window::Theme>) {}
 
-    fn set_content_protected(&self, _protected: bool) {}
+    fn set_content_protected(&self, _protected: bool) -> bool {
+        false
+    }
 
     fn rwh_06_window_handle(&self) -> &dyn rwh_06::HasWindowHandle {
         self
@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::window::WindowId;
+
+/// Tracks the redraws scheduled with `Window::request_redraw_at`.
+///
+/// This is shared between the windows, which may live on other threads, and the event loop, which
+/// waits for the earliest deadline.
+#[derive(Debug, Default)]
+pub struct RedrawSchedule {
+    deadlines: Mutex<HashMap<WindowId, Instant>>,
+}
+
+impl RedrawSchedule {
+    /// Schedules a redraw, keeping the earliest deadline if one is already scheduled.
+    ///
+    /// Returns whether the deadline of the window changed, in which case the event loop must be
+    /// woken up to take it into account.
+    pub fn schedule(&self, window_id: WindowId, deadline: Instant) -> bool {
+        let mut deadlines = self.deadlines.lock().unwrap();
+        match deadlines.get_mut(&window_id) {
+            Some(scheduled) if *scheduled <= deadline => false,
+            Some(scheduled) => {
+                *scheduled = deadline;
+                true
+            },
+            None => {
+                deadlines.insert(window_id, deadline);
+                true
+            },
+        }
+    }
+
+    /// Forgets the redraw of a window, which must be done when the window is destroyed.
+    pub fn cancel(&self, window_id: WindowId) {
+        self.deadlines.lock().unwrap().remove(&window_id);
+    }
+
+    /// The deadline of the earliest redraw.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadlines.lock().unwrap().values().min().copied()
+    }
+
+    /// The time left until the earliest redraw, to be combined with the control flow timeout.
+    pub fn timeout(&self, now: Instant) -> Option<Duration> {
+        self.deadline().map(|deadline| deadline.saturating_duration_since(now))
+    }
+
+    /// Removes and returns the windows whose redraw is due.
+    pub fn take_due(&self, now: Instant) -> Vec<WindowId> {
+        let mut due = Vec::new();
+        self.deadlines.lock().unwrap().retain(|&window_id, &mut deadline| {
+            let is_due = deadline <= now;
+            if is_due {
+                due.push(window_id);
+            }
+            !is_due
+        });
+        due
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_earliest_deadline() {
+        let schedule = RedrawSchedule::default();
+        let window_id = WindowId::from_raw(1);
+        let now = Instant::now();
+        assert_eq!(schedule.timeout(now), None);
+
+        assert!(schedule.schedule(window_id, now + Duration::from_millis(20)));
+        assert!(!schedule.schedule(window_id, now + Duration::from_millis(30)));
+        assert!(schedule.schedule(window_id, now + Duration::from_millis(10)));
+        assert_eq!(schedule.timeout(now), Some(Duration::from_millis(10)));
+        assert_eq!(schedule.deadline(), Some(now + Duration::from_millis(10)));
+
+        // The redraw is delivered once.
+        assert!(schedule.take_due(now + Duration::from_millis(5)).is_empty());
+        assert_eq!(schedule.take_due(now + Duration::from_millis(10)), [window_id]);
+        assert!(schedule.take_due(now + Duration::from_millis(40)).is_empty());
+        assert_eq!(schedule.timeout(now), None);
+    }
+
+    #[test]
+    fn cancelled_redraws_are_not_delivered() {
+        let schedule = RedrawSchedule::default();
+        let (first, second) = (WindowId::from_raw(1), WindowId::from_raw(2));
+        let now = Instant::now();
+
+        schedule.schedule(first, now + Duration::from_millis(10));
+        schedule.schedule(second, now + Duration::from_millis(20));
+        schedule.cancel(first);
+        assert_eq!(schedule.deadline(), Some(now + Duration::from_millis(20)));
+        assert_eq!(schedule.take_due(now + Duration::from_millis(20)), [second]);
+
+        // Cancelling a window without a scheduled redraw does nothing.
+        schedule.cancel(first);
+        assert_eq!(schedule.deadline(), None);
+    }
+}
@@ -0,0 +1,100 @@
+use std::cell::{Cell, RefCell};
+
+use dpi::PhysicalSize;
+
+use crate::event::WindowEvent;
+use crate::window::WindowId;
+
+/// Buffers the `SurfaceResized` events requested with `ActiveEventLoop::set_resize_coalescing`.
+#[derive(Debug, Default)]
+pub struct ResizeCoalescer {
+    enabled: Cell<bool>,
+    pending: RefCell<Vec<(WindowId, PhysicalSize<u32>)>>,
+}
+
+impl ResizeCoalescer {
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.set(enabled);
+    }
+
+    /// Holds back the event if it's a resize to coalesce, otherwise hands it back.
+    ///
+    /// A pending resize is dropped when the window is destroyed, so that it isn't delivered after
+    /// [`WindowEvent::Destroyed`].
+    pub fn coalesce(&self, window_id: WindowId, event: WindowEvent) -> Option<WindowEvent> {
+        match event {
+            WindowEvent::SurfaceResized(size) if self.enabled.get() => {
+                let mut pending = self.pending.borrow_mut();
+                match pending.iter_mut().find(|(id, _)| *id == window_id) {
+                    Some((_, pending_size)) => *pending_size = size,
+                    None => pending.push((window_id, size)),
+                }
+                None
+            },
+            WindowEvent::Destroyed => {
+                self.remove(window_id);
+                Some(WindowEvent::Destroyed)
+            },
+            event => Some(event),
+        }
+    }
+
+    /// Drops the pending resize of a window, for backends that don't deliver
+    /// [`WindowEvent::Destroyed`] through [`coalesce`](Self::coalesce).
+    pub fn remove(&self, window_id: WindowId) {
+        self.pending.borrow_mut().retain(|(id, _)| *id != window_id);
+    }
+
+    /// The latest size of every resized window, in the order they were first resized.
+    pub fn take(&self) -> Vec<(WindowId, PhysicalSize<u32>)> {
+        self.pending.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rapid_resizes_collapse_to_one() {
+        let coalescer = ResizeCoalescer::default();
+        let first = WindowId::from_raw(1);
+        let second = WindowId::from_raw(2);
+
+        let resized = WindowEvent::SurfaceResized(PhysicalSize::new(1, 1));
+        assert_eq!(coalescer.coalesce(first, resized.clone()), Some(resized));
+        assert!(coalescer.take().is_empty());
+
+        coalescer.set_enabled(true);
+        for size in 1..=10 {
+            let resized = WindowEvent::SurfaceResized(PhysicalSize::new(size, size));
+            assert_eq!(coalescer.coalesce(first, resized), None);
+        }
+        let resized = WindowEvent::SurfaceResized(PhysicalSize::new(5, 5));
+        assert_eq!(coalescer.coalesce(second, resized), None);
+        let focused = WindowEvent::Focused(true);
+        assert_eq!(coalescer.coalesce(first, focused.clone()), Some(focused));
+
+        assert_eq!(coalescer.take(), vec![
+            (first, PhysicalSize::new(10, 10)),
+            (second, PhysicalSize::new(5, 5))
+        ]);
+        assert!(coalescer.take().is_empty());
+    }
+
+    #[test]
+    fn destroyed_window_drops_pending_resize() {
+        let coalescer = ResizeCoalescer::default();
+        coalescer.set_enabled(true);
+        let first = WindowId::from_raw(1);
+        let second = WindowId::from_raw(2);
+
+        let resized = WindowEvent::SurfaceResized(PhysicalSize::new(1, 1));
+        assert_eq!(coalescer.coalesce(first, resized.clone()), None);
+        assert_eq!(coalescer.coalesce(second, resized), None);
+        let destroyed = WindowEvent::Destroyed;
+        assert_eq!(coalescer.coalesce(first, destroyed.clone()), Some(destroyed));
+
+        assert_eq!(coalescer.take(), vec![(second, PhysicalSize::new(1, 1))]);
+    }
+}
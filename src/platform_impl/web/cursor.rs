@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::cell::RefCell;
 use std::future::{self, Future};
 use std::hash::{Hash, Hasher};
@@ -25,6 +26,7 @@ use super::main_thread::{MainThreadMarker, MainThreadSafe};
 use super::r#async::{AbortHandle, Abortable, DropAbortHandle, Notified, Notifier};
 use super::ActiveEventLoop;
 use crate::cursor::{BadImage, Cursor, CursorImage, CustomCursor as RootCustomCursor};
+use crate::icon::unpremultiply_rgba;
 use crate::platform::web::CustomCursorError;
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
@@ -41,9 +43,15 @@ impl CustomCursorSource {
         height: u16,
         hotspot_x: u16,
         hotspot_y: u16,
+        premultiplied: bool,
     ) -> Result<CustomCursorSource, BadImage> {
         Ok(CustomCursorSource::Image(CursorImage::from_rgba(
-            rgba, width, height, hotspot_x, hotspot_y,
+            rgba,
+            width,
+            height,
+            hotspot_x,
+            hotspot_y,
+            premultiplied,
         )?))
     }
 }
@@ -509,6 +517,14 @@ fn from_rgba(
     // 6. Decode the image on an `HTMLImageElement` from the URL.
 
     // 1. Create an `ImageData` from the RGBA data.
+    // `ImageData` uses straight alpha.
+    let rgba = if image.premultiplied {
+        let mut rgba = image.rgba.clone();
+        unpremultiply_rgba(&mut rgba);
+        Cow::Owned(rgba)
+    } else {
+        Cow::Borrowed(image.rgba.as_slice())
+    };
     // Adapted from https://github.com/rust-windowing/softbuffer/blob/ab7688e2ed2e2eca51b3c4e1863a5bd7fe85800e/src/web.rs#L196-L223
     #[cfg(target_feature = "atomics")]
     // Can't share `SharedArrayBuffer` with `ImageData`.
@@ -524,18 +540,16 @@ fn from_rgba(
             fn new(array: Uint8ClampedArray, sw: u32) -> Result<ImageDataExt, JsValue>;
         }
 
-        let array = Uint8Array::new_with_length(image.rgba.len() as u32);
-        array.copy_from(&image.rgba);
+        let array = Uint8Array::new_with_length(rgba.len() as u32);
+        array.copy_from(&rgba);
         let array = Uint8ClampedArray::new(&array);
         ImageDataExt::new(array, image.width as u32)
             .map(JsValue::from)
             .map(ImageData::unchecked_from_js)
     };
     #[cfg(not(target_feature = "atomics"))]
-    let result = ImageData::new_with_u8_clamped_array(
-        wasm_bindgen::Clamped(&image.rgba),
-        image.width as u32,
-    );
+    let result =
+        ImageData::new_with_u8_clamped_array(wasm_bindgen::Clamped(&rgba), image.width as u32);
     let image_data = result.expect("found wrong image size");
 
     // 2. Create an `ImageBitmap` from the `ImageData`.
@@ -95,8 +95,11 @@ fn handle_event<A: ApplicationHandler>(app: &mut A, target: &ActiveEventLoop, ev
         Event::Suspended => app.suspended(target),
         Event::Resumed => app.resumed(target),
         Event::CreateSurfaces => app.can_create_surfaces(target),
+        Event::IdleTick => app.idle_tick(target),
         Event::AboutToWait => app.about_to_wait(target),
         Event::LoopExiting => app.exiting(target),
         Event::MemoryWarning => app.memory_warning(target),
+        Event::MonitorConnected(monitor) => app.monitor_connected(target, monitor),
+        Event::MonitorDisconnected(monitor) => app.monitor_disconnected(target, monitor),
     }
 }
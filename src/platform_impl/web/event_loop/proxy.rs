@@ -14,6 +14,7 @@ struct State {
     awoken: AtomicBool,
     waker: AtomicWaker,
     closed: AtomicBool,
+    loop_alive: AtomicBool,
 }
 
 impl EventLoopProxy {
@@ -22,6 +23,7 @@ impl EventLoopProxy {
             awoken: AtomicBool::new(false),
             waker: AtomicWaker::new(),
             closed: AtomicBool::new(false),
+            loop_alive: AtomicBool::new(true),
         });
 
         Self(Wrapper::new(
@@ -83,6 +85,10 @@ impl EventLoopProxy {
 
         self.0.with_sender_data(|state| state.awoken.swap(false, Ordering::Relaxed))
     }
+
+    pub fn loop_destroyed(&self) {
+        self.0.with_sender_data(|state| state.loop_alive.store(false, Ordering::Relaxed))
+    }
 }
 
 impl Drop for EventLoopProxy {
@@ -98,4 +104,8 @@ impl EventLoopProxyProvider for EventLoopProxy {
     fn wake_up(&self) {
         self.0.send(())
     }
+
+    fn is_alive(&self) -> bool {
+        self.0.with_sender_data(|state| state.loop_alive.load(Ordering::Relaxed))
+    }
 }
@@ -1,5 +1,5 @@
 use std::cell::{Cell, RefCell};
-use std::collections::{HashSet, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::iter;
 use std::ops::Deref;
 use std::rc::{Rc, Weak};
@@ -10,9 +10,9 @@ use wasm_bindgen::JsCast;
 use web_sys::{Document, KeyboardEvent, Navigator, PageTransitionEvent, PointerEvent, WheelEvent};
 use web_time::{Duration, Instant};
 
-use super::super::event;
 use super::super::main_thread::MainThreadMarker;
 use super::super::monitor::MonitorHandler;
+use super::super::{event, lock};
 use super::backend;
 use super::proxy::EventLoopProxy;
 use super::state::State;
@@ -20,9 +20,11 @@ use crate::dpi::PhysicalSize;
 use crate::event::{DeviceEvent, ElementState, Event, RawKeyEvent, StartCause, WindowEvent};
 use crate::event_loop::{ControlFlow, DeviceEvents};
 use crate::platform::web::{PollStrategy, WaitUntilStrategy};
+use crate::platform_impl::idle::IdleTimer;
 use crate::platform_impl::platform::backend::{EventListenerHandle, SafeAreaHandle};
 use crate::platform_impl::platform::r#async::DispatchRunner;
 use crate::platform_impl::platform::window::Inner;
+use crate::platform_impl::resize::ResizeCoalescer;
 use crate::window::WindowId;
 
 pub struct Shared(Rc<Execution>);
@@ -43,6 +45,7 @@ struct Execution {
     control_flow: Cell<ControlFlow>,
     poll_strategy: Cell<PollStrategy>,
     wait_until_strategy: Cell<WaitUntilStrategy>,
+    automatic_canvas_resize: Cell<bool>,
     exit: Cell<bool>,
     runner: RefCell<RunnerEnum>,
     suspended: Cell<bool>,
@@ -55,6 +58,14 @@ struct Execution {
     #[allow(clippy::type_complexity)]
     all_canvases: RefCell<Vec<(WindowId, Weak<backend::Canvas>, DispatchRunner<Inner>)>>,
     redraw_pending: RefCell<HashSet<WindowId>>,
+    /// The redraws scheduled with `Window::request_redraw_at`, with the timeout waking them up.
+    redraw_scheduled: RefCell<HashMap<WindowId, (Instant, backend::Schedule)>>,
+    /// The ticks requested with `ActiveEventLoop::set_idle_interval`, with the timeout waking
+    /// them up.
+    idle_timer: IdleTimer,
+    idle_timeout: RefCell<Option<backend::Schedule>>,
+    /// The resizes held back with `ActiveEventLoop::set_resize_coalescing`.
+    resize_coalescer: ResizeCoalescer,
     destroy_pending: RefCell<VecDeque<WindowId>>,
     pub(crate) monitor: Rc<MonitorHandler>,
     safe_area: Rc<SafeAreaHandle>,
@@ -160,6 +171,7 @@ impl Shared {
                 control_flow: Cell::new(ControlFlow::default()),
                 poll_strategy: Cell::new(PollStrategy::default()),
                 wait_until_strategy: Cell::new(WaitUntilStrategy::default()),
+                automatic_canvas_resize: Cell::new(true),
                 exit: Cell::new(false),
                 runner: RefCell::new(RunnerEnum::Pending),
                 suspended: Cell::new(false),
@@ -171,6 +183,10 @@ impl Shared {
                 id: Cell::new(0),
                 all_canvases: RefCell::new(Vec::new()),
                 redraw_pending: RefCell::new(HashSet::new()),
+                redraw_scheduled: RefCell::new(HashMap::new()),
+                idle_timer: IdleTimer::default(),
+                idle_timeout: RefCell::new(None),
+                resize_coalescer: ResizeCoalescer::default(),
                 destroy_pending: RefCell::new(VecDeque::new()),
                 monitor: Rc::new(monitor),
                 safe_area: Rc::new(safe_area),
@@ -303,12 +319,15 @@ impl Shared {
 
                 // pointer move event
                 let mut delta = backend::event::MouseDelta::init(&navigator, &event);
+                let document = runner.document();
+                let raw = document.pointer_lock_element().is_some()
+                    && lock::is_cursor_lock_raw(&navigator, document);
                 runner.send_events(backend::event::pointer_move_event(event).map(|event| {
                     let delta = delta.delta(&event).to_physical(backend::scale_factor(&window));
 
                     Event::DeviceEvent {
                         device_id,
-                        event: DeviceEvent::PointerMotion { delta: (delta.x, delta.y) },
+                        event: DeviceEvent::PointerMotion { delta: (delta.x, delta.y), raw },
                     }
                 }));
             }),
@@ -448,6 +467,45 @@ impl Shared {
         self.send_events::<EventWrapper>(iter::empty());
     }
 
+    // Schedule a redraw for the given deadline, keeping the earliest one if a redraw is already
+    // scheduled for this window.
+    pub fn request_redraw_at(&self, id: WindowId, deadline: Instant) {
+        let mut redraw_scheduled = self.0.redraw_scheduled.borrow_mut();
+        if matches!(redraw_scheduled.get(&id), Some((scheduled, _)) if *scheduled <= deadline) {
+            return;
+        }
+
+        let this = Rc::downgrade(&self.0);
+        let timeout = backend::Schedule::new_with_duration(
+            self.wait_until_strategy(),
+            self.window(),
+            move || {
+                if let Some(shared) = this.upgrade() {
+                    Shared(shared).resume_scheduled_redraw(id)
+                }
+            },
+            deadline.saturating_duration_since(Instant::now()),
+        );
+        redraw_scheduled.insert(id, (deadline, timeout));
+    }
+
+    // Request the scheduled redraw once its deadline is reached, through the canvas like
+    // `Window::request_redraw` does.
+    fn resume_scheduled_redraw(&self, id: WindowId) {
+        self.0.redraw_scheduled.borrow_mut().remove(&id);
+
+        let canvas = self
+            .0
+            .all_canvases
+            .borrow()
+            .iter()
+            .find(|&&(item_id, ..)| item_id == id)
+            .and_then(|(_, canvas, _)| canvas.upgrade());
+        if let Some(canvas) = canvas {
+            canvas.request_animation_frame();
+        }
+    }
+
     fn init(&self) {
         // NB: For consistency all platforms must call `can_create_surfaces` even though Web
         // applications don't themselves have a formal surface destroy/create lifecycle.
@@ -465,8 +523,11 @@ impl Shared {
     // Run the logic for waking from a WaitUntil, which involves clearing the queue
     // Generally there shouldn't be events built up when this is called
     pub fn resume_time_reached(&self, start: Instant, requested_resume: Instant) {
-        let start_cause =
-            Event::NewEvents(StartCause::ResumeTimeReached { start, requested_resume });
+        let start_cause = Event::NewEvents(StartCause::ResumeTimeReached {
+            start,
+            requested_resume,
+            actual_resume: Instant::now(),
+        });
         self.run_until_cleared(iter::once(start_cause));
     }
 
@@ -519,6 +580,14 @@ impl Shared {
         if self.is_closed() {
             return;
         }
+        let events = events.into_iter().filter_map(|event| match event.into() {
+            EventWrapper::Event(Event::WindowEvent { window_id, event }) => self
+                .0
+                .resize_coalescer
+                .coalesce(window_id, event)
+                .map(|event| EventWrapper::Event(Event::WindowEvent { window_id, event })),
+            event => Some(event),
+        });
         // If we can run the event processing right now, or need to queue this and wait for later
         let mut process_immediately = true;
         match self.0.runner.try_borrow().as_ref().map(Deref::deref) {
@@ -572,6 +641,8 @@ impl Shared {
                 event: crate::event::WindowEvent::Destroyed,
             });
             self.0.redraw_pending.borrow_mut().remove(&id);
+            self.0.redraw_scheduled.borrow_mut().remove(&id);
+            self.0.resize_coalescer.remove(id);
         }
     }
 
@@ -585,6 +656,13 @@ impl Shared {
         }
         self.process_destroy_pending_windows();
 
+        for (window_id, size) in self.0.resize_coalescer.take() {
+            self.handle_event(Event::WindowEvent {
+                window_id,
+                event: WindowEvent::SurfaceResized(size),
+            });
+        }
+
         // Collect all of the redraw events to avoid double-locking the RefCell
         let redraw_events: Vec<WindowId> = self.0.redraw_pending.borrow_mut().drain().collect();
         for window_id in redraw_events {
@@ -594,6 +672,9 @@ impl Shared {
             });
         }
 
+        if self.0.idle_timer.poll(Instant::now()) {
+            self.handle_event(Event::IdleTick);
+        }
         self.handle_event(Event::AboutToWait);
 
         self.apply_control_flow();
@@ -710,11 +791,39 @@ impl Shared {
         if let RunnerEnum::Running(ref mut runner) = *self.0.runner.borrow_mut() {
             runner.state = new_state;
         }
+
+        self.schedule_idle_tick();
+    }
+
+    // Wake up the event loop for the next idle tick, independently of the control flow.
+    fn schedule_idle_tick(&self) {
+        let timeout = match self.0.idle_timer.timeout(Instant::now()) {
+            Some(timeout) if !self.exiting() => timeout,
+            _ => {
+                self.0.idle_timeout.borrow_mut().take();
+                return;
+            },
+        };
+
+        let this = Rc::downgrade(&self.0);
+        let schedule = backend::Schedule::new_with_duration(
+            self.wait_until_strategy(),
+            self.window(),
+            move || {
+                if let Some(shared) = this.upgrade() {
+                    Shared(shared).send_events::<EventWrapper>(iter::empty())
+                }
+            },
+            timeout,
+        );
+        *self.0.idle_timeout.borrow_mut() = Some(schedule);
     }
 
     fn handle_loop_destroyed(&self) {
         self.handle_event(Event::LoopExiting);
         let all_canvases = std::mem::take(&mut *self.0.all_canvases.borrow_mut());
+        self.0.redraw_scheduled.borrow_mut().clear();
+        self.0.idle_timeout.borrow_mut().take();
         *self.0.page_transition_event_handle.borrow_mut() = None;
         *self.0.on_mouse_move.borrow_mut() = None;
         *self.0.on_wheel.borrow_mut() = None;
@@ -726,6 +835,7 @@ impl Shared {
         // Dropping the `Runner` drops the event handler closure, which will in
         // turn drop all `Window`s moved into the closure.
         *self.0.runner.borrow_mut() = RunnerEnum::Destroyed;
+        self.0.event_loop_proxy.loop_destroyed();
         for (_, canvas, _) in all_canvases {
             // In case any remaining `Window`s are still not dropped, we will need
             // to explicitly remove the event handlers associated with their canvases.
@@ -795,6 +905,15 @@ impl Shared {
         self.0.control_flow.set(control_flow)
     }
 
+    pub(crate) fn set_resize_coalescing(&self, enabled: bool) {
+        self.0.resize_coalescer.set_enabled(enabled)
+    }
+
+    pub(crate) fn set_idle_interval(&self, interval: Option<Duration>) {
+        // The timeout is scheduled once the handler returns, in `apply_control_flow`.
+        self.0.idle_timer.set_interval(interval)
+    }
+
     pub(crate) fn exit(&self) {
         self.0.exit.set(true)
     }
@@ -819,6 +938,14 @@ impl Shared {
         self.0.wait_until_strategy.get()
     }
 
+    pub(crate) fn set_automatic_canvas_resize(&self, automatic: bool) {
+        self.0.automatic_canvas_resize.set(automatic)
+    }
+
+    pub(crate) fn automatic_canvas_resize(&self) -> bool {
+        self.0.automatic_canvas_resize.get()
+    }
+
     pub(crate) fn event_loop_proxy(&self) -> &Arc<EventLoopProxy> {
         &self.0.event_loop_proxy
     }
@@ -4,21 +4,25 @@ use std::iter;
 use std::rc::Rc;
 use std::sync::Arc;
 
+use smol_str::SmolStr;
 use web_sys::Element;
+use web_time::{Duration, Instant};
 
 use super::super::monitor::MonitorPermissionFuture;
 use super::super::{lock, KeyEventExtra};
 use super::runner::EventWrapper;
 use super::{backend, runner};
+use crate::dpi::PhysicalPosition;
 use crate::error::{NotSupportedError, RequestError};
-use crate::event::{ElementState, Event, KeyEvent, TouchPhase, WindowEvent};
+use crate::event::{ButtonSource, ElementState, Event, KeyEvent, TouchPhase, WindowEvent};
 use crate::event_loop::{
     ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEvents,
     EventLoopProxy as RootEventLoopProxy, OwnedDisplayHandle as CoreOwnedDisplayHandle,
 };
-use crate::keyboard::ModifiersState;
+use crate::keyboard::{ModifiersState, PhysicalKey};
 use crate::monitor::MonitorHandle as RootMonitorHandle;
 use crate::platform::web::{CustomCursorFuture, PollStrategy, WaitUntilStrategy};
+use crate::platform_impl::click::ClickCounter;
 use crate::platform_impl::platform::cursor::CustomCursor;
 use crate::platform_impl::web::event_loop::proxy::EventLoopProxy;
 use crate::platform_impl::Window;
@@ -240,6 +244,11 @@ impl ActiveEventLoop {
             }
         });
 
+        // Browsers don't expose the system double-click settings, so where they don't report the
+        // click count themselves the defaults are used with the time the events are handled at.
+        let click_counter = Rc::new(ClickCounter::default());
+        let click_start = Instant::now();
+
         canvas.on_pointer_move(
             {
                 let runner = self.runner.clone();
@@ -278,8 +287,9 @@ impl ActiveEventLoop {
                 let runner = self.runner.clone();
                 let has_focus = has_focus.clone();
                 let modifiers = self.modifiers.clone();
+                let click_counter = click_counter.clone();
 
-                move |active_modifiers, device_id, primary, position, state, button| {
+                move |active_modifiers, device_id, primary, position, state, button, detail| {
                     let modifiers =
                         (has_focus.get() && modifiers.get() != active_modifiers).then(|| {
                             modifiers.set(active_modifiers);
@@ -289,6 +299,9 @@ impl ActiveEventLoop {
                             }
                         });
 
+                    let click_count =
+                        click_count(&click_counter, click_start, state, position, &button, detail);
+
                     runner.send_events(modifiers.into_iter().chain([Event::WindowEvent {
                         window_id,
                         event: WindowEvent::PointerButton {
@@ -296,6 +309,7 @@ impl ActiveEventLoop {
                             primary,
                             state,
                             position,
+                            click_count,
                             button,
                         },
                     }]));
@@ -306,8 +320,9 @@ impl ActiveEventLoop {
         canvas.on_pointer_press({
             let runner = self.runner.clone();
             let modifiers = self.modifiers.clone();
+            let click_counter = click_counter.clone();
 
-            move |active_modifiers, device_id, primary, position, button| {
+            move |active_modifiers, device_id, primary, position, button, detail| {
                 let modifiers = (modifiers.get() != active_modifiers).then(|| {
                     modifiers.set(active_modifiers);
                     Event::WindowEvent {
@@ -316,6 +331,15 @@ impl ActiveEventLoop {
                     }
                 });
 
+                let click_count = click_count(
+                    &click_counter,
+                    click_start,
+                    ElementState::Pressed,
+                    position,
+                    &button,
+                    detail,
+                );
+
                 runner.send_events(modifiers.into_iter().chain(iter::once(Event::WindowEvent {
                     window_id,
                     event: WindowEvent::PointerButton {
@@ -323,6 +347,7 @@ impl ActiveEventLoop {
                         primary,
                         state: ElementState::Pressed,
                         position,
+                        click_count,
                         button,
                     },
                 })));
@@ -334,7 +359,7 @@ impl ActiveEventLoop {
             let has_focus = has_focus.clone();
             let modifiers = self.modifiers.clone();
 
-            move |active_modifiers, device_id, primary, position, button| {
+            move |active_modifiers, device_id, primary, position, button, detail| {
                 let modifiers =
                     (has_focus.get() && modifiers.get() != active_modifiers).then(|| {
                         modifiers.set(active_modifiers);
@@ -344,6 +369,15 @@ impl ActiveEventLoop {
                         }
                     });
 
+                let click_count = click_count(
+                    &click_counter,
+                    click_start,
+                    ElementState::Released,
+                    position,
+                    &button,
+                    detail,
+                );
+
                 runner.send_events(modifiers.into_iter().chain(iter::once(Event::WindowEvent {
                     window_id,
                     event: WindowEvent::PointerButton {
@@ -351,6 +385,7 @@ impl ActiveEventLoop {
                         primary,
                         state: ElementState::Released,
                         position,
+                        click_count,
                         button,
                     },
                 })));
@@ -422,10 +457,15 @@ impl ActiveEventLoop {
         );
 
         let runner = self.runner.clone();
-        canvas.on_intersection(move |is_intersecting| {
-            // only fire if visible while skipping the first event if it's intersecting
+        canvas.on_intersection(move |is_intersecting, ratio| {
+            canvas_clone.intersection_ratio.set(Some(ratio));
+
+            // only fire if visible and the intersection changed, while skipping the first event if
+            // it's intersecting
+            let was_intersecting = canvas_clone.is_intersecting.get();
             if backend::is_visible(runner.document())
-                && !(is_intersecting && canvas_clone.is_intersecting.get().is_none())
+                && was_intersecting != Some(is_intersecting)
+                && !(is_intersecting && was_intersecting.is_none())
             {
                 runner.send_event(Event::WindowEvent {
                     window_id,
@@ -458,6 +498,14 @@ impl ActiveEventLoop {
         self.runner.wait_until_strategy()
     }
 
+    pub(crate) fn set_automatic_canvas_resize(&self, automatic: bool) {
+        self.runner.set_automatic_canvas_resize(automatic)
+    }
+
+    pub(crate) fn automatic_canvas_resize(&self) -> bool {
+        self.runner.automatic_canvas_resize()
+    }
+
     pub(crate) fn is_cursor_lock_raw(&self) -> bool {
         lock::is_cursor_lock_raw(self.runner.navigator(), self.runner.document())
     }
@@ -531,6 +579,14 @@ impl RootActiveEventLoop for ActiveEventLoop {
         })
     }
 
+    fn accent_color(&self) -> Option<[u8; 4]> {
+        None
+    }
+
+    fn key_text(&self, _: PhysicalKey, _: ModifiersState) -> Option<SmolStr> {
+        None
+    }
+
     fn set_control_flow(&self, control_flow: ControlFlow) {
         self.runner.set_control_flow(control_flow)
     }
@@ -539,10 +595,28 @@ impl RootActiveEventLoop for ActiveEventLoop {
         self.runner.control_flow()
     }
 
+    fn set_idle_interval(&self, interval: Option<Duration>) -> Result<(), RequestError> {
+        self.runner.set_idle_interval(interval);
+        Ok(())
+    }
+
+    fn set_resize_coalescing(&self, enabled: bool) {
+        self.runner.set_resize_coalescing(enabled)
+    }
+
+    fn scroll_lines_to_pixels(&self, lines: f32) -> f32 {
+        lines * 3.0 * 16.0
+    }
+
     fn exit(&self) {
         self.runner.exit()
     }
 
+    fn exit_with_code(&self, _code: i32) {
+        // `EventLoop::run_app()` never returns on Web, so there is nowhere to report the code.
+        self.exit()
+    }
+
     fn exiting(&self) -> bool {
         self.runner.exiting()
     }
@@ -572,3 +646,26 @@ impl rwh_06::HasDisplayHandle for OwnedDisplayHandle {
         unsafe { Ok(rwh_06::DisplayHandle::borrow_raw(raw)) }
     }
 }
+
+/// The click count of a mouse button event, touch and unknown pointers always count `1`.
+///
+/// The count reported by the browser in `detail` is preferred, the counter still tracks every
+/// press for when it isn't reported.
+fn click_count(
+    counter: &ClickCounter,
+    start: Instant,
+    state: ElementState,
+    position: PhysicalPosition<f64>,
+    button: &ButtonSource,
+    detail: Option<u32>,
+) -> u32 {
+    let count = match (state, button) {
+        // Truncating the milliseconds wraps them around, which the counter handles.
+        (ElementState::Pressed, ButtonSource::Mouse(button)) => {
+            counter.press(*button, position, start.elapsed().as_millis() as u32)
+        },
+        (ElementState::Released, ButtonSource::Mouse(button)) => counter.release(*button),
+        _ => return 1,
+    };
+    detail.unwrap_or(count)
+}
@@ -19,8 +19,8 @@ use wasm_bindgen::prelude::wasm_bindgen;
 use wasm_bindgen::{JsCast, JsValue};
 use wasm_bindgen_futures::JsFuture;
 use web_sys::{
-    console, DomException, Navigator, OrientationLockType, OrientationType, PermissionState,
-    PermissionStatus, ScreenOrientation, Window,
+    console, DomException, EventTarget, Navigator, OrientationLockType, OrientationType,
+    PermissionState, PermissionStatus, ScreenOrientation, Window,
 };
 
 use super::event_loop::runner::WeakShared;
@@ -28,10 +28,12 @@ use super::main_thread::MainThreadMarker;
 use super::r#async::{Dispatcher, Notified, Notifier};
 use super::web_sys::{Engine, EventListenerHandle};
 use crate::dpi::{PhysicalPosition, PhysicalSize};
+use crate::event::Event;
 use crate::monitor::{MonitorHandle as RootMonitorHandle, VideoMode};
 use crate::platform::web::{
     MonitorPermissionError, Orientation, OrientationData, OrientationLock, OrientationLockError,
 };
+use crate::platform_impl::hotplug::MonitorChanges;
 
 #[derive(Clone, Eq)]
 pub struct MonitorHandle {
@@ -54,10 +56,22 @@ impl MonitorHandle {
         self.inner.queue(|inner| inner.position())
     }
 
+    pub fn work_area(&self) -> Option<(PhysicalPosition<i32>, PhysicalSize<u32>)> {
+        self.inner.queue(|inner| inner.work_area())
+    }
+
     pub fn name(&self) -> Option<String> {
         self.inner.queue(|inner| inner.name())
     }
 
+    pub fn physical_size_mm(&self) -> Option<(f64, f64)> {
+        None
+    }
+
+    pub fn is_primary(&self) -> bool {
+        self.inner.queue(|inner| inner.is_primary())
+    }
+
     pub fn current_video_mode(&self) -> Option<VideoMode> {
         Some(VideoMode {
             size: self.inner.queue(|inner| inner.size()),
@@ -282,6 +296,17 @@ impl Inner {
         }
     }
 
+    fn work_area(&self) -> Option<(PhysicalPosition<i32>, PhysicalSize<u32>)> {
+        if let Screen::Detailed { screen, .. } = &self.screen {
+            let position = PhysicalPosition::new(screen.avail_left(), screen.avail_top());
+            let width = screen.avail_width().unwrap();
+            let height = screen.avail_height().unwrap();
+            Some((position, self.to_physical_size(width, height)))
+        } else {
+            None
+        }
+    }
+
     fn name(&self) -> Option<String> {
         if let Screen::Detailed { screen, .. } = &self.screen {
             Some(screen.label())
@@ -334,6 +359,14 @@ impl Inner {
         }
     }
 
+    fn is_primary(&self) -> bool {
+        if let Screen::Detailed { screen, .. } = &self.screen {
+            screen.is_primary()
+        } else {
+            false
+        }
+    }
+
     fn is_detailed(&self) -> bool {
         matches!(self.screen, Screen::Detailed { .. })
     }
@@ -341,7 +374,10 @@ impl Inner {
     fn size(&self) -> PhysicalSize<u32> {
         let width = self.screen.width().unwrap();
         let height = self.screen.height().unwrap();
+        self.to_physical_size(width, height)
+    }
 
+    fn to_physical_size(&self, width: i32, height: i32) -> PhysicalSize<u32> {
         if let Some(Engine::Chromium) = self.engine {
             PhysicalSize::new(width, height).cast()
         } else {
@@ -399,6 +435,9 @@ pub struct MonitorHandler {
     window: WindowExt,
     engine: Option<Engine>,
     screen: ScreenExt,
+    /// The monitors reported so far, starting with the ones available once [`ScreenDetails`] was
+    /// retrieved, to report hotplug.
+    monitors: RefCell<Vec<MonitorHandle>>,
 }
 
 enum State {
@@ -413,6 +452,7 @@ struct Detailed {
     details: ScreenDetails,
     id_counter: u64,
     screens: Vec<(u64, Weak<ScreenDetailed>)>,
+    _screens_change_handle: EventListenerHandle<dyn Fn()>,
 }
 
 impl Detailed {
@@ -548,7 +588,15 @@ impl MonitorHandler {
             State::Unsupported
         };
 
-        Self { runner, state: RefCell::new(state), main_thread, window, engine, screen }
+        Self {
+            runner,
+            state: RefCell::new(state),
+            main_thread,
+            window,
+            engine,
+            screen,
+            monitors: RefCell::default(),
+        }
     }
 
     /// Listens to external permission changes and elevates [`MonitorHandle`] automatically.
@@ -588,8 +636,43 @@ impl MonitorHandler {
 
     /// Elevate [`MonitorHandler`] to [`ScreenDetails`].
     fn upgrade(&self, details: ScreenDetails) {
-        *self.state.borrow_mut() =
-            State::Detailed(Detailed { details, id_counter: 0, screens: Vec::new() });
+        let runner = self.runner.clone();
+        let handle = EventListenerHandle::new(
+            details.clone(),
+            "screenschange",
+            Closure::new(move || {
+                if let Some(runner) = runner.upgrade() {
+                    runner.monitor().screens_changed();
+                }
+            }),
+        );
+
+        *self.state.borrow_mut() = State::Detailed(Detailed {
+            details,
+            id_counter: 0,
+            screens: Vec::new(),
+            _screens_change_handle: handle,
+        });
+        *self.monitors.borrow_mut() = self.available_monitors();
+    }
+
+    /// Reports the monitors that were connected or disconnected since the last enumeration.
+    fn screens_changed(&self) {
+        let monitors = self.available_monitors();
+        let previous = self.monitors.replace(monitors.clone());
+        let changes = MonitorChanges::new(&previous, &monitors, MonitorHandle::eq);
+
+        if let Some(runner) = self.runner.upgrade() {
+            let disconnected = changes
+                .disconnected
+                .into_iter()
+                .map(|monitor| Event::MonitorDisconnected(monitor.into()));
+            let connected = changes
+                .connected
+                .into_iter()
+                .map(|monitor| Event::MonitorConnected(monitor.into()));
+            runner.send_events(disconnected.chain(connected));
+        }
     }
 
     pub fn is_extended(&self) -> Option<bool> {
@@ -869,6 +952,8 @@ extern "C" {
     #[wasm_bindgen(method, js_name = getScreenDetails)]
     fn screen_details(this: &WindowExt) -> Promise;
 
+    #[derive(Clone)]
+    #[wasm_bindgen(extends = EventTarget)]
     type ScreenDetails;
 
     #[wasm_bindgen(method, getter, js_name = currentScreen)]
@@ -915,6 +1000,12 @@ extern "C" {
     #[wasm_bindgen(method, getter)]
     fn top(this: &ScreenDetailed) -> i32;
 
+    #[wasm_bindgen(method, getter, js_name = availLeft)]
+    fn avail_left(this: &ScreenDetailed) -> i32;
+
+    #[wasm_bindgen(method, getter, js_name = availTop)]
+    fn avail_top(this: &ScreenDetailed) -> i32;
+
     #[wasm_bindgen(extends = Object)]
     type PermissionDescriptor;
 
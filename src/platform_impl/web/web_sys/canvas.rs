@@ -8,7 +8,7 @@ use wasm_bindgen::closure::Closure;
 use wasm_bindgen::JsCast;
 use web_sys::{
     CssStyleDeclaration, Document, Event, FocusEvent, HtmlCanvasElement, KeyboardEvent, Navigator,
-    PointerEvent, WheelEvent,
+    OffscreenCanvas, PointerEvent, WheelEvent,
 };
 
 use super::super::cursor::CursorHandler;
@@ -26,6 +26,7 @@ use crate::event::{
     SurfaceSizeWriter,
 };
 use crate::keyboard::{Key, KeyLocation, ModifiersState, PhysicalKey};
+use crate::platform::web::ScaleChangeSource;
 use crate::platform_impl::Fullscreen;
 use crate::window::{WindowAttributes, WindowId};
 
@@ -34,9 +35,13 @@ pub struct Canvas {
     main_thread: MainThreadMarker,
     common: Common,
     id: WindowId,
+    offscreen: Option<OffscreenCanvas>,
     pub has_focus: Rc<Cell<bool>>,
+    pub automatic_resize: Cell<bool>,
+    pub scale_change_source: Rc<Cell<ScaleChangeSource>>,
     pub prevent_default: Rc<Cell<bool>>,
     pub is_intersecting: Cell<Option<bool>>,
+    pub intersection_ratio: Cell<Option<f64>>,
     pub cursor: CursorHandler,
     handlers: RefCell<Handlers>,
 }
@@ -113,6 +118,12 @@ impl Canvas {
                 .map_err(|_| os_error!("Failed to set a tabindex"))?;
         }
 
+        let offscreen = match attr.platform_specific.offscreen_canvas.map(Arc::try_unwrap) {
+            Some(Ok(canvas)) => Some(canvas.into_inner(main_thread)),
+            Some(Err(canvas)) => Some(canvas.get(main_thread).clone()),
+            None => None,
+        };
+
         let style = Style::new(&window, &canvas);
 
         let cursor = CursorHandler::new(main_thread, canvas.clone(), style.clone());
@@ -127,19 +138,33 @@ impl Canvas {
             current_size: Rc::default(),
         };
 
-        if let Some(size) = attr.surface_size {
-            let size = size.to_logical(super::scale_factor(&common.window));
-            super::set_canvas_size(&common.document, &common.raw, &common.style, size);
-        }
-
-        if let Some(size) = attr.min_surface_size {
-            let size = size.to_logical(super::scale_factor(&common.window));
-            super::set_canvas_min_size(&common.document, &common.raw, &common.style, Some(size));
-        }
-
-        if let Some(size) = attr.max_surface_size {
-            let size = size.to_logical(super::scale_factor(&common.window));
-            super::set_canvas_max_size(&common.document, &common.raw, &common.style, Some(size));
+        let automatic_resize = attr.platform_specific.automatic_canvas_resize.unwrap_or(true);
+
+        if automatic_resize {
+            if let Some(size) = attr.surface_size {
+                let size = size.to_logical(super::scale_factor(&common.window));
+                super::set_canvas_size(&common.document, &common.raw, &common.style, size);
+            }
+
+            if let Some(size) = attr.min_surface_size {
+                let size = size.to_logical(super::scale_factor(&common.window));
+                super::set_canvas_min_size(
+                    &common.document,
+                    &common.raw,
+                    &common.style,
+                    Some(size),
+                );
+            }
+
+            if let Some(size) = attr.max_surface_size {
+                let size = size.to_logical(super::scale_factor(&common.window));
+                super::set_canvas_max_size(
+                    &common.document,
+                    &common.raw,
+                    &common.style,
+                    Some(size),
+                );
+            }
         }
 
         if let Some(position) = attr.position {
@@ -165,9 +190,13 @@ impl Canvas {
             main_thread,
             common,
             id,
+            offscreen,
             has_focus: Rc::new(Cell::new(false)),
+            automatic_resize: Cell::new(automatic_resize),
+            scale_change_source: Rc::default(),
             prevent_default: Rc::new(Cell::new(attr.platform_specific.prevent_default)),
             is_intersecting: Cell::new(None),
+            intersection_ratio: Cell::new(None),
             cursor,
             handlers: RefCell::new(Handlers {
                 animation_frame_handler: AnimationFrameHandler::new(window),
@@ -248,6 +277,10 @@ impl Canvas {
         &self.common.raw
     }
 
+    pub fn offscreen(&self) -> Option<&OffscreenCanvas> {
+        self.offscreen.as_ref()
+    }
+
     #[inline]
     pub fn style(&self) -> &Style {
         &self.common.style
@@ -348,7 +381,14 @@ impl Canvas {
     pub fn on_pointer_release<C>(&self, handler: C)
     where
         C: 'static
-            + FnMut(ModifiersState, Option<DeviceId>, bool, PhysicalPosition<f64>, ButtonSource),
+            + FnMut(
+                ModifiersState,
+                Option<DeviceId>,
+                bool,
+                PhysicalPosition<f64>,
+                ButtonSource,
+                Option<u32>,
+            ),
     {
         self.handlers.borrow_mut().pointer_handler.on_pointer_release(&self.common, handler)
     }
@@ -356,7 +396,14 @@ impl Canvas {
     pub fn on_pointer_press<C>(&self, handler: C)
     where
         C: 'static
-            + FnMut(ModifiersState, Option<DeviceId>, bool, PhysicalPosition<f64>, ButtonSource),
+            + FnMut(
+                ModifiersState,
+                Option<DeviceId>,
+                bool,
+                PhysicalPosition<f64>,
+                ButtonSource,
+                Option<u32>,
+            ),
     {
         self.handlers.borrow_mut().pointer_handler.on_pointer_press(
             &self.common,
@@ -382,6 +429,7 @@ impl Canvas {
                 PhysicalPosition<f64>,
                 ElementState,
                 ButtonSource,
+                Option<u32>,
             ),
     {
         self.handlers.borrow_mut().pointer_handler.on_pointer_move(
@@ -432,6 +480,7 @@ impl Canvas {
             self.document().clone(),
             self.raw().clone(),
             self.style().clone(),
+            Rc::clone(&self.scale_change_source),
             scale_handler,
             size_handler,
         ));
@@ -439,7 +488,7 @@ impl Canvas {
 
     pub(crate) fn on_intersection<F>(&self, handler: F)
     where
-        F: 'static + FnMut(bool),
+        F: 'static + FnMut(bool, f64),
     {
         self.handlers.borrow_mut().on_intersect =
             Some(IntersectionObserverHandle::new(self.raw(), handler));
@@ -506,6 +555,8 @@ impl Canvas {
             let new_size = *new_size.lock().unwrap();
             new_size
         };
+        // The requested size is ignored if the user manages the canvas size themselves.
+        let new_size = if self.automatic_resize.get() { new_size } else { current_size };
 
         if current_size != new_size {
             // Then we resize the canvas to the new size, a new `SurfaceResized` event will be sent
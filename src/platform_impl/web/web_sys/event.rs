@@ -7,7 +7,7 @@ use wasm_bindgen::{JsCast, JsValue};
 use web_sys::{KeyboardEvent, MouseEvent, Navigator, PointerEvent, WheelEvent};
 
 use super::Engine;
-use crate::event::{FingerId, MouseButton, MouseScrollDelta, PointerKind};
+use crate::event::{FingerId, MouseButton, MouseScrollDelta, PointerKind, PointerSource};
 use crate::keyboard::{Key, KeyLocation, ModifiersState, NamedKey, PhysicalKey};
 
 bitflags::bitflags! {
@@ -80,6 +80,12 @@ impl MouseButton {
     }
 }
 
+/// The click count reported by the browser in `UIEvent.detail`, pointer events leave it at `0` in
+/// some browsers.
+pub fn click_count(event: &MouseEvent) -> Option<u32> {
+    u32::try_from(event.detail()).ok().filter(|&count| count > 0)
+}
+
 pub fn mouse_position(event: &MouseEvent) -> LogicalPosition<f64> {
     #[wasm_bindgen]
     extern "C" {
@@ -164,10 +170,20 @@ pub fn pointer_type(event: &PointerEvent, pointer_id: i32) -> PointerKind {
     match event.pointer_type().as_str() {
         "mouse" => PointerKind::Mouse,
         "touch" => PointerKind::Touch(FingerId::from_raw(pointer_id as usize)),
+        "pen" => PointerKind::Pen,
         _ => PointerKind::Unknown,
     }
 }
 
+// https://www.w3.org/TR/pointerevents3/#pointerevent-interface
+pub fn pen_source(event: &PointerEvent) -> PointerSource {
+    PointerSource::Pen {
+        pressure: Some(event.pressure()),
+        tilt: Some((event.tilt_x() as f32, event.tilt_y() as f32)),
+        twist: Some(event.twist() as f32),
+    }
+}
+
 pub fn key_code(event: &KeyboardEvent) -> PhysicalKey {
     let code = event.code();
     PhysicalKey::from_key_code_attribute_value(&code)
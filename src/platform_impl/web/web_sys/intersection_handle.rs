@@ -1,7 +1,10 @@
 use js_sys::Array;
 use wasm_bindgen::prelude::Closure;
-use wasm_bindgen::JsCast;
-use web_sys::{Element, IntersectionObserver, IntersectionObserverEntry};
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{Element, IntersectionObserver, IntersectionObserverEntry, IntersectionObserverInit};
+
+/// The intersection ratios at which the callback is invoked, see `Window::occlusion()`.
+const THRESHOLDS: [f64; 5] = [0.0, 0.25, 0.5, 0.75, 1.0];
 
 pub(super) struct IntersectionObserverHandle {
     observer: IntersectionObserver,
@@ -11,15 +14,18 @@ pub(super) struct IntersectionObserverHandle {
 impl IntersectionObserverHandle {
     pub fn new<F>(element: &Element, mut callback: F) -> Self
     where
-        F: 'static + FnMut(bool),
+        F: 'static + FnMut(bool, f64),
     {
         let closure = Closure::new(move |entries: Array| {
             let entry: IntersectionObserverEntry = entries.get(0).unchecked_into();
-            callback(entry.is_intersecting());
+            callback(entry.is_intersecting(), entry.intersection_ratio());
         });
-        let observer = IntersectionObserver::new(closure.as_ref().unchecked_ref())
-            // we don't provide any `options`
-            .expect("Invalid `options`");
+        let options = IntersectionObserverInit::new();
+        let thresholds: Array = THRESHOLDS.into_iter().map(JsValue::from).collect();
+        options.set_threshold(&thresholds);
+        let observer =
+            IntersectionObserver::new_with_options(closure.as_ref().unchecked_ref(), &options)
+                .expect("Invalid `options`");
         observer.observe(element);
 
         Self { observer, _closure: closure }
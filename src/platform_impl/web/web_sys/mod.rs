@@ -16,7 +16,9 @@ use js_sys::Array;
 use wasm_bindgen::closure::Closure;
 use wasm_bindgen::prelude::wasm_bindgen;
 use wasm_bindgen::JsCast;
-use web_sys::{Document, HtmlCanvasElement, Navigator, PageTransitionEvent, VisibilityState};
+use web_sys::{
+    Document, HtmlCanvasElement, Navigator, OffscreenCanvas, PageTransitionEvent, VisibilityState,
+};
 
 pub use self::canvas::{Canvas, Style};
 pub use self::event_handle::EventListenerHandle;
@@ -164,6 +166,7 @@ pub fn is_visible(document: &Document) -> bool {
 }
 
 pub type RawCanvasType = HtmlCanvasElement;
+pub type RawOffscreenCanvasType = OffscreenCanvas;
 
 #[derive(Clone, Copy)]
 pub enum Engine {
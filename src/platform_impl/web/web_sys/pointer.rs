@@ -72,7 +72,14 @@ impl PointerHandler {
     pub fn on_pointer_release<C>(&mut self, canvas_common: &Common, mut handler: C)
     where
         C: 'static
-            + FnMut(ModifiersState, Option<DeviceId>, bool, PhysicalPosition<f64>, ButtonSource),
+            + FnMut(
+                ModifiersState,
+                Option<DeviceId>,
+                bool,
+                PhysicalPosition<f64>,
+                ButtonSource,
+                Option<u32>,
+            ),
     {
         let window = canvas_common.window.clone();
         self.on_pointer_release =
@@ -84,7 +91,7 @@ impl PointerHandler {
                 let button = event::mouse_button(&event).expect("no mouse button pressed");
 
                 let source = match kind {
-                    PointerKind::Mouse => ButtonSource::Mouse(button),
+                    PointerKind::Mouse | PointerKind::Pen => ButtonSource::Mouse(button),
                     PointerKind::Touch(finger_id) => ButtonSource::Touch {
                         finger_id,
                         force: Some(Force::Normalized(event.pressure().into())),
@@ -98,6 +105,7 @@ impl PointerHandler {
                     event.is_primary(),
                     event::mouse_position(&event).to_physical(super::scale_factor(&window)),
                     source,
+                    event::click_count(&event),
                 )
             }));
     }
@@ -109,7 +117,14 @@ impl PointerHandler {
         prevent_default: Rc<Cell<bool>>,
     ) where
         C: 'static
-            + FnMut(ModifiersState, Option<DeviceId>, bool, PhysicalPosition<f64>, ButtonSource),
+            + FnMut(
+                ModifiersState,
+                Option<DeviceId>,
+                bool,
+                PhysicalPosition<f64>,
+                ButtonSource,
+                Option<u32>,
+            ),
     {
         let window = canvas_common.window.clone();
         let canvas = canvas_common.raw().clone();
@@ -138,6 +153,7 @@ impl PointerHandler {
 
                         ButtonSource::Mouse(button)
                     },
+                    PointerKind::Pen => ButtonSource::Mouse(button),
                     PointerKind::Touch(finger_id) => ButtonSource::Touch {
                         finger_id,
                         force: Some(Force::Normalized(event.pressure().into())),
@@ -151,6 +167,7 @@ impl PointerHandler {
                     event.is_primary(),
                     event::mouse_position(&event).to_physical(super::scale_factor(&window)),
                     source,
+                    event::click_count(&event),
                 )
             }));
     }
@@ -177,6 +194,7 @@ impl PointerHandler {
                 PhysicalPosition<f64>,
                 ElementState,
                 ButtonSource,
+                Option<u32>,
             ),
     {
         let window = canvas_common.window.clone();
@@ -204,7 +222,7 @@ impl PointerHandler {
                     };
 
                     let button = match kind {
-                        PointerKind::Mouse => ButtonSource::Mouse(button),
+                        PointerKind::Mouse | PointerKind::Pen => ButtonSource::Mouse(button),
                         PointerKind::Touch(finger_id) => {
                             let button_id = button.to_id();
 
@@ -227,6 +245,7 @@ impl PointerHandler {
                         event::mouse_position(&event).to_physical(super::scale_factor(&window)),
                         state,
                         button,
+                        event::click_count(&event),
                     );
 
                     return;
@@ -244,6 +263,7 @@ impl PointerHandler {
                             event::mouse_position(&event).to_physical(scale),
                             match kind {
                                 PointerKind::Mouse => PointerSource::Mouse,
+                                PointerKind::Pen => event::pen_source(&event),
                                 PointerKind::Touch(finger_id) => PointerSource::Touch {
                                     finger_id,
                                     force: Some(Force::Normalized(event.pressure().into())),
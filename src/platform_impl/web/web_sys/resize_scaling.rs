@@ -14,6 +14,7 @@ use super::super::backend;
 use super::canvas::Style;
 use super::media_query_handle::MediaQueryListHandle;
 use crate::dpi::{LogicalSize, PhysicalSize};
+use crate::platform::web::ScaleChangeSource;
 
 pub struct ResizeScaleHandle(Rc<ResizeScaleInternal>);
 
@@ -23,6 +24,7 @@ impl ResizeScaleHandle {
         document: Document,
         canvas: HtmlCanvasElement,
         style: Style,
+        scale_change_source: Rc<Cell<ScaleChangeSource>>,
         scale_handler: S,
         resize_handler: R,
     ) -> Self
@@ -35,6 +37,7 @@ impl ResizeScaleHandle {
             document,
             canvas,
             style,
+            scale_change_source,
             scale_handler,
             resize_handler,
         ))
@@ -58,6 +61,10 @@ struct ResizeScaleInternal {
     scale_handler: Box<dyn Fn(PhysicalSize<u32>, f64)>,
     resize_handler: Box<dyn Fn(PhysicalSize<u32>)>,
     notify_scale: Cell<bool>,
+    scale_change_source: Rc<Cell<ScaleChangeSource>>,
+    /// The ratio between the outer and inner width of the browser window, as of the last resize
+    /// observation. Between two observations, it only changes with the zoom level.
+    zoom_ratio: Cell<Option<f64>>,
 }
 
 impl ResizeScaleInternal {
@@ -66,6 +73,7 @@ impl ResizeScaleInternal {
         document: Document,
         canvas: HtmlCanvasElement,
         style: Style,
+        scale_change_source: Rc<Cell<ScaleChangeSource>>,
         scale_handler: S,
         resize_handler: R,
     ) -> Rc<Self>
@@ -92,12 +100,14 @@ impl ResizeScaleInternal {
                         let scale = backend::scale_factor(&this.window);
                         (this.scale_handler)(size, scale)
                     } else {
-                        (this.resize_handler)(size)
+                        this.handle_resize(size)
                     }
                 }
             });
             let observer = Self::create_observer(&canvas, observer_closure.as_ref());
 
+            let zoom_ratio = Cell::new(zoom_ratio(&window));
+
             Self {
                 window,
                 document,
@@ -109,6 +119,8 @@ impl ResizeScaleInternal {
                 scale_handler: Box::new(scale_handler),
                 resize_handler: Box::new(resize_handler),
                 notify_scale: Cell::new(false),
+                scale_change_source,
+                zoom_ratio,
             }
         })
     }
@@ -156,7 +168,7 @@ impl ResizeScaleInternal {
                 let scale = backend::scale_factor(&self.window);
                 (self.scale_handler)(size, scale)
             } else {
-                (self.resize_handler)(size)
+                self.handle_resize(size)
             }
 
             return;
@@ -192,10 +204,17 @@ impl ResizeScaleInternal {
             let scale = backend::scale_factor(&self.window);
             (self.scale_handler)(size, scale)
         } else {
-            (self.resize_handler)(size)
+            self.handle_resize(size)
         }
     }
 
+    fn handle_resize(&self, size: PhysicalSize<u32>) {
+        // Resizing the browser window changes the zoom ratio too, which must not be mistaken for a
+        // zoom change on the next scale change.
+        self.zoom_ratio.set(zoom_ratio(&self.window));
+        (self.resize_handler)(size)
+    }
+
     fn handle_scale(self: Rc<Self>, mql: &MediaQueryList) {
         let weak_self = Rc::downgrade(&self);
         let scale = super::scale_factor(&self.window);
@@ -219,6 +238,14 @@ impl ResizeScaleInternal {
         });
         self.mql.replace(new_mql);
 
+        let new_ratio = zoom_ratio(&self.window);
+        let source = match (self.zoom_ratio.replace(new_ratio), new_ratio) {
+            (Some(old), Some(new)) if (old - new).abs() > 0.01 => ScaleChangeSource::BrowserZoom,
+            (Some(_), Some(_)) => ScaleChangeSource::DisplayChange,
+            _ => ScaleChangeSource::Unknown,
+        };
+        self.scale_change_source.set(source);
+
         self.notify_scale.set(true);
         self.notify();
     }
@@ -275,6 +302,15 @@ impl Drop for ResizeScaleInternal {
     }
 }
 
+/// The zoom level scales the inner size of the browser window, but not its outer size. Returns
+/// [`None`] if either isn't available.
+fn zoom_ratio(window: &Window) -> Option<f64> {
+    let outer = window.outer_width().ok()?.as_f64()?;
+    let inner = window.inner_width().ok()?.as_f64()?;
+
+    (outer > 0. && inner > 0.).then(|| outer / inner)
+}
+
 // TODO: Remove when Safari supports `devicePixelContentBoxSize`.
 // See <https://bugs.webkit.org/show_bug.cgi?id=219005>.
 pub fn has_device_pixel_support() -> bool {
@@ -26,6 +26,10 @@ enum Inner {
         window: web_sys::Window,
         handle: u32,
     },
+    AnimationFrame {
+        window: web_sys::Window,
+        handle: i32,
+    },
     Timeout {
         window: web_sys::Window,
         handle: i32,
@@ -44,6 +48,8 @@ impl Schedule {
             Self::new_scheduler(window, f, None)
         } else if strategy == PollStrategy::IdleCallback && has_idle_callback_support(window) {
             Self::new_idle_callback(window.clone(), f)
+        } else if strategy == PollStrategy::AnimationFrame {
+            Self::new_animation_frame(window.clone(), f)
         } else {
             Self::new_timeout(window.clone(), f, None)
         }
@@ -118,6 +124,18 @@ impl Schedule {
         Schedule { _closure: closure, inner: Inner::IdleCallback { window, handle } }
     }
 
+    fn new_animation_frame<F>(window: web_sys::Window, f: F) -> Schedule
+    where
+        F: 'static + FnMut(),
+    {
+        let closure = Closure::new(f);
+        let handle = window
+            .request_animation_frame(closure.as_ref().unchecked_ref())
+            .expect("Failed to request animation frame");
+
+        Schedule { _closure: closure, inner: Inner::AnimationFrame { window, handle } }
+    }
+
     fn new_timeout<F>(window: web_sys::Window, f: F, duration: Option<Duration>) -> Schedule
     where
         F: 'static + FnMut(),
@@ -214,6 +232,9 @@ impl Drop for Schedule {
         match &self.inner {
             Inner::Scheduler { controller, .. } => controller.abort(),
             Inner::IdleCallback { window, handle, .. } => window.cancel_idle_callback(*handle),
+            Inner::AnimationFrame { window, handle } => {
+                window.cancel_animation_frame(*handle).expect("Failed to cancel animation frame")
+            },
             Inner::Timeout { window, handle, port, .. } => {
                 window.clear_timeout_with_handle(*handle);
                 port.close();
@@ -3,8 +3,12 @@ use std::rc::Rc;
 use std::sync::Arc;
 
 use dpi::{LogicalPosition, LogicalSize};
-use web_sys::HtmlCanvasElement;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use web_sys::{HtmlCanvasElement, OffscreenCanvas};
+use web_time::Instant;
 
+use super::event_loop::runner;
 use super::main_thread::{MainThreadMarker, MainThreadSafe};
 use super::monitor::MonitorHandler;
 use super::r#async::Dispatcher;
@@ -13,10 +17,11 @@ use crate::dpi::{LogicalInsets, PhysicalInsets, PhysicalPosition, PhysicalSize,
 use crate::error::{NotSupportedError, RequestError};
 use crate::icon::Icon;
 use crate::monitor::MonitorHandle as RootMonitorHandle;
+use crate::platform::web::ScaleChangeSource;
 use crate::window::{
-    Cursor, CursorGrabMode, Fullscreen as RootFullscreen, ImePurpose, ResizeDirection, Theme,
-    UserAttentionType, Window as RootWindow, WindowAttributes, WindowButtons, WindowId,
-    WindowLevel,
+    Cursor, CursorGrabMode, Fullscreen as RootFullscreen, ImePurpose, RedrawPriority,
+    ResizeDirection, Theme, UserAttentionType, Window as RootWindow, WindowAttributes,
+    WindowButtons, WindowId, WindowLevel,
 };
 
 pub struct Window {
@@ -29,16 +34,21 @@ pub struct Inner {
     monitor: Rc<MonitorHandler>,
     safe_area: Rc<backend::SafeAreaHandle>,
     canvas: Rc<backend::Canvas>,
+    runner: runner::Shared,
     destroy_fn: Option<Box<dyn FnOnce()>>,
 }
 
 impl Window {
     pub(crate) fn new(
         target: &ActiveEventLoop,
-        attr: WindowAttributes,
+        mut attr: WindowAttributes,
     ) -> Result<Self, RequestError> {
         let id = target.generate_id();
 
+        attr.platform_specific
+            .automatic_canvas_resize
+            .get_or_insert_with(|| target.automatic_canvas_resize());
+
         let window = target.runner.window();
         let navigator = target.runner.navigator();
         let document = target.runner.document();
@@ -55,7 +65,10 @@ impl Window {
         target.register(&canvas, id);
 
         let runner = target.runner.clone();
-        let destroy_fn = Box::new(move || runner.notify_destroy_window(id));
+        let destroy_fn = Box::new({
+            let runner = runner.clone();
+            move || runner.notify_destroy_window(id)
+        });
 
         let inner = Inner {
             id,
@@ -63,6 +76,7 @@ impl Window {
             monitor: Rc::clone(target.runner.monitor()),
             safe_area: Rc::clone(target.runner.safe_area()),
             canvas,
+            runner,
             destroy_fn: Some(destroy_fn),
         };
 
@@ -78,6 +92,11 @@ impl Window {
             .map(|main_thread| Ref::map(self.inner.value(main_thread), |inner| inner.canvas.raw()))
     }
 
+    pub fn offscreen_canvas(&self) -> Option<Ref<'_, OffscreenCanvas>> {
+        let main_thread = MainThreadMarker::new()?;
+        Ref::filter_map(self.inner.value(main_thread), |inner| inner.canvas.offscreen()).ok()
+    }
+
     pub(crate) fn prevent_default(&self) -> bool {
         self.inner.queue(|inner| inner.canvas.prevent_default.get())
     }
@@ -86,11 +105,23 @@ impl Window {
         self.inner.dispatch(move |inner| inner.canvas.prevent_default.set(prevent_default))
     }
 
+    pub(crate) fn set_automatic_canvas_resize(&self, automatic: bool) {
+        self.inner.dispatch(move |inner| inner.canvas.automatic_resize.set(automatic))
+    }
+
+    pub(crate) fn automatic_canvas_resize(&self) -> bool {
+        self.inner.queue(|inner| inner.canvas.automatic_resize.get())
+    }
+
     pub(crate) fn is_cursor_lock_raw(&self) -> bool {
         self.inner.queue(move |inner| {
             lock::is_cursor_lock_raw(inner.canvas.navigator(), inner.canvas.document())
         })
     }
+
+    pub(crate) fn last_scale_change_source(&self) -> ScaleChangeSource {
+        self.inner.queue(|inner| inner.canvas.scale_change_source.get())
+    }
 }
 
 impl RootWindow for Window {
@@ -106,6 +137,14 @@ impl RootWindow for Window {
         self.inner.dispatch(|inner| inner.canvas.request_animation_frame())
     }
 
+    fn request_redraw_with_priority(&self, _: RedrawPriority) {
+        self.request_redraw();
+    }
+
+    fn request_redraw_at(&self, deadline: Instant) {
+        self.inner.dispatch(move |inner| inner.runner.request_redraw_at(inner.id, deadline))
+    }
+
     fn pre_present_notify(&self) {}
 
     fn reset_dead_keys(&self) {
@@ -139,6 +178,10 @@ impl RootWindow for Window {
 
     fn request_surface_size(&self, size: Size) -> Option<PhysicalSize<u32>> {
         self.inner.queue(|inner| {
+            if !inner.canvas.automatic_resize.get() {
+                return None;
+            }
+
             let size = size.to_logical(self.scale_factor());
             backend::set_canvas_size(
                 inner.canvas.document(),
@@ -185,6 +228,10 @@ impl RootWindow for Window {
 
     fn set_min_surface_size(&self, min_size: Option<Size>) {
         self.inner.dispatch(move |inner| {
+            if !inner.canvas.automatic_resize.get() {
+                return;
+            }
+
             let dimensions = min_size.map(|min_size| min_size.to_logical(inner.scale_factor()));
             backend::set_canvas_min_size(
                 inner.canvas.document(),
@@ -197,6 +244,10 @@ impl RootWindow for Window {
 
     fn set_max_surface_size(&self, max_size: Option<Size>) {
         self.inner.dispatch(move |inner| {
+            if !inner.canvas.automatic_resize.get() {
+                return;
+            }
+
             let dimensions = max_size.map(|dimensions| dimensions.to_logical(inner.scale_factor()));
             backend::set_canvas_max_size(
                 inner.canvas.document(),
@@ -207,6 +258,14 @@ impl RootWindow for Window {
         })
     }
 
+    fn min_surface_size(&self) -> Option<PhysicalSize<u32>> {
+        None
+    }
+
+    fn max_surface_size(&self) -> Option<PhysicalSize<u32>> {
+        None
+    }
+
     fn surface_resize_increments(&self) -> Option<PhysicalSize<u32>> {
         None
     }
@@ -219,6 +278,10 @@ impl RootWindow for Window {
         self.inner.queue(|inner| inner.canvas.set_attribute("alt", title))
     }
 
+    fn set_title_with_subtitle(&self, title: &str, _: Option<&str>) {
+        self.set_title(title);
+    }
+
     fn set_transparent(&self, _: bool) {}
 
     fn set_blur(&self, _: bool) {}
@@ -231,6 +294,19 @@ impl RootWindow for Window {
         None
     }
 
+    fn occlusion(&self) -> Option<f32> {
+        self.inner.queue(|inner| {
+            if !backend::is_visible(inner.canvas.document()) {
+                return Some(1.0);
+            }
+
+            // The intersection ratio is only updated when crossing a multiple of `0.25`, so round
+            // it to the nearest one instead of reporting a stale value in between.
+            let ratio = inner.canvas.intersection_ratio.get()?;
+            Some(1.0 - (ratio * 4.0).round() as f32 / 4.0)
+        })
+    }
+
     fn set_resizable(&self, _: bool) {
         // Intentionally a no-op: users can't resize canvas elements
     }
@@ -311,6 +387,8 @@ impl RootWindow for Window {
         // Currently not implemented
     }
 
+    fn set_ime_theme(&self, _: Option<Theme>) {}
+
     fn focus_window(&self) {
         self.inner.dispatch(|inner| {
             let _ = inner.canvas.raw().focus();
@@ -339,7 +417,9 @@ impl RootWindow for Window {
         })
     }
 
-    fn set_content_protected(&self, _: bool) {}
+    fn set_content_protected(&self, _: bool) -> bool {
+        false
+    }
 
     fn title(&self) -> String {
         String::new()
@@ -371,6 +451,8 @@ impl RootWindow for Window {
         })?)
     }
 
+    fn set_cursor_grab_restore_on_focus(&self, _: bool) {}
+
     fn set_cursor_visible(&self, visible: bool) {
         self.inner.dispatch(move |inner| inner.canvas.cursor.set_cursor_visible(visible))
     }
@@ -420,17 +502,26 @@ impl rwh_06::HasWindowHandle for Window {
         MainThreadMarker::new()
             .map(|main_thread| {
                 let inner = self.inner.value(main_thread);
-                // SAFETY: This will only work if the reference to `HtmlCanvasElement` stays valid.
-                let canvas: &wasm_bindgen::JsValue = inner.canvas.raw();
-                let window_handle =
-                    rwh_06::WebCanvasWindowHandle::new(std::ptr::NonNull::from(canvas).cast());
+                let raw = if let Some(canvas) = inner.canvas.offscreen() {
+                    // SAFETY: This will only work if the reference to `OffscreenCanvas` stays
+                    // valid.
+                    let canvas: &wasm_bindgen::JsValue = canvas;
+                    rwh_06::RawWindowHandle::WebOffscreenCanvas(
+                        rwh_06::WebOffscreenCanvasWindowHandle::new(
+                            std::ptr::NonNull::from(canvas).cast(),
+                        ),
+                    )
+                } else {
+                    // SAFETY: This will only work if the reference to `HtmlCanvasElement` stays
+                    // valid.
+                    let canvas: &wasm_bindgen::JsValue = inner.canvas.raw();
+                    rwh_06::RawWindowHandle::WebCanvas(rwh_06::WebCanvasWindowHandle::new(
+                        std::ptr::NonNull::from(canvas).cast(),
+                    ))
+                };
                 // SAFETY: The pointer won't be invalidated as long as `Window` lives, which the
                 // lifetime is bound to.
-                unsafe {
-                    rwh_06::WindowHandle::borrow_raw(rwh_06::RawWindowHandle::WebCanvas(
-                        window_handle,
-                    ))
-                }
+                unsafe { rwh_06::WindowHandle::borrow_raw(raw) }
             })
             .ok_or(rwh_06::HandleError::Unavailable)
     }
@@ -456,12 +547,18 @@ impl Drop for Inner {
         }
     }
 }
+/// The canvases are skipped by `serde`, as they only make sense for the current session.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default))]
 pub struct PlatformSpecificWindowAttributes {
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub(crate) canvas: Option<Arc<MainThreadSafe<backend::RawCanvasType>>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) offscreen_canvas: Option<Arc<MainThreadSafe<backend::RawOffscreenCanvasType>>>,
     pub(crate) prevent_default: bool,
     pub(crate) focusable: bool,
     pub(crate) append: bool,
+    pub(crate) automatic_canvas_resize: Option<bool>,
 }
 
 impl PartialEq for PlatformSpecificWindowAttributes {
@@ -470,9 +567,14 @@ impl PartialEq for PlatformSpecificWindowAttributes {
             (Some(this), Some(other)) => Arc::ptr_eq(this, other),
             (None, None) => true,
             _ => false,
+        }) && (match (&self.offscreen_canvas, &other.offscreen_canvas) {
+            (Some(this), Some(other)) => Arc::ptr_eq(this, other),
+            (None, None) => true,
+            _ => false,
         }) && self.prevent_default.eq(&other.prevent_default)
             && self.focusable.eq(&other.focusable)
             && self.append.eq(&other.append)
+            && self.automatic_canvas_resize.eq(&other.automatic_canvas_resize)
     }
 }
 
@@ -488,10 +590,29 @@ impl PlatformSpecificWindowAttributes {
 
         self.canvas = Some(Arc::new(MainThreadSafe::new(main_thread, canvas)));
     }
+
+    pub(crate) fn set_offscreen_canvas(&mut self, canvas: Option<backend::RawOffscreenCanvasType>) {
+        let Some(canvas) = canvas else {
+            self.offscreen_canvas = None;
+            return;
+        };
+
+        let main_thread = MainThreadMarker::new()
+            .expect("received an `OffscreenCanvas` outside the window context");
+
+        self.offscreen_canvas = Some(Arc::new(MainThreadSafe::new(main_thread, canvas)));
+    }
 }
 
 impl Default for PlatformSpecificWindowAttributes {
     fn default() -> Self {
-        Self { canvas: None, prevent_default: true, focusable: true, append: false }
+        Self {
+            canvas: None,
+            offscreen_canvas: None,
+            prevent_default: true,
+            focusable: true,
+            append: false,
+            automatic_canvas_resize: None,
+        }
     }
 }
@@ -1,9 +1,11 @@
+use std::sync::OnceLock;
 /// This is a simple implementation of support for Windows Dark Mode,
 /// which is inspired by the solution in https://github.com/ysc3839/win32-darkmode
 use std::{ffi::c_void, ptr};
 
 use windows_sys::core::PCSTR;
 use windows_sys::Win32::Foundation::{BOOL, HWND, NTSTATUS, S_OK};
+use windows_sys::Win32::Graphics::Dwm::DwmGetColorizationColor;
 use windows_sys::Win32::System::LibraryLoader::{GetProcAddress, LoadLibraryA};
 use windows_sys::Win32::System::SystemInformation::OSVERSIONINFOW;
 use windows_sys::Win32::UI::Accessibility::{HCF_HIGHCONTRASTON, HIGHCONTRASTA};
@@ -14,7 +16,7 @@ use super::util;
 use crate::utils::Lazy;
 use crate::window::Theme;
 
-static WIN10_BUILD_VERSION: Lazy<Option<u32>> = Lazy::new(|| {
+pub(crate) static WIN10_BUILD_VERSION: Lazy<Option<u32>> = Lazy::new(|| {
     type RtlGetVersion = unsafe extern "system" fn(*mut OSVERSIONINFOW) -> NTSTATUS;
     let handle = get_function!("ntdll.dll", RtlGetVersion);
 
@@ -54,10 +56,55 @@ static DARK_MODE_SUPPORTED: Lazy<bool> = Lazy::new(|| {
 static DARK_THEME_NAME: Lazy<Vec<u16>> = Lazy::new(|| util::encode_wide("DarkMode_Explorer"));
 static LIGHT_THEME_NAME: Lazy<Vec<u16>> = Lazy::new(|| util::encode_wide(""));
 
+/// The theme set with `EventLoopBuilder::with_theme`, used for windows without a preferred theme.
+static APP_THEME: OnceLock<Theme> = OnceLock::new();
+
+/// Set the theme of the whole application, including system menus and dialogs.
+pub fn set_app_theme(theme: Option<Theme>) {
+    let Some(theme) = theme else {
+        return;
+    };
+
+    let _ = APP_THEME.set(theme);
+
+    // `SetPreferredAppMode` is only available from Windows 10 1903 onwards.
+    if !WIN10_BUILD_VERSION.is_some_and(|v| v >= 18362) {
+        return;
+    }
+
+    // Uses Windows undocumented API SetPreferredAppMode,
+    // as seen in win32-darkmode example linked at top of file.
+    type SetPreferredAppMode = unsafe extern "system" fn(u32) -> u32;
+    const FORCE_DARK: u32 = 2;
+    const FORCE_LIGHT: u32 = 3;
+
+    let set_preferred_app_mode: Option<SetPreferredAppMode> = unsafe {
+        const UXTHEME_SETPREFERREDAPPMODE_ORDINAL: PCSTR = 135 as PCSTR;
+
+        let module = LoadLibraryA("uxtheme.dll\0".as_ptr());
+
+        if module == 0 {
+            return;
+        }
+
+        GetProcAddress(module, UXTHEME_SETPREFERREDAPPMODE_ORDINAL)
+            .map(|handle| std::mem::transmute(handle))
+    };
+
+    if let Some(set_preferred_app_mode) = set_preferred_app_mode {
+        let mode = match theme {
+            Theme::Dark => FORCE_DARK,
+            Theme::Light => FORCE_LIGHT,
+        };
+        unsafe { set_preferred_app_mode(mode) };
+    }
+}
+
 /// Attempt to set a theme on a window, if necessary.
 /// Returns the theme that was picked
 pub fn try_theme(hwnd: HWND, preferred_theme: Option<Theme>) -> Theme {
     if *DARK_MODE_SUPPORTED {
+        let preferred_theme = preferred_theme.or_else(|| APP_THEME.get().copied());
         let is_dark_mode = match preferred_theme {
             Some(theme) => theme == Theme::Dark,
             None => should_use_dark_mode(),
@@ -161,3 +208,18 @@ fn is_high_contrast() -> bool {
 
     ok != false.into() && util::has_flag(hc.dwFlags, HCF_HIGHCONTRASTON)
 }
+
+/// The DWM colorization color, which is the accent color used for the window frames.
+pub fn accent_color() -> Option<[u8; 4]> {
+    let mut color: u32 = 0;
+    let mut opaque_blend: BOOL = false.into();
+    let result = unsafe { DwmGetColorizationColor(&mut color, &mut opaque_blend) };
+    (result == S_OK).then(|| colorization_to_rgba(color))
+}
+
+/// Convert a `0xAARRGGBB` colorization color.
+pub fn colorization_to_rgba(color: u32) -> [u8; 4] {
+    let [_, red, green, blue] = color.to_be_bytes();
+    // The alpha is the intensity of the colorization, not part of the accent color itself.
+    [red, green, blue, u8::MAX]
+}
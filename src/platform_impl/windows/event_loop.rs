@@ -6,12 +6,13 @@ use std::cell::Cell;
 use std::ffi::c_void;
 use std::os::windows::io::{AsRawHandle as _, FromRawHandle as _, OwnedHandle, RawHandle};
 use std::rc::Rc;
-use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::{Arc, Mutex, MutexGuard};
 use std::time::{Duration, Instant};
 use std::{mem, panic, ptr};
 
 use runner::EventLoopRunner;
+use smol_str::SmolStr;
 use windows_sys::Win32::Devices::HumanInterfaceDevice::MOUSE_MOVE_RELATIVE;
 use windows_sys::Win32::Foundation::{
     GetLastError, FALSE, HANDLE, HWND, LPARAM, LRESULT, POINT, RECT, WAIT_FAILED, WPARAM,
@@ -28,7 +29,7 @@ use windows_sys::Win32::System::Threading::{
 use windows_sys::Win32::UI::Controls::{HOVER_DEFAULT, WM_MOUSELEAVE};
 use windows_sys::Win32::UI::Input::Ime::{GCS_COMPSTR, GCS_RESULTSTR, ISC_SHOWUICOMPOSITIONWINDOW};
 use windows_sys::Win32::UI::Input::KeyboardAndMouse::{
-    ReleaseCapture, SetCapture, TrackMouseEvent, TME_LEAVE, TRACKMOUSEEVENT,
+    GetDoubleClickTime, ReleaseCapture, SetCapture, TrackMouseEvent, TME_LEAVE, TRACKMOUSEEVENT,
 };
 use windows_sys::Win32::UI::Input::Pointer::{
     POINTER_FLAG_DOWN, POINTER_FLAG_PRIMARY, POINTER_FLAG_UP, POINTER_FLAG_UPDATE,
@@ -40,22 +41,24 @@ use windows_sys::Win32::UI::Input::Touch::{
 use windows_sys::Win32::UI::Input::{RAWINPUT, RIM_TYPEKEYBOARD, RIM_TYPEMOUSE};
 use windows_sys::Win32::UI::WindowsAndMessaging::{
     CreateWindowExW, DefWindowProcW, DestroyWindow, DispatchMessageW, GetClientRect, GetCursorPos,
-    GetMenu, LoadCursorW, MsgWaitForMultipleObjectsEx, PeekMessageW, PostMessageW,
-    RegisterClassExW, RegisterWindowMessageA, SetCursor, SetWindowPos, TranslateMessage,
-    CREATESTRUCTW, GWL_STYLE, GWL_USERDATA, HTCAPTION, HTCLIENT, MINMAXINFO, MNC_CLOSE, MSG,
-    MWMO_INPUTAVAILABLE, NCCALCSIZE_PARAMS, PM_REMOVE, PT_TOUCH, QS_ALLINPUT, RI_MOUSE_HWHEEL,
-    RI_MOUSE_WHEEL, SC_MINIMIZE, SC_RESTORE, SIZE_MAXIMIZED, SWP_NOACTIVATE, SWP_NOMOVE,
-    SWP_NOSIZE, SWP_NOZORDER, WHEEL_DELTA, WINDOWPOS, WMSZ_BOTTOM, WMSZ_BOTTOMLEFT,
-    WMSZ_BOTTOMRIGHT, WMSZ_LEFT, WMSZ_RIGHT, WMSZ_TOP, WMSZ_TOPLEFT, WMSZ_TOPRIGHT,
-    WM_CAPTURECHANGED, WM_CLOSE, WM_CREATE, WM_DESTROY, WM_DPICHANGED, WM_ENTERSIZEMOVE,
-    WM_EXITSIZEMOVE, WM_GETMINMAXINFO, WM_IME_COMPOSITION, WM_IME_ENDCOMPOSITION,
-    WM_IME_SETCONTEXT, WM_IME_STARTCOMPOSITION, WM_INPUT, WM_KEYDOWN, WM_KEYUP, WM_KILLFOCUS,
-    WM_LBUTTONDOWN, WM_LBUTTONUP, WM_MBUTTONDOWN, WM_MBUTTONUP, WM_MENUCHAR, WM_MOUSEHWHEEL,
-    WM_MOUSEMOVE, WM_MOUSEWHEEL, WM_NCACTIVATE, WM_NCCALCSIZE, WM_NCCREATE, WM_NCDESTROY,
-    WM_NCLBUTTONDOWN, WM_PAINT, WM_POINTERDOWN, WM_POINTERUP, WM_POINTERUPDATE, WM_RBUTTONDOWN,
-    WM_RBUTTONUP, WM_SETCURSOR, WM_SETFOCUS, WM_SETTINGCHANGE, WM_SIZE, WM_SIZING, WM_SYSCOMMAND,
-    WM_SYSKEYDOWN, WM_SYSKEYUP, WM_TOUCH, WM_WINDOWPOSCHANGED, WM_WINDOWPOSCHANGING,
-    WM_XBUTTONDOWN, WM_XBUTTONUP, WNDCLASSEXW, WS_EX_LAYERED, WS_EX_NOACTIVATE, WS_EX_TOOLWINDOW,
+    GetMenu, GetMessageTime, GetSystemMetrics, MsgWaitForMultipleObjectsEx, PeekMessageW,
+    PostMessageW, RegisterClassExW, RegisterWindowMessageA, SetCursor, SetWindowPos,
+    SystemParametersInfoW, TranslateMessage, WindowFromPoint, CREATESTRUCTW, GWL_STYLE,
+    GWL_USERDATA, HTCAPTION, HTCLIENT, MINMAXINFO, MNC_CLOSE, MSG, MWMO_INPUTAVAILABLE,
+    NCCALCSIZE_PARAMS, PM_REMOVE, PT_TOUCH, QS_ALLINPUT, RI_MOUSE_HWHEEL, RI_MOUSE_WHEEL,
+    SC_MINIMIZE, SC_RESTORE, SIZE_MAXIMIZED, SM_CXDOUBLECLK, SM_CYDOUBLECLK,
+    SPI_GETWHEELSCROLLLINES, SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE, SWP_NOZORDER, WHEEL_DELTA,
+    WHEEL_PAGESCROLL, WINDOWPOS, WMSZ_BOTTOM, WMSZ_BOTTOMLEFT, WMSZ_BOTTOMRIGHT, WMSZ_LEFT,
+    WMSZ_RIGHT, WMSZ_TOP, WMSZ_TOPLEFT, WMSZ_TOPRIGHT, WM_CAPTURECHANGED, WM_CLOSE, WM_CREATE,
+    WM_DESTROY, WM_DPICHANGED, WM_DWMCOLORIZATIONCOLORCHANGED, WM_ENTERSIZEMOVE, WM_EXITSIZEMOVE,
+    WM_GETMINMAXINFO, WM_IME_COMPOSITION, WM_IME_ENDCOMPOSITION, WM_IME_SETCONTEXT,
+    WM_IME_STARTCOMPOSITION, WM_INPUT, WM_KEYDOWN, WM_KEYUP, WM_KILLFOCUS, WM_LBUTTONDOWN,
+    WM_LBUTTONUP, WM_MBUTTONDOWN, WM_MBUTTONUP, WM_MENUCHAR, WM_MOUSEHWHEEL, WM_MOUSEMOVE,
+    WM_MOUSEWHEEL, WM_NCACTIVATE, WM_NCCALCSIZE, WM_NCCREATE, WM_NCDESTROY, WM_NCLBUTTONDOWN,
+    WM_PAINT, WM_POINTERDOWN, WM_POINTERUP, WM_POINTERUPDATE, WM_RBUTTONDOWN, WM_RBUTTONUP,
+    WM_SETCURSOR, WM_SETFOCUS, WM_SETTINGCHANGE, WM_SIZE, WM_SIZING, WM_SYSCOMMAND, WM_SYSKEYDOWN,
+    WM_SYSKEYUP, WM_TIMER, WM_TOUCH, WM_WINDOWPOSCHANGED, WM_WINDOWPOSCHANGING, WM_XBUTTONDOWN,
+    WM_XBUTTONUP, WNDCLASSEXW, WS_EX_LAYERED, WS_EX_NOACTIVATE, WS_EX_TOOLWINDOW,
     WS_EX_TRANSPARENT, WS_OVERLAPPED, WS_POPUP, WS_VISIBLE,
 };
 
@@ -65,20 +68,22 @@ use crate::application::ApplicationHandler;
 use crate::dpi::{PhysicalPosition, PhysicalSize};
 use crate::error::{EventLoopError, RequestError};
 use crate::event::{
-    Event, FingerId, Force, Ime, RawKeyEvent, SurfaceSizeWriter, TouchPhase, WindowEvent,
+    Event, FingerId, Force, Ime, Modifiers, MouseButton, RawKeyEvent, SurfaceSizeWriter,
+    TouchPhase, WindowEvent,
 };
 use crate::event_loop::{
     ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEvents,
     EventLoopProxy as RootEventLoopProxy, EventLoopProxyProvider,
     OwnedDisplayHandle as CoreOwnedDisplayHandle,
 };
-use crate::keyboard::ModifiersState;
+use crate::keyboard::{ModifiersState, PhysicalKey};
 use crate::monitor::MonitorHandle as RootMonitorHandle;
 use crate::platform::pump_events::PumpStatus;
+use crate::platform_impl::click::ClickCounter;
 use crate::platform_impl::platform::dark_mode::try_theme;
 use crate::platform_impl::platform::dpi::{become_dpi_aware, dpi_to_scale_factor};
 use crate::platform_impl::platform::drop_handler::FileDropHandler;
-use crate::platform_impl::platform::icon::WinCursor;
+use crate::platform_impl::platform::icon::{WinCursor, CURSOR_ANIMATION_TIMER_ID};
 use crate::platform_impl::platform::ime::ImeContext;
 use crate::platform_impl::platform::keyboard::KeyEventBuilder;
 use crate::platform_impl::platform::keyboard_layout::LAYOUT_CACHE;
@@ -102,6 +107,7 @@ pub(crate) struct WindowData {
     pub _file_drop_handler: Option<FileDropHandler>,
     pub userdata_removed: Cell<bool>,
     pub recurse_depth: Cell<u32>,
+    pub click_counter: ClickCounter,
 }
 
 impl WindowData {
@@ -144,11 +150,12 @@ pub(crate) struct PlatformSpecificEventLoopAttributes {
     pub(crate) any_thread: bool,
     pub(crate) dpi_aware: bool,
     pub(crate) msg_hook: Option<Box<dyn FnMut(*const c_void) -> bool + 'static>>,
+    pub(crate) theme: Option<Theme>,
 }
 
 impl Default for PlatformSpecificEventLoopAttributes {
     fn default() -> Self {
-        Self { any_thread: false, dpi_aware: true, msg_hook: None }
+        Self { any_thread: false, dpi_aware: true, msg_hook: None, theme: None }
     }
 }
 
@@ -156,6 +163,7 @@ impl PartialEq for PlatformSpecificEventLoopAttributes {
     fn eq(&self, other: &Self) -> bool {
         self.any_thread.eq(&other.any_thread)
             && self.dpi_aware.eq(&other.dpi_aware)
+            && self.theme.eq(&other.theme)
             && match (&self.msg_hook, &other.msg_hook) {
                 (Some(this), Some(other)) => std::ptr::eq(&this, &other),
                 (None, None) => true,
@@ -170,6 +178,7 @@ impl std::hash::Hash for PlatformSpecificEventLoopAttributes {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         self.any_thread.hash(state);
         self.dpi_aware.hash(state);
+        self.theme.hash(state);
         std::ptr::hash(&self.msg_hook, state);
     }
 }
@@ -178,6 +187,7 @@ pub struct ActiveEventLoop {
     thread_id: u32,
     thread_msg_target: HWND,
     pub(crate) runner_shared: Rc<EventLoopRunner>,
+    event_loop_proxy: Arc<EventLoopProxy>,
 }
 
 impl EventLoop {
@@ -199,6 +209,8 @@ impl EventLoop {
             become_dpi_aware();
         }
 
+        super::dark_mode::set_app_theme(attributes.theme);
+
         let thread_msg_target = create_event_target_window();
 
         let runner_shared = Rc::new(EventLoopRunner::new(thread_msg_target));
@@ -210,7 +222,12 @@ impl EventLoop {
         );
 
         Ok(EventLoop {
-            window_target: ActiveEventLoop { thread_id, thread_msg_target, runner_shared },
+            window_target: ActiveEventLoop {
+                thread_id,
+                thread_msg_target,
+                runner_shared,
+                event_loop_proxy: Arc::new(EventLoopProxy::new(thread_msg_target)),
+            },
             msg_hook: attributes.msg_hook.take(),
             high_resolution_timer: None,
         })
@@ -249,9 +266,16 @@ impl EventLoop {
                     Event::Suspended => app.suspended(event_loop_windows_ref),
                     Event::Resumed => app.resumed(event_loop_windows_ref),
                     Event::CreateSurfaces => app.can_create_surfaces(event_loop_windows_ref),
+                    Event::IdleTick => app.idle_tick(event_loop_windows_ref),
                     Event::AboutToWait => app.about_to_wait(event_loop_windows_ref),
                     Event::LoopExiting => app.exiting(event_loop_windows_ref),
                     Event::MemoryWarning => app.memory_warning(event_loop_windows_ref),
+                    Event::MonitorConnected(monitor) => {
+                        app.monitor_connected(event_loop_windows_ref, monitor)
+                    },
+                    Event::MonitorDisconnected(monitor) => {
+                        app.monitor_disconnected(event_loop_windows_ref, monitor)
+                    },
                 });
             }
         }
@@ -316,9 +340,16 @@ impl EventLoop {
                     Event::Suspended => app.suspended(event_loop_windows_ref),
                     Event::Resumed => app.resumed(event_loop_windows_ref),
                     Event::CreateSurfaces => app.can_create_surfaces(event_loop_windows_ref),
+                    Event::IdleTick => app.idle_tick(event_loop_windows_ref),
                     Event::AboutToWait => app.about_to_wait(event_loop_windows_ref),
                     Event::LoopExiting => app.exiting(event_loop_windows_ref),
                     Event::MemoryWarning => app.memory_warning(event_loop_windows_ref),
+                    Event::MonitorConnected(monitor) => {
+                        app.monitor_connected(event_loop_windows_ref, monitor)
+                    },
+                    Event::MonitorDisconnected(monitor) => {
+                        app.monitor_disconnected(event_loop_windows_ref, monitor)
+                    },
                 });
 
                 runner.wakeup();
@@ -377,9 +408,23 @@ impl EventLoop {
         // `MsgWaitForMultipleObjectsEx`.
         //
         runner.prepare_wait();
-        wait_for_messages_impl(&mut self.high_resolution_timer, runner.control_flow(), timeout);
+        let now = Instant::now();
+        let redraw_timeout = runner.scheduled_redraws.timeout(now);
+        let idle_timeout = runner.idle_timer.timeout(now);
+        wait_for_messages_impl(
+            &mut self.high_resolution_timer,
+            runner.control_flow(),
+            min_timeout(min_timeout(timeout, redraw_timeout), idle_timeout),
+        );
         // Before we potentially exit, make sure to consistently emit an event for the wake up
         runner.wakeup();
+
+        // Turn the scheduled redraws that are due into `WM_PAINT` messages, which are dispatched
+        // right after.
+        for window_id in runner.scheduled_redraws.take_due(Instant::now()) {
+            let window = window_id.into_raw() as HWND;
+            unsafe { RedrawWindow(window, ptr::null(), 0, RDW_INTERNALPAINT) };
+        }
     }
 
     /// Dispatch all queued messages via `PeekMessageW`
@@ -439,6 +484,7 @@ impl EventLoop {
 
 impl Drop for EventLoop {
     fn drop(&mut self) {
+        self.window_target.event_loop_proxy.alive.store(false, Ordering::Relaxed);
         unsafe {
             DestroyWindow(self.window_target.thread_msg_target);
         }
@@ -462,8 +508,7 @@ impl ActiveEventLoop {
 
 impl RootActiveEventLoop for ActiveEventLoop {
     fn create_proxy(&self) -> RootEventLoopProxy {
-        let event_loop_proxy = EventLoopProxy { target_window: self.thread_msg_target };
-        RootEventLoopProxy::new(Arc::new(event_loop_proxy))
+        RootEventLoopProxy::new(self.event_loop_proxy.clone())
     }
 
     fn create_window(
@@ -477,7 +522,7 @@ impl RootActiveEventLoop for ActiveEventLoop {
         &self,
         source: CustomCursorSource,
     ) -> Result<RootCustomCursor, RequestError> {
-        Ok(RootCustomCursor { inner: WinCursor::new(&source.inner.0)? })
+        Ok(RootCustomCursor { inner: WinCursor::new(&source.inner)? })
     }
 
     fn available_monitors(&self) -> Box<dyn Iterator<Item = crate::monitor::MonitorHandle>> {
@@ -500,6 +545,17 @@ impl RootActiveEventLoop for ActiveEventLoop {
         Some(if super::dark_mode::should_use_dark_mode() { Theme::Dark } else { Theme::Light })
     }
 
+    fn accent_color(&self) -> Option<[u8; 4]> {
+        super::dark_mode::accent_color()
+    }
+
+    fn key_text(&self, key: PhysicalKey, modifiers: ModifiersState) -> Option<SmolStr> {
+        let PhysicalKey::Code(key_code) = key else {
+            return None;
+        };
+        LAYOUT_CACHE.lock().unwrap().key_text(key_code, modifiers)
+    }
+
     fn listen_device_events(&self, allowed: DeviceEvents) {
         raw_input::register_all_mice_and_keyboards_for_raw_input(self.thread_msg_target, allowed);
     }
@@ -512,8 +568,38 @@ impl RootActiveEventLoop for ActiveEventLoop {
         self.runner_shared.control_flow()
     }
 
+    fn set_idle_interval(&self, interval: Option<Duration>) -> Result<(), RequestError> {
+        self.runner_shared.idle_timer.set_interval(interval);
+        Ok(())
+    }
+
+    fn set_resize_coalescing(&self, enabled: bool) {
+        self.runner_shared.resize_coalescer.set_enabled(enabled)
+    }
+
+    fn scroll_lines_to_pixels(&self, lines: f32) -> f32 {
+        let mut scroll_lines: u32 = 3;
+        let ok = unsafe {
+            SystemParametersInfoW(
+                SPI_GETWHEELSCROLLLINES,
+                0,
+                &mut scroll_lines as *mut u32 as *mut _,
+                0,
+            )
+        };
+        if ok == false.into() || scroll_lines == WHEEL_PAGESCROLL {
+            scroll_lines = 3;
+        }
+
+        lines * scroll_lines as f32 * 16.0
+    }
+
     fn exit(&self) {
-        self.runner_shared.set_exit_code(0)
+        self.exit_with_code(0)
+    }
+
+    fn exit_with_code(&self, code: i32) {
+        self.runner_shared.set_exit_code(code)
     }
 
     fn owned_display_handle(&self) -> CoreOwnedDisplayHandle {
@@ -804,6 +890,7 @@ type ThreadExecFn = Box<Box<dyn FnMut()>>;
 
 pub struct EventLoopProxy {
     target_window: HWND,
+    alive: AtomicBool,
 }
 
 unsafe impl Send for EventLoopProxy {}
@@ -812,6 +899,16 @@ impl EventLoopProxyProvider for EventLoopProxy {
     fn wake_up(&self) {
         unsafe { PostMessageW(self.target_window, USER_EVENT_MSG_ID.get(), 0, 0) };
     }
+
+    fn is_alive(&self) -> bool {
+        self.alive.load(Ordering::Relaxed)
+    }
+}
+
+impl EventLoopProxy {
+    fn new(target_window: HWND) -> Self {
+        Self { target_window, alive: AtomicBool::new(true) }
+    }
 }
 
 /// A lazily-initialized window message ID.
@@ -975,6 +1072,25 @@ fn normalize_pointer_pressure(pressure: u32) -> Option<Force> {
     }
 }
 
+/// Registers a mouse button press with the current system double-click settings and returns its
+/// click count.
+fn press_click_count(
+    userdata: &WindowData,
+    button: MouseButton,
+    position: PhysicalPosition<f64>,
+) -> u32 {
+    // The settings can change at any time, and are cheap to query.
+    let interval = unsafe { GetDoubleClickTime() };
+    userdata.click_counter.set_interval(Duration::from_millis(interval.into()));
+    // The presses must be within a rectangle of that size, centered on the first one.
+    let width = unsafe { GetSystemMetrics(SM_CXDOUBLECLK) };
+    let height = unsafe { GetSystemMetrics(SM_CYDOUBLECLK) };
+    userdata.click_counter.set_distance(PhysicalSize::new(width as f64 / 2.0, height as f64 / 2.0));
+
+    let time = unsafe { GetMessageTime() };
+    userdata.click_counter.press(button, position, time as u32)
+}
+
 /// Emit a `ModifiersChanged` event whenever modifiers have changed.
 /// Returns the current modifier state
 fn update_modifiers(window: HWND, userdata: &WindowData) {
@@ -982,7 +1098,7 @@ fn update_modifiers(window: HWND, userdata: &WindowData) {
 
     let modifiers = {
         let mut layouts = LAYOUT_CACHE.lock().unwrap();
-        layouts.get_agnostic_mods()
+        Modifiers { state: layouts.get_agnostic_mods(), pressed_mods: layouts.get_pressed_mods() }
     };
 
     let mut window_state = userdata.window_state.lock().unwrap();
@@ -994,7 +1110,7 @@ fn update_modifiers(window: HWND, userdata: &WindowData) {
 
         userdata.send_event(Event::WindowEvent {
             window_id: WindowId::from_raw(window as usize),
-            event: ModifiersChanged(modifiers.into()),
+            event: ModifiersChanged(modifiers),
         });
     }
 }
@@ -1004,6 +1120,15 @@ unsafe fn gain_active_focus(window: HWND, userdata: &WindowData) {
 
     update_modifiers(window, userdata);
 
+    // The cursor clip is dropped when another window is activated, so restore it.
+    let mut window_state = userdata.window_state_lock();
+    if window_state.mouse.cursor_grab_restore
+        && window_state.mouse.cursor_flags().contains(CursorFlags::GRABBED)
+    {
+        let _ = window_state.mouse.set_cursor_flags(window, |_| ());
+    }
+    drop(window_state);
+
     userdata.send_event(Event::WindowEvent {
         window_id: WindowId::from_raw(window as usize),
         event: Focused(true),
@@ -1013,7 +1138,17 @@ unsafe fn gain_active_focus(window: HWND, userdata: &WindowData) {
 unsafe fn lose_active_focus(window: HWND, userdata: &WindowData) {
     use crate::event::WindowEvent::{Focused, ModifiersChanged};
 
-    userdata.window_state_lock().modifiers_state = ModifiersState::empty();
+    let mut window_state = userdata.window_state_lock();
+    window_state.modifiers_state = Modifiers::default();
+    if window_state.mouse.cursor_flags().contains(CursorFlags::GRABBED) {
+        // Don't keep the cursor confined while another window is active.
+        let _ = util::set_cursor_clip(None);
+        if !window_state.mouse.cursor_grab_restore {
+            let _ = window_state.mouse.set_cursor_flags(window, |f| f.remove(CursorFlags::GRABBED));
+        }
+    }
+    drop(window_state);
+
     userdata.send_event(Event::WindowEvent {
         window_id: WindowId::from_raw(window as usize),
         event: ModifiersChanged(ModifiersState::empty().into()),
@@ -1512,11 +1647,10 @@ unsafe fn public_window_callback_inner(
 
                 // Next, receive preedit range for next composing if exist.
                 if (lparam as u32 & GCS_COMPSTR) != 0 {
-                    if let Some((text, first, last)) =
+                    if let Some((text, cursor_range)) =
                         unsafe { ime_context.get_composing_text_and_cursor() }
                     {
                         userdata.window_state_lock().ime_state = ImeState::Preedit;
-                        let cursor_range = first.map(|f| (f, last.unwrap_or(f)));
 
                         userdata.send_event(Event::WindowEvent {
                             window_id: WindowId::from_raw(window as usize),
@@ -1760,6 +1894,7 @@ unsafe fn public_window_callback_inner(
             let x = super::get_x_lparam(lparam as u32) as i32;
             let y = super::get_y_lparam(lparam as u32) as i32;
             let position = PhysicalPosition::new(x as f64, y as f64);
+            let click_count = press_click_count(userdata, Left, position);
 
             userdata.send_event(Event::WindowEvent {
                 window_id: WindowId::from_raw(window as usize),
@@ -1768,6 +1903,7 @@ unsafe fn public_window_callback_inner(
                     primary: true,
                     state: Pressed,
                     position,
+                    click_count,
                     button: Left.into(),
                 },
             });
@@ -1786,6 +1922,7 @@ unsafe fn public_window_callback_inner(
             let x = super::get_x_lparam(lparam as u32) as i32;
             let y = super::get_y_lparam(lparam as u32) as i32;
             let position = PhysicalPosition::new(x as f64, y as f64);
+            let click_count = userdata.click_counter.release(Left);
 
             userdata.send_event(Event::WindowEvent {
                 window_id: WindowId::from_raw(window as usize),
@@ -1794,6 +1931,7 @@ unsafe fn public_window_callback_inner(
                     primary: true,
                     state: Released,
                     position,
+                    click_count,
                     button: Left.into(),
                 },
             });
@@ -1812,6 +1950,7 @@ unsafe fn public_window_callback_inner(
             let x = super::get_x_lparam(lparam as u32) as i32;
             let y = super::get_y_lparam(lparam as u32) as i32;
             let position = PhysicalPosition::new(x as f64, y as f64);
+            let click_count = press_click_count(userdata, Right, position);
 
             userdata.send_event(Event::WindowEvent {
                 window_id: WindowId::from_raw(window as usize),
@@ -1820,6 +1959,7 @@ unsafe fn public_window_callback_inner(
                     primary: true,
                     state: Pressed,
                     position,
+                    click_count,
                     button: Right.into(),
                 },
             });
@@ -1838,6 +1978,7 @@ unsafe fn public_window_callback_inner(
             let x = super::get_x_lparam(lparam as u32) as i32;
             let y = super::get_y_lparam(lparam as u32) as i32;
             let position = PhysicalPosition::new(x as f64, y as f64);
+            let click_count = userdata.click_counter.release(Right);
 
             userdata.send_event(Event::WindowEvent {
                 window_id: WindowId::from_raw(window as usize),
@@ -1846,6 +1987,7 @@ unsafe fn public_window_callback_inner(
                     primary: true,
                     state: Released,
                     position,
+                    click_count,
                     button: Right.into(),
                 },
             });
@@ -1864,6 +2006,7 @@ unsafe fn public_window_callback_inner(
             let x = super::get_x_lparam(lparam as u32) as i32;
             let y = super::get_y_lparam(lparam as u32) as i32;
             let position = PhysicalPosition::new(x as f64, y as f64);
+            let click_count = press_click_count(userdata, Middle, position);
 
             userdata.send_event(Event::WindowEvent {
                 window_id: WindowId::from_raw(window as usize),
@@ -1872,6 +2015,7 @@ unsafe fn public_window_callback_inner(
                     primary: true,
                     state: Pressed,
                     position,
+                    click_count,
                     button: Middle.into(),
                 },
             });
@@ -1890,6 +2034,7 @@ unsafe fn public_window_callback_inner(
             let x = super::get_x_lparam(lparam as u32) as i32;
             let y = super::get_y_lparam(lparam as u32) as i32;
             let position = PhysicalPosition::new(x as f64, y as f64);
+            let click_count = userdata.click_counter.release(Middle);
 
             userdata.send_event(Event::WindowEvent {
                 window_id: WindowId::from_raw(window as usize),
@@ -1898,6 +2043,7 @@ unsafe fn public_window_callback_inner(
                     primary: true,
                     state: Released,
                     position,
+                    click_count,
                     button: Middle.into(),
                 },
             });
@@ -1917,6 +2063,12 @@ unsafe fn public_window_callback_inner(
             let x = super::get_x_lparam(lparam as u32) as i32;
             let y = super::get_y_lparam(lparam as u32) as i32;
             let position = PhysicalPosition::new(x as f64, y as f64);
+            let button = match xbutton {
+                1 => Back,
+                2 => Forward,
+                _ => Other(xbutton),
+            };
+            let click_count = press_click_count(userdata, button, position);
 
             userdata.send_event(Event::WindowEvent {
                 window_id: WindowId::from_raw(window as usize),
@@ -1925,12 +2077,8 @@ unsafe fn public_window_callback_inner(
                     primary: true,
                     state: Pressed,
                     position,
-                    button: match xbutton {
-                        1 => Back,
-                        2 => Forward,
-                        _ => Other(xbutton),
-                    }
-                    .into(),
+                    click_count,
+                    button: button.into(),
                 },
             });
             result = ProcResult::Value(0);
@@ -1949,6 +2097,12 @@ unsafe fn public_window_callback_inner(
             let x = super::get_x_lparam(lparam as u32) as i32;
             let y = super::get_y_lparam(lparam as u32) as i32;
             let position = PhysicalPosition::new(x as f64, y as f64);
+            let button = match xbutton {
+                1 => Back,
+                2 => Forward,
+                _ => Other(xbutton),
+            };
+            let click_count = userdata.click_counter.release(button);
 
             userdata.send_event(Event::WindowEvent {
                 window_id: WindowId::from_raw(window as usize),
@@ -1957,12 +2111,8 @@ unsafe fn public_window_callback_inner(
                     primary: true,
                     state: Released,
                     position,
-                    button: match xbutton {
-                        1 => Back,
-                        2 => Forward,
-                        _ => Other(xbutton),
-                    }
-                    .into(),
+                    click_count,
+                    button: button.into(),
                 },
             });
             result = ProcResult::Value(0);
@@ -2028,6 +2178,7 @@ unsafe fn public_window_callback_inner(
                                 primary,
                                 state: Pressed,
                                 position,
+                                click_count: 1,
                                 button: Touch { finger_id, force: None },
                             },
                         });
@@ -2039,6 +2190,7 @@ unsafe fn public_window_callback_inner(
                                 primary,
                                 state: Released,
                                 position,
+                                click_count: 1,
                                 button: Touch { finger_id, force: None },
                             },
                         });
@@ -2203,6 +2355,7 @@ unsafe fn public_window_callback_inner(
                                 primary,
                                 state: Pressed,
                                 position,
+                                click_count: 1,
                                 button: if let PT_TOUCH = pointer_info.pointerType {
                                     ButtonSource::Touch { finger_id, force }
                                 } else {
@@ -2218,6 +2371,7 @@ unsafe fn public_window_callback_inner(
                                 primary,
                                 state: Released,
                                 position,
+                                click_count: 1,
                                 button: if let PT_TOUCH = pointer_info.pointerType {
                                     ButtonSource::Touch { finger_id, force }
                                 } else {
@@ -2276,7 +2430,15 @@ unsafe fn public_window_callback_inner(
         },
 
         WM_SETFOCUS => {
-            let active_focus_changed = userdata.window_state_lock().set_focused(true);
+            let (active_focus_changed, ime_theme) = {
+                let mut window_state = userdata.window_state_lock();
+                (window_state.set_focused(true), window_state.ime_window_theme())
+            };
+            // The default IME window is shared with the other windows of the thread, so give it
+            // the theme of this window now that it has the focus.
+            if let Some(ime_theme) = ime_theme {
+                unsafe { ImeContext::set_ime_theme(window, ime_theme) };
+            }
             if active_focus_changed {
                 unsafe { gain_active_focus(window, userdata) };
             }
@@ -2307,19 +2469,45 @@ unsafe fn public_window_callback_inner(
 
             match set_cursor_to {
                 Some(selected_cursor) => {
-                    let hcursor = match selected_cursor {
-                        SelectedCursor::Named(cursor_icon) => unsafe {
-                            LoadCursorW(0, util::to_windows_cursor(cursor_icon))
-                        },
-                        SelectedCursor::Custom(cursor) => cursor.as_raw_handle(),
-                    };
-                    unsafe { SetCursor(hcursor) };
+                    unsafe { SetCursor(selected_cursor.handle()) };
                     result = ProcResult::Value(0);
                 },
                 None => result = ProcResult::DefWindowProc(wparam),
             }
         },
 
+        WM_TIMER if wparam == CURSOR_ANIMATION_TIMER_ID => {
+            let hcursor = {
+                let mut window_state = userdata.window_state_lock();
+                match &mut window_state.mouse.selected_cursor {
+                    SelectedCursor::Animation { animation, frame } => {
+                        *frame = (*frame + 1) % animation.frames.len();
+                        Some(animation.frames[*frame].as_raw_handle())
+                    },
+                    _ => None,
+                }
+            };
+
+            // Only replace the cursor while it's over the client area, like `WM_SETCURSOR` does.
+            if let Some(hcursor) = hcursor {
+                let mut point = POINT { x: 0, y: 0 };
+                let mut rect: RECT = unsafe { mem::zeroed() };
+                let over_client_area = unsafe {
+                    GetCursorPos(&mut point) != false.into()
+                        && WindowFromPoint(point) == window
+                        && ScreenToClient(window, &mut point) != false.into()
+                        && GetClientRect(window, &mut rect) != false.into()
+                } && point.x >= rect.left
+                    && point.x < rect.right
+                    && point.y >= rect.top
+                    && point.y < rect.bottom;
+                if over_client_area {
+                    unsafe { SetCursor(hcursor) };
+                }
+            }
+            result = ProcResult::Value(0);
+        },
+
         WM_GETMINMAXINFO => {
             let mmi = lparam as *mut MINMAXINFO;
 
@@ -2571,6 +2759,24 @@ unsafe fn public_window_callback_inner(
                     });
                 }
             }
+
+            // The IME window may follow the theme of the window, and the system may reset its
+            // theme along with the settings.
+            let ime_theme = userdata.window_state_lock().ime_window_theme();
+            if let Some(ime_theme) = ime_theme {
+                unsafe { ImeContext::set_ime_theme(window, ime_theme) };
+            }
+            result = ProcResult::DefWindowProc(wparam);
+        },
+
+        WM_DWMCOLORIZATIONCOLORCHANGED => {
+            use crate::event::WindowEvent::AccentColorChanged;
+
+            let accent_color = super::dark_mode::colorization_to_rgba(wparam as u32);
+            userdata.send_event(Event::WindowEvent {
+                window_id: WindowId::from_raw(window as usize),
+                event: AccentColorChanged(accent_color),
+            });
             result = ProcResult::DefWindowProc(wparam);
         },
 
@@ -2692,7 +2898,7 @@ unsafe fn handle_raw_input(userdata: &ThreadMsgTargetData, data: RAWINPUT) {
             if x != 0.0 || y != 0.0 {
                 userdata.send_event(Event::DeviceEvent {
                     device_id,
-                    event: PointerMotion { delta: (x, y) },
+                    event: PointerMotion { delta: (x, y), raw: true },
                 });
             }
         }
@@ -10,8 +10,11 @@ use windows_sys::Win32::Foundation::HWND;
 use super::ControlFlow;
 use crate::dpi::PhysicalSize;
 use crate::event::{Event, StartCause, SurfaceSizeWriter, WindowEvent};
+use crate::platform_impl::idle::IdleTimer;
 use crate::platform_impl::platform::event_loop::{WindowData, GWL_USERDATA};
 use crate::platform_impl::platform::get_window_long;
+use crate::platform_impl::redraw::RedrawSchedule;
+use crate::platform_impl::resize::ResizeCoalescer;
 use crate::window::WindowId;
 
 type EventHandler = Cell<Option<Box<dyn FnMut(Event)>>>;
@@ -32,6 +35,15 @@ pub(crate) struct EventLoopRunner {
     event_handler: EventHandler,
     event_buffer: RefCell<VecDeque<BufferedEvent>>,
 
+    // The redraws scheduled with `Window::request_redraw_at`, shared with the windows.
+    pub(crate) scheduled_redraws: Arc<RedrawSchedule>,
+
+    // The ticks requested with `ActiveEventLoop::set_idle_interval`.
+    pub(crate) idle_timer: IdleTimer,
+
+    // The resizes held back with `ActiveEventLoop::set_resize_coalescing`.
+    pub(crate) resize_coalescer: ResizeCoalescer,
+
     panic_error: Cell<Option<PanicError>>,
 }
 
@@ -68,6 +80,9 @@ impl EventLoopRunner {
             last_events_cleared: Cell::new(Instant::now()),
             event_handler: Cell::new(None),
             event_buffer: RefCell::new(VecDeque::new()),
+            scheduled_redraws: Default::default(),
+            idle_timer: IdleTimer::default(),
+            resize_coalescer: ResizeCoalescer::default(),
         }
     }
 
@@ -104,15 +119,19 @@ impl EventLoopRunner {
             interrupt_msg_dispatch,
             runner_state,
             panic_error,
-            control_flow: _,
+            control_flow,
             exit,
             last_events_cleared: _,
             event_handler,
             event_buffer: _,
+            scheduled_redraws: _,
+            idle_timer: _,
+            resize_coalescer: _,
         } = self;
         interrupt_msg_dispatch.set(false);
         runner_state.set(RunnerState::Uninitialized);
         panic_error.set(None);
+        control_flow.set(ControlFlow::default());
         exit.set(None);
         event_handler.set(None);
     }
@@ -201,7 +220,20 @@ impl EventLoopRunner {
     }
 
     pub(crate) fn send_event(&self, event: Event) {
+        let event = match event {
+            Event::WindowEvent { window_id, event } => {
+                match self.resize_coalescer.coalesce(window_id, event) {
+                    Some(event) => Event::WindowEvent { window_id, event },
+                    None => return,
+                }
+            },
+            event => event,
+        };
+
         if let Event::WindowEvent { event: WindowEvent::RedrawRequested, .. } = event {
+            // Redraw with the latest size, notably while the window is resized in a modal loop,
+            // where no `AboutToWait` is emitted until the user releases the border.
+            self.dispatch_coalesced_resizes();
             self.call_event_handler(event);
             // As a rule, to ensure that `pump_events` can't block an external event loop
             // for too long, we always guarantee that `pump_events` will return control to
@@ -287,12 +319,12 @@ impl EventLoopRunner {
             },
             (Uninitialized, Idle) => {
                 self.call_new_events(true);
-                self.call_event_handler(Event::AboutToWait);
+                self.call_about_to_wait();
                 self.last_events_cleared.set(Instant::now());
             },
             (Uninitialized, Destroyed) => {
                 self.call_new_events(true);
-                self.call_event_handler(Event::AboutToWait);
+                self.call_about_to_wait();
                 self.last_events_cleared.set(Instant::now());
                 self.call_event_handler(Event::LoopExiting);
             },
@@ -308,11 +340,11 @@ impl EventLoopRunner {
 
             (HandlingMainEvents, Idle) => {
                 // This is always the last event we dispatch before waiting for new events
-                self.call_event_handler(Event::AboutToWait);
+                self.call_about_to_wait();
                 self.last_events_cleared.set(Instant::now());
             },
             (HandlingMainEvents, Destroyed) => {
-                self.call_event_handler(Event::AboutToWait);
+                self.call_about_to_wait();
                 self.last_events_cleared.set(Instant::now());
                 self.call_event_handler(Event::LoopExiting);
             },
@@ -321,6 +353,23 @@ impl EventLoopRunner {
         }
     }
 
+    fn dispatch_coalesced_resizes(&self) {
+        for (window_id, size) in self.resize_coalescer.take() {
+            self.call_event_handler(Event::WindowEvent {
+                window_id,
+                event: WindowEvent::SurfaceResized(size),
+            });
+        }
+    }
+
+    fn call_about_to_wait(&self) {
+        self.dispatch_coalesced_resizes();
+        if self.idle_timer.poll(Instant::now()) {
+            self.call_event_handler(Event::IdleTick);
+        }
+        self.call_event_handler(Event::AboutToWait);
+    }
+
     fn call_new_events(&self, init: bool) {
         let start_cause = match (init, self.control_flow(), self.exit.get()) {
             (true, ..) => StartCause::Init,
@@ -330,7 +379,8 @@ impl EventLoopRunner {
                 start: self.last_events_cleared.get(),
             },
             (false, ControlFlow::WaitUntil(requested_resume), None) => {
-                if Instant::now() < requested_resume {
+                let actual_resume = Instant::now();
+                if actual_resume < requested_resume {
                     StartCause::WaitCancelled {
                         requested_resume: Some(requested_resume),
                         start: self.last_events_cleared.get(),
@@ -338,6 +388,7 @@ impl EventLoopRunner {
                 } else {
                     StartCause::ResumeTimeReached {
                         requested_resume,
+                        actual_resume,
                         start: self.last_events_cleared.get(),
                     }
                 }
@@ -1,6 +1,7 @@
 use std::ffi::c_void;
 use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 use std::{fmt, io, mem};
 
 use cursor_icon::CursorIcon;
@@ -10,15 +11,17 @@ use windows_sys::Win32::Graphics::Gdi::{
     CreateBitmap, CreateCompatibleBitmap, DeleteObject, GetDC, ReleaseDC, SetBitmapBits,
 };
 use windows_sys::Win32::UI::WindowsAndMessaging::{
-    CreateIcon, CreateIconIndirect, DestroyCursor, DestroyIcon, LoadImageW, SendMessageW, HCURSOR,
-    HICON, ICONINFO, ICON_BIG, ICON_SMALL, IMAGE_ICON, LR_DEFAULTSIZE, LR_LOADFROMFILE, WM_SETICON,
+    CreateIcon, CreateIconFromResourceEx, CreateIconIndirect, DestroyCursor, DestroyIcon,
+    KillTimer, LoadCursorW, LoadImageW, SendMessageW, SetTimer, HCURSOR, HICON, ICONINFO, ICON_BIG,
+    ICON_SMALL, IMAGE_ICON, LR_DEFAULTCOLOR, LR_DEFAULTSIZE, LR_LOADFROMFILE, WM_SETICON,
 };
 
 use super::util;
-use crate::cursor::CursorImage;
+use crate::cursor::{BadImage, CursorImage};
 use crate::dpi::PhysicalSize;
-use crate::error::RequestError;
+use crate::error::{NotSupportedError, RequestError};
 use crate::icon::*;
+use crate::window::CustomCursor as RootCustomCursor;
 
 impl Pixel {
     fn convert_to_bgra(&mut self) {
@@ -28,7 +31,11 @@ impl Pixel {
 
 impl RgbaIcon {
     fn into_windows_icon(self) -> Result<WinIcon, BadIcon> {
-        let rgba = self.rgba;
+        let mut rgba = self.rgba;
+        // `CreateIcon` expects straight alpha.
+        if self.premultiplied {
+            unpremultiply_rgba(&mut rgba);
+        }
         let pixel_count = rgba.len() / PIXEL_SIZE;
         let mut and_mask = Vec::with_capacity(pixel_count);
         let pixels =
@@ -129,8 +136,13 @@ impl WinIcon {
         }
     }
 
-    pub fn from_rgba(rgba: Vec<u8>, width: u32, height: u32) -> Result<Self, BadIcon> {
-        let rgba_icon = RgbaIcon::from_rgba(rgba, width, height)?;
+    pub fn from_rgba(
+        rgba: Vec<u8>,
+        width: u32,
+        height: u32,
+        premultiplied: bool,
+    ) -> Result<Self, BadIcon> {
+        let rgba_icon = RgbaIcon::from_rgba(rgba, width, height, premultiplied)?;
         rgba_icon.into_windows_icon()
     }
 
@@ -167,6 +179,11 @@ pub fn unset_for_window(hwnd: HWND, icon_type: IconType) {
 pub enum SelectedCursor {
     Named(CursorIcon),
     Custom(Arc<RaiiCursor>),
+    /// An animation advanced by the [`CURSOR_ANIMATION_TIMER_ID`] timer of the window.
+    Animation {
+        animation: Arc<CursorAnimation>,
+        frame: usize,
+    },
 }
 
 impl Default for SelectedCursor {
@@ -175,13 +192,95 @@ impl Default for SelectedCursor {
     }
 }
 
+/// The timer of a window that advances the frames of [`SelectedCursor::Animation`].
+pub(crate) const CURSOR_ANIMATION_TIMER_ID: usize = 0x5749_4e54;
+
+impl SelectedCursor {
+    /// The cursor to show, which is the current frame for animations.
+    pub(crate) fn handle(&self) -> HCURSOR {
+        match self {
+            Self::Named(icon) => unsafe { LoadCursorW(0, util::to_windows_cursor(*icon)) },
+            Self::Custom(cursor) => cursor.as_raw_handle(),
+            Self::Animation { animation, frame } => animation.frames[*frame].as_raw_handle(),
+        }
+    }
+
+    /// Starts the timer advancing the animation on the window, or stops it for other cursors.
+    pub(crate) fn update_animation_timer(&self, hwnd: HWND) {
+        match self {
+            Self::Animation { animation, .. } => {
+                let delay = animation.delay.as_millis().try_into().unwrap_or(u32::MAX);
+                unsafe { SetTimer(hwnd, CURSOR_ANIMATION_TIMER_ID, delay, None) };
+            },
+            _ => {
+                unsafe { KillTimer(hwnd, CURSOR_ANIMATION_TIMER_ID) };
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub(crate) enum PlatformCustomCursorSource {
+    Image(CursorImage),
+    /// Created with `CustomCursorExtWindows::from_animation`.
+    Animation {
+        duration: Duration,
+        cursors: Vec<RootCustomCursor>,
+    },
+}
+
+impl PlatformCustomCursorSource {
+    pub(crate) fn from_rgba(
+        rgba: Vec<u8>,
+        width: u16,
+        height: u16,
+        hotspot_x: u16,
+        hotspot_y: u16,
+        premultiplied: bool,
+    ) -> Result<Self, BadImage> {
+        CursorImage::from_rgba(rgba, width, height, hotspot_x, hotspot_y, premultiplied)
+            .map(Self::Image)
+    }
+}
+
 #[derive(Clone, Debug, Hash, Eq, PartialEq)]
-pub struct WinCursor(pub(super) Arc<RaiiCursor>);
+pub enum WinCursor {
+    /// A static cursor, which keeps its image to be a frame of animations.
+    Image { cursor: Arc<RaiiCursor>, image: Arc<CursorImage> },
+    /// An animated cursor driven by the system.
+    Animation(Arc<RaiiCursor>),
+    /// An animation the system couldn't create, driven by a timer on the window instead.
+    Frames(Arc<CursorAnimation>),
+}
 
 impl WinCursor {
-    pub(crate) fn new(image: &CursorImage) -> Result<Self, RequestError> {
-        let mut bgra = image.rgba.clone();
-        bgra.chunks_exact_mut(4).for_each(|chunk| chunk.swap(0, 2));
+    pub(crate) fn new(source: &PlatformCustomCursorSource) -> Result<Self, RequestError> {
+        match source {
+            PlatformCustomCursorSource::Image(image) => Self::from_image(image),
+            PlatformCustomCursorSource::Animation { duration, cursors } => {
+                Self::from_animation(*duration, cursors)
+            },
+        }
+    }
+
+    pub(crate) fn is_animation(&self) -> bool {
+        !matches!(self, Self::Image { .. })
+    }
+
+    /// The cursor to select on a window.
+    pub(crate) fn selected(&self) -> SelectedCursor {
+        match self {
+            Self::Image { cursor, .. } | Self::Animation(cursor) => {
+                SelectedCursor::Custom(cursor.clone())
+            },
+            Self::Frames(animation) => {
+                SelectedCursor::Animation { animation: animation.clone(), frame: 0 }
+            },
+        }
+    }
+
+    fn from_image(image: &CursorImage) -> Result<Self, RequestError> {
+        let bgra = bgra_pixels(image);
 
         let w = image.width as i32;
         let h = image.height as i32;
@@ -224,9 +323,143 @@ impl WinCursor {
                 return Err(os_error!(io::Error::last_os_error()).into());
             }
 
-            Ok(Self(Arc::new(RaiiCursor { handle })))
+            Ok(Self::Image {
+                cursor: Arc::new(RaiiCursor { handle }),
+                image: Arc::new(image.clone()),
+            })
+        }
+    }
+
+    fn from_animation(
+        duration: Duration,
+        cursors: &[RootCustomCursor],
+    ) -> Result<Self, RequestError> {
+        let frames = cursors
+            .iter()
+            .map(|frame| match &frame.inner {
+                Self::Image { cursor, image } => Ok((cursor.clone(), image.clone())),
+                _ => Err(NotSupportedError::new("animation frames can't be animations").into()),
+            })
+            .collect::<Result<Vec<_>, RequestError>>()?;
+
+        // The frames are shown for equal parts of the animation.
+        let delay = (duration / frames.len() as u32).max(Duration::from_millis(1));
+
+        let images: Vec<&CursorImage> = frames.iter().map(|(_, image)| &**image).collect();
+        let resource = ani_resource(&images, delay);
+        let handle = unsafe {
+            CreateIconFromResourceEx(
+                resource.as_ptr(),
+                resource.len() as u32,
+                false.into(),
+                0x0003_0000,
+                0,
+                0,
+                LR_DEFAULTCOLOR,
+            )
+        };
+        if handle != 0 {
+            return Ok(Self::Animation(Arc::new(RaiiCursor { handle })));
         }
+
+        tracing::warn!(
+            "failed to create an animated cursor, falling back to a timer: {}",
+            io::Error::last_os_error()
+        );
+        let frames = frames.into_iter().map(|(cursor, _)| cursor).collect();
+        Ok(Self::Frames(Arc::new(CursorAnimation { frames, delay })))
+    }
+}
+
+/// The frames of an animated cursor that the window advances itself.
+#[derive(Debug, Hash, Eq, PartialEq)]
+pub struct CursorAnimation {
+    pub(super) frames: Vec<Arc<RaiiCursor>>,
+    delay: Duration,
+}
+
+/// The pixels of the image in BGRA order.
+fn bgra_pixels(image: &CursorImage) -> Vec<u8> {
+    let mut bgra = image.rgba.clone();
+    // Cursor bitmaps use straight alpha.
+    if image.premultiplied {
+        unpremultiply_rgba(&mut bgra);
+    }
+    bgra.chunks_exact_mut(4).for_each(|chunk| chunk.swap(0, 2));
+    bgra
+}
+
+/// Encodes the frames as an animated cursor, in the RIFF `ACON` format of `.ani` files.
+fn ani_resource(frames: &[&CursorImage], delay: Duration) -> Vec<u8> {
+    // The display rate is in jiffies, sixtieths of a second.
+    let jiffies = (delay.as_millis() * 60 / 1000).clamp(1, u32::MAX as u128) as u32;
+    let frame_count = frames.len() as u32;
+
+    // `ANIHEADER`, with the `AF_ICON` flag since the frames are `.cur` files.
+    let mut header = Vec::with_capacity(36);
+    for field in [36, frame_count, frame_count, 0, 0, 0, 0, jiffies, 1u32] {
+        header.extend_from_slice(&field.to_le_bytes());
+    }
+
+    let mut list = b"fram".to_vec();
+    for frame in frames {
+        push_riff_chunk(&mut list, b"icon", &cur_resource(frame));
+    }
+
+    let mut riff = b"ACON".to_vec();
+    push_riff_chunk(&mut riff, b"anih", &header);
+    push_riff_chunk(&mut riff, b"LIST", &list);
+
+    let mut resource = Vec::new();
+    push_riff_chunk(&mut resource, b"RIFF", &riff);
+    resource
+}
+
+fn push_riff_chunk(buffer: &mut Vec<u8>, id: &[u8; 4], data: &[u8]) {
+    buffer.extend_from_slice(id);
+    buffer.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    buffer.extend_from_slice(data);
+    // Chunks are aligned to two bytes.
+    if data.len() % 2 == 1 {
+        buffer.push(0);
+    }
+}
+
+/// Encodes the image as a `.cur` file with a single 32-bit bitmap.
+fn cur_resource(image: &CursorImage) -> Vec<u8> {
+    let (width, height) = (image.width as usize, image.height as usize);
+    let bgra = bgra_pixels(image);
+    // The AND mask is ignored with an alpha channel, but must be present. Its rows are aligned to
+    // four bytes.
+    let mask_size = width.div_ceil(32) * 4 * height;
+    let bitmap_size = 40 + bgra.len() + mask_size;
+
+    let mut cur = Vec::with_capacity(22 + bitmap_size);
+    // `ICONDIR`, with a type of 2 for cursors.
+    for field in [0u16, 2, 1] {
+        cur.extend_from_slice(&field.to_le_bytes());
+    }
+    // `ICONDIRENTRY`, where a size of 0 stands for 256 pixels.
+    cur.push(u8::try_from(width).unwrap_or(0));
+    cur.push(u8::try_from(height).unwrap_or(0));
+    cur.extend_from_slice(&[0, 0]);
+    cur.extend_from_slice(&image.hotspot_x.to_le_bytes());
+    cur.extend_from_slice(&image.hotspot_y.to_le_bytes());
+    cur.extend_from_slice(&(bitmap_size as u32).to_le_bytes());
+    cur.extend_from_slice(&22u32.to_le_bytes());
+    // `BITMAPINFOHEADER`, whose height covers both the color bitmap and the mask.
+    for field in [40, width as i32, 2 * height as i32] {
+        cur.extend_from_slice(&field.to_le_bytes());
+    }
+    cur.extend_from_slice(&1u16.to_le_bytes());
+    cur.extend_from_slice(&32u16.to_le_bytes());
+    cur.extend_from_slice(&[0; 24]);
+    // The rows are stored bottom-up.
+    for row in bgra.chunks_exact(width.max(1) * 4).rev() {
+        cur.extend_from_slice(row);
     }
+    cur.resize(cur.len() + mask_size, 0);
+    cur
 }
 
 #[derive(Debug, Hash, Eq, PartialEq)]
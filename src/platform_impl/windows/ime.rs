@@ -5,15 +5,17 @@ use std::ptr::null_mut;
 use windows_sys::Win32::Foundation::{POINT, RECT};
 use windows_sys::Win32::Globalization::HIMC;
 use windows_sys::Win32::UI::Input::Ime::{
-    ImmAssociateContextEx, ImmGetCompositionStringW, ImmGetContext, ImmReleaseContext,
-    ImmSetCandidateWindow, ImmSetCompositionWindow, ATTR_TARGET_CONVERTED,
+    ImmAssociateContextEx, ImmGetCompositionStringW, ImmGetContext, ImmGetDefaultIMEWnd,
+    ImmReleaseContext, ImmSetCandidateWindow, ImmSetCompositionWindow, ATTR_TARGET_CONVERTED,
     ATTR_TARGET_NOTCONVERTED, CANDIDATEFORM, CFS_EXCLUDE, CFS_POINT, COMPOSITIONFORM, GCS_COMPATTR,
     GCS_COMPSTR, GCS_CURSORPOS, GCS_RESULTSTR, IACE_CHILDREN, IACE_DEFAULT,
 };
 use windows_sys::Win32::UI::WindowsAndMessaging::{GetSystemMetrics, SM_IMMENABLED};
 
+use super::dark_mode::try_theme;
 use crate::dpi::{Position, Size};
 use crate::platform::windows::HWND;
+use crate::window::Theme;
 
 pub struct ImeContext {
     hwnd: HWND,
@@ -26,49 +28,46 @@ impl ImeContext {
         ImeContext { hwnd, himc }
     }
 
-    pub unsafe fn get_composing_text_and_cursor(
-        &self,
-    ) -> Option<(String, Option<usize>, Option<usize>)> {
+    pub unsafe fn get_composing_text_and_cursor(&self) -> Option<(String, Option<(usize, usize)>)> {
         let text = unsafe { self.get_composition_string(GCS_COMPSTR) }?;
-        let attrs = unsafe { self.get_composition_data(GCS_COMPATTR) }.unwrap_or_default();
-
-        let mut first = None;
-        let mut last = None;
-        let mut boundary_before_char = 0;
-
-        for (attr, chr) in attrs.into_iter().zip(text.chars()) {
-            let char_is_targeted =
-                attr as u32 == ATTR_TARGET_CONVERTED || attr as u32 == ATTR_TARGET_NOTCONVERTED;
-
-            if first.is_none() && char_is_targeted {
-                first = Some(boundary_before_char);
-            } else if first.is_some() && last.is_none() && !char_is_targeted {
-                last = Some(boundary_before_char);
-            }
-
-            boundary_before_char += chr.len_utf8();
+        if text.is_empty() {
+            // The composition was cleared, e.g. by deleting all composing characters.
+            return Some((text, None));
         }
 
-        if first.is_some() && last.is_none() {
-            last = Some(text.len());
-        } else if first.is_none() {
+        // Attributes are reported per UTF-16 code unit, not per character.
+        let attrs = unsafe { self.get_composition_data(GCS_COMPATTR) }.unwrap_or_default();
+        let is_targeted = |attr: &u8| {
+            *attr as u32 == ATTR_TARGET_CONVERTED || *attr as u32 == ATTR_TARGET_NOTCONVERTED
+        };
+
+        let range = match attrs.iter().position(is_targeted) {
+            Some(first) => {
+                let last = attrs[first..]
+                    .iter()
+                    .position(|attr| !is_targeted(attr))
+                    .map_or(attrs.len(), |len| first + len);
+                Some((first, last))
+            },
             // IME haven't split words and select any clause yet, so trying to retrieve normal
             // cursor.
-            let cursor = unsafe { self.get_composition_cursor(&text) };
-            first = cursor;
-            last = cursor;
-        }
+            None => unsafe { self.get_composition_cursor() }.map(|cursor| (cursor, cursor)),
+        };
+
+        let range = range.map(|(first, last)| {
+            (utf16_offset_to_byte_offset(&text, first), utf16_offset_to_byte_offset(&text, last))
+        });
 
-        Some((text, first, last))
+        Some((text, range))
     }
 
     pub unsafe fn get_composed_text(&self) -> Option<String> {
         unsafe { self.get_composition_string(GCS_RESULTSTR) }
     }
 
-    unsafe fn get_composition_cursor(&self, text: &str) -> Option<usize> {
+    unsafe fn get_composition_cursor(&self) -> Option<usize> {
         let cursor = unsafe { ImmGetCompositionStringW(self.himc, GCS_CURSORPOS, null_mut(), 0) };
-        (cursor >= 0).then(|| text.chars().take(cursor as _).map(|c| c.len_utf8()).sum())
+        (cursor >= 0).then_some(cursor as usize)
     }
 
     unsafe fn get_composition_string(&self, gcs_mode: u32) -> Option<String> {
@@ -144,6 +143,13 @@ impl ImeContext {
         }
     }
 
+    pub unsafe fn set_ime_theme(hwnd: HWND, theme: Theme) {
+        let ime_hwnd = unsafe { ImmGetDefaultIMEWnd(hwnd) };
+        if ime_hwnd != 0 {
+            try_theme(ime_hwnd, Some(theme));
+        }
+    }
+
     unsafe fn system_has_ime() -> bool {
         unsafe { GetSystemMetrics(SM_IMMENABLED) != 0 }
     }
@@ -154,3 +160,40 @@ impl Drop for ImeContext {
         unsafe { ImmReleaseContext(self.hwnd, self.himc) };
     }
 }
+
+/// Converts an offset in UTF-16 code units, as used by IMM32, to a byte offset into `text`.
+fn utf16_offset_to_byte_offset(text: &str, offset: usize) -> usize {
+    let mut utf16_offset = 0;
+    for (byte_offset, chr) in text.char_indices() {
+        if utf16_offset >= offset {
+            return byte_offset;
+        }
+        utf16_offset += chr.len_utf16();
+    }
+    text.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn utf16_offset_to_byte_offset_in_text() {
+        // `😀` is a surrogate pair in UTF-16 and takes 4 bytes, `€` a single code unit but 3 bytes.
+        let text = "a😀b€";
+        assert_eq!(utf16_offset_to_byte_offset(text, 0), 0);
+        assert_eq!(utf16_offset_to_byte_offset(text, 1), 1);
+        assert_eq!(utf16_offset_to_byte_offset(text, 3), 5);
+        assert_eq!(utf16_offset_to_byte_offset(text, 4), 6);
+        assert_eq!(utf16_offset_to_byte_offset(text, 5), text.len());
+
+        // An offset within a surrogate pair moves past the character.
+        assert_eq!(utf16_offset_to_byte_offset(text, 2), 5);
+    }
+
+    #[test]
+    fn utf16_offset_to_byte_offset_out_of_range() {
+        assert_eq!(utf16_offset_to_byte_offset("a😀b€", 100), 9);
+        assert_eq!(utf16_offset_to_byte_offset("", 1), 0);
+    }
+}
@@ -41,7 +41,9 @@ use windows_sys::Win32::UI::Input::KeyboardAndMouse::{
 };
 use windows_sys::Win32::UI::TextServices::HKL;
 
-use crate::keyboard::{Key, KeyCode, ModifiersState, NamedKey, NativeKey, PhysicalKey};
+use crate::keyboard::{
+    Key, KeyCode, ModifiersKeys, ModifiersState, NamedKey, NativeKey, PhysicalKey,
+};
 use crate::platform_impl::{loword, primarylangid, scancode_to_physicalkey};
 use crate::utils::Lazy;
 
@@ -283,6 +285,37 @@ impl LayoutCache {
         mods
     }
 
+    /// Text produced by the key with the given modifiers in the current layout.
+    pub fn key_text(&mut self, key_code: KeyCode, mods: ModifiersState) -> Option<SmolStr> {
+        let mut win_mods = WindowsModifiers::empty();
+        win_mods.set(WindowsModifiers::SHIFT, mods.shift_key());
+        win_mods.set(WindowsModifiers::CONTROL, mods.control_key());
+        win_mods.set(WindowsModifiers::ALT, mods.alt_key());
+
+        let (_, layout) = self.get_current_layout();
+        match layout.keys.get(&win_mods)?.get(&key_code)? {
+            Key::Character(text) => Some(text.clone()),
+            _ => None,
+        }
+    }
+
+    /// Which side of the modifiers is held, see [`get_agnostic_mods`](Self::get_agnostic_mods).
+    pub fn get_pressed_mods(&mut self) -> ModifiersKeys {
+        let (_, layout) = self.get_current_layout();
+        // AltGr is sent as left control and right alt, only keep the key that is actually held.
+        let filter_out_altgr = layout.has_alt_graph && key_pressed(VK_RMENU);
+        let mut mods = ModifiersKeys::empty();
+        mods.set(ModifiersKeys::LSHIFT, key_pressed(VK_LSHIFT));
+        mods.set(ModifiersKeys::RSHIFT, key_pressed(VK_RSHIFT));
+        mods.set(ModifiersKeys::LCONTROL, key_pressed(VK_LCONTROL) && !filter_out_altgr);
+        mods.set(ModifiersKeys::RCONTROL, key_pressed(VK_RCONTROL));
+        mods.set(ModifiersKeys::LALT, key_pressed(VK_LMENU));
+        mods.set(ModifiersKeys::RALT, key_pressed(VK_RMENU));
+        mods.set(ModifiersKeys::LSUPER, key_pressed(VK_LWIN));
+        mods.set(ModifiersKeys::RSUPER, key_pressed(VK_RWIN));
+        mods
+    }
+
     fn prepare_layout(locale_id: u64) -> Layout {
         let mut layout = Layout {
             hkl: locale_id,
@@ -5,10 +5,11 @@ use std::{io, mem, ptr};
 
 use windows_sys::Win32::Foundation::{BOOL, HWND, LPARAM, POINT, RECT};
 use windows_sys::Win32::Graphics::Gdi::{
-    EnumDisplayMonitors, EnumDisplaySettingsExW, GetMonitorInfoW, MonitorFromPoint,
-    MonitorFromWindow, DEVMODEW, DM_BITSPERPEL, DM_DISPLAYFREQUENCY, DM_PELSHEIGHT, DM_PELSWIDTH,
-    ENUM_CURRENT_SETTINGS, HDC, HMONITOR, MONITORINFO, MONITORINFOEXW, MONITOR_DEFAULTTONEAREST,
-    MONITOR_DEFAULTTOPRIMARY,
+    CreateDCW, DeleteDC, EnumDisplayMonitors, EnumDisplaySettingsExW, GetDeviceCaps,
+    GetMonitorInfoW, MonitorFromPoint, MonitorFromWindow, DEVMODEW, DM_BITSPERPEL,
+    DM_DISPLAYFREQUENCY, DM_PELSHEIGHT, DM_PELSWIDTH, ENUM_CURRENT_SETTINGS, HDC, HMONITOR,
+    HORZSIZE, MONITORINFO, MONITORINFOEXW, MONITORINFOF_PRIMARY, MONITOR_DEFAULTTONEAREST,
+    MONITOR_DEFAULTTOPRIMARY, VERTSIZE,
 };
 
 use super::util::decode_wide;
@@ -157,11 +158,49 @@ impl MonitorHandle {
             .ok()
     }
 
+    #[inline]
+    pub fn work_area(&self) -> Option<(PhysicalPosition<i32>, PhysicalSize<u32>)> {
+        get_monitor_info(self.0)
+            .map(|info| {
+                let rc_work = info.monitorInfo.rcWork;
+                let position = PhysicalPosition::new(rc_work.left, rc_work.top);
+                let size = PhysicalSize::new(
+                    (rc_work.right - rc_work.left) as u32,
+                    (rc_work.bottom - rc_work.top) as u32,
+                );
+                (position, size)
+            })
+            .ok()
+    }
+
     #[inline]
     pub fn scale_factor(&self) -> f64 {
         dpi_to_scale_factor(get_monitor_dpi(self.0).unwrap_or(96))
     }
 
+    #[inline]
+    pub fn physical_size_mm(&self) -> Option<(f64, f64)> {
+        let monitor_info = get_monitor_info(self.0).ok()?;
+        let (width, height) = unsafe {
+            let hdc =
+                CreateDCW(ptr::null(), monitor_info.szDevice.as_ptr(), ptr::null(), ptr::null());
+            if hdc == 0 {
+                return None;
+            }
+            let size = (GetDeviceCaps(hdc, HORZSIZE), GetDeviceCaps(hdc, VERTSIZE));
+            DeleteDC(hdc);
+            size
+        };
+        (width > 0 && height > 0).then_some((width as f64, height as f64))
+    }
+
+    #[inline]
+    pub fn is_primary(&self) -> bool {
+        get_monitor_info(self.0)
+            .map(|info| has_flag(info.monitorInfo.dwFlags, MONITORINFOF_PRIMARY))
+            .unwrap_or(false)
+    }
+
     #[inline]
     pub fn current_video_mode(&self) -> Option<VideoMode> {
         let monitor_info = get_monitor_info(self.0).ok()?;
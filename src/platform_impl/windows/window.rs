@@ -5,6 +5,7 @@ use std::ffi::c_void;
 use std::mem::{self, MaybeUninit};
 use std::sync::mpsc::channel;
 use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Instant;
 use std::{io, panic, ptr};
 
 use tracing::warn;
@@ -34,8 +35,8 @@ use windows_sys::Win32::UI::Input::Touch::{RegisterTouchWindow, TWF_WANTPALM};
 use windows_sys::Win32::UI::WindowsAndMessaging::{
     CreateWindowExW, EnableMenuItem, FlashWindowEx, GetClientRect, GetCursorPos,
     GetForegroundWindow, GetSystemMenu, GetSystemMetrics, GetWindowPlacement, GetWindowTextLengthW,
-    GetWindowTextW, IsWindowVisible, LoadCursorW, PeekMessageW, PostMessageW, RegisterClassExW,
-    SetCursor, SetCursorPos, SetForegroundWindow, SetMenuDefaultItem, SetWindowDisplayAffinity,
+    GetWindowTextW, IsWindowVisible, PeekMessageW, PostMessageW, RegisterClassExW, SetCursor,
+    SetCursorPos, SetForegroundWindow, SetMenuDefaultItem, SetWindowDisplayAffinity,
     SetWindowPlacement, SetWindowPos, SetWindowTextW, TrackPopupMenu, CS_HREDRAW, CS_VREDRAW,
     CW_USEDEFAULT, FLASHWINFO, FLASHW_ALL, FLASHW_STOP, FLASHW_TIMERNOFG, FLASHW_TRAY,
     GWLP_HINSTANCE, HTBOTTOM, HTBOTTOMLEFT, HTBOTTOMRIGHT, HTCAPTION, HTLEFT, HTRIGHT, HTTOP,
@@ -51,6 +52,7 @@ use crate::error::{NotSupportedError, RequestError};
 use crate::icon::Icon;
 use crate::monitor::MonitorHandle as CoreMonitorHandle;
 use crate::platform::windows::{BackdropType, Color, CornerPreference};
+use crate::platform_impl::click::ClickCounter;
 use crate::platform_impl::platform::dark_mode::try_theme;
 use crate::platform_impl::platform::definitions::{
     CLSID_TaskbarList, IID_ITaskbarList, IID_ITaskbarList2, ITaskbarList, ITaskbarList2,
@@ -67,9 +69,10 @@ use crate::platform_impl::platform::window_state::{
     CursorFlags, SavedWindow, WindowFlags, WindowState,
 };
 use crate::platform_impl::platform::{monitor, util, Fullscreen, SelectedCursor};
+use crate::platform_impl::redraw::RedrawSchedule;
 use crate::window::{
-    CursorGrabMode, Fullscreen as CoreFullscreen, ImePurpose, ResizeDirection, Theme,
-    UserAttentionType, Window as CoreWindow, WindowAttributes, WindowButtons, WindowId,
+    CursorGrabMode, Fullscreen as CoreFullscreen, ImePurpose, RedrawPriority, ResizeDirection,
+    Theme, UserAttentionType, Window as CoreWindow, WindowAttributes, WindowButtons, WindowId,
     WindowLevel,
 };
 
@@ -83,6 +86,9 @@ pub(crate) struct Window {
 
     // The events loop proxy.
     thread_executor: event_loop::EventLoopThreadExecutor,
+
+    /// The redraws scheduled with `request_redraw_at`, shared with the event loop.
+    scheduled_redraws: Arc<RedrawSchedule>,
 }
 
 impl Window {
@@ -158,14 +164,8 @@ impl Window {
     }
 
     pub fn set_system_backdrop(&self, backdrop_type: BackdropType) {
-        unsafe {
-            DwmSetWindowAttribute(
-                self.hwnd(),
-                DWMWA_SYSTEMBACKDROP_TYPE as u32,
-                &(backdrop_type as i32) as *const _ as _,
-                mem::size_of::<DWM_SYSTEMBACKDROP_TYPE>() as _,
-            );
-        }
+        self.window_state_lock().backdrop_type = backdrop_type;
+        unsafe { set_system_backdrop_type(self.hwnd(), backdrop_type) };
     }
 
     pub fn set_taskbar_icon(&self, taskbar_icon: Option<Icon>) {
@@ -329,6 +329,22 @@ impl Window {
             );
         }
     }
+
+    pub fn set_cursor_position_force(&self, position: Position) -> Result<(), RequestError> {
+        let scale_factor = self.scale_factor();
+        let (x, y) = position.to_physical::<i32>(scale_factor).into();
+
+        let mut point = POINT { x, y };
+        unsafe {
+            if ClientToScreen(self.hwnd(), &mut point) == false.into() {
+                return Err(os_error!(io::Error::last_os_error()).into());
+            }
+            if SetCursorPos(point.x, point.y) == false.into() {
+                return Err(os_error!(io::Error::last_os_error()).into());
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Drop for Window {
@@ -343,6 +359,9 @@ impl Drop for Window {
             // custom message to be handled by our callback to do the actual work.
             PostMessageW(self.hwnd(), DESTROY_MSG_ID.get(), 0, 0);
         }
+
+        // Window handles are reused by the system.
+        self.scheduled_redraws.cancel(self.id());
     }
 }
 
@@ -368,6 +387,10 @@ impl CoreWindow for Window {
         }
     }
 
+    fn set_title_with_subtitle(&self, title: &str, _subtitle: Option<&str>) {
+        self.set_title(title);
+    }
+
     fn set_transparent(&self, transparent: bool) {
         let window = self.window;
         let window_state = Arc::clone(&self.window_state);
@@ -379,7 +402,21 @@ impl CoreWindow for Window {
         });
     }
 
-    fn set_blur(&self, _blur: bool) {}
+    fn set_blur(&self, blur: bool) {
+        let window = self.window;
+        let window_state = Arc::clone(&self.window_state);
+        self.thread_executor.execute_in_thread(move || {
+            let window_state = window_state.lock().unwrap();
+            let flags = window_state.window_flags();
+            if flags.contains(WindowFlags::TRANSPARENT)
+                && !flags.contains(WindowFlags::NO_BACK_BUFFER)
+            {
+                let backdrop_type = window_state.backdrop_type;
+                drop(window_state);
+                unsafe { apply_blur(window, blur, backdrop_type) };
+            }
+        });
+    }
 
     fn set_visible(&self, visible: bool) {
         let window = self.window;
@@ -396,6 +433,10 @@ impl CoreWindow for Window {
         Some(unsafe { IsWindowVisible(self.window) == 1 })
     }
 
+    fn occlusion(&self) -> Option<f32> {
+        None
+    }
+
     fn request_redraw(&self) {
         // NOTE: mark that we requested a redraw to handle requests during `WM_PAINT` handling.
         self.window_state.lock().unwrap().redraw_requested = true;
@@ -404,6 +445,17 @@ impl CoreWindow for Window {
         }
     }
 
+    fn request_redraw_with_priority(&self, _priority: RedrawPriority) {
+        self.request_redraw();
+    }
+
+    fn request_redraw_at(&self, deadline: Instant) {
+        if self.scheduled_redraws.schedule(self.id(), deadline) {
+            // Wake up the event loop so that it waits for the new deadline.
+            self.thread_executor.execute_in_thread(|| {});
+        }
+    }
+
     fn pre_present_notify(&self) {}
 
     fn outer_position(&self) -> Result<PhysicalPosition<i32>, RequestError> {
@@ -512,6 +564,18 @@ impl CoreWindow for Window {
         let _ = self.request_surface_size(size.into());
     }
 
+    fn min_surface_size(&self) -> Option<PhysicalSize<u32>> {
+        let w = self.window_state_lock();
+        let scale_factor = w.scale_factor;
+        w.min_size.map(|size| size.to_physical(scale_factor))
+    }
+
+    fn max_surface_size(&self) -> Option<PhysicalSize<u32>> {
+        let w = self.window_state_lock();
+        let scale_factor = w.scale_factor;
+        w.max_size.map(|size| size.to_physical(scale_factor))
+    }
+
     fn surface_resize_increments(&self) -> Option<PhysicalSize<u32>> {
         let w = self.window_state_lock();
         let scale_factor = w.scale_factor;
@@ -569,22 +633,16 @@ impl CoreWindow for Window {
     }
 
     fn set_cursor(&self, cursor: Cursor) {
-        match cursor {
-            Cursor::Icon(icon) => {
-                self.window_state_lock().mouse.selected_cursor = SelectedCursor::Named(icon);
-                self.thread_executor.execute_in_thread(move || unsafe {
-                    let cursor = LoadCursorW(0, util::to_windows_cursor(icon));
-                    SetCursor(cursor);
-                });
-            },
-            Cursor::Custom(cursor) => {
-                self.window_state_lock().mouse.selected_cursor =
-                    SelectedCursor::Custom(cursor.inner.0.clone());
-                self.thread_executor.execute_in_thread(move || unsafe {
-                    SetCursor(cursor.inner.0.as_raw_handle());
-                });
-            },
-        }
+        let selected_cursor = match cursor {
+            Cursor::Icon(icon) => SelectedCursor::Named(icon),
+            Cursor::Custom(cursor) => cursor.inner.selected(),
+        };
+        self.window_state_lock().mouse.selected_cursor = selected_cursor.clone();
+        let window = self.window;
+        self.thread_executor.execute_in_thread(move || unsafe {
+            SetCursor(selected_cursor.handle());
+            selected_cursor.update_animation_timer(window);
+        });
     }
 
     fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), RequestError> {
@@ -614,6 +672,10 @@ impl CoreWindow for Window {
         rx.recv().unwrap()
     }
 
+    fn set_cursor_grab_restore_on_focus(&self, restore: bool) {
+        self.window_state_lock().mouse.cursor_grab_restore = restore;
+    }
+
     fn set_cursor_visible(&self, visible: bool) {
         let window = self.window;
         let window_state = Arc::clone(&self.window_state);
@@ -637,19 +699,14 @@ impl CoreWindow for Window {
     }
 
     fn set_cursor_position(&self, position: Position) -> Result<(), RequestError> {
-        let scale_factor = self.scale_factor();
-        let (x, y) = position.to_physical::<i32>(scale_factor).into();
-
-        let mut point = POINT { x, y };
-        unsafe {
-            if ClientToScreen(self.hwnd(), &mut point) == false.into() {
-                return Err(os_error!(io::Error::last_os_error()).into());
-            }
-            if SetCursorPos(point.x, point.y) == false.into() {
-                return Err(os_error!(io::Error::last_os_error()).into());
-            }
+        // Warping the cursor from a background window would take it away from the application
+        // the user is currently interacting with.
+        if !self.has_focus() {
+            tracing::warn!("`set_cursor_position` ignored since the window isn't focused");
+            return Err(RequestError::Ignored);
         }
-        Ok(())
+
+        self.set_cursor_position_force(position)
     }
 
     fn drag_window(&self) -> Result<(), RequestError> {
@@ -677,6 +734,13 @@ impl CoreWindow for Window {
         Ok(())
     }
 
+    fn resize_window_with_keyboard(&self) -> Result<(), RequestError> {
+        // Same as picking "Size" in the window menu, the arrow keys then resize the window.
+        unsafe { PostMessageW(self.hwnd(), WM_SYSCOMMAND, SC_SIZE as WPARAM, 0) };
+
+        Ok(())
+    }
+
     fn show_window_menu(&self, position: Position) {
         unsafe {
             self.handle_showing_window_menu(position);
@@ -961,13 +1025,35 @@ impl CoreWindow for Window {
         let window = self.window;
         let state = self.window_state.clone();
         self.thread_executor.execute_in_thread(move || unsafe {
-            state.lock().unwrap().ime_allowed = allowed;
+            let ime_theme = {
+                let mut state = state.lock().unwrap();
+                state.ime_allowed = allowed;
+                state.ime_window_theme()
+            };
             ImeContext::set_ime_allowed(window, allowed);
+            if let Some(ime_theme) = ime_theme {
+                ImeContext::set_ime_theme(window, ime_theme);
+            }
         })
     }
 
     fn set_ime_purpose(&self, _purpose: ImePurpose) {}
 
+    fn set_ime_theme(&self, theme: Option<Theme>) {
+        let window = self.window;
+        let state = self.window_state.clone();
+        self.thread_executor.execute_in_thread(move || unsafe {
+            let ime_theme = {
+                let mut state = state.lock().unwrap();
+                state.ime_theme = theme;
+                state.ime_window_theme()
+            };
+            if let Some(ime_theme) = ime_theme {
+                ImeContext::set_ime_theme(window, ime_theme);
+            }
+        })
+    }
+
     fn request_user_attention(&self, request_type: Option<UserAttentionType>) {
         let window = self.window;
         let active_window_handle = unsafe { GetActiveWindow() };
@@ -1028,13 +1114,13 @@ impl CoreWindow for Window {
     }
 
     #[inline]
-    fn set_content_protected(&self, protected: bool) {
+    fn set_content_protected(&self, protected: bool) -> bool {
         unsafe {
             SetWindowDisplayAffinity(
                 self.hwnd(),
                 if protected { WDA_EXCLUDEFROMCAPTURE } else { WDA_NONE },
-            )
-        };
+            ) != false.into()
+        }
     }
 
     #[inline]
@@ -1111,7 +1197,12 @@ impl InitData<'_> {
 
         unsafe { ImeContext::set_ime_allowed(window, false) };
 
-        Window { window, window_state, thread_executor: self.event_loop.create_thread_executor() }
+        Window {
+            window,
+            window_state,
+            thread_executor: self.event_loop.create_thread_executor(),
+            scheduled_redraws: self.event_loop.runner_shared.scheduled_redraws.clone(),
+        }
     }
 
     unsafe fn create_window_data(&self, win: &Window) -> event_loop::WindowData {
@@ -1151,6 +1242,7 @@ impl InitData<'_> {
             _file_drop_handler: file_drop_handler,
             userdata_removed: Cell::new(false),
             recurse_depth: Cell::new(0),
+            click_counter: ClickCounter::default(),
         }
     }
 
@@ -1176,21 +1268,10 @@ impl InitData<'_> {
         let win = self.window.as_mut().expect("failed window creation");
 
         // making the window transparent
-        if self.attributes.transparent && !self.attributes.platform_specific.no_redirection_bitmap {
-            // Empty region for the blur effect, so the window is fully transparent
-            let region = unsafe { CreateRectRgn(0, 0, -1, -1) };
-
-            let bb = DWM_BLURBEHIND {
-                dwFlags: DWM_BB_ENABLE | DWM_BB_BLURREGION,
-                fEnable: true.into(),
-                hRgnBlur: region,
-                fTransitionOnMaximized: false.into(),
-            };
-            let hr = unsafe { DwmEnableBlurBehindWindow(win.hwnd(), &bb) };
-            if hr < 0 {
-                warn!("Setting transparent window is failed. HRESULT Code: 0x{:X}", hr);
-            }
-            unsafe { DeleteObject(region) };
+        let blur_supported =
+            self.attributes.transparent && !self.attributes.platform_specific.no_redirection_bitmap;
+        if blur_supported {
+            unsafe { set_blur_behind(win.hwnd(), false) };
         }
 
         win.set_skip_taskbar(self.attributes.platform_specific.skip_taskbar);
@@ -1211,7 +1292,10 @@ impl InitData<'_> {
 
         win.set_enabled_buttons(attributes.enabled_buttons);
 
-        let size = attributes.surface_size.unwrap_or_else(|| PhysicalSize::new(800, 600).into());
+        let monitor_size = monitor::current_monitor(win.hwnd()).size();
+        let size = attributes
+            .initial_surface_size(Some(monitor_size))
+            .unwrap_or_else(|| PhysicalSize::new(800, 600).into());
         let max_size = attributes
             .max_surface_size
             .unwrap_or_else(|| PhysicalSize::new(f64::MAX, f64::MAX).into());
@@ -1232,7 +1316,12 @@ impl InitData<'_> {
             win.set_outer_position(position);
         }
 
-        win.set_system_backdrop(self.attributes.platform_specific.backdrop_type);
+        let backdrop_type = self.attributes.platform_specific.backdrop_type;
+        if blur_supported && self.attributes.blur {
+            unsafe { apply_blur(win.hwnd(), true, backdrop_type) };
+        } else {
+            win.set_system_backdrop(backdrop_type);
+        }
 
         if let Some(color) = self.attributes.platform_specific.border_color {
             win.set_border_color(color);
@@ -1278,7 +1367,8 @@ unsafe fn init(
     window_flags.set(WindowFlags::CLOSABLE, true);
     window_flags.set(WindowFlags::CLIP_CHILDREN, attributes.platform_specific.clip_children);
 
-    let mut fallback_parent = || match attributes.platform_specific.owner {
+    let owner = attributes.owner.map(|owner| owner.into_raw() as HWND);
+    let mut fallback_parent = || match attributes.platform_specific.owner.or(owner) {
         Some(parent) => {
             window_flags.set(WindowFlags::POPUP, true);
             Some(parent)
@@ -1350,6 +1440,58 @@ unsafe fn init(
     Ok(win)
 }
 
+/// Blur the background of a transparent window.
+///
+/// From Windows 11 build 22523 onwards, blur-behind doesn't blur anymore, so the Acrylic
+/// system backdrop is drawn behind the transparent background instead, replacing the backdrop the
+/// user selected until the blur is disabled.
+unsafe fn apply_blur(handle: HWND, blur: bool, backdrop_type: BackdropType) {
+    if super::dark_mode::WIN10_BUILD_VERSION.is_some_and(|build| build >= 22523) {
+        let backdrop_type = if blur { BackdropType::TransientWindow } else { backdrop_type };
+        unsafe {
+            set_blur_behind(handle, false);
+            set_system_backdrop_type(handle, backdrop_type);
+        }
+    } else {
+        unsafe { set_blur_behind(handle, blur) };
+    }
+}
+
+unsafe fn set_system_backdrop_type(handle: HWND, backdrop_type: BackdropType) {
+    unsafe {
+        DwmSetWindowAttribute(
+            handle,
+            DWMWA_SYSTEMBACKDROP_TYPE as u32,
+            &(backdrop_type as i32) as *const _ as _,
+            mem::size_of::<DWM_SYSTEMBACKDROP_TYPE>() as _,
+        );
+    }
+}
+
+/// Enable DWM blur-behind, which is what makes the window background transparent.
+///
+/// The whole client area is blurred when `blur` is set, otherwise an empty blur region keeps the
+/// background transparent without blurring it.
+unsafe fn set_blur_behind(handle: HWND, blur: bool) {
+    // A null region applies the blur to the entire client area.
+    let region = if blur { 0 } else { unsafe { CreateRectRgn(0, 0, -1, -1) } };
+
+    let bb = DWM_BLURBEHIND {
+        dwFlags: DWM_BB_ENABLE | DWM_BB_BLURREGION,
+        fEnable: true.into(),
+        hRgnBlur: region,
+        fTransitionOnMaximized: false.into(),
+    };
+    let hr = unsafe { DwmEnableBlurBehindWindow(handle, &bb) };
+    if hr < 0 {
+        warn!("Setting window blur-behind is failed. HRESULT Code: 0x{:X}", hr);
+    }
+
+    if region != 0 {
+        unsafe { DeleteObject(region) };
+    }
+}
+
 unsafe fn register_window_class(class_name: &[u16]) {
     let class = WNDCLASSEXW {
         cbSize: mem::size_of::<WNDCLASSEXW>() as u32,
@@ -17,8 +17,9 @@ use windows_sys::Win32::UI::WindowsAndMessaging::{
 };
 
 use crate::dpi::{PhysicalPosition, PhysicalSize, Size};
+use crate::event::Modifiers;
 use crate::icon::Icon;
-use crate::keyboard::ModifiersState;
+use crate::platform::windows::BackdropType;
 use crate::platform_impl::platform::{event_loop, util, Fullscreen, SelectedCursor};
 use crate::window::{Theme, WindowAttributes};
 
@@ -38,7 +39,7 @@ pub(crate) struct WindowState {
     pub saved_window: Option<SavedWindow>,
     pub scale_factor: f64,
 
-    pub modifiers_state: ModifiersState,
+    pub modifiers_state: Modifiers,
     pub fullscreen: Option<Fullscreen>,
     pub current_theme: Theme,
     pub preferred_theme: Option<Theme>,
@@ -47,6 +48,7 @@ pub(crate) struct WindowState {
 
     pub ime_state: ImeState,
     pub ime_allowed: bool,
+    pub ime_theme: Option<Theme>,
 
     // Used by WM_NCACTIVATE, WM_SETFOCUS and WM_KILLFOCUS
     pub is_active: bool,
@@ -58,6 +60,9 @@ pub(crate) struct WindowState {
     pub dragging: bool,
 
     pub skip_taskbar: bool,
+
+    /// Used to restore the system backdrop when the blur is disabled.
+    pub backdrop_type: BackdropType,
 }
 
 #[derive(Clone)]
@@ -70,6 +75,8 @@ pub struct MouseProperties {
     pub(crate) selected_cursor: SelectedCursor,
     pub capture_count: u32,
     cursor_flags: CursorFlags,
+    /// Whether the cursor grab is re-applied when the window regains focus.
+    pub cursor_grab_restore: bool,
     pub last_position: Option<PhysicalPosition<f64>>,
 }
 
@@ -148,6 +155,7 @@ impl WindowState {
                 selected_cursor: SelectedCursor::default(),
                 capture_count: 0,
                 cursor_flags: CursorFlags::empty(),
+                cursor_grab_restore: true,
                 last_position: None,
             },
 
@@ -162,7 +170,7 @@ impl WindowState {
             saved_window: None,
             scale_factor,
 
-            modifiers_state: ModifiersState::default(),
+            modifiers_state: Modifiers::default(),
             fullscreen: None,
             current_theme,
             preferred_theme,
@@ -170,6 +178,7 @@ impl WindowState {
 
             ime_state: ImeState::Disabled,
             ime_allowed: false,
+            ime_theme: None,
 
             is_active: false,
             is_focused: false,
@@ -178,6 +187,8 @@ impl WindowState {
             dragging: false,
 
             skip_taskbar: false,
+
+            backdrop_type: attributes.platform_specific.backdrop_type,
         }
     }
 
@@ -221,6 +232,14 @@ impl WindowState {
         self.is_focused = is_focused;
         old != self.has_active_focus()
     }
+
+    /// The theme to give to the default IME window, if this window should set it.
+    ///
+    /// The default IME window is shared by all the windows of the thread, so only the focused
+    /// window with IME allowed sets its theme.
+    pub fn ime_window_theme(&self) -> Option<Theme> {
+        (self.is_focused && self.ime_allowed).then(|| self.ime_theme.unwrap_or(self.current_theme))
+    }
 }
 
 impl MouseProperties {
@@ -1,14 +1,21 @@
 //! The [`Window`] struct and associated types.
 use std::fmt;
+#[cfg(not(web_platform))]
+use std::time::Instant;
 
 #[doc(inline)]
 pub use cursor_icon::{CursorIcon, ParseError as CursorIconParseError};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+#[cfg(web_platform)]
+use web_time::Instant;
 
-pub use crate::cursor::{BadImage, Cursor, CustomCursor, CustomCursorSource, MAX_CURSOR_SIZE};
+pub use crate::cursor::{
+    BadAnimation, BadImage, Cursor, CustomCursor, CustomCursorSource, MAX_CURSOR_SIZE,
+};
 use crate::dpi::{PhysicalInsets, PhysicalPosition, PhysicalSize, Position, Size};
-use crate::error::RequestError;
+use crate::error::{NotSupportedError, RequestError};
+use crate::event::MouseButton;
 pub use crate::icon::{BadIcon, Icon};
 use crate::monitor::{MonitorHandle, VideoMode};
 use crate::platform_impl::PlatformSpecificWindowAttributes;
@@ -46,12 +53,21 @@ impl fmt::Debug for WindowId {
 }
 
 /// Attributes used when creating a window.
+///
+/// With the `serde` feature, the attributes can be serialized, e.g. to restore the layout of
+/// windows in the next session. Fields that only make sense for the current session are skipped,
+/// and are reset to their default when deserializing: the window icon, the cursor, the
+/// fullscreen mode (which refers to a monitor), the parent and owner windows, and the handles and
+/// icons among the platform-specific attributes. Missing fields also take their default value.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default))]
 pub struct WindowAttributes {
     pub surface_size: Option<Size>,
+    pub fractional_monitor_size: Option<f64>,
     pub min_surface_size: Option<Size>,
     pub max_surface_size: Option<Size>,
     pub surface_resize_increments: Option<Size>,
+    pub surface_aspect_ratio: Option<(u32, u32)>,
     pub position: Option<Position>,
     pub resizable: bool,
     pub enabled_buttons: WindowButtons,
@@ -61,13 +77,19 @@ pub struct WindowAttributes {
     pub transparent: bool,
     pub blur: bool,
     pub decorations: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub window_icon: Option<Icon>,
     pub preferred_theme: Option<Theme>,
     pub content_protected: bool,
     pub window_level: WindowLevel,
     pub active: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub cursor: Cursor,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub(crate) parent_window: Option<SendSyncRawWindowHandle>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub owner: Option<WindowId>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub fullscreen: Option<Fullscreen>,
     // Platform-specific configuration.
     #[allow(dead_code)]
@@ -79,9 +101,11 @@ impl Default for WindowAttributes {
     fn default() -> WindowAttributes {
         WindowAttributes {
             surface_size: None,
+            fractional_monitor_size: None,
             min_surface_size: None,
             max_surface_size: None,
             surface_resize_increments: None,
+            surface_aspect_ratio: None,
             position: None,
             resizable: true,
             enabled_buttons: WindowButtons::all(),
@@ -98,6 +122,7 @@ impl Default for WindowAttributes {
             content_protected: false,
             cursor: Cursor::default(),
             parent_window: None,
+            owner: None,
             active: true,
             platform_specific: Default::default(),
         }
@@ -133,6 +158,43 @@ impl WindowAttributes {
         self
     }
 
+    /// Requests the surface to be sized to a fraction of the monitor it is created on.
+    ///
+    /// For example, `0.7` makes both dimensions of the surface 70% of the monitor's resolution.
+    /// This takes precedence over [`WindowAttributes::with_surface_size`], which is used as the
+    /// fallback when the monitor isn't known, along with the platform-specific default dimensions.
+    ///
+    /// The size is only resolved once, when the window is created.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Uses the monitor under the cursor.
+    /// - **Windows:** Uses the monitor the window is created on.
+    /// - **macOS:** Uses the screen that has keyboard focus.
+    /// - **Wayland:** Unsupported, the output is only known once the window is mapped.
+    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    #[inline]
+    pub fn with_fractional_monitor_size(mut self, fraction: f64) -> Self {
+        self.fractional_monitor_size = Some(fraction);
+        self
+    }
+
+    /// The size to create the surface with on a monitor of the given size.
+    #[cfg_attr(not(any(x11_platform, windows_platform, macos_platform)), allow(dead_code))]
+    pub(crate) fn initial_surface_size(
+        &self,
+        monitor_size: Option<PhysicalSize<u32>>,
+    ) -> Option<Size> {
+        match (self.fractional_monitor_size, monitor_size) {
+            (Some(fraction), Some(monitor_size)) => {
+                let scale = |length: u32| (length as f64 * fraction).round().max(1.0) as u32;
+                let size = PhysicalSize::new(scale(monitor_size.width), scale(monitor_size.height));
+                Some(size.into())
+            },
+            _ => self.surface_size,
+        }
+    }
+
     /// Sets the minimum dimensions the surface can have.
     ///
     /// If this is not set, the surface will have no minimum dimensions (aside from reserved).
@@ -170,6 +232,20 @@ impl WindowAttributes {
         self
     }
 
+    /// Sets the minimum and maximum dimensions of the surface, along with its aspect ratio.
+    ///
+    /// This replaces the sizes from [`WindowAttributes::with_min_surface_size`] and
+    /// [`WindowAttributes::with_max_surface_size`].
+    ///
+    /// See [`Window::set_resize_constraints`] for details.
+    #[inline]
+    pub fn with_resize_constraints(mut self, constraints: ResizeConstraints) -> Self {
+        self.min_surface_size = constraints.min;
+        self.max_surface_size = constraints.max;
+        self.surface_aspect_ratio = constraints.aspect_ratio;
+        self
+    }
+
     /// Sets a desired initial position for the window.
     ///
     /// If this is not set, some platform-specific position will be chosen.
@@ -350,11 +426,15 @@ impl WindowAttributes {
     ///
     /// ## Platform-specific
     ///
-    /// - **macOS**: if `false`, [`NSWindowSharingNone`] is used but doesn't completely prevent all
+    /// - **macOS**: if `true`, [`NSWindowSharingNone`] is used but doesn't completely prevent all
     ///   apps from reading the window content, for instance, QuickTime.
-    /// - **iOS / Android / Web / x11 / Orbital:** Ignored.
+    /// - **Windows**: Uses [`WDA_EXCLUDEFROMCAPTURE`], which requires Windows 10 version 2004.
+    /// - **iOS / Android / Web / x11 / Wayland / Orbital:** Ignored.
+    ///
+    /// See [`Window::set_content_protected`] to find out whether protection could be applied.
     ///
     /// [`NSWindowSharingNone`]: https://developer.apple.com/documentation/appkit/nswindowsharingtype/nswindowsharingnone
+    /// [`WDA_EXCLUDEFROMCAPTURE`]: https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-setwindowdisplayaffinity
     #[inline]
     pub fn with_content_protected(mut self, protected: bool) -> Self {
         self.content_protected = protected;
@@ -411,6 +491,32 @@ impl WindowAttributes {
         self.parent_window = parent_window.map(SendSyncRawWindowHandle);
         self
     }
+
+    /// Build window owned by another window of the same event loop.
+    ///
+    /// An owned window, such as a dialog, is a top-level window that stays above its owner.
+    /// Unlike [`WindowAttributes::with_parent_window()`], it is not confined to the owner.
+    ///
+    /// Closing the owner doesn't close the owned window, except on Windows: the application is
+    /// expected to drop owned windows along with their owner.
+    ///
+    /// The default is `None`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Sets `WM_TRANSIENT_FOR`, the window manager decides how to stack the window. This
+    ///   is unrelated to `WindowAttributesExtX11::with_embed_parent_window`, which reparents the
+    ///   window into another one.
+    /// - **Wayland:** Sets the parent of the toplevel, if the owner is still alive.
+    /// - **macOS:** The window is made a child window of the owner, and moves along with it.
+    /// - **Windows:** Same as `WindowAttributesExtWindows::with_owner_window`, which takes
+    ///   precedence. The window is destroyed along with its owner.
+    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    #[inline]
+    pub fn with_owner(mut self, owner: WindowId) -> Self {
+        self.owner = Some(owner);
+        self
+    }
 }
 
 /// Represents a window.
@@ -526,6 +632,44 @@ pub trait Window: AsAny + Send + Sync {
     /// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
     fn request_redraw(&self);
 
+    /// Like [`Window::request_redraw`], but with a priority for the redraw.
+    ///
+    /// When several windows have a pending redraw in the same event loop iteration,
+    /// [`WindowEvent::RedrawRequested`] is delivered to the ones with a higher priority first. This
+    /// lets redraws caused by user input be serviced before scheduled animation frames. Requesting
+    /// a redraw for a window which already has one pending keeps the higher of both priorities.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11 / macOS:** Fully supported.
+    /// - **Others:** The priority is ignored and this behaves like [`Window::request_redraw`].
+    ///
+    /// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
+    fn request_redraw_with_priority(&self, priority: RedrawPriority);
+
+    /// Schedules a single [`WindowEvent::RedrawRequested`] to be emitted close to `deadline`.
+    ///
+    /// This is meant for animations that must present a frame at a given time, without managing
+    /// [`ControlFlow::WaitUntil`] and calling [`Window::request_redraw`] manually. The event loop
+    /// wakes up for the redraw regardless of the current [`ControlFlow`].
+    ///
+    /// The timing is best-effort: the event is never emitted before `deadline`, but may be emitted
+    /// later, e.g. when the event loop is busy or the window is waiting for a frame callback. If a
+    /// redraw is already scheduled for this window, the earliest of both deadlines is kept. A
+    /// deadline in the past behaves like [`Window::request_redraw`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11 / Wayland / Windows / macOS / Web:** Fully supported, as well as by the headless
+    ///   backend.
+    /// - **iOS / Android / Orbital:** The deadline is ignored and this behaves like
+    ///   [`Window::request_redraw`].
+    ///
+    /// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
+    /// [`ControlFlow::WaitUntil`]: crate::event_loop::ControlFlow::WaitUntil
+    /// [`ControlFlow`]: crate::event_loop::ControlFlow
+    fn request_redraw_at(&self, deadline: Instant);
+
     /// Notify the windowing system before presenting to the window.
     ///
     /// You should call this event after your drawing operations, but before you submit
@@ -552,6 +696,11 @@ pub trait Window: AsAny + Send + Sync {
     /// # }
     /// ```
     ///
+    /// Damage regions and buffer age are not handled by winit, since it neither allocates nor
+    /// submits the buffers. Use the facilities of your graphics API for partial redraws instead,
+    /// like `EGL_EXT_buffer_age` with `eglSwapBuffersWithDamageKHR`, or softbuffer's
+    /// `Buffer::present_with_damage`.
+    ///
     /// ## Platform-specific
     ///
     /// - **Android / iOS / X11 / Web / Windows / macOS / Orbital:** Unsupported.
@@ -615,7 +764,9 @@ pub trait Window: AsAny + Send + Sync {
     /// ## Platform-specific
     ///
     /// - **Web:** Returns the top-left coordinates relative to the viewport.
-    /// - **Android / Wayland:** Always returns [`RequestError::NotSupported`].
+    /// - **Android:** Always returns [`RequestError::NotSupported`].
+    /// - **Wayland:** Always returns [`RequestError::NotSupported`], since the xdg-shell protocol
+    ///   never tells clients the position of their toplevel windows.
     fn outer_position(&self) -> Result<PhysicalPosition<i32>, RequestError>;
 
     /// Sets the position of the window on the desktop.
@@ -696,12 +847,54 @@ pub trait Window: AsAny + Send + Sync {
     /// ## Platform-specific
     ///
     /// - **Web:** Sets the size of the canvas element. Doesn't account for CSS [`transform`].
+    ///   Always returns `None`.
+    /// - **Wayland:** The client decides its own size, so the applied size is always returned.
+    /// - **X11 / Windows / macOS / Orbital:** Always returns `None`, even for override-redirect
+    ///   windows on X11, since the new size is only known once the display server reports it.
+    /// - **Android / iOS:** Resizing is not supported, the current size is always returned.
     ///
     /// [`WindowEvent::SurfaceResized`]: crate::event::WindowEvent::SurfaceResized
     /// [`transform`]: https://developer.mozilla.org/en-US/docs/Web/CSS/transform
     #[must_use]
     fn request_surface_size(&self, size: Size) -> Option<PhysicalSize<u32>>;
 
+    /// Request the new size for the surface, moving the window so that `anchor` stays in place.
+    ///
+    /// For example, with [`Anchor::SouthEast`] the window grows towards the top-left, keeping its
+    /// bottom-right corner fixed.
+    ///
+    /// The return value is the same as for [`Window::request_surface_size`]. If the windowing
+    /// system clamps the requested size, the anchor may not stay exactly in place.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** The window is moved and resized with a single request.
+    /// - **Windows / macOS / Web / iOS / Orbital:** Best-effort, the window is moved with
+    ///   [`Window::set_outer_position`] and then resized with [`Window::request_surface_size`]. The
+    ///   two requests aren't atomic, so the window may briefly be seen with only one of them
+    ///   applied.
+    /// - **Android / Wayland:** The window can't be moved, so this is the same as
+    ///   [`Window::request_surface_size`].
+    #[must_use]
+    fn request_surface_size_anchored(
+        &self,
+        size: Size,
+        anchor: Anchor,
+    ) -> Option<PhysicalSize<u32>> {
+        if let Ok(position) = self.outer_position() {
+            let old_size = self.surface_size();
+            let new_size = size.to_physical::<u32>(self.scale_factor());
+            let (dx, dy) = anchor.offset(old_size, new_size);
+            if dx != 0 || dy != 0 {
+                self.set_outer_position(
+                    PhysicalPosition::new(position.x - dx, position.y - dy).into(),
+                );
+            }
+        }
+
+        self.request_surface_size(size)
+    }
+
     /// Returns the size of the entire window.
     ///
     /// These dimensions include window decorations like the title bar and borders. If you don't
@@ -794,11 +987,34 @@ pub trait Window: AsAny + Send + Sync {
     /// - **iOS / Android / Orbital:** Unsupported.
     fn set_max_surface_size(&self, max_size: Option<Size>);
 
+    /// Returns the minimum dimensions of the window's surface that Winit currently applies.
+    ///
+    /// This reflects the last value set with [`Window::set_min_surface_size`] or at window
+    /// creation, converted with the current scale factor.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Always returns a size, since a minimum of 2x1 logical pixels is enforced. The
+    ///   minimum is also the current size while the window is not resizable.
+    /// - **iOS / Android / Web / Orbital:** Always returns [`None`].
+    fn min_surface_size(&self) -> Option<PhysicalSize<u32>>;
+
+    /// Returns the maximum dimensions of the window's surface that Winit currently applies.
+    ///
+    /// This reflects the last value set with [`Window::set_max_surface_size`] or at window
+    /// creation, converted with the current scale factor.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** The maximum is the current size while the window is not resizable.
+    /// - **iOS / Android / Web / Orbital:** Always returns [`None`].
+    fn max_surface_size(&self) -> Option<PhysicalSize<u32>>;
+
     /// Returns surface resize increments if any were set.
     ///
     /// ## Platform-specific
     ///
-    /// - **iOS / Android / Web / Wayland / Orbital:** Always returns [`None`].
+    /// - **iOS / Android / Web / Orbital:** Always returns [`None`].
     fn surface_resize_increments(&self) -> Option<PhysicalSize<u32>>;
 
     /// Sets resize increments of the surface.
@@ -810,10 +1026,32 @@ pub trait Window: AsAny + Send + Sync {
     ///
     /// - **macOS:** Increments are converted to logical size and then macOS rounds them to whole
     ///   numbers.
-    /// - **Wayland:** Not implemented.
+    /// - **X11:** Window managers may ignore the hint.
+    /// - **Wayland:** Best-effort, the sizes suggested by the compositor are shrunk to a multiple
+    ///   of the increments while the window is neither maximized, fullscreen nor tiled.
     /// - **iOS / Android / Web / Orbital:** Unsupported.
     fn set_surface_resize_increments(&self, increments: Option<Size>);
 
+    /// Sets the minimum and maximum dimensions of the surface, along with its aspect ratio, at
+    /// once.
+    ///
+    /// The sizes behave like with [`Window::set_min_surface_size`] and
+    /// [`Window::set_max_surface_size`]. The aspect ratio is a hint that is applied when the
+    /// user resizes the window, it doesn't resize the window by itself.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** The aspect ratio is set as both the minimum and maximum aspect of
+    ///   `WM_NORMAL_HINTS`, and the window manager is free to ignore it.
+    /// - **Wayland:** The aspect ratio is enforced by shrinking the size suggested by the
+    ///   compositor while the window is floating, i.e. not maximized, fullscreen or tiled.
+    /// - **Others:** The aspect ratio is ignored, and this behaves like setting the minimum and
+    ///   maximum sizes separately.
+    fn set_resize_constraints(&self, constraints: ResizeConstraints) {
+        self.set_min_surface_size(constraints.min);
+        self.set_max_surface_size(constraints.max);
+    }
+
     /// Modifies the title of the window.
     ///
     /// ## Platform-specific
@@ -821,6 +1059,16 @@ pub trait Window: AsAny + Send + Sync {
     /// - **iOS / Android:** Unsupported.
     fn set_title(&self, title: &str);
 
+    /// Modifies the title of the window, along with a subtitle shown below it.
+    ///
+    /// Passing [`None`] as the subtitle removes any previously set subtitle.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** The subtitle is only shown on macOS 11 and later.
+    /// - **Others:** The subtitle is ignored and this behaves like [`Window::set_title`].
+    fn set_title_with_subtitle(&self, title: &str, subtitle: Option<&str>);
+
     /// Change the window transparency state.
     ///
     /// This is just a hint that may not change anything about
@@ -844,8 +1092,13 @@ pub trait Window: AsAny + Send + Sync {
     ///
     /// ## Platform-specific
     ///
-    /// - **Android / iOS / X11 / Web / Windows:** Unsupported.
+    /// - **Android / iOS / X11 / Web / Orbital:** Unsupported.
     /// - **Wayland:** Only works with org_kde_kwin_blur_manager protocol.
+    /// - **Windows:** Only applies to transparent windows, and not to windows created with
+    ///   `WindowAttributesExtWindows::with_no_redirection_bitmap`. From Windows 11 build 22523
+    ///   onwards, the Acrylic system backdrop is used, replacing the one set with
+    ///   `WindowExtWindows::set_system_backdrop` while enabled. Earlier versions use DWM
+    ///   blur-behind, which only blurs on Windows 7 and keeps the background transparent otherwise.
     fn set_blur(&self, blur: bool);
 
     /// Modifies the window's visibility.
@@ -868,6 +1121,29 @@ pub trait Window: AsAny + Send + Sync {
     /// - **Wayland / iOS / Android / Web:** Unsupported.
     fn is_visible(&self) -> Option<bool>;
 
+    /// Gets how much of the window is currently hidden from view.
+    ///
+    /// `0.0` means the window is fully visible and `1.0` that it is fully hidden, see
+    /// [`WindowEvent::Occluded`]. This can be used to throttle rendering of a window that is mostly
+    /// covered. `None` means it couldn't be determined.
+    ///
+    /// Most platforms only report whether the window is occluded or not, in which case this is
+    /// either `0.0` or `1.0`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Only `0.0` or `1.0`, from the window's occlusion state.
+    /// - **Web:** The fraction of the canvas outside of the viewport, rounded to a multiple of
+    ///   `0.25`. `1.0` while the page is hidden. Doesn't take into account CSS [`border`],
+    ///   [`padding`], or [`transform`].
+    /// - **Others:** Unsupported, always returns `None`.
+    ///
+    /// [`WindowEvent::Occluded`]: crate::event::WindowEvent::Occluded
+    /// [`border`]: https://developer.mozilla.org/en-US/docs/Web/CSS/border
+    /// [`padding`]: https://developer.mozilla.org/en-US/docs/Web/CSS/padding
+    /// [`transform`]: https://developer.mozilla.org/en-US/docs/Web/CSS/transform
+    fn occlusion(&self) -> Option<f32>;
+
     /// Sets whether the window is resizable or not.
     ///
     /// Note that making the window unresizable doesn't exempt you from handling
@@ -1018,7 +1294,10 @@ pub trait Window: AsAny + Send + Sync {
     ///
     /// ## Platform-specific
     ///
-    /// - **iOS / Android / Web / Wayland / macOS / Orbital:** Unsupported.
+    /// - **iOS / Android / Web / macOS / Orbital:** Unsupported.
+    ///
+    /// - **Wayland:** Requires the compositor to support the `xdg-toplevel-icon-v1` protocol,
+    ///   otherwise this does nothing. Non-square icons are centered in a square.
     ///
     /// - **Windows:** Sets `ICON_SMALL`. The base size for a window icon is 16x16, but it's
     ///   recommended to account for screen scaling and pick a multiple of that, i.e. 32x32.
@@ -1101,6 +1380,19 @@ pub trait Window: AsAny + Send + Sync {
     /// - **iOS / Android / Web / Windows / X11 / macOS / Orbital:** Unsupported.
     fn set_ime_purpose(&self, purpose: ImePurpose);
 
+    /// Hints the theme the IME candidate window should use.
+    ///
+    /// [`None`] makes the candidate window follow the theme of the window, which is the default.
+    /// This is useful to keep the candidate window from standing out over a themed text area.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Only affects input methods using the system provided IME window. This window
+    ///   is shared by the windows of a thread, so it takes the theme of the focused window with IME
+    ///   allowed.
+    /// - **iOS / Android / Web / Wayland / X11 / macOS / Orbital:** Unsupported.
+    fn set_ime_theme(&self, theme: Option<Theme>);
+
     /// Brings the window to the front and sets input focus. Has no effect if the window is
     /// already in focus, minimized, or not visible.
     ///
@@ -1131,7 +1423,8 @@ pub trait Window: AsAny + Send + Sync {
     ///
     /// - **iOS / Android / Web / Orbital:** Unsupported.
     /// - **macOS:** `None` has no effect.
-    /// - **X11:** Requests for user attention must be manually cleared.
+    /// - **X11:** Requests for user attention must be manually cleared. Only the urgency hint is
+    ///   set by default, see `WindowExtX11::set_attention_mechanism` to change that.
     /// - **Wayland:** Requires `xdg_activation_v1` protocol, `None` has no effect.
     fn request_user_attention(&self, request_type: Option<UserAttentionType>);
 
@@ -1156,18 +1449,24 @@ pub trait Window: AsAny + Send + Sync {
     ///
     /// - **iOS / Android / x11 / Orbital:** Unsupported.
     /// - **Wayland:** Only returns theme overrides.
+    /// - **Web:** Returns the result of the `prefers-color-scheme` media query.
     fn theme(&self) -> Option<Theme>;
 
     /// Prevents the window contents from being captured by other apps.
     ///
+    /// Returns `true` if the request was forwarded to the platform, and `false` if content
+    /// protection isn't supported, in which case the window contents can still be captured.
+    ///
     /// ## Platform-specific
     ///
-    /// - **macOS**: if `false`, [`NSWindowSharingNone`] is used but doesn't completely prevent all
+    /// - **macOS**: if `true`, [`NSWindowSharingNone`] is used but doesn't completely prevent all
     ///   apps from reading the window content, for instance, QuickTime.
-    /// - **iOS / Android / x11 / Wayland / Web / Orbital:** Unsupported.
+    /// - **Windows**: Uses [`WDA_EXCLUDEFROMCAPTURE`], which requires Windows 10 version 2004.
+    /// - **iOS / Android / x11 / Wayland / Web / Orbital:** Unsupported, always returns `false`.
     ///
     /// [`NSWindowSharingNone`]: https://developer.apple.com/documentation/appkit/nswindowsharingtype/nswindowsharingnone
-    fn set_content_protected(&self, protected: bool);
+    /// [`WDA_EXCLUDEFROMCAPTURE`]: https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-setwindowdisplayaffinity
+    fn set_content_protected(&self, protected: bool) -> bool;
 
     /// Gets the current title of the window.
     ///
@@ -1199,12 +1498,44 @@ pub trait Window: AsAny + Send + Sync {
     /// # }
     /// ```
     ///
+    /// The position isn't validated, a position outside of the surface moves the cursor outside of
+    /// the window where the platform allows it. Use [`Window::set_cursor_position_clamped`] to keep
+    /// the cursor inside the surface instead.
+    ///
     /// ## Platform-specific
     ///
-    /// - **Wayland**: Cursor must be in [`CursorGrabMode::Locked`].
+    /// - **Wayland**: The cursor can only be moved while it's in [`CursorGrabMode::Locked`], and
+    ///   only within the surface. Returns [`RequestError::NotSupported`] in any other grab mode,
+    ///   including [`CursorGrabMode::Confined`], or if the compositor doesn't support the
+    ///   `pointer-constraints` protocol.
+    /// - **Windows:** Returns [`RequestError::Ignored`] if the window isn't focused, use
+    ///   [`set_cursor_position_force`] to bypass this. The position is clamped to the screen.
     /// - **iOS / Android / Web / Orbital:** Always returns an [`RequestError::NotSupported`].
+    #[cfg_attr(
+        any(windows_platform, docsrs),
+        doc = "",
+        doc = "[`set_cursor_position_force`]: \
+               crate::platform::windows::WindowExtWindows::set_cursor_position_force"
+    )]
+    #[cfg_attr(
+        not(any(windows_platform, docsrs)),
+        doc = "",
+        doc = "[`set_cursor_position_force`]: #only-available-on-windows"
+    )]
     fn set_cursor_position(&self, position: Position) -> Result<(), RequestError>;
 
+    /// Changes the position of the cursor in window coordinates, clamping it to the surface.
+    ///
+    /// This behaves like [`Window::set_cursor_position`], except that a position outside of the
+    /// [surface][Window::surface_size] is moved to the closest point inside of it instead of
+    /// being passed as is to the platform, which may reject it or move the cursor outside of the
+    /// window.
+    fn set_cursor_position_clamped(&self, position: Position) -> Result<(), RequestError> {
+        let position = position.to_physical(self.scale_factor());
+        let position = clamp_to_surface(position, self.surface_size());
+        self.set_cursor_position(position.into())
+    }
+
     /// Set grabbing [mode][CursorGrabMode] on the cursor preventing it from leaving the window.
     ///
     /// # Example
@@ -1220,8 +1551,33 @@ pub trait Window: AsAny + Send + Sync {
     ///     .unwrap();
     /// # }
     /// ```
+    ///
+    /// The requested mode is remembered: when the window loses focus the grab is released, and it
+    /// is re-applied once the window is focused again. Use
+    /// [`Window::set_cursor_grab_restore_on_focus`] to opt out of this.
+    ///
+    /// The mode is never substituted for another one: if the platform can't honor the requested
+    /// mode, [`RequestError::NotSupported`] is returned and the current grab is left untouched. See
+    /// [`CursorGrabMode`] for which modes each platform supports.
     fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), RequestError>;
 
+    /// Sets whether the [cursor grab][Window::set_cursor_grab] is re-applied automatically when the
+    /// window regains focus.
+    ///
+    /// When disabled, the grab is reset to [`CursorGrabMode::None`] on focus loss, and it is up to
+    /// the application to request it again on [`WindowEvent::Focused(true)`].
+    ///
+    /// The default is `true`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** The compositor restores the pointer constraint by itself, so this has no
+    ///   effect.
+    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    ///
+    /// [`WindowEvent::Focused(true)`]: crate::event::WindowEvent::Focused
+    fn set_cursor_grab_restore_on_focus(&self, restore: bool);
+
     /// Modifies the cursor's visibility.
     ///
     /// If `false`, this will hide the cursor. If `true`, this will show the cursor.
@@ -1252,7 +1608,8 @@ pub trait Window: AsAny + Send + Sync {
     /// Resizes the window with the left mouse button until the button is released.
     ///
     /// There's no guarantee that this will work unless the left mouse button was pressed
-    /// immediately before this function is called.
+    /// immediately before this function is called. To resize the window without a pointer, see
+    /// [`Window::resize_window_with_keyboard`].
     ///
     /// ## Platform-specific
     ///
@@ -1260,6 +1617,92 @@ pub trait Window: AsAny + Send + Sync {
     /// - **iOS / Android / Web:** Always returns an [`RequestError::NotSupported`].
     fn drag_resize_window(&self, direction: ResizeDirection) -> Result<(), RequestError>;
 
+    /// Moves the window with the given mouse button until the button is released.
+    ///
+    /// This is the same as [`Window::drag_window`], except that the drag doesn't have to be
+    /// started with the left mouse button, e.g. to move the window with the middle mouse button.
+    ///
+    /// ## Platform-specific
+    ///
+    /// Only X11 honors `button`, the other platforms behave like [`Window::drag_window`].
+    ///
+    /// - **Wayland:** The drag follows the latest button press the compositor saw.
+    /// - **macOS:** The drag follows the event currently being handled.
+    /// - **Windows / Orbital:** The drag is always done with the left mouse button.
+    /// - **iOS / Android / Web:** Always returns an [`RequestError::NotSupported`].
+    fn drag_window_with_button(&self, button: MouseButton) -> Result<(), RequestError> {
+        let _ = button;
+        self.drag_window()
+    }
+
+    /// Resizes the window with the given mouse button until the button is released.
+    ///
+    /// This is the same as [`Window::drag_resize_window`], except that the drag doesn't have to be
+    /// started with the left mouse button.
+    ///
+    /// ## Platform-specific
+    ///
+    /// Only X11 honors `button`, the other platforms behave like [`Window::drag_resize_window`].
+    ///
+    /// - **Wayland:** The drag follows the latest button press the compositor saw.
+    /// - **Windows / Orbital:** The drag is always done with the left mouse button.
+    /// - **macOS:** Always returns an [`RequestError::NotSupported`].
+    /// - **iOS / Android / Web:** Always returns an [`RequestError::NotSupported`].
+    fn drag_resize_window_with_button(
+        &self,
+        direction: ResizeDirection,
+        button: MouseButton,
+    ) -> Result<(), RequestError> {
+        let _ = button;
+        self.drag_resize_window(direction)
+    }
+
+    /// Resizes the window from the edge or corner closest to `touch_position` until the touch is
+    /// released.
+    ///
+    /// `touch_position` is in surface coordinates, as reported by
+    /// [`WindowEvent::PointerButton`]. The edge is picked with
+    /// [`ResizeDirection::from_surface_position`] using `edge_threshold` physical pixels as the
+    /// size of the hit area, which is useful for touch input where the exact edge is hard to hit.
+    ///
+    /// Returns [`RequestError::Ignored`] if `touch_position` isn't within `edge_threshold` of any
+    /// edge.
+    ///
+    /// ## Platform-specific
+    ///
+    /// See [`Window::drag_resize_window`].
+    ///
+    /// [`WindowEvent::PointerButton`]: crate::event::WindowEvent::PointerButton
+    fn begin_resize_drag_touch(
+        &self,
+        touch_position: PhysicalPosition<f64>,
+        edge_threshold: f64,
+    ) -> Result<(), RequestError> {
+        let size = self.surface_size();
+        match ResizeDirection::from_surface_position(touch_position, size, edge_threshold) {
+            Some(direction) => self.drag_resize_window(direction),
+            None => Err(RequestError::Ignored),
+        }
+    }
+
+    /// Starts resizing the window with the keyboard.
+    ///
+    /// This lets users resize windows without a pointer, e.g. from a keyboard shortcut of the
+    /// application. The windowing system takes over: the arrow keys usually resize the window,
+    /// and `Enter` or `Escape` end the resize. Unlike [`Window::drag_resize_window`], the edge is
+    /// picked by the windowing system.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Requires a window manager supporting keyboard resizing with `_NET_WM_MOVERESIZE`.
+    ///   Un-grabs the cursor.
+    /// - **Windows:** Same as picking "Size" in the window menu.
+    /// - **Wayland / macOS / iOS / Android / Web / Orbital:** Always returns an
+    ///   [`RequestError::NotSupported`].
+    fn resize_window_with_keyboard(&self) -> Result<(), RequestError> {
+        Err(NotSupportedError::new("resize_window_with_keyboard is not supported").into())
+    }
+
     /// Show [window menu] at a specified position in surface coordinates.
     ///
     /// This is the context menu that is normally shown when interacting with
@@ -1282,6 +1725,30 @@ pub trait Window: AsAny + Send + Sync {
     /// - **iOS / Android / Web / Orbital:** Always returns an [`RequestError::NotSupported`].
     fn set_cursor_hittest(&self, hittest: bool) -> Result<(), RequestError>;
 
+    /// Restricts the parts of the window that catch cursor events.
+    ///
+    /// Cursor events inside one of the `regions` are delivered to the window, while the ones
+    /// anywhere else are passed through to whatever is behind it. `None` restores hit-testing for
+    /// the whole window. The regions are relative to the top-left corner of the surface.
+    ///
+    /// This overrides any previous call to [`Window::set_cursor_hittest`], and the other way
+    /// around.
+    ///
+    /// ## Platform-specific
+    ///
+    /// This is best-effort, some compositors or window managers may ignore the regions.
+    ///
+    /// - **Wayland:** The regions are rounded to logical pixels.
+    /// - **Android / iOS / macOS / Orbital / Web / Windows:** Always returns an
+    ///   [`RequestError::NotSupported`].
+    fn set_cursor_hittest_region(
+        &self,
+        regions: Option<Vec<PhysicalRect>>,
+    ) -> Result<(), RequestError> {
+        let _ = regions;
+        Err(NotSupportedError::new("set_cursor_hittest_region is not supported").into())
+    }
+
     /// Returns the monitor on which the window currently resides.
     ///
     /// Returns `None` if current monitor can't be detected.
@@ -1400,6 +1867,55 @@ pub enum CursorGrabMode {
     Locked,
 }
 
+/// The point of a window which stays in place in [`Window::request_surface_size_anchored`].
+///
+/// The anchors on an edge stay at the middle of that edge, so the window grows or shrinks equally
+/// on both sides along it.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Anchor {
+    /// The top-left corner, which is where windows grow from by default.
+    #[default]
+    NorthWest,
+    /// The middle of the top edge.
+    North,
+    /// The top-right corner.
+    NorthEast,
+    /// The middle of the left edge.
+    West,
+    /// The center of the window, which grows or shrinks equally in every direction.
+    Center,
+    /// The middle of the right edge.
+    East,
+    /// The bottom-left corner.
+    SouthWest,
+    /// The middle of the bottom edge.
+    South,
+    /// The bottom-right corner.
+    SouthEast,
+}
+
+impl Anchor {
+    /// The distance the window origin has to move when resizing from `old` to `new`.
+    pub(crate) fn offset(self, old: PhysicalSize<u32>, new: PhysicalSize<u32>) -> (i32, i32) {
+        let dw = new.width as i32 - old.width as i32;
+        let dh = new.height as i32 - old.height as i32;
+
+        let dx = match self {
+            Self::NorthWest | Self::West | Self::SouthWest => 0,
+            Self::North | Self::Center | Self::South => dw / 2,
+            Self::NorthEast | Self::East | Self::SouthEast => dw,
+        };
+        let dy = match self {
+            Self::NorthWest | Self::North | Self::NorthEast => 0,
+            Self::West | Self::Center | Self::East => dh / 2,
+            Self::SouthWest | Self::South | Self::SouthEast => dh,
+        };
+
+        (dx, dy)
+    }
+}
+
 /// Defines the orientation that a window resize will be performed.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -1414,6 +1930,39 @@ pub enum ResizeDirection {
     West,
 }
 
+impl ResizeDirection {
+    /// Returns the edge or corner of a surface of `size` that `position` is within `border`
+    /// physical pixels of.
+    ///
+    /// Corners take precedence over edges. Returns `None` if `position` is further than `border`
+    /// away from every edge.
+    pub fn from_surface_position(
+        position: PhysicalPosition<f64>,
+        size: PhysicalSize<u32>,
+        border: f64,
+    ) -> Option<Self> {
+        let width = size.width as f64;
+        let height = size.height as f64;
+
+        let west = position.x < border;
+        let east = position.x >= width - border;
+        let north = position.y < border;
+        let south = position.y >= height - border;
+
+        match (north, south, west, east) {
+            (true, _, true, _) => Some(Self::NorthWest),
+            (true, _, _, true) => Some(Self::NorthEast),
+            (_, true, true, _) => Some(Self::SouthWest),
+            (_, true, _, true) => Some(Self::SouthEast),
+            (true, ..) => Some(Self::North),
+            (_, true, ..) => Some(Self::South),
+            (_, _, true, _) => Some(Self::West),
+            (.., true) => Some(Self::East),
+            _ => None,
+        }
+    }
+}
+
 impl From<ResizeDirection> for CursorIcon {
     fn from(direction: ResizeDirection) -> Self {
         use ResizeDirection::*;
@@ -1430,6 +1979,19 @@ impl From<ResizeDirection> for CursorIcon {
     }
 }
 
+/// The priority of a redraw requested with [`Window::request_redraw_with_priority`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum RedrawPriority {
+    /// Redraws that can wait, for example scheduled animation frames.
+    Low,
+    /// The priority used by [`Window::request_redraw`].
+    #[default]
+    Normal,
+    /// Redraws that should be serviced first, for example ones caused by user input.
+    High,
+}
+
 /// Fullscreen modes.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Fullscreen {
@@ -1478,8 +2040,43 @@ pub enum UserAttentionType {
     Informational,
 }
 
+/// Bounds applied to the surface size when the window is resized.
+///
+/// See [`Window::set_resize_constraints`] for details.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ResizeConstraints {
+    /// The minimum dimensions of the surface.
+    pub min: Option<Size>,
+    /// The maximum dimensions of the surface.
+    pub max: Option<Size>,
+    /// The ratio between the width and the height of the surface, as `(width, height)`.
+    ///
+    /// A ratio with a zero component is ignored.
+    pub aspect_ratio: Option<(u32, u32)>,
+}
+
+/// An axis-aligned rectangle in physical pixels.
+///
+/// See [`Window::set_cursor_hittest_region`] for details.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PhysicalRect {
+    /// The position of the top-left corner.
+    pub origin: PhysicalPosition<i32>,
+    /// The width and height of the rectangle.
+    pub size: PhysicalSize<u32>,
+}
+
+impl PhysicalRect {
+    /// Creates a new rectangle from its top-left corner and its size.
+    pub fn new(origin: PhysicalPosition<i32>, size: PhysicalSize<u32>) -> Self {
+        Self { origin, size }
+    }
+}
+
 bitflags::bitflags! {
     #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     pub struct WindowButtons: u32 {
         const CLOSE  = 1 << 0;
         const MINIMIZE  = 1 << 1;
@@ -1575,3 +2172,88 @@ impl ActivationToken {
         self.token
     }
 }
+
+/// Clamp a position to the pixels covered by a surface of the given size.
+fn clamp_to_surface(
+    position: PhysicalPosition<f64>,
+    size: PhysicalSize<u32>,
+) -> PhysicalPosition<f64> {
+    let max_x = size.width.saturating_sub(1) as f64;
+    let max_y = size.height.saturating_sub(1) as f64;
+    PhysicalPosition::new(position.x.clamp(0.0, max_x), position.y.clamp(0.0, max_y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dpi::LogicalSize;
+
+    #[test]
+    fn fractional_monitor_size() {
+        let monitor = Some(PhysicalSize::new(1920, 1080));
+        let attributes = WindowAttributes::default().with_surface_size(LogicalSize::new(400, 300));
+        assert_eq!(attributes.initial_surface_size(monitor), attributes.surface_size);
+
+        let attributes = attributes.with_fractional_monitor_size(0.7);
+        assert_eq!(
+            attributes.initial_surface_size(monitor),
+            Some(PhysicalSize::new(1344, 756).into())
+        );
+        // Without a monitor, fall back to the regular surface size.
+        assert_eq!(attributes.initial_surface_size(None), Some(LogicalSize::new(400, 300).into()));
+    }
+
+    #[test]
+    fn cursor_position_is_clamped_to_surface() {
+        let size = PhysicalSize::new(800, 600);
+        let clamp = |x, y| clamp_to_surface(PhysicalPosition::new(x, y), size);
+
+        assert_eq!(clamp(400.5, 300.0), PhysicalPosition::new(400.5, 300.0));
+        assert_eq!(clamp(-10.0, 650.0), PhysicalPosition::new(0.0, 599.0));
+        assert_eq!(clamp(1200.0, -0.5), PhysicalPosition::new(799.0, 0.0));
+        // An empty surface collapses everything to the origin.
+        let empty = clamp_to_surface(PhysicalPosition::new(5.0, 5.0), PhysicalSize::new(0, 0));
+        assert_eq!(empty, PhysicalPosition::new(0.0, 0.0));
+    }
+
+    #[test]
+    fn anchor_offset() {
+        let old = PhysicalSize::new(400, 300);
+        let grown = PhysicalSize::new(500, 361);
+        assert_eq!(Anchor::NorthWest.offset(old, grown), (0, 0));
+        assert_eq!(Anchor::North.offset(old, grown), (50, 0));
+        assert_eq!(Anchor::East.offset(old, grown), (100, 30));
+        assert_eq!(Anchor::Center.offset(old, grown), (50, 30));
+        assert_eq!(Anchor::SouthEast.offset(old, grown), (100, 61));
+
+        // Shrinking moves the origin the other way.
+        let shrunk = PhysicalSize::new(200, 100);
+        assert_eq!(Anchor::SouthWest.offset(old, shrunk), (0, -200));
+        assert_eq!(Anchor::Center.offset(old, shrunk), (-100, -100));
+        assert_eq!(Anchor::NorthEast.offset(old, old), (0, 0));
+    }
+
+    #[test]
+    fn resize_direction_from_surface_position() {
+        let size = PhysicalSize::new(800, 600);
+        let direction =
+            |x, y| ResizeDirection::from_surface_position(PhysicalPosition::new(x, y), size, 8.0);
+
+        // Edges.
+        assert_eq!(direction(400.0, 0.0), Some(ResizeDirection::North));
+        assert_eq!(direction(400.0, 595.0), Some(ResizeDirection::South));
+        assert_eq!(direction(7.9, 300.0), Some(ResizeDirection::West));
+        assert_eq!(direction(792.0, 300.0), Some(ResizeDirection::East));
+
+        // Corners take precedence over edges.
+        assert_eq!(direction(2.0, 3.0), Some(ResizeDirection::NorthWest));
+        assert_eq!(direction(799.0, 0.0), Some(ResizeDirection::NorthEast));
+        assert_eq!(direction(0.0, 599.0), Some(ResizeDirection::SouthWest));
+        assert_eq!(direction(795.0, 592.0), Some(ResizeDirection::SouthEast));
+
+        // Inside the border.
+        assert_eq!(direction(400.0, 300.0), None);
+        assert_eq!(direction(8.0, 8.0), None);
+        assert_eq!(direction(791.9, 591.9), None);
+    }
+}
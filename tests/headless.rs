@@ -0,0 +1,63 @@
+#![cfg(all(
+    feature = "null",
+    unix,
+    not(target_vendor = "apple"),
+    not(target_os = "android"),
+    not(target_os = "emscripten"),
+    not(target_os = "redox")
+))]
+
+use std::thread;
+
+use winit::application::ApplicationHandler;
+use winit::event::WindowEvent;
+use winit::event_loop::{ActiveEventLoop, EventLoop};
+use winit::platform::null::EventLoopBuilderExtNull;
+use winit::window::{Window, WindowAttributes, WindowId};
+
+/// Creates a window, and exits once it got its first redraw.
+#[derive(Default)]
+struct App {
+    window: Option<Box<dyn Window>>,
+    redrawn: bool,
+}
+
+impl ApplicationHandler for App {
+    fn can_create_surfaces(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.window = Some(event_loop.create_window(WindowAttributes::default()).unwrap());
+    }
+
+    fn window_event(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        _window_id: WindowId,
+        event: WindowEvent,
+    ) {
+        if let WindowEvent::RedrawRequested = event {
+            self.redrawn = true;
+            event_loop.exit();
+        }
+    }
+}
+
+fn run_headless_app() {
+    let event_loop = EventLoop::builder().with_headless().build().unwrap();
+    let mut app = App::default();
+    event_loop.run_app(&mut app).unwrap();
+    assert!(app.redrawn);
+}
+
+#[test]
+fn headless_event_loops_can_be_recreated() {
+    // Tests don't run on the main thread.
+    run_headless_app();
+    run_headless_app();
+}
+
+#[test]
+fn headless_event_loops_on_several_threads() {
+    let threads: Vec<_> = (0..2).map(|_| thread::spawn(run_headless_app)).collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+}
@@ -4,7 +4,9 @@ use serde::{Deserialize, Serialize};
 use winit::dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize};
 use winit::event::{ElementState, MouseButton, MouseScrollDelta, TouchPhase};
 use winit::keyboard::{Key, KeyCode, KeyLocation, ModifiersState, NamedKey, PhysicalKey};
-use winit::window::CursorIcon;
+use winit::window::{
+    CursorIcon, PhysicalRect, Theme, WindowAttributes, WindowButtons, WindowLevel,
+};
 
 #[allow(dead_code)]
 fn needs_serde<S: Serialize + Deserialize<'static>>() {}
@@ -12,6 +14,11 @@ fn needs_serde<S: Serialize + Deserialize<'static>>() {}
 #[test]
 fn window_serde() {
     needs_serde::<CursorIcon>();
+    needs_serde::<PhysicalRect>();
+    needs_serde::<Theme>();
+    needs_serde::<WindowLevel>();
+    needs_serde::<WindowButtons>();
+    needs_serde::<WindowAttributes>();
 }
 
 #[test]
@@ -36,3 +43,76 @@ fn dpi_serde() {
     needs_serde::<LogicalSize<f64>>();
     needs_serde::<PhysicalSize<u32>>();
 }
+
+#[test]
+fn window_attributes_default_missing_fields() {
+    use serde::de::value::{Error, MapDeserializer};
+
+    let fields = [("maximized", true), ("decorations", false)];
+    let attributes =
+        WindowAttributes::deserialize(MapDeserializer::<_, Error>::new(fields.into_iter()))
+            .unwrap();
+    assert_eq!(
+        attributes,
+        WindowAttributes::default().with_maximized(true).with_decorations(false)
+    );
+}
+
+fn round_trip(attributes: &WindowAttributes) -> WindowAttributes {
+    let json = serde_json::to_string(attributes).unwrap();
+    serde_json::from_str(&json).unwrap()
+}
+
+#[test]
+fn window_attributes_round_trip() {
+    let attributes = WindowAttributes::default()
+        .with_title("serde")
+        .with_surface_size(LogicalSize::new(640.0, 480.0))
+        .with_position(PhysicalPosition::new(10, 20))
+        .with_resizable(false)
+        .with_enabled_buttons(WindowButtons::CLOSE | WindowButtons::MINIMIZE)
+        .with_window_level(WindowLevel::AlwaysOnTop)
+        .with_theme(Some(Theme::Dark))
+        .with_transparent(true);
+    assert_eq!(round_trip(&attributes), attributes);
+}
+
+#[test]
+fn window_attributes_skip_session_fields() {
+    let attributes = WindowAttributes::default().with_title("serde");
+    let with_cursor = attributes.clone().with_cursor(CursorIcon::Wait);
+    assert_eq!(round_trip(&with_cursor), attributes);
+}
+
+#[cfg(x11_platform)]
+#[test]
+fn x11_window_attributes_round_trip() {
+    use winit::platform::x11::{WindowAttributesExtX11, WindowType};
+
+    let attributes = WindowAttributes::default()
+        .with_override_redirect(true)
+        .with_x11_window_type(vec![WindowType::Utility, WindowType::Dialog]);
+    assert_eq!(round_trip(&attributes), attributes);
+}
+
+#[cfg(windows_platform)]
+#[test]
+fn windows_window_attributes_round_trip() {
+    use winit::platform::windows::WindowAttributesExtWindows;
+
+    let attributes = WindowAttributes::default().with_skip_taskbar(true);
+    assert_eq!(round_trip(&attributes), attributes);
+}
+
+#[cfg(macos_platform)]
+#[test]
+fn macos_window_attributes_round_trip() {
+    use winit::platform::macos::WindowAttributesExtMacOS;
+
+    let attributes = WindowAttributes::default()
+        .with_titlebar_transparent(true)
+        .with_title_hidden(true)
+        .with_titlebar_buttons_hidden(true)
+        .with_fullsize_content_view(true);
+    assert_eq!(round_trip(&attributes), attributes);
+}